@@ -54,8 +54,13 @@ pub fn command_name_from_command(command: &str) -> String {
 }
 
 pub fn command_log_path(home_dir: &Path, timestamp: NaiveDateTime, command: &str) -> PathBuf {
-    home_dir
-        .join(".commands")
+    command_log_path_in(&home_dir.join(".commands"), timestamp, command)
+}
+
+/// Like [`command_log_path`], but relative to an explicit log root instead of
+/// `~/.commands`.
+pub fn command_log_path_in(log_root: &Path, timestamp: NaiveDateTime, command: &str) -> PathBuf {
+    log_root
         .join(timestamp.format("%y%m%d").to_string())
         .join(format!(
             "{}-{}.log",
@@ -70,23 +75,23 @@ pub fn run_with_home_and_timestamp(
     home_dir: &Path,
     timestamp: NaiveDateTime,
 ) -> Result<Spawned> {
-    run_with_home_timestamp_and_notification(
+    run_with_log_root_timestamp_and_notification(
         command,
         args,
-        home_dir,
+        &home_dir.join(".commands"),
         timestamp,
         CompletionNotification::Disabled,
     )
 }
 
-fn run_with_home_timestamp_and_notification(
+fn run_with_log_root_timestamp_and_notification(
     command: &str,
     args: &[String],
-    home_dir: &Path,
+    log_root: &Path,
     timestamp: NaiveDateTime,
     completion_notification: CompletionNotification,
 ) -> Result<Spawned> {
-    let log_path = command_log_path(home_dir, timestamp, command);
+    let log_path = command_log_path_in(log_root, timestamp, command);
     if let Some(log_dir) = log_path.parent() {
         fs::create_dir_all(log_dir).with_context(|| {
             format!(
@@ -353,15 +358,33 @@ fn spawn_with_completion_notification(
     // Launch the command in the background and return to the shell immediately.
     // Output is still captured to the log file; dropping the handle leaves the
     // detached process running.
-    run_with_home_timestamp_and_notification(
+    run_with_log_root_timestamp_and_notification(
         command,
         &args,
-        &home_dir()?,
+        &log_root()?,
         chrono::Local::now().naive_local(),
         completion_notification,
     )
 }
 
+/// Resolve the command log directory from `[zzz] log_root` in the shared
+/// configuration, falling back to `~/.commands`.
+pub fn log_root() -> Result<PathBuf> {
+    let home = home_dir()?;
+    let cwd = env::current_dir().unwrap_or_else(|_| home.clone());
+    Ok(log_root_in(&home, crate::config::Config::load(&cwd)))
+}
+
+/// A configuration that fails to load falls back to the default too, so a
+/// mistake in another tool's settings never stops a command from running.
+fn log_root_in(home: &Path, config: Result<crate::config::Config>) -> PathBuf {
+    config
+        .ok()
+        .and_then(|config| config.zzz.log_root)
+        .map(|root| crate::config::expand_home(&root))
+        .unwrap_or_else(|| home.join(".commands"))
+}
+
 /// Resolve the user's home directory from HOME (or USERPROFILE on Windows).
 pub fn home_dir() -> Result<PathBuf> {
     env::var_os("HOME")
@@ -586,6 +609,22 @@ mod tests {
         fs::remove_dir_all(fixture.home).unwrap();
     }
 
+    #[test]
+    fn log_root_falls_back_to_the_default_on_config_errors() {
+        let home = Path::new("/home/dev");
+        let mut config = crate::config::Config::default();
+        assert_eq!(
+            log_root_in(home, Ok(config.clone())),
+            home.join(".commands")
+        );
+
+        config.zzz.log_root = Some(PathBuf::from("/var/log/zzz"));
+        assert_eq!(log_root_in(home, Ok(config)), PathBuf::from("/var/log/zzz"));
+
+        let broken = Err(anyhow::anyhow!("Invalid cli-tools configuration"));
+        assert_eq!(log_root_in(home, broken), home.join(".commands"));
+    }
+
     #[cfg(target_os = "macos")]
     struct NotificationFixture {
         home: PathBuf,
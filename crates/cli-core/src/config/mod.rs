@@ -1,25 +1,1171 @@
-use serde::{Deserialize, Serialize};
+//! Shared configuration for every binary in the workspace.
+//!
+//! Values are resolved from several layers, later layers winning:
+//!
+//! 1. built-in defaults
+//! 2. the user file, `~/.config/cli-tools/config.toml` (or `$XDG_CONFIG_HOME`)
+//! 3. the repository file, `.cli-tools.toml`, found by walking up from the
+//!    analyzed path to the repository root
+//! 4. `CLI_TOOLS_*` environment variables (`__` separates sections, e.g.
+//!    `CLI_TOOLS_CODE_COST__LINES_PER_HOUR=25`), kept as text until the
+//!    setting they override asks for a number or a boolean
+//! 5. command-line flags, applied by each tool through [`LayeredConfig::set_flag`]
+//!
+//! Every resolved key remembers the layer it came from so `config show
+//! --effective` can explain where a value originated.
+
+use anyhow::{Context, Result};
+use serde::de::{self, DeserializeSeed, IntoDeserializer, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::currency::Currency;
+pub use crate::scaffold::Ecosystem;
+
+/// File name of the per-repository configuration.
+pub const REPO_CONFIG_FILE: &str = ".cli-tools.toml";
+
+const ENV_PREFIX: &str = "CLI_TOOLS_";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     pub hourly_rate: f64,
-    pub currency: String,
+    /// Currency `hourly_rate` is in, and reports are in unless `report_currency` is set.
+    pub currency: Currency,
+    /// Currency reports convert every amount to, such as a client's.
+    pub report_currency: Option<Currency>,
+    pub code_cost: CodeCostConfig,
+    pub work_summary: WorkSummaryConfig,
+    pub git_tools: GitToolsConfig,
+    pub zzz: ZzzConfig,
+    /// People who commit under several names or addresses, merged into one.
+    pub identities: Vec<Identity>,
 }
 
 impl Config {
-    pub fn new(hourly_rate: f64, currency: impl Into<String>) -> Self {
+    pub fn new(hourly_rate: f64, currency: Currency) -> Self {
         Self {
             hourly_rate,
-            currency: currency.into(),
+            currency,
+            ..Self::default()
         }
     }
+
+    /// Resolve the configuration for `start` without any command-line overrides.
+    pub fn load(start: &Path) -> Result<Self> {
+        LayeredConfig::load(start)?.config()
+    }
+
+    /// The configured identity a commit by `name <email>` belongs to.
+    pub fn identity(&self, name: &str, email: &str) -> Option<&Identity> {
+        self.identities
+            .iter()
+            .find(|identity| identity.matches(name, email))
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             hourly_rate: 10_030.0, // 2025년 대한민국 최저시급
-            currency: "KRW".to_string(),
+            currency: Currency::KRW,
+            report_currency: None,
+            code_cost: CodeCostConfig::default(),
+            work_summary: WorkSummaryConfig::default(),
+            git_tools: GitToolsConfig::default(),
+            zzz: ZzzConfig::default(),
+            identities: Vec::new(),
+        }
+    }
+}
+
+/// One `[[identities]]` entry: a person's canonical name and email, and the
+/// other addresses or names their commits use.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Identity {
+    pub name: String,
+    pub email: String,
+    /// Emails, compared case-insensitively, or exact author names.
+    pub aliases: Vec<String>,
+}
+
+impl Identity {
+    /// Whether a commit by `name <email>` is this person's.
+    pub fn matches(&self, name: &str, email: &str) -> bool {
+        let is_email = |candidate: &str| !email.is_empty() && candidate.eq_ignore_ascii_case(email);
+        is_email(&self.email)
+            || self
+                .aliases
+                .iter()
+                .any(|alias| is_email(alias) || alias == name)
+    }
+}
+
+/// `[code_cost]` section.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CodeCostConfig {
+    /// Lines of code a developer writes per hour in the base estimate.
+    pub lines_per_hour: f64,
+    /// Per-language effort weights that replace the built-in table.
+    pub language_weights: BTreeMap<String, f64>,
+    /// Estimation model used when `--model` is not given.
+    pub model: EstimationModelKind,
+    /// Working hours in a person-month, converting model effort to hours.
+    pub hours_per_month: f64,
+    pub cocomo81: Cocomo81Config,
+    pub cocomo2: Cocomo2Config,
+    pub custom_model: CustomModelConfig,
+    pub dependencies: DependencyMaintenanceConfig,
+    pub duplication: DuplicationConfig,
+    /// LLM pricing catalogue ids to simulate token costs for; the first one
+    /// is the AI cost in summaries.
+    pub llm_models: Vec<String>,
+    /// Catalogue adding to or overriding the built-in LLM prices (default:
+    /// `llm-pricing.toml` next to the user configuration).
+    pub llm_pricing: Option<PathBuf>,
+    /// lcov, Cobertura, JaCoCo or llvm-cov report, relative to the repository
+    /// (default: the first found in the usual output locations).
+    pub coverage: Option<PathBuf>,
+}
+
+impl Default for CodeCostConfig {
+    fn default() -> Self {
+        Self {
+            lines_per_hour: 20.0,
+            language_weights: BTreeMap::new(),
+            model: EstimationModelKind::default(),
+            hours_per_month: 152.0, // COCOMO II person-month
+            cocomo81: Cocomo81Config::default(),
+            cocomo2: Cocomo2Config::default(),
+            custom_model: CustomModelConfig::default(),
+            dependencies: DependencyMaintenanceConfig::default(),
+            duplication: DuplicationConfig::default(),
+            llm_models: vec!["claude-opus-4-7".to_string()],
+            llm_pricing: None,
+            coverage: None,
+        }
+    }
+}
+
+/// Methodology `code-cost` estimates effort with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EstimationModelKind {
+    /// Lines per hour adjusted for language, complexity and maturity.
+    #[default]
+    Heuristic,
+    Cocomo81Basic,
+    Cocomo81Intermediate,
+    /// COCOMO II post-architecture.
+    Cocomo2,
+    /// Power-law model with the constants in `[code_cost.custom_model]`.
+    Custom,
+}
+
+/// COCOMO 81 project class.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CocomoMode {
+    #[default]
+    Organic,
+    SemiDetached,
+    Embedded,
+}
+
+/// A rating from a COCOMO driver table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rating {
+    VeryLow,
+    Low,
+    Nominal,
+    High,
+    VeryHigh,
+    ExtraHigh,
+}
+
+/// A cost driver or scale factor: a table rating, or a calibrated value used as is.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DriverValue {
+    Rating(Rating),
+    Value(f64),
+}
+
+/// `[code_cost.cocomo81]` section.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Cocomo81Config {
+    pub mode: CocomoMode,
+    /// Intermediate cost drivers by name (`RELY = "high"`); unset drivers are nominal.
+    pub cost_drivers: BTreeMap<String, DriverValue>,
+}
+
+/// `[code_cost.cocomo2]` section.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Cocomo2Config {
+    /// Scale factors by name (`PREC`, `FLEX`, `RESL`, `TEAM`, `PMAT`); unset ones are nominal.
+    pub scale_factors: BTreeMap<String, DriverValue>,
+    /// Post-architecture effort multipliers by name (`CPLX = "high"`); unset ones are nominal.
+    pub effort_multipliers: BTreeMap<String, DriverValue>,
+}
+
+/// `[code_cost.custom_model]` section: effort is `a * KSLOC^b` person-months
+/// times every multiplier, and the schedule is `c * effort^d` months.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CustomModelConfig {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    /// Named multipliers applied to effort, such as in-house calibration factors.
+    pub effort_multipliers: BTreeMap<String, f64>,
+}
+
+impl Default for CustomModelConfig {
+    fn default() -> Self {
+        // Basic COCOMO 81 organic constants.
+        Self {
+            a: 2.4,
+            b: 1.05,
+            c: 2.5,
+            d: 0.38,
+            effort_multipliers: BTreeMap::new(),
+        }
+    }
+}
+
+/// `[code_cost.dependencies]` section: hours of upkeep each locked dependency
+/// adds to the estimate. Zero hours leave dependencies out of the cost.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DependencyMaintenanceConfig {
+    /// Per dependency a manifest declares: choosing, integrating and upgrading it.
+    pub direct_hours: f64,
+    /// Per dependency pulled in by another: auditing and following its updates.
+    pub transitive_hours: f64,
+    /// Per extra version of a dependency locked more than once.
+    pub duplicate_hours: f64,
+}
+
+impl Default for DependencyMaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            direct_hours: 4.0,
+            transitive_hours: 0.5,
+            duplicate_hours: 2.0,
+        }
+    }
+}
+
+/// `[code_cost.duplication]`: how much code copied within the codebase counts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DuplicationConfig {
+    /// Share of a duplicated line's effort left out of the estimate, from 0
+    /// (counted in full) to 1 (not counted).
+    pub discount: f64,
+}
+
+impl Default for DuplicationConfig {
+    fn default() -> Self {
+        Self { discount: 0.75 }
+    }
+}
+
+/// `[work_summary]` section with the time estimator constants.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkSummaryConfig {
+    /// Gaps between commits longer than this are not counted as one session.
+    pub max_session_gap_hours: f64,
+    pub lines_per_hour: f64,
+    /// Share of the estimate derived from commit time gaps.
+    pub time_weight: f64,
+    /// Share of the estimate derived from changed lines.
+    pub change_weight: f64,
+}
+
+impl Default for WorkSummaryConfig {
+    fn default() -> Self {
+        Self {
+            max_session_gap_hours: 4.0,
+            lines_per_hour: 20.0,
+            time_weight: 0.6,
+            change_weight: 0.4,
+        }
+    }
+}
+
+/// `[git_tools]` section.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GitToolsConfig {
+    pub health: HealthConfig,
+    pub scan: ScanConfig,
+    pub env: EnvConfig,
+    pub cleanup: CleanupConfig,
+}
+
+/// `[git_tools.health]` section.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HealthConfig {
+    /// Names of health checks to skip (e.g. `"Docker"`, `"GEMINI.md"`).
+    pub skip: Vec<String>,
+    /// Lowest acceptable weighted percentage of passing checks; `health` and
+    /// `pulse` exit non-zero below it.
+    pub min_score: Option<f64>,
+    /// Ecosystem packs to enable even when their manifest is not detected.
+    pub packs: Vec<Ecosystem>,
+    /// Project-specific checks, declared as `[[git_tools.health.checks]]`.
+    pub checks: Vec<CustomCheck>,
+}
+
+/// How much a failing health check matters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    #[default]
+    Warning,
+    /// A failing `error` check makes `health` exit non-zero.
+    Error,
+}
+
+/// A health check declared in configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomCheck {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub advice: Option<String>,
+    #[serde(default)]
+    pub severity: Severity,
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+    #[serde(flatten)]
+    pub rule: CheckRule,
+}
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+/// What a [`CustomCheck`] verifies, relative to the analyzed directory.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CheckRule {
+    /// `file_exists = "path"`: the file or directory exists.
+    FileExists { file_exists: String },
+    /// `glob_absent = "**/*.pem"`: no file matches the glob.
+    GlobAbsent { glob_absent: String },
+    /// `file = "path"` and `regex = "..."`: the file contains a match.
+    RegexPresent { file: String, regex: String },
+}
+
+/// `[git_tools.scan]` section.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScanConfig {
+    /// Most code markers allowed before `scan` and `pulse` exit non-zero.
+    pub max_markers: Option<usize>,
+}
+
+/// `[git_tools.env]` section.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnvConfig {
+    /// Most `.env.example` keys that may be missing from `.env` before `env` exits non-zero.
+    pub max_missing: Option<usize>,
+}
+
+/// `[git_tools.cleanup]` section.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CleanupConfig {
+    /// Most merged-but-undeleted branches allowed before `cleanup` exits non-zero.
+    pub max_merged: Option<usize>,
+}
+
+/// `[zzz]` section.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ZzzConfig {
+    /// Directory for background command logs (default: `~/.commands`).
+    /// A leading `~/` is expanded to the home directory.
+    pub log_root: Option<PathBuf>,
+}
+
+/// The layer a configuration value was resolved from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigSource {
+    Default,
+    User,
+    Repository,
+    Env,
+    Flag,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Default => "default",
+            Self::User => "user",
+            Self::Repository => "repository",
+            Self::Env => "env",
+            Self::Flag => "flag",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A single resolved key for the effective configuration view.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigEntry {
+    pub key: String,
+    pub value: String,
+    pub source: ConfigSource,
+}
+
+/// Configuration values merged from every layer, with per-key provenance.
+#[derive(Debug, Clone)]
+pub struct LayeredConfig {
+    values: toml::Table,
+    sources: BTreeMap<String, ConfigSource>,
+    files: Vec<(ConfigSource, PathBuf)>,
+}
+
+impl LayeredConfig {
+    /// Load defaults, the user and repository files, and the process environment.
+    pub fn load(start: &Path) -> Result<Self> {
+        Self::load_from(user_config_path().as_deref(), start, std::env::vars())
+    }
+
+    fn load_from(
+        user_file: Option<&Path>,
+        start: &Path,
+        env: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self> {
+        let defaults = match toml::Value::try_from(Config::default())
+            .context("Failed to serialize default configuration")?
+        {
+            toml::Value::Table(table) => table,
+            _ => unreachable!("Config serializes to a table"),
+        };
+
+        let mut layered = Self {
+            values: toml::Table::new(),
+            sources: BTreeMap::new(),
+            files: Vec::new(),
+        };
+        layered.merge(defaults, ConfigSource::Default);
+
+        if let Some(path) = user_file.filter(|p| p.is_file()) {
+            layered.merge_file(path, ConfigSource::User)?;
+        }
+
+        if let Some(path) = find_repo_config(start) {
+            layered.merge_file(&path, ConfigSource::Repository)?;
+        }
+
+        for (name, raw) in env {
+            if let Some(key) = env_key(&name) {
+                layered.set(&key, toml::Value::String(raw), ConfigSource::Env);
+            }
+        }
+
+        Ok(layered)
+    }
+
+    /// Override a dotted key (e.g. `"hourly_rate"` or `"code_cost.lines_per_hour"`)
+    /// with a value passed on the command line.
+    pub fn set_flag(&mut self, key: &str, value: impl Into<toml::Value>) {
+        self.set(key, value.into(), ConfigSource::Flag);
+    }
+
+    /// Deserialize the merged values into a typed [`Config`].
+    pub fn config(&self) -> Result<Config> {
+        Config::deserialize(Layered {
+            value: toml::Value::Table(self.values.clone()),
+            key: String::new(),
+            sources: &self.sources,
+        })
+        .context("Invalid cli-tools configuration")
+    }
+
+    /// Configuration files that contributed to this configuration, in load order.
+    pub fn files(&self) -> &[(ConfigSource, PathBuf)] {
+        &self.files
+    }
+
+    /// Every resolved leaf key, sorted, with the layer it came from.
+    pub fn entries(&self) -> Vec<ConfigEntry> {
+        let mut entries = Vec::new();
+        collect_entries("", &self.values, &self.sources, &mut entries);
+        entries
+    }
+
+    fn merge_file(&mut self, path: &Path, source: ConfigSource) -> Result<()> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        let table: toml::Table = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+        self.merge(table, source);
+        self.files.push((source, path.to_path_buf()));
+        Ok(())
+    }
+
+    fn merge(&mut self, table: toml::Table, source: ConfigSource) {
+        let mut leaves = Vec::new();
+        flatten("", table, &mut leaves);
+        for (key, value) in leaves {
+            self.set(&key, value, source);
+        }
+    }
+
+    fn set(&mut self, key: &str, value: toml::Value, source: ConfigSource) {
+        let mut parts: Vec<&str> = key.split('.').collect();
+        let Some(last) = parts.pop() else {
+            return;
+        };
+
+        let mut table = &mut self.values;
+        for part in parts {
+            let entry = table
+                .entry(part.to_string())
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            if !entry.is_table() {
+                *entry = toml::Value::Table(toml::Table::new());
+            }
+            table = entry.as_table_mut().expect("entry was just made a table");
         }
+
+        table.insert(last.to_string(), value);
+        self.sources.insert(key.to_string(), source);
+    }
+}
+
+/// Path of the user configuration file, if a home directory is known.
+pub fn user_config_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| crate::command_log::home_dir().ok().map(|home| home.join(".config")))?;
+    Some(base.join("cli-tools").join("config.toml"))
+}
+
+/// Find `.cli-tools.toml` in `start` or one of its parents, stopping at the
+/// repository root (the first directory containing `.git`).
+pub fn find_repo_config(start: &Path) -> Option<PathBuf> {
+    let start = start.canonicalize().ok()?;
+    for dir in start.ancestors() {
+        let candidate = dir.join(REPO_CONFIG_FILE);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+    None
+}
+
+/// Expand a leading `~/` against the home directory.
+pub fn expand_home(path: &Path) -> PathBuf {
+    match path.strip_prefix("~") {
+        Ok(rest) => crate::command_log::home_dir()
+            .map(|home| home.join(rest))
+            .unwrap_or_else(|_| path.to_path_buf()),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+fn flatten(prefix: &str, table: toml::Table, leaves: &mut Vec<(String, toml::Value)>) {
+    for (name, value) in table {
+        let key = join_key(prefix, &name);
+        match value {
+            toml::Value::Table(nested) if !is_map_section(&key) => flatten(&key, nested, leaves),
+            value => leaves.push((key, value)),
+        }
+    }
+}
+
+fn collect_entries(
+    prefix: &str,
+    table: &toml::Table,
+    sources: &BTreeMap<String, ConfigSource>,
+    entries: &mut Vec<ConfigEntry>,
+) {
+    for (name, value) in table {
+        let key = join_key(prefix, name);
+        match value {
+            toml::Value::Table(nested) if !is_map_section(&key) => {
+                collect_entries(&key, nested, sources, entries)
+            }
+            value => entries.push(ConfigEntry {
+                source: sources.get(&key).copied().unwrap_or(ConfigSource::Default),
+                value: value.to_string(),
+                key,
+            }),
+        }
+    }
+}
+
+/// Tables whose keys are user data rather than settings are replaced as a
+/// whole by a higher layer instead of being merged key by key.
+fn is_map_section(key: &str) -> bool {
+    key == "code_cost.language_weights" || key == "code_cost.custom_model.effort_multipliers"
+}
+
+fn join_key(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{prefix}.{name}")
+    }
+}
+
+fn env_key(name: &str) -> Option<String> {
+    let rest = name.strip_prefix(ENV_PREFIX)?;
+    if rest.is_empty() {
+        return None;
+    }
+    Some(rest.to_lowercase().replace("__", "."))
+}
+
+/// The variable that sets `key`, e.g. `CLI_TOOLS_CODE_COST__LINES_PER_HOUR`.
+fn env_name(key: &str) -> String {
+    format!("{ENV_PREFIX}{}", key.to_uppercase().replace('.', "__"))
+}
+
+/// Deserializes the merged values. Environment variables are always text, so
+/// a setting that wants a number or a boolean parses the text itself; a
+/// value from a file must already have the right type.
+struct Layered<'a> {
+    value: toml::Value,
+    /// Dotted key of `value`, as in `sources`.
+    key: String,
+    sources: &'a BTreeMap<String, ConfigSource>,
+}
+
+impl Layered<'_> {
+    /// `value` parsed as `T` when it came from the environment.
+    fn parse_env<T: FromStr>(&self, expected: &str) -> Option<Result<T, toml::de::Error>> {
+        let toml::Value::String(raw) = &self.value else {
+            return None;
+        };
+        if self.sources.get(&self.key) != Some(&ConfigSource::Env) {
+            return None;
+        }
+        Some(raw.parse().map_err(|_| {
+            de::Error::custom(format!("{}={raw:?} is not {expected}", env_name(&self.key)))
+        }))
+    }
+}
+
+macro_rules! deserialize_env {
+    ($($method:ident => $visit:ident($ty:ty, $expected:literal),)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            match self.parse_env::<$ty>($expected) {
+                Some(value) => visitor.$visit(value?),
+                None => self.deserialize_any(visitor),
+            }
+        }
+    )*};
+}
+
+impl<'de> Deserializer<'de> for Layered<'_> {
+    type Error = toml::de::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            toml::Value::Table(table) => visitor.visit_map(LayeredTable {
+                entries: table.into_iter(),
+                next: None,
+                prefix: self.key,
+                sources: self.sources,
+            }),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    deserialize_env! {
+        deserialize_bool => visit_bool(bool, "true or false"),
+        deserialize_i8 => visit_i64(i64, "an integer"),
+        deserialize_i16 => visit_i64(i64, "an integer"),
+        deserialize_i32 => visit_i64(i64, "an integer"),
+        deserialize_i64 => visit_i64(i64, "an integer"),
+        deserialize_u8 => visit_u64(u64, "a non-negative integer"),
+        deserialize_u16 => visit_u64(u64, "a non-negative integer"),
+        deserialize_u32 => visit_u64(u64, "a non-negative integer"),
+        deserialize_u64 => visit_u64(u64, "a non-negative integer"),
+        deserialize_f32 => visit_f64(f64, "a number"),
+        deserialize_f64 => visit_f64(f64, "a number"),
+    }
+
+    // A present value is never `None`; TOML has no null.
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.value.deserialize_enum(name, variants, visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct seq tuple tuple_struct
+        map struct identifier ignored_any
+    }
+}
+
+/// The entries of a table, each deserialized as a [`Layered`] value.
+struct LayeredTable<'a> {
+    entries: toml::map::IntoIter<String, toml::Value>,
+    /// Key and value of the entry whose name was just returned.
+    next: Option<(String, toml::Value)>,
+    prefix: String,
+    sources: &'a BTreeMap<String, ConfigSource>,
+}
+
+impl<'de> MapAccess<'de> for LayeredTable<'_> {
+    type Error = toml::de::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some((name, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+        self.next = Some((join_key(&self.prefix, &name), value));
+        seed.deserialize(name.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (key, value) = self
+            .next
+            .take()
+            .expect("serde asks for a value after its key");
+        seed.deserialize(Layered {
+            value,
+            key,
+            sources: self.sources,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    static NEXT_TEMP_ID: AtomicU64 = AtomicU64::new(0);
+
+    fn temp_dir() -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!(
+            "cli-core-config-{}-{}-{}",
+            std::process::id(),
+            nanos,
+            NEXT_TEMP_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn source_of(layered: &LayeredConfig, key: &str) -> ConfigSource {
+        layered
+            .entries()
+            .into_iter()
+            .find(|entry| entry.key == key)
+            .unwrap()
+            .source
+    }
+
+    #[test]
+    fn defaults_apply_without_files_or_env() {
+        let dir = temp_dir();
+        let layered = LayeredConfig::load_from(None, &dir, Vec::new()).unwrap();
+        let config = layered.config().unwrap();
+
+        assert_eq!(config.hourly_rate, 10_030.0);
+        assert_eq!(config.work_summary.max_session_gap_hours, 4.0);
+        assert_eq!(source_of(&layered, "hourly_rate"), ConfigSource::Default);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn layers_override_in_precedence_order() {
+        let dir = temp_dir();
+        let user = dir.join("user.toml");
+        fs::write(
+            &user,
+            "hourly_rate = 30000\ncurrency = \"USD\"\n[code_cost]\nlines_per_hour = 15\n",
+        )
+        .unwrap();
+        let repo = dir.join("repo");
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        fs::write(
+            repo.join(REPO_CONFIG_FILE),
+            "hourly_rate = 40000\n[git_tools.health]\nskip = [\"Docker\"]\n",
+        )
+        .unwrap();
+        let env = vec![
+            ("CLI_TOOLS_CURRENCY".to_string(), "EUR".to_string()),
+            ("UNRELATED".to_string(), "1".to_string()),
+        ];
+
+        let mut layered = LayeredConfig::load_from(Some(&user), &repo, env).unwrap();
+        layered.set_flag("code_cost.lines_per_hour", 25.0);
+        let config = layered.config().unwrap();
+
+        assert_eq!(config.hourly_rate, 40_000.0);
+        assert_eq!(config.currency.code(), "EUR");
+        assert_eq!(config.code_cost.lines_per_hour, 25.0);
+        assert_eq!(config.git_tools.health.skip, vec!["Docker".to_string()]);
+        assert_eq!(source_of(&layered, "hourly_rate"), ConfigSource::Repository);
+        assert_eq!(source_of(&layered, "currency"), ConfigSource::Env);
+        assert_eq!(
+            source_of(&layered, "code_cost.lines_per_hour"),
+            ConfigSource::Flag
+        );
+        assert_eq!(
+            source_of(&layered, "work_summary.time_weight"),
+            ConfigSource::Default
+        );
+        assert_eq!(layered.files().len(), 2);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn nested_env_keys_use_double_underscore() {
+        let dir = temp_dir();
+        let env = vec![(
+            "CLI_TOOLS_WORK_SUMMARY__MAX_SESSION_GAP_HOURS".to_string(),
+            "2.5".to_string(),
+        )];
+
+        let config = LayeredConfig::load_from(None, &dir, env)
+            .unwrap()
+            .config()
+            .unwrap();
+
+        assert_eq!(config.work_summary.max_session_gap_hours, 2.5);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn repo_config_search_stops_at_repository_root() {
+        let dir = temp_dir();
+        fs::write(dir.join(REPO_CONFIG_FILE), "hourly_rate = 1\n").unwrap();
+        let repo = dir.join("repo");
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        fs::create_dir_all(repo.join("src")).unwrap();
+
+        assert_eq!(find_repo_config(&repo.join("src")), None);
+
+        fs::write(repo.join(REPO_CONFIG_FILE), "hourly_rate = 2\n").unwrap();
+        assert_eq!(
+            find_repo_config(&repo.join("src")),
+            Some(repo.canonicalize().unwrap().join(REPO_CONFIG_FILE))
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn git_tools_thresholds_are_unset_by_default() {
+        let dir = temp_dir();
+        let env = vec![(
+            "CLI_TOOLS_GIT_TOOLS__HEALTH__MIN_SCORE".to_string(),
+            "80".to_string(),
+        )];
+
+        let mut layered = LayeredConfig::load_from(None, &dir, env).unwrap();
+        layered.set_flag("git_tools.scan.max_markers", 0);
+        let config = layered.config().unwrap();
+
+        assert_eq!(config.git_tools.health.min_score, Some(80.0));
+        assert_eq!(config.git_tools.scan.max_markers, Some(0));
+        assert_eq!(config.git_tools.env.max_missing, None);
+        assert_eq!(config.git_tools.cleanup.max_merged, None);
+        assert!(config.git_tools.health.checks.is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn custom_health_checks_parse_each_rule() {
+        let dir = temp_dir();
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::write(
+            dir.join(REPO_CONFIG_FILE),
+            "[git_tools.health]\npacks = [\"node\"]\n\n\
+             [[git_tools.health.checks]]\nname = \"nvmrc\"\nfile_exists = \".nvmrc\"\nseverity = \"error\"\nweight = 2\n\n\
+             [[git_tools.health.checks]]\nname = \"No keys\"\nglob_absent = \"**/*.pem\"\n\n\
+             [[git_tools.health.checks]]\nname = \"Strict\"\nfile = \"tsconfig.json\"\nregex = \"strict\"\n",
+        )
+        .unwrap();
+
+        let config = Config::load(&dir).unwrap();
+        let health = config.git_tools.health;
+
+        assert_eq!(health.packs, vec![Ecosystem::Node]);
+        assert_eq!(health.checks.len(), 3);
+        assert_eq!(health.checks[0].severity, Severity::Error);
+        assert_eq!(health.checks[0].weight, 2.0);
+        assert_eq!(health.checks[1].severity, Severity::Warning);
+        assert_eq!(health.checks[1].weight, 1.0);
+        assert_eq!(
+            health.checks[2].rule,
+            CheckRule::RegexPresent {
+                file: "tsconfig.json".to_string(),
+                regex: "strict".to_string()
+            }
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn estimation_model_drivers_accept_ratings_or_values() {
+        let dir = temp_dir();
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::write(
+            dir.join(REPO_CONFIG_FILE),
+            "[code_cost]\nmodel = \"cocomo81-intermediate\"\n\n\
+             [code_cost.cocomo81]\nmode = \"semi-detached\"\ncost_drivers = { RELY = \"very_high\", TOOL = 0.95 }\n\n\
+             [code_cost.cocomo2.scale_factors]\nPREC = \"high\"\nPMAT = 3\n",
+        )
+        .unwrap();
+
+        let mut layered = LayeredConfig::load(&dir).unwrap();
+        layered.set_flag("code_cost.model", "cocomo2");
+        let code_cost = layered.config().unwrap().code_cost;
+
+        assert_eq!(code_cost.model, EstimationModelKind::Cocomo2);
+        assert_eq!(code_cost.cocomo81.mode, CocomoMode::SemiDetached);
+        assert_eq!(
+            code_cost.cocomo81.cost_drivers["RELY"],
+            DriverValue::Rating(Rating::VeryHigh)
+        );
+        assert_eq!(code_cost.cocomo81.cost_drivers["TOOL"], DriverValue::Value(0.95));
+        assert_eq!(
+            code_cost.cocomo2.scale_factors["PREC"],
+            DriverValue::Rating(Rating::High)
+        );
+        assert_eq!(code_cost.cocomo2.scale_factors["PMAT"], DriverValue::Value(3.0));
+        assert_eq!(code_cost.custom_model.a, 2.4);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn llm_models_flag_replaces_the_configured_list() {
+        let dir = temp_dir();
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::write(
+            dir.join(REPO_CONFIG_FILE),
+            "[code_cost]\nllm_models = [\"gpt-4o\"]\nllm_pricing = \"prices.toml\"\n",
+        )
+        .unwrap();
+
+        let mut layered = LayeredConfig::load(&dir).unwrap();
+        assert_eq!(layered.config().unwrap().code_cost.llm_models, ["gpt-4o"]);
+
+        layered.set_flag("code_cost.llm_models", vec!["claude-opus-4-7", "gpt-5"]);
+        let code_cost = layered.config().unwrap().code_cost;
+        assert_eq!(code_cost.llm_models, ["claude-opus-4-7", "gpt-5"]);
+        assert_eq!(code_cost.llm_pricing, Some(PathBuf::from("prices.toml")));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn dependency_hours_default_individually() {
+        let dir = temp_dir();
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::write(
+            dir.join(REPO_CONFIG_FILE),
+            "[code_cost.dependencies]
+transitive_hours = 0
+",
+        )
+        .unwrap();
+
+        let dependencies = LayeredConfig::load(&dir)
+            .unwrap()
+            .config()
+            .unwrap()
+            .code_cost
+            .dependencies;
+        assert_eq!(dependencies.direct_hours, 4.0);
+        assert_eq!(dependencies.transitive_hours, 0.0);
+        assert_eq!(dependencies.duplicate_hours, 2.0);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn duplication_discount_from_repository_config() {
+        let dir = temp_dir();
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::write(
+            dir.join(REPO_CONFIG_FILE),
+            "[code_cost.duplication]
+discount = 1.0
+",
+        )
+        .unwrap();
+
+        let config = LayeredConfig::load(&dir).unwrap().config().unwrap();
+        assert_eq!(config.code_cost.duplication.discount, 1.0);
+        assert_eq!(CodeCostConfig::default().duplication.discount, 0.75);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn identities_match_aliases() {
+        let dir = temp_dir();
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::write(
+            dir.join(REPO_CONFIG_FILE),
+            r#"[[identities]]
+name = "Jane Doe"
+email = "jane@example.com"
+aliases = ["Jane@Personal.example", "jdoe"]
+"#,
+        )
+        .unwrap();
+
+        let config = LayeredConfig::load(&dir).unwrap().config().unwrap();
+        let canonical = |name: &str, email: &str| {
+            config
+                .identity(name, email)
+                .map(|identity| identity.email.as_str())
+        };
+        let jane = Some("jane@example.com");
+        assert_eq!(canonical("J. Doe", "JANE@example.com"), jane);
+        assert_eq!(canonical("Jane", "jane@personal.example"), jane);
+        assert_eq!(canonical("jdoe", "jdoe@build.local"), jane);
+        assert_eq!(canonical("Jane Doe", "someone@example.com"), None);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn env_values_take_the_type_of_their_setting() {
+        let dir = temp_dir();
+        let env = [
+            ("CLI_TOOLS_HOURLY_RATE", "25"),
+            ("CLI_TOOLS_CODE_COST__LINES_PER_HOUR", "12.5"),
+            ("CLI_TOOLS_CODE_COST__LANGUAGE_WEIGHTS__RUST", "2"),
+            ("CLI_TOOLS_GIT_TOOLS__ENV__MAX_MISSING", "3"),
+            ("CLI_TOOLS_REPORT_CURRENCY", "usd"),
+            // Text settings keep text that looks like a number or a boolean.
+            ("CLI_TOOLS_ZZZ__LOG_ROOT", "2024"),
+            ("CLI_TOOLS_CODE_COST__LLM_PRICING", "true"),
+        ]
+        .map(|(name, value)| (name.to_string(), value.to_string()));
+
+        let config = LayeredConfig::load_from(None, &dir, env)
+            .unwrap()
+            .config()
+            .unwrap();
+
+        assert_eq!(config.hourly_rate, 25.0);
+        assert_eq!(config.code_cost.lines_per_hour, 12.5);
+        assert_eq!(config.code_cost.language_weights["rust"], 2.0);
+        assert_eq!(config.git_tools.env.max_missing, Some(3));
+        assert_eq!(config.zzz.log_root, Some(PathBuf::from("2024")));
+        assert_eq!(config.code_cost.llm_pricing, Some(PathBuf::from("true")));
+        assert_eq!(config.report_currency, Some(Currency::USD));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn env_values_that_do_not_parse_name_the_variable() {
+        let dir = temp_dir();
+        let env = vec![(
+            "CLI_TOOLS_GIT_TOOLS__ENV__MAX_MISSING".to_string(),
+            "-1".to_string(),
+        )];
+
+        let error = LayeredConfig::load_from(None, &dir, env)
+            .unwrap()
+            .config()
+            .unwrap_err();
+
+        assert!(
+            format!("{error:#}").contains(
+                "CLI_TOOLS_GIT_TOOLS__ENV__MAX_MISSING=\"-1\" is not a non-negative integer"
+            ),
+            "{error:#}"
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn file_values_are_not_coerced() {
+        let dir = temp_dir();
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::write(dir.join(REPO_CONFIG_FILE), "hourly_rate = \"25\"\n").unwrap();
+
+        let layered = LayeredConfig::load_from(None, &dir, Vec::new()).unwrap();
+
+        assert!(layered.config().is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn invalid_currency_codes_fail_when_loading() {
+        let dir = temp_dir();
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::write(dir.join(REPO_CONFIG_FILE), "currency = \"EURO\"\n").unwrap();
+
+        let layered = LayeredConfig::load_from(None, &dir, Vec::new()).unwrap();
+        let error = layered.config().unwrap_err();
+
+        assert!(format!("{error:#}").contains("Invalid currency code: EURO"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn invalid_values_name_the_configuration() {
+        let dir = temp_dir();
+        let env = vec![("CLI_TOOLS_HOURLY_RATE".to_string(), "lots".to_string())];
+
+        let error = LayeredConfig::load_from(None, &dir, env)
+            .unwrap()
+            .config()
+            .unwrap_err();
+
+        assert_eq!(error.to_string(), "Invalid cli-tools configuration");
+        fs::remove_dir_all(dir).unwrap();
     }
 }
@@ -0,0 +1,463 @@
+//! Money in more than one currency.
+//!
+//! Amounts are formatted in the convention of the currency's home market
+//! (`₩1,234`, `$1,234.56`, `1.234,56 €`) and converted with an offline
+//! exchange-rate table. The table lives next to the user configuration in
+//! `rates.toml`; `dev-tools currency --update-rates` refreshes it, and until
+//! then an approximate built-in table is used.
+//!
+//! ```toml
+//! base = "USD"
+//! date = "2026-10-16"
+//!
+//! [rates]
+//! EUR = 0.92
+//! KRW = 1400.0
+//! ```
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::config::{user_config_path, Config, LayeredConfig};
+use crate::output::format_integer;
+
+/// File name of the exchange-rate table, next to the user configuration.
+pub const RATES_FILE: &str = "rates.toml";
+
+/// An ISO 4217 currency code such as `KRW` or `USD`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Currency([u8; 3]);
+
+impl Currency {
+    pub const KRW: Currency = Currency(*b"KRW");
+    pub const USD: Currency = Currency(*b"USD");
+
+    pub fn code(&self) -> &str {
+        std::str::from_utf8(&self.0).expect("currency codes are ASCII")
+    }
+
+    /// Format `amount` the way the currency's home market writes it, rounded
+    /// to its minor unit. Unknown currencies get the code after the number.
+    pub fn format(&self, amount: f64) -> String {
+        let style = self.style();
+        let scale = 10f64.powi(style.decimals as i32);
+        let minor = (amount.abs() * scale).round() as i64;
+        let mut number = format_integer(minor / scale as i64).replace(',', style.group);
+        if style.decimals > 0 {
+            number.push_str(style.decimal);
+            number.push_str(&format!(
+                "{:0width$}",
+                minor % scale as i64,
+                width = style.decimals
+            ));
+        }
+        let sign = if amount < 0.0 && minor != 0 { "-" } else { "" };
+        match style.symbol {
+            Some(symbol) if style.prefix => format!("{sign}{symbol}{number}"),
+            Some(symbol) => format!("{sign}{number}\u{a0}{symbol}"),
+            None => format!("{sign}{number} {}", self.code()),
+        }
+    }
+
+    fn style(&self) -> &'static Style {
+        STYLES
+            .iter()
+            .find(|style| style.code == self.code())
+            .unwrap_or(&FALLBACK)
+    }
+}
+
+impl Default for Currency {
+    fn default() -> Self {
+        Self::KRW
+    }
+}
+
+impl FromStr for Currency {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let code = s.trim().to_ascii_uppercase();
+        match <[u8; 3]>::try_from(code.as_bytes()) {
+            Ok(bytes) if bytes.iter().all(u8::is_ascii_uppercase) => Ok(Self(bytes)),
+            _ => bail!("Invalid currency code: {s} (expected three letters, e.g. USD)"),
+        }
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl Serialize for Currency {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        code.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// How a currency's home market writes amounts.
+struct Style {
+    code: &'static str,
+    symbol: Option<&'static str>,
+    /// Symbol before the number; otherwise after it with a space.
+    prefix: bool,
+    decimals: usize,
+    group: &'static str,
+    decimal: &'static str,
+}
+
+const fn style(
+    code: &'static str,
+    symbol: &'static str,
+    prefix: bool,
+    decimals: usize,
+    group: &'static str,
+    decimal: &'static str,
+) -> Style {
+    Style {
+        code,
+        symbol: Some(symbol),
+        prefix,
+        decimals,
+        group,
+        decimal,
+    }
+}
+
+const STYLES: &[Style] = &[
+    style("KRW", "₩", true, 0, ",", "."),
+    style("USD", "$", true, 2, ",", "."),
+    style("EUR", "€", false, 2, ".", ","),
+    style("JPY", "¥", true, 0, ",", "."),
+    style("GBP", "£", true, 2, ",", "."),
+    style("CNY", "CN¥", true, 2, ",", "."),
+    style("CAD", "CA$", true, 2, ",", "."),
+    style("AUD", "A$", true, 2, ",", "."),
+    style("CHF", "CHF", false, 2, "’", "."),
+    style("SGD", "S$", true, 2, ",", "."),
+    style("INR", "₹", true, 2, ",", "."),
+];
+
+const FALLBACK: Style = Style {
+    code: "",
+    symbol: None,
+    prefix: false,
+    decimals: 2,
+    group: ",",
+    decimal: ".",
+};
+
+/// A number with an optional currency, as `--hourly-rate` takes it:
+/// `45USD`, `45 usd`, `10,030` or `10030KRW`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Amount {
+    pub value: f64,
+    pub currency: Option<Currency>,
+}
+
+impl FromStr for Amount {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let split = s.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(s.len());
+        let (number, code) = s.split_at(split);
+        let value: f64 = number
+            .trim()
+            .replace([',', '_'], "")
+            .parse()
+            .with_context(|| format!("Invalid amount: {s} (expected e.g. 45USD or 10030)"))?;
+        if !value.is_finite() || value < 0.0 {
+            bail!("Invalid amount: {s} (expected a positive number)");
+        }
+        let currency = match code.trim() {
+            "" => None,
+            code => Some(code.parse()?),
+        };
+        Ok(Self { value, currency })
+    }
+}
+
+/// Apply `--hourly-rate` and `--currency` to `layered`. A rate with a
+/// currency also sets `currency`, the currency `hourly_rate` is in.
+pub fn set_flags(
+    layered: &mut LayeredConfig,
+    hourly_rate: Option<Amount>,
+    report_currency: Option<Currency>,
+) {
+    if let Some(rate) = hourly_rate {
+        layered.set_flag("hourly_rate", rate.value);
+        if let Some(currency) = rate.currency {
+            layered.set_flag("currency", currency.code());
+        }
+    }
+    if let Some(currency) = report_currency {
+        layered.set_flag("report_currency", currency.code());
+    }
+}
+
+/// Exchange rates: how much of each currency one unit of `base` buys.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rates {
+    pub base: Currency,
+    /// Day the rates were published.
+    pub date: String,
+    pub rates: BTreeMap<Currency, f64>,
+    /// Where the table was read from; `None` for the built-in one.
+    #[serde(skip)]
+    pub source: Option<PathBuf>,
+}
+
+impl Rates {
+    /// The rates file if there is one, otherwise the built-in table.
+    pub fn load() -> Result<Self> {
+        match rates_path() {
+            Some(path) if path.is_file() => Self::load_from(&path),
+            _ => Ok(Self::builtin()),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut rates: Self = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        rates.source = Some(path.to_path_buf());
+        Ok(rates)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let content = format!(
+            "# Exchange rates for cli-tools: units of each currency per 1 {}.\n{}",
+            self.base,
+            toml::to_string(self)?
+        );
+        fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Approximate rates shipped with the tools, for use before the first
+    /// `dev-tools currency --update-rates`.
+    pub fn builtin() -> Self {
+        let rates = [
+            ("AUD", 1.52),
+            ("CAD", 1.36),
+            ("CHF", 0.88),
+            ("CNY", 7.2),
+            ("EUR", 0.92),
+            ("GBP", 0.79),
+            ("INR", 83.0),
+            ("JPY", 150.0),
+            ("KRW", 1400.0),
+            ("SGD", 1.34),
+        ];
+        Self {
+            base: Currency::USD,
+            date: "2025-01-01".to_string(),
+            rates: rates
+                .into_iter()
+                .map(|(code, rate)| (code.parse().expect("built-in codes are valid"), rate))
+                .collect(),
+            source: None,
+        }
+    }
+
+    /// Units of `to` that one unit of `from` buys.
+    pub fn rate(&self, from: Currency, to: Currency) -> Result<f64> {
+        if from == to {
+            return Ok(1.0);
+        }
+        Ok(self.per_base(to)? / self.per_base(from)?)
+    }
+
+    pub fn convert(&self, amount: f64, from: Currency, to: Currency) -> Result<f64> {
+        Ok(amount * self.rate(from, to)?)
+    }
+
+    /// "rates of 2026-10-16" or "built-in rates of 2025-01-01".
+    pub fn describe(&self) -> String {
+        match self.source {
+            Some(_) => format!("rates of {}", self.date),
+            None => format!("built-in rates of {}", self.date),
+        }
+    }
+
+    fn per_base(&self, currency: Currency) -> Result<f64> {
+        if currency == self.base {
+            return Ok(1.0);
+        }
+        match self.rates.get(&currency) {
+            Some(&rate) if rate > 0.0 => Ok(rate),
+            _ => bail!(
+                "No exchange rate for {currency} in the {}; add it to {} or run `dev-tools currency --update-rates`",
+                self.describe(),
+                rates_path()
+                    .map(|path| path.display().to_string())
+                    .unwrap_or_else(|| RATES_FILE.to_string())
+            ),
+        }
+    }
+}
+
+/// Path of the exchange-rate table, next to the user configuration.
+pub fn rates_path() -> Option<PathBuf> {
+    Some(user_config_path()?.with_file_name(RATES_FILE))
+}
+
+/// The currency a tool reports in, its hourly rate in that currency, and the
+/// rates to bring other amounts into it.
+#[derive(Debug, Clone)]
+pub struct Pricing {
+    pub currency: Currency,
+    pub hourly_rate: f64,
+    pub rates: Rates,
+}
+
+impl Pricing {
+    /// Report in `report_currency`, else in `currency`, converting
+    /// `hourly_rate` from `currency` when they differ.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        Self::with_rates(config, Rates::load()?)
+    }
+
+    pub fn with_rates(config: &Config, rates: Rates) -> Result<Self> {
+        let rate_currency = config.currency;
+        let currency = config.report_currency.unwrap_or(rate_currency);
+        Ok(Self {
+            currency,
+            hourly_rate: rates.convert(config.hourly_rate, rate_currency, currency)?,
+            rates,
+        })
+    }
+
+    /// `amount` of `from` in the reporting currency.
+    pub fn convert(&self, amount: f64, from: Currency) -> Result<f64> {
+        self.rates.convert(amount, from, self.currency)
+    }
+
+    pub fn format(&self, amount: f64) -> String {
+        self.currency.format(amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn currency(code: &str) -> Currency {
+        code.parse().unwrap()
+    }
+
+    fn rates() -> Rates {
+        Rates {
+            base: Currency::USD,
+            date: "2026-01-02".to_string(),
+            rates: [(Currency::KRW, 1400.0), (currency("EUR"), 0.8)]
+                .into_iter()
+                .collect(),
+            source: None,
+        }
+    }
+
+    #[test]
+    fn formats_in_each_home_convention() {
+        assert_eq!(Currency::KRW.format(1_234_567.4), "₩1,234,567");
+        assert_eq!(Currency::USD.format(1_234.5), "$1,234.50");
+        assert_eq!(currency("EUR").format(1_234.567), "1.234,57\u{a0}€");
+        assert_eq!(currency("JPY").format(999.5), "¥1,000");
+        assert_eq!(Currency::USD.format(-0.004), "$0.00");
+        assert_eq!(Currency::USD.format(-12.0), "-$12.00");
+        assert_eq!(currency("NZD").format(1_000.0), "1,000.00 NZD");
+    }
+
+    #[test]
+    fn currency_codes_are_three_letters() {
+        assert_eq!(currency(" usd ").code(), "USD");
+        assert!("US".parse::<Currency>().is_err());
+        assert!("US1".parse::<Currency>().is_err());
+        assert_eq!(serde_json::to_string(&Currency::KRW).unwrap(), "\"KRW\"");
+    }
+
+    #[test]
+    fn amounts_take_an_optional_currency_suffix() {
+        let amount: Amount = "45USD".parse().unwrap();
+        assert_eq!((amount.value, amount.currency), (45.0, Some(Currency::USD)));
+        let amount: Amount = "10,030".parse().unwrap();
+        assert_eq!((amount.value, amount.currency), (10_030.0, None));
+        let amount: Amount = "37.5 eur".parse().unwrap();
+        assert_eq!(amount.currency, Some(currency("EUR")));
+        assert!("USD".parse::<Amount>().is_err());
+        assert!("-5USD".parse::<Amount>().is_err());
+    }
+
+    #[test]
+    fn converts_through_the_base_currency() {
+        let rates = rates();
+        assert_eq!(
+            rates
+                .convert(14_000.0, Currency::KRW, Currency::USD)
+                .unwrap(),
+            10.0
+        );
+        assert_eq!(
+            rates.convert(10.0, Currency::USD, currency("EUR")).unwrap(),
+            8.0
+        );
+        assert_eq!(
+            rates
+                .convert(5.0, currency("XYZ"), currency("XYZ"))
+                .unwrap(),
+            5.0
+        );
+        let error = rates
+            .convert(1.0, Currency::USD, currency("JPY"))
+            .unwrap_err();
+        assert!(error.to_string().starts_with("No exchange rate for JPY"));
+    }
+
+    #[test]
+    fn pricing_converts_the_hourly_rate_into_the_report_currency() {
+        let mut config = Config::new(45.0, Currency::USD);
+        let pricing = Pricing::with_rates(&config, rates()).unwrap();
+        assert_eq!(
+            (pricing.currency, pricing.hourly_rate),
+            (Currency::USD, 45.0)
+        );
+
+        config.report_currency = Some(Currency::KRW);
+        let pricing = Pricing::with_rates(&config, rates()).unwrap();
+        assert_eq!(
+            (pricing.currency, pricing.hourly_rate),
+            (Currency::KRW, 63_000.0)
+        );
+        assert_eq!(pricing.format(pricing.hourly_rate), "₩63,000");
+    }
+
+    #[test]
+    fn rates_round_trip_through_toml() {
+        let dir = std::env::temp_dir().join(format!("cli-core-rates-{}", std::process::id()));
+        let path = dir.join(RATES_FILE);
+        rates().save(&path).unwrap();
+        let loaded = Rates::load_from(&path).unwrap();
+        assert_eq!(loaded.rates, rates().rates);
+        assert_eq!(loaded.describe(), "rates of 2026-01-02");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,8 +1,10 @@
 pub mod command_log;
 pub mod config;
+pub mod currency;
 pub mod date_range;
 pub mod output;
+pub mod scaffold;
 pub mod ui;
 
-pub use config::Config;
+pub use config::{Config, ConfigSource, LayeredConfig};
 pub use output::{ExportFormat, Formatter, OutputFormat};
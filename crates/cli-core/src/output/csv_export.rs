@@ -1,6 +1,9 @@
 use serde::Serialize;
+use serde_json::Value;
 use anyhow::Result;
+use indexmap::IndexMap;
 use std::fs::File;
+use super::Formatter;
 
 pub struct CsvExporter;
 
@@ -27,3 +30,87 @@ impl Default for CsvExporter {
         Self::new()
     }
 }
+
+/// Formats a list of records as CSV text.
+///
+/// Columns follow the field order of the records. Nested values are written
+/// as compact JSON, so any serializable row can be exported.
+pub struct CsvFormatter;
+
+impl CsvFormatter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for CsvFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Serialize> Formatter<T> for CsvFormatter {
+    fn format(&self, data: &T) -> Result<String> {
+        // Round-trip through JSON text so the records keep their field order.
+        let json = serde_json::to_string(data)?;
+        let records: Vec<IndexMap<String, Value>> = match serde_json::from_str(&json)? {
+            Value::Array(_) => serde_json::from_str(&json)?,
+            Value::Object(_) => vec![serde_json::from_str(&json)?],
+            _ => anyhow::bail!("CSV output needs a list of records"),
+        };
+
+        let mut headers: Vec<&String> = Vec::new();
+        for key in records.iter().flat_map(|record| record.keys()) {
+            if !headers.contains(&key) {
+                headers.push(key);
+            }
+        }
+
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(&headers)?;
+        for record in &records {
+            writer.write_record(headers.iter().map(|key| cell(record.get(*key))))?;
+        }
+
+        Ok(String::from_utf8(writer.into_inner()?)?)
+    }
+}
+
+fn cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Row {
+        path: &'static str,
+        lines: usize,
+        tags: Vec<&'static str>,
+        owner: Option<&'static str>,
+    }
+
+    #[test]
+    fn formats_records_in_field_order() {
+        let rows = vec![
+            Row { path: "b, c", lines: 10, tags: vec!["x"], owner: None },
+            Row { path: "a", lines: 2, tags: vec![], owner: Some("me") },
+        ];
+
+        assert_eq!(
+            CsvFormatter::new().format(&rows).unwrap(),
+            "path,lines,tags,owner\n\"b, c\",10,\"[\"\"x\"\"]\",\na,2,[],me\n"
+        );
+    }
+
+    #[test]
+    fn rejects_scalars() {
+        assert!(CsvFormatter::new().format(&42).is_err());
+    }
+}
@@ -1,12 +1,3 @@
-/// Korean Won currency formatting with comma separators.
-///
-/// Formats a monetary value in KRW with `₩` prefix and comma grouping.
-/// For display-only use; not suitable for accounting precision.
-pub fn format_currency_krw(value: f64) -> String {
-    let integer = value.round() as i64;
-    format!("₩{}", format_integer(integer))
-}
-
 /// Format an unsigned integer with comma separators.
 ///
 /// ```ignore
@@ -34,13 +25,6 @@ pub fn format_integer(value: i64) -> String {
 mod tests {
     use super::*;
 
-    #[test]
-    fn currency_adds_won_sign_and_comma() {
-        assert_eq!(format_currency_krw(1_000_000.0), "₩1,000,000");
-        assert_eq!(format_currency_krw(0.0), "₩0");
-        assert_eq!(format_currency_krw(999.9), "₩1,000");
-    }
-
     #[test]
     fn integer_handles_large_and_small() {
         assert_eq!(format_integer(0), "0");
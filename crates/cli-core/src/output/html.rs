@@ -1,7 +1,26 @@
-use serde::Serialize;
-use anyhow::Result;
+use anyhow::{Context, Result};
+use askama::Template;
 use std::fs;
 
+use super::report::{Report, ReportTable, SectionContent};
+
+#[derive(Template)]
+#[template(path = "report.html")]
+struct ReportTemplate<'a> {
+    report: &'a Report,
+}
+
+impl ReportTemplate<'_> {
+    // Template method arguments arrive borrowed, hence `&usize`.
+    fn numeric(&self, table: &ReportTable, column: &usize) -> bool {
+        table.is_numeric(*column)
+    }
+}
+
+/// Renders a [`Report`] as a single self-contained HTML file.
+///
+/// Styles and charts are inlined so the file opens offline and can be handed
+/// to clients as-is.
 pub struct HtmlExporter;
 
 impl HtmlExporter {
@@ -9,64 +28,15 @@ impl HtmlExporter {
         Self
     }
 
-    pub fn export<T: Serialize>(&self, data: &T, path: &str) -> Result<()> {
-        let json_data = serde_json::to_string_pretty(data)?;
-
-        let html = format!(
-            r#"<!DOCTYPE html>
-<html lang="ko">
-<head>
-    <meta charset="UTF-8">
-    <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <title>Code Cost Analysis Report</title>
-    <style>
-        body {{
-            font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif;
-            max-width: 1200px;
-            margin: 0 auto;
-            padding: 20px;
-            background: #f5f5f5;
-        }}
-        .container {{
-            background: white;
-            border-radius: 8px;
-            box-shadow: 0 2px 8px rgba(0,0,0,0.1);
-            padding: 30px;
-        }}
-        h1 {{
-            color: #2c3e50;
-            border-bottom: 3px solid #3498db;
-            padding-bottom: 10px;
-        }}
-        pre {{
-            background: #f8f9fa;
-            border: 1px solid #dee2e6;
-            border-radius: 4px;
-            padding: 15px;
-            overflow-x: auto;
-        }}
-        .footer {{
-            margin-top: 30px;
-            text-align: center;
-            color: #7f8c8d;
-            font-size: 14px;
-        }}
-    </style>
-</head>
-<body>
-    <div class="container">
-        <h1>📊 Code Cost Analysis Report</h1>
-        <pre>{}</pre>
-        <div class="footer">
-            Generated by <strong>code-cost</strong> - CLI Tools Collection
-        </div>
-    </div>
-</body>
-</html>"#,
-            json_data
-        );
+    pub fn render(&self, report: &Report) -> Result<String> {
+        ReportTemplate { report }
+            .render()
+            .context("Failed to render HTML report")
+    }
 
-        fs::write(path, html)?;
+    pub fn export(&self, report: &Report, path: &str) -> Result<()> {
+        let html = self.render(report)?;
+        fs::write(path, html).with_context(|| format!("Failed to write HTML report: {path}"))?;
         Ok(())
     }
 }
@@ -76,3 +46,44 @@ impl Default for HtmlExporter {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::report::{Card, Histogram};
+
+    fn sample_report() -> Report {
+        let mut report = Report::new("Sample <Report>", "code-cost").with_subtitle("2 repositories");
+        report.push(
+            "Summary",
+            SectionContent::Cards(vec![Card::new("Lines", "1,234").with_detail("code only")]),
+        );
+        let mut table = ReportTable::new(&["Language", ">Lines"]);
+        table.row(vec!["Rust".into(), "1,000".into()]);
+        report.push_sub(
+            "Languages",
+            SectionContent::Table(table.with_footer(vec!["Total".into(), "1,000".into()])),
+        );
+        report.push(
+            "Activity",
+            SectionContent::Histogram(Histogram::new(vec!["Mon".into()], vec![3.0])),
+        );
+        report
+    }
+
+    #[test]
+    fn renders_sections_without_external_resources() {
+        let html = HtmlExporter::new().render(&sample_report()).unwrap();
+
+        assert!(html.contains("<title>Sample &lt;Report&gt;</title>"));
+        assert!(html.contains("<p class=\"subtitle\">2 repositories</p>"));
+        assert!(html.contains("<div class=\"value\">1,234</div>"));
+        assert!(html.contains("<h3>Languages</h3>"));
+        assert!(html.contains("<td class=\"num\">1,000</td>"));
+        assert!(html.contains("<tfoot>"));
+        assert!(html.contains("<svg class=\"histogram\""));
+        assert!(html.contains("<strong>code-cost</strong>"));
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("http://") && !html.contains("https://"));
+    }
+}
@@ -1,7 +1,13 @@
-use serde::Serialize;
-use anyhow::Result;
+use anyhow::{Context, Result};
+use std::fmt::Write;
 use std::fs;
 
+use super::report::{Card, Histogram, Report, ReportTable, SectionContent};
+
+/// Renders a [`Report`] as GitHub-flavoured Markdown.
+///
+/// Output depends only on the report contents, so exports from two runs can
+/// be diffed directly.
 pub struct MarkdownExporter;
 
 impl MarkdownExporter {
@@ -9,26 +15,38 @@ impl MarkdownExporter {
         Self
     }
 
-    pub fn export<T: Serialize>(&self, data: &T, path: &str) -> Result<()> {
-        let json_data = serde_json::to_string_pretty(data)?;
-
-        let markdown = format!(
-            r#"# Code Cost Analysis Report
+    pub fn render(&self, report: &Report) -> String {
+        let mut out = String::new();
 
-## Analysis Results
+        let _ = writeln!(out, "# {}", escape(&report.title));
+        if let Some(subtitle) = &report.subtitle {
+            let _ = writeln!(out, "\n_{}_", escape(subtitle));
+        }
 
-```json
-{}
-```
+        for section in &report.sections {
+            let hashes = "#".repeat(section.level as usize);
+            let _ = writeln!(out, "\n{} {}\n", hashes, escape(&section.heading));
+            match &section.content {
+                SectionContent::Cards(cards) => render_cards(&mut out, cards),
+                SectionContent::Table(table) => render_table(&mut out, table),
+                SectionContent::Histogram(histogram) => render_histogram(&mut out, histogram),
+                SectionContent::Text(text) => {
+                    let _ = writeln!(out, "{}", escape(text));
+                }
+            }
+        }
 
----
-
-*Generated by **code-cost** - CLI Tools Collection*
-"#,
-            json_data
+        let _ = writeln!(
+            out,
+            "\n---\n\n*Generated by **{}** - CLI Tools Collection*",
+            report.tool
         );
+        out
+    }
 
-        fs::write(path, markdown)?;
+    pub fn export(&self, report: &Report, path: &str) -> Result<()> {
+        fs::write(path, self.render(report))
+            .with_context(|| format!("Failed to write Markdown report: {path}"))?;
         Ok(())
     }
 }
@@ -38,3 +56,110 @@ impl Default for MarkdownExporter {
         Self::new()
     }
 }
+
+fn render_cards(out: &mut String, cards: &[Card]) {
+    for card in cards {
+        let _ = write!(out, "- **{}:** {}", escape(&card.label), escape(&card.value));
+        if let Some(detail) = &card.detail {
+            let _ = write!(out, " ({})", escape(detail));
+        }
+        out.push('\n');
+    }
+}
+
+fn render_table(out: &mut String, table: &ReportTable) {
+    write_row(out, &table.headers);
+    let align: Vec<String> = (0..table.headers.len())
+        .map(|i| if table.is_numeric(i) { "---:" } else { "---" }.to_string())
+        .collect();
+    write_row(out, &align);
+    for row in &table.rows {
+        write_row(out, row);
+    }
+    if let Some(footer) = &table.footer {
+        let bold: Vec<String> = footer
+            .iter()
+            .map(|cell| {
+                if cell.is_empty() {
+                    String::new()
+                } else {
+                    format!("**{cell}**")
+                }
+            })
+            .collect();
+        write_row(out, &bold);
+    }
+}
+
+// Charts become a single transposed table: labels across, counts below.
+fn render_histogram(out: &mut String, histogram: &Histogram) {
+    let values: Vec<String> = histogram.bars().into_iter().map(|bar| bar.value).collect();
+    write_row(out, &histogram.labels);
+    write_row(out, &vec!["---:".to_string(); histogram.labels.len()]);
+    write_row(out, &values);
+}
+
+fn write_row(out: &mut String, cells: &[String]) {
+    out.push('|');
+    for cell in cells {
+        let _ = write!(out, " {} |", escape_cell(cell));
+    }
+    out.push('\n');
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\n', " ")
+}
+
+fn escape_cell(text: &str) -> String {
+    escape(text).replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_report() -> Report {
+        let mut report = Report::new("Work Summary Report", "work-summary");
+        report.push(
+            "Overall Summary",
+            SectionContent::Cards(vec![Card::new("Commits", "12").with_detail("2 repos")]),
+        );
+        let mut table = ReportTable::new(&["Language", ">Lines"]);
+        table.row(vec!["C|C++".to_string(), "1,200".to_string()]);
+        report.push_sub(
+            "Languages",
+            SectionContent::Table(table.with_footer(vec!["Total".to_string(), "1,200".to_string()])),
+        );
+        report.push_sub(
+            "Commits by Weekday",
+            SectionContent::Histogram(Histogram::new(
+                vec!["Mon".to_string(), "Tue".to_string()],
+                vec![3.0, 0.0],
+            )),
+        );
+        report
+    }
+
+    #[test]
+    fn renders_headings_tables_and_footer() {
+        let markdown = MarkdownExporter::new().render(&sample_report());
+
+        assert_eq!(
+            markdown,
+            "# Work Summary Report\n\
+             \n## Overall Summary\n\n\
+             - **Commits:** 12 (2 repos)\n\
+             \n### Languages\n\n\
+             | Language | Lines |\n\
+             | --- | ---: |\n\
+             | C\\|C++ | 1,200 |\n\
+             | **Total** | **1,200** |\n\
+             \n### Commits by Weekday\n\n\
+             | Mon | Tue |\n\
+             | ---: | ---: |\n\
+             | 3 | 0 |\n\
+             \n---\n\n*Generated by **work-summary** - CLI Tools Collection*\n"
+        );
+    }
+}
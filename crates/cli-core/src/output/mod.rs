@@ -1,29 +1,91 @@
 mod formatter;
 mod table;
 mod json;
+mod yaml;
+mod ndjson;
 mod csv_export;
 mod html;
 mod markdown;
 mod currency;
+mod report;
 
 pub use formatter::Formatter;
 pub use table::TableFormatter;
 pub use json::JsonFormatter;
-pub use csv_export::CsvExporter;
+pub use yaml::YamlFormatter;
+pub use ndjson::NdjsonFormatter;
+pub use csv_export::{CsvExporter, CsvFormatter};
 pub use html::HtmlExporter;
 pub use markdown::MarkdownExporter;
-pub use currency::{format_currency_krw, format_integer};
+pub use currency::format_integer;
+pub use report::{
+    Bar, Card, Histogram, Report, ReportSection, ReportTable, SectionContent, ToReport,
+};
 
 use anyhow::Result;
+use clap::{Args, ValueEnum};
+use serde::Serialize;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum OutputFormat {
     Table,
     Json,
+    #[value(alias = "pretty")]
     JsonPretty,
+    #[value(alias = "yml")]
+    Yaml,
+    /// One compact JSON record per line, for streaming into `jq`.
+    #[value(alias = "jsonl")]
+    Ndjson,
+    Csv,
+}
+
+/// `--format` of the commands with structured output.
+#[derive(Args, Debug, Clone, Copy)]
+pub struct FormatArg {
+    /// Output format
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        value_enum,
+        ignore_case = true,
+        default_value_t = OutputFormat::Table,
+        global = true
+    )]
+    pub format: OutputFormat,
 }
 
 impl OutputFormat {
+    /// Whether the command should render its own human-readable output.
+    pub fn is_table(self) -> bool {
+        self == Self::Table
+    }
+
+    /// The formatter for a structured format, or `None` for [`OutputFormat::Table`].
+    pub fn formatter<T: Serialize>(self) -> Option<Box<dyn Formatter<T>>> {
+        match self {
+            Self::Table => None,
+            Self::Json => Some(Box::new(JsonFormatter::new(false))),
+            Self::JsonPretty => Some(Box::new(JsonFormatter::new(true))),
+            Self::Yaml => Some(Box::new(YamlFormatter::new())),
+            Self::Ndjson => Some(Box::new(NdjsonFormatter::new())),
+            Self::Csv => Some(Box::new(CsvFormatter::new())),
+        }
+    }
+
+    /// Write `data` to stdout in this format.
+    ///
+    /// `Table` has no generic rendering, so it falls back to pretty JSON;
+    /// commands draw their own tables before reaching this point.
+    pub fn print<T: Serialize>(self, data: &T) -> Result<()> {
+        let formatter = self
+            .formatter()
+            .unwrap_or_else(|| Box::new(JsonFormatter::new(true)));
+        let output = formatter.format(data)?;
+        println!("{}", output.trim_end_matches('\n'));
+        Ok(())
+    }
+
     /// Parses an output format without requiring callers to import [`std::str::FromStr`].
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<Self> {
@@ -39,6 +101,9 @@ impl std::str::FromStr for OutputFormat {
             "table" => Ok(Self::Table),
             "json" => Ok(Self::Json),
             "json-pretty" | "pretty" => Ok(Self::JsonPretty),
+            "yaml" | "yml" => Ok(Self::Yaml),
+            "ndjson" | "jsonl" => Ok(Self::Ndjson),
+            "csv" => Ok(Self::Csv),
             _ => anyhow::bail!("Unknown output format: {}", s),
         }
     }
@@ -73,6 +138,11 @@ mod tests {
             ("JSON", OutputFormat::Json),
             ("json-pretty", OutputFormat::JsonPretty),
             ("pretty", OutputFormat::JsonPretty),
+            ("yaml", OutputFormat::Yaml),
+            ("YML", OutputFormat::Yaml),
+            ("ndjson", OutputFormat::Ndjson),
+            ("jsonl", OutputFormat::Ndjson),
+            ("csv", OutputFormat::Csv),
         ] {
             assert_eq!(input.parse::<OutputFormat>().unwrap(), expected);
         }
@@ -80,9 +150,42 @@ mod tests {
 
     #[test]
     fn output_format_rejects_unknown_names() {
-        let error = "yaml".parse::<OutputFormat>().unwrap_err();
+        let error = "xml".parse::<OutputFormat>().unwrap_err();
+
+        assert_eq!(error.to_string(), "Unknown output format: xml");
+    }
+
+    #[test]
+    fn only_table_has_no_formatter() {
+        assert!(OutputFormat::Table.formatter::<Vec<u8>>().is_none());
+        assert!(OutputFormat::Csv.formatter::<Vec<u8>>().is_some());
+        assert!(OutputFormat::Table.is_table());
+        assert!(!OutputFormat::Yaml.is_table());
+    }
 
-        assert_eq!(error.to_string(), "Unknown output format: yaml");
+    #[test]
+    fn format_arg_accepts_the_same_names_as_the_parser() {
+        use super::FormatArg;
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct Cli {
+            #[command(flatten)]
+            output: FormatArg,
+        }
+
+        let parse = |args: &[&str]| {
+            Cli::try_parse_from(std::iter::once("cli").chain(args.iter().copied()))
+                .map(|cli| cli.output.format)
+        };
+        assert_eq!(parse(&[]).unwrap(), OutputFormat::Table);
+        for name in ["table", "JSON", "pretty", "YML", "jsonl", "csv"] {
+            assert_eq!(
+                parse(&["--format", name]).unwrap(),
+                name.parse::<OutputFormat>().unwrap()
+            );
+        }
+        assert!(parse(&["--format", "xml"]).is_err());
     }
 
     #[test]
@@ -0,0 +1,70 @@
+use serde::Serialize;
+use serde_json::value::RawValue;
+use anyhow::Result;
+use super::Formatter;
+
+/// Newline-delimited JSON: one compact record per line.
+///
+/// Sequences are split into one line per element; any other value is written
+/// as a single line.
+pub struct NdjsonFormatter;
+
+impl NdjsonFormatter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for NdjsonFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Serialize> Formatter<T> for NdjsonFormatter {
+    fn format(&self, data: &T) -> Result<String> {
+        let json = serde_json::to_string(data)?;
+        if !json.starts_with('[') {
+            return Ok(json);
+        }
+
+        // Raw values keep each record's field order exactly as serialized.
+        let records: Vec<Box<RawValue>> = serde_json::from_str(&json)?;
+        Ok(records
+            .iter()
+            .map(|record| record.get())
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Row {
+        name: &'static str,
+        count: usize,
+    }
+
+    #[test]
+    fn writes_one_record_per_line_in_field_order() {
+        let rows = vec![Row { name: "b", count: 1 }, Row { name: "a", count: 2 }];
+
+        assert_eq!(
+            NdjsonFormatter::new().format(&rows).unwrap(),
+            "{\"name\":\"b\",\"count\":1}\n{\"name\":\"a\",\"count\":2}"
+        );
+    }
+
+    #[test]
+    fn writes_single_values_on_one_line() {
+        let row = Row { name: "a", count: 2 };
+
+        assert_eq!(
+            NdjsonFormatter::new().format(&row).unwrap(),
+            "{\"name\":\"a\",\"count\":2}"
+        );
+    }
+}
@@ -0,0 +1,258 @@
+use serde::Serialize;
+
+/// A tool-agnostic report document rendered by the HTML exporter.
+///
+/// Each tool decides its own title and which sections to include; the
+/// exporters only know how to lay out the section kinds below.
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub title: String,
+    pub subtitle: Option<String>,
+    /// Name of the binary that produced the report, shown in the footer.
+    pub tool: String,
+    pub sections: Vec<ReportSection>,
+}
+
+impl Report {
+    pub fn new(title: impl Into<String>, tool: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            subtitle: None,
+            tool: tool.into(),
+            sections: Vec::new(),
+        }
+    }
+
+    pub fn with_subtitle(mut self, subtitle: impl Into<String>) -> Self {
+        self.subtitle = Some(subtitle.into());
+        self
+    }
+
+    pub fn push(&mut self, heading: impl Into<String>, content: SectionContent) {
+        self.sections.push(ReportSection {
+            heading: heading.into(),
+            level: 2,
+            content,
+        });
+    }
+
+    /// Add a section nested under the previous top-level section.
+    pub fn push_sub(&mut self, heading: impl Into<String>, content: SectionContent) {
+        self.sections.push(ReportSection {
+            heading: heading.into(),
+            level: 3,
+            content,
+        });
+    }
+}
+
+/// Implemented by tool results that can be exported as a [`Report`].
+///
+/// The HTML and Markdown exporters both render the same report, so a tool
+/// only decides once which sections it wants to show.
+pub trait ToReport {
+    fn to_report(&self) -> Report;
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportSection {
+    pub heading: String,
+    /// Heading level: 2 for top-level sections, 3 for nested ones.
+    pub level: u8,
+    pub content: SectionContent,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub enum SectionContent {
+    Cards(Vec<Card>),
+    Table(ReportTable),
+    Histogram(Histogram),
+    Text(String),
+}
+
+/// A headline number shown as a summary card.
+#[derive(Debug, Clone, Serialize)]
+pub struct Card {
+    pub label: String,
+    pub value: String,
+    pub detail: Option<String>,
+}
+
+impl Card {
+    pub fn new(label: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            value: value.into(),
+            detail: None,
+        }
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportTable {
+    pub headers: Vec<String>,
+    /// Columns rendered right-aligned (numbers, money, percentages).
+    pub numeric: Vec<bool>,
+    pub rows: Vec<Vec<String>>,
+    /// Optional totals row rendered below the body.
+    pub footer: Option<Vec<String>>,
+}
+
+impl ReportTable {
+    /// Create a table; headers prefixed with `>` are right-aligned numeric columns.
+    pub fn new<S: AsRef<str>>(headers: &[S]) -> Self {
+        let (headers, numeric) = headers
+            .iter()
+            .map(|h| match h.as_ref().strip_prefix('>') {
+                Some(name) => (name.to_string(), true),
+                None => (h.as_ref().to_string(), false),
+            })
+            .unzip();
+        Self {
+            headers,
+            numeric,
+            rows: Vec::new(),
+            footer: None,
+        }
+    }
+
+    pub fn row(&mut self, cells: Vec<String>) {
+        self.rows.push(cells);
+    }
+
+    pub fn with_footer(mut self, cells: Vec<String>) -> Self {
+        self.footer = Some(cells);
+        self
+    }
+
+    pub fn is_numeric(&self, column: usize) -> bool {
+        self.numeric.get(column).copied().unwrap_or(false)
+    }
+}
+
+/// A labelled bar chart, rendered as inline SVG in HTML.
+#[derive(Debug, Clone, Serialize)]
+pub struct Histogram {
+    pub labels: Vec<String>,
+    pub values: Vec<f64>,
+}
+
+/// Geometry of one histogram bar in SVG user units.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bar {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub label_x: f64,
+    pub label: String,
+    pub value: String,
+}
+
+impl Histogram {
+    pub const WIDTH: f64 = 720.0;
+    pub const HEIGHT: f64 = 180.0;
+    /// Vertical space reserved under the bars for labels.
+    pub const LABEL_SPACE: f64 = 20.0;
+
+    pub fn new(labels: Vec<String>, values: Vec<f64>) -> Self {
+        Self { labels, values }
+    }
+
+    pub fn width(&self) -> f64 {
+        Self::WIDTH
+    }
+
+    pub fn height(&self) -> f64 {
+        Self::HEIGHT + Self::LABEL_SPACE
+    }
+
+    pub fn baseline(&self) -> f64 {
+        Self::HEIGHT
+    }
+
+    /// Lay out the bars so the largest value fills the chart height.
+    pub fn bars(&self) -> Vec<Bar> {
+        let count = self.values.len().max(1) as f64;
+        let slot = Self::WIDTH / count;
+        let width = (slot * 0.8).max(1.0);
+        let max = self.values.iter().copied().fold(0.0_f64, f64::max);
+
+        self.values
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| {
+                let height = if max > 0.0 {
+                    (value / max) * (Self::HEIGHT - 16.0)
+                } else {
+                    0.0
+                };
+                let x = i as f64 * slot + (slot - width) / 2.0;
+                Bar {
+                    x: round1(x),
+                    y: round1(Self::HEIGHT - height),
+                    width: round1(width),
+                    height: round1(height),
+                    label_x: round1(x + width / 2.0),
+                    label: self.labels.get(i).cloned().unwrap_or_default(),
+                    value: format_number(value),
+                }
+            })
+            .collect()
+    }
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value as i64)
+    } else {
+        format!("{value:.1}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_headers_mark_numeric_columns() {
+        let table = ReportTable::new(&["Language", ">Lines"]);
+
+        assert_eq!(table.headers, vec!["Language", "Lines"]);
+        assert!(!table.is_numeric(0));
+        assert!(table.is_numeric(1));
+        assert!(!table.is_numeric(5));
+    }
+
+    #[test]
+    fn histogram_scales_to_largest_value() {
+        let histogram = Histogram::new(
+            vec!["a".into(), "b".into(), "c".into()],
+            vec![0.0, 5.0, 10.0],
+        );
+        let bars = histogram.bars();
+
+        assert_eq!(bars.len(), 3);
+        assert_eq!(bars[0].height, 0.0);
+        assert_eq!(bars[2].height, Histogram::HEIGHT - 16.0);
+        assert_eq!(bars[1].height, (Histogram::HEIGHT - 16.0) / 2.0);
+        assert_eq!(bars[2].y + bars[2].height, Histogram::HEIGHT);
+        assert_eq!(bars[1].value, "5");
+    }
+
+    #[test]
+    fn empty_histogram_has_flat_bars() {
+        let histogram = Histogram::new(vec!["a".into()], vec![0.0]);
+
+        assert!(histogram.bars().iter().all(|bar| bar.height == 0.0));
+    }
+}
@@ -0,0 +1,23 @@
+use serde::Serialize;
+use anyhow::Result;
+use super::Formatter;
+
+pub struct YamlFormatter;
+
+impl YamlFormatter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for YamlFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Serialize> Formatter<T> for YamlFormatter {
+    fn format(&self, data: &T) -> Result<String> {
+        Ok(serde_yaml::to_string(data)?)
+    }
+}
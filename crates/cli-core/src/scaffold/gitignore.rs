@@ -0,0 +1,53 @@
+//! Built-in `.gitignore` templates, used when the gitignore.io API is unavailable
+//! and by `git-tools health --fix`.
+
+const TEMPLATES: &[(&str, &str)] = &[
+    (
+        "rust",
+        "# Rust\n/target/\n**/*.rs.bk\n*.pdb\n",
+    ),
+    (
+        "node",
+        "# Node\nnode_modules/\ndist/\ncoverage/\n.npm/\nnpm-debug.log*\nyarn-debug.log*\nyarn-error.log*\n.env\n.env.*\n!.env.example\n",
+    ),
+    (
+        "python",
+        "# Python\n__pycache__/\n*.py[cod]\n*.egg-info/\n.venv/\nvenv/\nbuild/\ndist/\n.pytest_cache/\n.mypy_cache/\n.env\n",
+    ),
+    (
+        "go",
+        "# Go\n/bin/\n*.exe\n*.test\n*.out\ncoverage.txt\nvendor/\n",
+    ),
+    (
+        "macos",
+        "# macOS\n.DS_Store\n.AppleDouble\n.LSOverride\n._*\n",
+    ),
+    (
+        "windows",
+        "# Windows\nThumbs.db\nehthumbs.db\nDesktop.ini\n$RECYCLE.BIN/\n",
+    ),
+    (
+        "linux",
+        "# Linux\n*~\n.directory\n.Trash-*\n",
+    ),
+];
+
+/// Names of the built-in templates.
+pub fn gitignore_targets() -> impl Iterator<Item = &'static str> {
+    TEMPLATES.iter().map(|(name, _)| *name)
+}
+
+/// Concatenate the built-in templates for `targets`, or `None` if any target is unknown.
+pub fn gitignore_template<S: AsRef<str>>(targets: &[S]) -> Option<String> {
+    let sections: Option<Vec<&str>> = targets
+        .iter()
+        .map(|target| {
+            let target = target.as_ref().trim().to_lowercase();
+            TEMPLATES
+                .iter()
+                .find(|(name, _)| *name == target)
+                .map(|(_, body)| *body)
+        })
+        .collect();
+    sections.map(|sections| sections.join("\n"))
+}
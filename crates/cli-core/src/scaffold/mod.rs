@@ -0,0 +1,289 @@
+//! Starter-file knowledge shared between tools: license texts, `.gitignore`
+//! templates, and the project metadata used to fill them in.
+
+mod gitignore;
+mod license;
+
+pub use gitignore::{gitignore_targets, gitignore_template};
+pub use license::LicenseType;
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Language ecosystem detected from the manifest files in a directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Ecosystem {
+    Rust,
+    Node,
+    Go,
+    Python,
+}
+
+impl Ecosystem {
+    pub const ALL: [Ecosystem; 4] = [Self::Rust, Self::Node, Self::Go, Self::Python];
+
+    /// Ecosystems whose manifest files exist directly in `dir`.
+    pub fn detect(dir: &Path) -> Vec<Ecosystem> {
+        Self::ALL
+            .into_iter()
+            .filter(|ecosystem| ecosystem.manifests().iter().any(|f| dir.join(f).exists()))
+            .collect()
+    }
+
+    /// Files whose presence marks a directory as part of this ecosystem.
+    pub fn manifests(&self) -> &'static [&'static str] {
+        match self {
+            Self::Rust => &["Cargo.toml"],
+            Self::Node => &["package.json"],
+            Self::Go => &["go.mod"],
+            Self::Python => &["pyproject.toml", "requirements.txt", "setup.py"],
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Rust => "Rust",
+            Self::Node => "Node",
+            Self::Go => "Go",
+            Self::Python => "Python",
+        }
+    }
+
+    /// Name of the matching built-in `.gitignore` template.
+    pub fn gitignore_target(&self) -> &'static str {
+        match self {
+            Self::Rust => "rust",
+            Self::Node => "node",
+            Self::Go => "go",
+            Self::Python => "python",
+        }
+    }
+}
+
+/// Project details read from `Cargo.toml` and `package.json`.
+///
+/// `Cargo.toml` wins when both define a field; `workspace = true` fields fall
+/// back to `[workspace.package]`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProjectMetadata {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub license: Option<String>,
+    pub authors: Vec<String>,
+    pub repository: Option<String>,
+    pub edition: Option<String>,
+    pub rust_version: Option<String>,
+    pub ecosystems: Vec<Ecosystem>,
+}
+
+impl ProjectMetadata {
+    /// Read whatever metadata `dir` provides; missing or unparsable manifests are skipped.
+    pub fn load(dir: &Path) -> Self {
+        let mut metadata = Self {
+            ecosystems: Ecosystem::detect(dir),
+            ..Self::default()
+        };
+
+        if let Some(cargo) = read_toml(&dir.join("Cargo.toml")) {
+            metadata.merge_cargo(&cargo);
+        }
+        if let Some(package) = read_json(&dir.join("package.json")) {
+            metadata.merge_package_json(&package);
+        }
+
+        metadata
+    }
+
+    /// Declared name, or the directory name when no manifest names the project.
+    pub fn project_name(&self, dir: &Path) -> String {
+        self.name.clone().unwrap_or_else(|| {
+            dir.canonicalize()
+                .ok()
+                .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+                .unwrap_or_else(|| "project".to_string())
+        })
+    }
+
+    /// First author without the `<email>` suffix.
+    pub fn holder(&self) -> Option<String> {
+        let author = self.authors.first()?;
+        let name = author.split('<').next().unwrap_or(author).trim();
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
+    /// First author e-mail address, if one is given as `Name <email>`.
+    pub fn contact_email(&self) -> Option<String> {
+        self.authors.iter().find_map(|author| {
+            let start = author.find('<')?;
+            let end = author[start..].find('>')? + start;
+            Some(author[start + 1..end].trim().to_string())
+        })
+    }
+
+    /// Owner segment of a GitHub repository URL (`https://github.com/owner/repo`).
+    pub fn github_owner(&self) -> Option<String> {
+        let repository = self.repository.as_deref()?;
+        let rest = repository.split("github.com").nth(1)?;
+        let owner = rest.trim_start_matches([':', '/']).split('/').next()?;
+        (!owner.is_empty()).then(|| owner.to_string())
+    }
+
+    fn merge_cargo(&mut self, cargo: &toml::Table) {
+        let package = cargo.get("package").and_then(|v| v.as_table());
+        let workspace = cargo
+            .get("workspace")
+            .and_then(|v| v.get("package"))
+            .and_then(|v| v.as_table());
+
+        let field = |key: &str| -> Option<&toml::Value> {
+            match package.and_then(|p| p.get(key)) {
+                Some(value) if value.get("workspace").is_some() => workspace?.get(key),
+                Some(value) => Some(value),
+                None if package.is_none() => workspace?.get(key),
+                None => None,
+            }
+        };
+        let string = |key: &str| field(key).and_then(|v| v.as_str()).map(str::to_string);
+
+        self.name = self.name.take().or_else(|| string("name"));
+        self.description = self.description.take().or_else(|| string("description"));
+        self.license = self.license.take().or_else(|| string("license"));
+        self.repository = self.repository.take().or_else(|| string("repository"));
+        self.edition = self.edition.take().or_else(|| string("edition"));
+        self.rust_version = self.rust_version.take().or_else(|| string("rust-version"));
+        if self.authors.is_empty() {
+            if let Some(authors) = field("authors").and_then(|v| v.as_array()) {
+                self.authors = authors
+                    .iter()
+                    .filter_map(|a| a.as_str().map(str::to_string))
+                    .collect();
+            }
+        }
+    }
+
+    fn merge_package_json(&mut self, package: &serde_json::Value) {
+        let string = |key: &str| package.get(key).and_then(|v| v.as_str()).map(str::to_string);
+
+        self.name = self.name.take().or_else(|| string("name"));
+        self.description = self.description.take().or_else(|| string("description"));
+        self.license = self.license.take().or_else(|| string("license"));
+        self.repository = self.repository.take().or_else(|| {
+            match package.get("repository") {
+                Some(serde_json::Value::Object(repo)) => {
+                    repo.get("url").and_then(|v| v.as_str()).map(str::to_string)
+                }
+                _ => string("repository"),
+            }
+        });
+        if self.authors.is_empty() {
+            match package.get("author") {
+                Some(serde_json::Value::String(author)) => self.authors.push(author.clone()),
+                Some(serde_json::Value::Object(author)) => {
+                    if let Some(name) = author.get("name").and_then(|v| v.as_str()) {
+                        let email = author.get("email").and_then(|v| v.as_str());
+                        self.authors.push(match email {
+                            Some(email) => format!("{name} <{email}>"),
+                            None => name.to_string(),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn read_toml(path: &Path) -> Option<toml::Table> {
+    fs::read_to_string(path).ok()?.parse().ok()
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!(
+            "cli-core-scaffold-{}-{}-{}",
+            name,
+            std::process::id(),
+            nanos
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn cargo_fields_inherit_from_workspace_package() {
+        let dir = temp_dir("cargo");
+        fs::write(
+            dir.join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nlicense.workspace = true\nauthors = { workspace = true }\n\n\
+             [workspace.package]\nlicense = \"MIT OR Apache-2.0\"\nauthors = [\"Jane Doe <jane@example.com>\"]\n\
+             repository = \"https://github.com/jane/demo\"\n",
+        )
+        .unwrap();
+
+        let metadata = ProjectMetadata::load(&dir);
+
+        assert_eq!(metadata.name.as_deref(), Some("demo"));
+        assert_eq!(metadata.license.as_deref(), Some("MIT OR Apache-2.0"));
+        assert_eq!(metadata.holder().as_deref(), Some("Jane Doe"));
+        assert_eq!(metadata.contact_email().as_deref(), Some("jane@example.com"));
+        // Explicit package fields do not fall back to the workspace.
+        assert_eq!(metadata.repository, None);
+        assert_eq!(metadata.ecosystems, vec![Ecosystem::Rust]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn package_json_fills_remaining_fields() {
+        let dir = temp_dir("node");
+        fs::write(
+            dir.join("package.json"),
+            r#"{"name":"web","author":{"name":"Sam","email":"sam@example.com"},
+               "repository":{"type":"git","url":"git+https://github.com/acme/web.git"}}"#,
+        )
+        .unwrap();
+
+        let metadata = ProjectMetadata::load(&dir);
+
+        assert_eq!(metadata.name.as_deref(), Some("web"));
+        assert_eq!(metadata.authors, vec!["Sam <sam@example.com>".to_string()]);
+        assert_eq!(metadata.github_owner().as_deref(), Some("acme"));
+        assert_eq!(metadata.ecosystems, vec![Ecosystem::Node]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn license_expressions_pick_first_supported_license() {
+        assert_eq!(
+            LicenseType::from_expression("MIT OR Apache-2.0"),
+            Some(LicenseType::Mit)
+        );
+        assert_eq!(
+            LicenseType::from_expression("(LGPL-2.1 OR BSD-3-Clause)"),
+            Some(LicenseType::Bsd3)
+        );
+        assert!(LicenseType::Mit.text(2026, "Jane Doe").contains("Copyright (c) 2026 Jane Doe"));
+    }
+
+    #[test]
+    fn gitignore_templates_reject_unknown_targets() {
+        let template = gitignore_template(&["rust", "macos"]).unwrap();
+        assert!(template.contains("/target/"));
+        assert!(template.contains(".DS_Store"));
+        assert_eq!(gitignore_template(&["rust", "cobol"]), None);
+    }
+}
@@ -51,3 +51,36 @@ impl Theme {
         text.as_ref().yellow().to_string()
     }
 }
+
+/// Draw `values` as a one-line chart of block characters, scaled from the
+/// smallest value to the largest.
+pub fn sparkline(values: &[f64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+
+    values
+        .iter()
+        .map(|&value| {
+            if range > 0.0 {
+                let level = ((value - min) / range * (BARS.len() - 1) as f64).round();
+                BARS[level as usize]
+            } else {
+                BARS[0]
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sparkline;
+
+    #[test]
+    fn sparkline_scales_from_smallest_to_largest() {
+        assert_eq!(sparkline(&[0.0, 50.0, 100.0]), "▁▅█");
+        assert_eq!(sparkline(&[3.0, 3.0]), "▁▁");
+        assert_eq!(sparkline(&[]), "");
+    }
+}
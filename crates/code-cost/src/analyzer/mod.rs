@@ -1,25 +1,48 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
+use cli_core::config::{expand_home, CodeCostConfig};
+use cli_core::ui::Theme;
+use git2::Repository;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 
-use crate::git::GitAnalyzer;
-use crate::metrics::MetricsCollector;
+use crate::cache::Cache;
+use crate::complexity::{self, ComplexityReport};
+use crate::coverage::{Coverage, CoverageReport};
+use crate::dependencies::Inventory;
+use crate::duplication::Duplication;
+use crate::git::{AiEvidence, GitAnalyzer, GitStats};
+use crate::history::Revision;
+use crate::metrics::{Metrics, MetricsCollector, TreeScanner};
+use crate::tokenizer::{TokenCounts, Tokenizer};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Analysis {
     pub total_lines: usize,
     pub code_lines: usize,
     pub comment_lines: usize,
+    /// Doc comments and docstrings, counted apart from ordinary comments.
+    pub doc_lines: usize,
     pub blank_lines: usize,
     pub total_files: usize,
     pub test_file_count: usize,
+    /// Line and branch coverage from a coverage report of the working tree.
+    pub coverage: Option<Coverage>,
+    /// Code copied between or within the analysed files.
+    pub duplication: Duplication,
     pub commit_count: usize,
     pub contributor_count: usize,
     pub age_in_days: i64,
-    pub total_tokens: usize,
+    /// Evidence of AI assistance in the history, reported by the estimate.
+    #[serde(skip)]
+    pub ai_evidence: AiEvidence,
+    pub total_tokens: TokenCounts,
+    /// Lockfiles among the analysed files.
+    pub dependencies: Inventory,
     pub language_stats: Vec<LanguageStat>,
     pub complexity_score: f64,
     pub maturity_score: f64,
+    pub complexity: ComplexityReport,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,50 +53,238 @@ pub struct LanguageStat {
     pub weight: f64,
 }
 
+impl Analysis {
+    /// The largest languages by line count, as "Rust 62%, TOML 12%".
+    pub fn language_mix(&self, limit: usize) -> String {
+        let total = self.total_lines.max(1) as f64;
+        self.language_stats
+            .iter()
+            .take(limit)
+            .map(|lang| format!("{} {:.0}%", lang.name, lang.lines as f64 / total * 100.0))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Language line counts, as "Rust:1200;TOML:40".
+    pub fn language_lines(&self) -> String {
+        self.language_stats
+            .iter()
+            .map(|lang| format!("{}:{}", lang.name, lang.lines))
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+}
+
 pub struct RepositoryAnalyzer {
-    #[allow(dead_code)]
-    hourly_rate: f64,
+    settings: CodeCostConfig,
+    use_cache: bool,
+    top_functions: usize,
+    tokenizers: Vec<Tokenizer>,
 }
 
 impl RepositoryAnalyzer {
-    pub fn new(hourly_rate: f64) -> Self {
-        Self { hourly_rate }
+    pub fn new(settings: &CodeCostConfig) -> Self {
+        Self {
+            settings: settings.clone(),
+            use_cache: true,
+            top_functions: complexity::DEFAULT_TOP,
+            tokenizers: Vec::new(),
+        }
     }
 
+    /// Whether to reuse and update the persistent analysis cache (on by default).
+    pub fn with_cache(mut self, use_cache: bool) -> Self {
+        self.use_cache = use_cache;
+        self
+    }
+
+    /// How many of the most complex functions to keep in the analysis.
+    pub fn with_top_functions(mut self, top_functions: usize) -> Self {
+        self.top_functions = top_functions;
+        self
+    }
+
+    /// Tokenizers to count with besides the heuristic, one per priced model.
+    pub fn with_tokenizers(mut self, tokenizers: Vec<Tokenizer>) -> Self {
+        self.tokenizers = tokenizers;
+        self
+    }
+
+    /// Analyze the working tree at `path`, or HEAD's tree when `path` is a
+    /// bare repository.
     pub async fn analyze(&self, path: &Path) -> Result<Analysis> {
+        if is_bare(path) {
+            return self.analyze_revision(path, "HEAD").await;
+        }
         let git_analyzer = GitAnalyzer::new();
-        let metrics_collector = MetricsCollector::new();
+        let metrics_collector = self.metrics_collector().with_coverage(self.coverage(path)?);
+        let mut cache = self.cache(path);
 
         // Collect metrics
-        let metrics = metrics_collector.collect(path)?;
+        let metrics = metrics_collector.collect(path, &mut cache)?;
 
         // Analyze git repository
-        let git_stats = git_analyzer.analyze(path)?;
+        let git_stats = git_analyzer.analyze(path, &mut cache)?;
+        let dependencies = Inventory::scan(path)?;
+
+        save_cache(cache);
+        Ok(self.build(metrics, git_stats, dependencies))
+    }
+
+    /// Analyze `path` once for every group `groups` assigns files to, by their
+    /// path relative to `path`. Git activity is the whole repository's.
+    pub async fn analyze_groups<K: Ord + Clone>(
+        &self,
+        path: &Path,
+        groups: impl Fn(&Path) -> Vec<K>,
+    ) -> Result<BTreeMap<K, Analysis>> {
+        if is_bare(path) {
+            bail!(
+                "A breakdown needs a working tree; {} is a bare repository",
+                path.display()
+            );
+        }
+        let git_analyzer = GitAnalyzer::new();
+        let metrics_collector = self.metrics_collector().with_coverage(self.coverage(path)?);
+        let mut cache = self.cache(path);
+
+        let metrics = metrics_collector.collect_groups(path, &mut cache, &groups)?;
+        let git_stats = git_analyzer.analyze(path, &mut cache)?;
+        let dependencies = Inventory::scan(path)?;
+
+        save_cache(cache);
+        Ok(metrics
+            .into_iter()
+            .map(|(key, metrics)| {
+                // A lockfile belongs to the groups its path does.
+                let dependencies = dependencies.filter(|lockfile| groups(lockfile).contains(&key));
+                let analysis = self.build(metrics, git_stats.clone(), dependencies);
+                (key, analysis)
+            })
+            .collect())
+    }
+
+    /// Analyze the tree `rev` names straight from the object database, so it
+    /// needs no checkout. Git activity covers the commits reachable from it,
+    /// and only a configured coverage report is used.
+    pub async fn analyze_revision(&self, path: &Path, rev: &str) -> Result<Analysis> {
+        let repo = Repository::open(path)?;
+        let revision = Revision::resolve(&repo, rev)?;
+        let coverage = match &self.settings.coverage {
+            Some(report) => Some(CoverageReport::load(&path.join(expand_home(report)))?),
+            None => None,
+        };
+        let metrics_collector = self.metrics_collector().with_coverage(coverage);
+        let mut cache = self.cache(path);
+        let mut scanner = TreeScanner::new(&repo, path);
+
+        let tree = repo.find_commit(revision.commit)?.tree()?;
+        let metrics = metrics_collector.collect_tree(&mut scanner, &tree, &mut cache)?;
+        let git_stats = GitAnalyzer::new().analyze_commit(&repo, revision.commit, &mut cache)?;
+        let dependencies = Inventory::scan_tree(&repo, &tree)?;
+
+        save_cache(cache);
+        Ok(self.build(metrics, git_stats, dependencies))
+    }
+
+    /// Analyze the committed state at each of `revisions`, oldest first,
+    /// reading blobs from the object database instead of checking them out.
+    pub async fn analyze_history(
+        &self,
+        path: &Path,
+        revisions: &[Revision],
+    ) -> Result<Vec<Analysis>> {
+        let repo = Repository::open(path)?;
+        let git_analyzer = GitAnalyzer::new();
+        let metrics_collector = self.metrics_collector();
+        let mut cache = self.cache(path);
+        let mut scanner = TreeScanner::new(&repo, path);
+
+        let mut history = None;
+        let mut analyses = Vec::with_capacity(revisions.len());
+        for revision in revisions {
+            let tree = repo.find_commit(revision.commit)?.tree()?;
+            let metrics = metrics_collector.collect_tree(&mut scanner, &tree, &mut cache)?;
+            // Each point only walks the commits added since the previous one.
+            let next = git_analyzer.history_at(&repo, revision.commit, history.as_ref())?;
+            let dependencies = Inventory::scan_tree(&repo, &tree)?;
+            analyses.push(self.build(metrics, next.stats(), dependencies));
+            history = Some(next);
+        }
+
+        save_cache(cache);
+        Ok(analyses)
+    }
 
+    fn metrics_collector(&self) -> MetricsCollector {
+        MetricsCollector::new()
+            .with_language_weights(self.settings.language_weights.clone())
+            .with_tokenizers(self.tokenizers.clone())
+    }
+
+    /// The report named by `code_cost.coverage`, relative to `path`, or else
+    /// one found in the usual output locations. A found report that cannot be
+    /// parsed is skipped with a warning.
+    fn coverage(&self, path: &Path) -> Result<Option<CoverageReport>> {
+        if let Some(report) = &self.settings.coverage {
+            return CoverageReport::load(&path.join(expand_home(report))).map(Some);
+        }
+        let Some(report) = CoverageReport::find(path) else {
+            return Ok(None);
+        };
+        match CoverageReport::load(&report) {
+            Ok(report) => Ok(Some(report)),
+            Err(e) => {
+                eprintln!("{} {:#}", Theme::warning("Coverage report skipped:"), e);
+                Ok(None)
+            }
+        }
+    }
+
+    fn cache(&self, path: &Path) -> Cache {
+        if self.use_cache {
+            Cache::load(path)
+        } else {
+            Cache::disabled(path)
+        }
+    }
+
+    fn build(&self, metrics: Metrics, git_stats: GitStats, dependencies: Inventory) -> Analysis {
         // Calculate complexity score (1.0 - 5.0)
         let complexity_score = self.calculate_complexity(&metrics);
 
         // Calculate maturity score (0.0 - 1.0)
         let maturity_score = self.calculate_maturity(&metrics, &git_stats);
 
-        Ok(Analysis {
+        // How complex the functions are outweighs how much code there is.
+        let complexity = ComplexityReport::new(metrics.functions, self.top_functions);
+        let complexity_score = 0.7 * complexity.score + 0.3 * complexity_score;
+
+        Analysis {
             total_lines: metrics.total_lines,
             code_lines: metrics.code_lines,
             comment_lines: metrics.comment_lines,
+            doc_lines: metrics.doc_lines,
             blank_lines: metrics.blank_lines,
             total_files: metrics.total_files,
             test_file_count: metrics.test_file_count,
+            coverage: metrics.coverage,
+            duplication: metrics.duplication,
             commit_count: git_stats.commit_count,
             contributor_count: git_stats.contributor_count,
             age_in_days: git_stats.age_in_days,
+            ai_evidence: git_stats.ai,
             total_tokens: metrics.total_tokens,
+            dependencies,
             language_stats: metrics.language_stats,
             complexity_score,
             maturity_score,
-        })
+            complexity,
+        }
     }
 
-    fn calculate_complexity(&self, metrics: &crate::metrics::Metrics) -> f64 {
+    /// Size-based complexity, blended with the structural score in `build`.
+    fn calculate_complexity(&self, metrics: &Metrics) -> f64 {
         // Base complexity from lines of code
         let loc_factor = (metrics.code_lines as f64 / 1000.0).min(3.0);
 
@@ -91,15 +302,23 @@ impl RepositoryAnalyzer {
         (loc_factor + lang_factor + file_factor).clamp(1.0, 5.0)
     }
 
-    fn calculate_maturity(&self, metrics: &crate::metrics::Metrics, git_stats: &crate::git::GitStats) -> f64 {
+    fn calculate_maturity(&self, metrics: &Metrics, git_stats: &GitStats) -> f64 {
         let mut score = 0.0;
 
-        // Test coverage indicator (based on test file presence)
-        let test_ratio = metrics.test_file_count as f64 / metrics.total_files.max(1) as f64;
-        score += (test_ratio * 0.3).min(0.3);
+        // Test coverage: measured when a report covers the code, else the
+        // share of test files. Branch coverage counts for 30% when recorded.
+        let coverage = match &metrics.coverage {
+            Some(coverage) => match coverage.total.branch_rate() {
+                Some(branches) => 0.7 * coverage.total.line_rate() + 0.3 * branches,
+                None => coverage.total.line_rate(),
+            },
+            None => metrics.test_file_count as f64 / metrics.total_files.max(1) as f64,
+        };
+        score += (coverage * 0.3).min(0.3);
 
         // Documentation (README, docs, comments)
-        let doc_ratio = metrics.comment_lines as f64 / metrics.code_lines.max(1) as f64;
+        let doc_ratio =
+            (metrics.comment_lines + metrics.doc_lines) as f64 / metrics.code_lines.max(1) as f64;
         score += (doc_ratio * 0.2).min(0.2);
         if metrics.has_readme {
             score += 0.1;
@@ -124,3 +343,108 @@ impl RepositoryAnalyzer {
         score.min(1.0)
     }
 }
+
+/// Whether `path` is a repository without a working tree.
+fn is_bare(path: &Path) -> bool {
+    Repository::open(path).is_ok_and(|repo| repo.is_bare())
+}
+
+/// A cache that cannot be written only costs speed on the next run.
+fn save_cache(cache: Cache) {
+    if let Err(e) = cache.save() {
+        eprintln!("{} {:#}", Theme::warning("Cache not saved:"), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{TempDir, TempRepo};
+    use git2::build::RepoBuilder;
+
+    /// What a revision and the working tree it was committed from share.
+    fn counts(analysis: &Analysis) -> (Vec<usize>, Vec<(String, usize, usize)>) {
+        let totals = vec![
+            analysis.total_files,
+            analysis.total_lines,
+            analysis.code_lines,
+            analysis.comment_lines,
+            analysis.blank_lines,
+            analysis.commit_count,
+            analysis.contributor_count,
+        ];
+        let languages = analysis
+            .language_stats
+            .iter()
+            .map(|lang| (lang.name.clone(), lang.lines, lang.files))
+            .collect();
+        (totals, languages)
+    }
+
+    fn analyzer() -> RepositoryAnalyzer {
+        RepositoryAnalyzer::new(&CodeCostConfig::default()).with_cache(false)
+    }
+
+    fn project() -> TempRepo {
+        let mut repo = TempRepo::new();
+        repo.write(
+            "src/main.rs",
+            "// Entry point\nfn main() {\n    println!(\"hi\");\n}\n",
+        );
+        repo.write(
+            "src/util.rs",
+            "\npub fn twice(x: u32) -> u32 {\n    x * 2\n}\n",
+        );
+        repo.write("tool.py", "import sys\n\n# Echo\nprint(sys.argv)\n");
+        repo.commit("first");
+        repo.write("README.md", "# Project\n");
+        repo.commit("second");
+        repo
+    }
+
+    #[tokio::test]
+    async fn a_tag_is_analyzed_as_it_was_committed() {
+        let mut repo = project();
+        let tagged = analyzer().analyze(repo.dir.path()).await.unwrap();
+        let head = repo.repo.revparse_single("HEAD").unwrap();
+        repo.repo.tag_lightweight("v1.0", &head, false).unwrap();
+        drop(head);
+
+        repo.write("src/util.rs", "pub fn once(x: u32) -> u32 {\n    x\n}\n");
+        repo.remove("tool.py");
+        repo.write("lib.go", "package lib\n");
+        repo.commit("third");
+
+        let analysis = analyzer()
+            .analyze_revision(repo.dir.path(), "v1.0")
+            .await
+            .unwrap();
+        assert_eq!(counts(&analysis), counts(&tagged));
+        assert_eq!(analysis.commit_count, 2);
+        assert_ne!(
+            counts(&analyzer().analyze(repo.dir.path()).await.unwrap()),
+            counts(&tagged)
+        );
+    }
+
+    #[tokio::test]
+    async fn a_bare_clone_is_analyzed_like_its_working_tree() {
+        let repo = project();
+        let clones = TempDir::new();
+        let url = format!("file://{}", repo.dir.path().display());
+        let bare = clones.path().join("project.git");
+        RepoBuilder::new().bare(true).clone(&url, &bare).unwrap();
+
+        let working_tree = analyzer().analyze(repo.dir.path()).await.unwrap();
+        let analysis = analyzer().analyze(&bare).await.unwrap();
+        assert_eq!(counts(&analysis), counts(&working_tree));
+        assert_eq!(analysis.total_files, 4);
+
+        let error = analyzer()
+            .analyze_groups(&bare, |_| vec![()])
+            .await
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("bare repository"), "{error}");
+    }
+}
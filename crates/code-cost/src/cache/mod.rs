@@ -0,0 +1,398 @@
+//! Persistent analysis cache.
+//!
+//! Per-file counts are keyed by git blob id (plus language, since comment
+//! syntax depends on it) and history totals by the HEAD commit they cover, so
+//! a re-run only reads files whose content changed and walks only new commits.
+//! The cache lives in `.git/code-cost/`, so it never shows up in the working
+//! tree, and is shared by every directory analysed in the repository. Entries
+//! not used by a run are dropped when it is saved.
+
+use anyhow::{Context, Result};
+use git2::{ObjectType, Oid, Repository};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::git::History;
+use crate::metrics::FileStats;
+
+/// Bumped whenever the cached data would be computed differently.
+const CACHE_VERSION: u32 = 7;
+const CACHE_FILE: &str = "cache.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheData {
+    version: u32,
+    files: HashMap<String, FileStats>,
+    history: Option<History>,
+}
+
+/// Index entry stat data, used to reuse the staged blob id without reading the file.
+struct IndexStat {
+    oid: Oid,
+    size: u32,
+    mtime: (i32, u32),
+}
+
+pub struct Cache {
+    /// `None` when caching is disabled.
+    file: Option<PathBuf>,
+    root: PathBuf,
+    /// `root` relative to the working tree, as index paths are; `None` when
+    /// there is no index to consult.
+    prefix: Option<PathBuf>,
+    previous: CacheData,
+    current: CacheData,
+    index: HashMap<PathBuf, IndexStat>,
+    /// Entries modified at or after this time may have changed without a size change.
+    index_mtime: i64,
+}
+
+impl Cache {
+    /// A cache that never hits and is never written (`--no-cache`).
+    pub fn disabled(root: &Path) -> Self {
+        Self {
+            file: None,
+            root: root.to_path_buf(),
+            prefix: None,
+            previous: CacheData::default(),
+            current: CacheData::default(),
+            index: HashMap::new(),
+            index_mtime: 0,
+        }
+    }
+
+    /// Open the cache of the repository `root` is in; a missing or stale file
+    /// starts empty, and directories outside a repository get no cache.
+    pub fn load(root: &Path) -> Self {
+        let Ok(repo) = Repository::discover(root) else {
+            return Self::disabled(root);
+        };
+        let file = repo.path().join("code-cost").join(CACHE_FILE);
+
+        let previous = fs::read_to_string(&file)
+            .ok()
+            .and_then(|content| serde_json::from_str::<CacheData>(&content).ok())
+            .filter(|data| data.version == CACHE_VERSION)
+            .unwrap_or_default();
+
+        let mut cache = Self {
+            file: Some(file),
+            prefix: workdir_prefix(&repo, root),
+            previous,
+            ..Self::disabled(root)
+        };
+        cache.read_index(&repo);
+        cache
+    }
+
+    fn read_index(&mut self, repo: &Repository) {
+        let Ok(index) = repo.index() else {
+            return;
+        };
+        self.index_mtime = index
+            .path()
+            .and_then(|path| fs::metadata(path).ok())
+            .and_then(|metadata| metadata.modified().ok())
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or(i64::MAX);
+        self.index = index
+            .iter()
+            .filter_map(|entry| {
+                let path = PathBuf::from(String::from_utf8(entry.path).ok()?);
+                let stat = IndexStat {
+                    oid: entry.id,
+                    size: entry.file_size,
+                    mtime: (entry.mtime.seconds(), entry.mtime.nanoseconds()),
+                };
+                Some((path, stat))
+            })
+            .collect();
+    }
+
+    /// Cache key for `path` counted as `language`, or `None` when caching is off.
+    ///
+    /// Tracked files whose size and mtime match the index reuse the staged blob
+    /// id; anything else is hashed the way `git hash-object` would.
+    pub fn key(&self, path: &Path, language: &str) -> Option<String> {
+        self.file.as_ref()?;
+        let oid = self
+            .indexed_oid(path)
+            .or_else(|| Oid::hash_file(ObjectType::Blob, path).ok())?;
+        self.blob_key(oid, language)
+    }
+
+    /// Cache key for the blob `oid` counted as `language`, or `None` when caching is off.
+    pub fn blob_key(&self, oid: Oid, language: &str) -> Option<String> {
+        self.file.as_ref()?;
+        Some(format!("{}:{}", oid, language))
+    }
+
+    fn indexed_oid(&self, path: &Path) -> Option<Oid> {
+        let relative = path.strip_prefix(&self.root).ok()?;
+        let stat = self.index.get(&self.prefix.as_ref()?.join(relative))?;
+        let metadata = fs::metadata(path).ok()?;
+        let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        let mtime = (modified.as_secs() as i32, modified.subsec_nanos());
+
+        // Like git's "racily clean" check: a file written in the same second as
+        // the index could have changed without its stat data changing.
+        let racy = i64::from(stat.mtime.0) >= self.index_mtime;
+        (!racy && stat.size == metadata.len() as u32 && stat.mtime == mtime).then_some(stat.oid)
+    }
+
+    /// Counts stored for `key` by a previous run or earlier in this one.
+    pub fn file(&self, key: &str) -> Option<FileStats> {
+        self.current
+            .files
+            .get(key)
+            .or_else(|| self.previous.files.get(key))
+            .cloned()
+    }
+
+    /// Keep `stats` for the next run, whether they were just computed or cached.
+    pub fn insert_file(&mut self, key: String, stats: FileStats) {
+        if self.file.is_some() {
+            self.current.files.insert(key, stats);
+        }
+    }
+
+    pub fn history(&self) -> Option<&History> {
+        self.previous.history.as_ref()
+    }
+
+    pub fn set_history(&mut self, history: History) {
+        self.current.history = Some(history);
+    }
+
+    /// Write the entries used by this run; a no-op when caching is disabled.
+    pub fn save(mut self) -> Result<()> {
+        let Some(file) = self.file.take() else {
+            return Ok(());
+        };
+        if let Some(dir) = file.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create cache directory: {}", dir.display()))?;
+        }
+        self.current.version = CACHE_VERSION;
+        // Runs that value past revisions leave the HEAD history untouched.
+        if self.current.history.is_none() {
+            self.current.history = self.previous.history.take();
+        }
+        let content = serde_json::to_string(&self.current)?;
+        fs::write(&file, content)
+            .with_context(|| format!("Failed to write cache file: {}", file.display()))
+    }
+}
+
+/// Where `root` is in the working tree of `repo`.
+fn workdir_prefix(repo: &Repository, root: &Path) -> Option<PathBuf> {
+    let workdir = repo.workdir()?.canonicalize().ok()?;
+    let root = root.canonicalize().ok()?;
+    root.strip_prefix(workdir).ok().map(Path::to_path_buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::GitAnalyzer;
+    use crate::testing::TempRepo;
+    use std::time::{Duration, SystemTime};
+
+    fn stats(code: usize) -> FileStats {
+        FileStats {
+            lines: code,
+            code,
+            comments: 0,
+            docs: 0,
+            blanks: 0,
+            tokens: Default::default(),
+            functions: Vec::new(),
+            fingerprints: Vec::new(),
+        }
+    }
+
+    fn cache_file(repo: &TempRepo) -> PathBuf {
+        repo.repo.path().join("code-cost").join(CACHE_FILE)
+    }
+
+    /// A repository with `src/main.rs` committed and its counts cached.
+    fn cached_repo() -> (TempRepo, String) {
+        let mut repo = TempRepo::new();
+        repo.write("src/main.rs", "fn main() {}\n");
+        repo.commit("init");
+
+        let mut cache = Cache::load(repo.dir.path());
+        let key = cache
+            .key(&repo.dir.path().join("src/main.rs"), "Rust")
+            .unwrap();
+        cache.insert_file(key.clone(), stats(1));
+        cache.save().unwrap();
+        (repo, key)
+    }
+
+    /// Rewrite the saved cache through its JSON form.
+    fn edit_cache(repo: &TempRepo, edit: impl FnOnce(&mut serde_json::Value)) {
+        let path = cache_file(repo);
+        let mut data: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        edit(&mut data);
+        fs::write(&path, data.to_string()).unwrap();
+    }
+
+    fn set_modified(path: &Path, time: SystemTime) {
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(time)
+            .unwrap();
+    }
+
+    fn commit_count(repo: &TempRepo) -> usize {
+        let mut cache = Cache::load(repo.dir.path());
+        let stats = GitAnalyzer::new()
+            .analyze(repo.dir.path(), &mut cache)
+            .unwrap();
+        cache.save().unwrap();
+        stats.commit_count
+    }
+
+    #[test]
+    fn entries_survive_a_reload() {
+        let (repo, key) = cached_repo();
+        let cache = Cache::load(repo.dir.path());
+        assert_eq!(cache.file(&key).map(|s| s.code), Some(1));
+    }
+
+    #[test]
+    fn version_bump_discards_entries() {
+        let (repo, key) = cached_repo();
+        edit_cache(&repo, |data| data["version"] = (CACHE_VERSION - 1).into());
+
+        assert!(Cache::load(repo.dir.path()).file(&key).is_none());
+    }
+
+    #[test]
+    fn key_includes_the_language() {
+        let (repo, key) = cached_repo();
+        let cache = Cache::load(repo.dir.path());
+        let path = repo.dir.path().join("src/main.rs");
+
+        assert_eq!(cache.key(&path, "Rust"), Some(key));
+        let other = cache.key(&path, "C").unwrap();
+        assert!(other.ends_with(":C"));
+        assert!(cache.file(&other).is_none());
+    }
+
+    #[test]
+    fn racily_clean_entries_are_rehashed() {
+        let (repo, _) = cached_repo();
+        let path = repo.dir.path().join("src/main.rs");
+        let staged = repo
+            .repo
+            .index()
+            .unwrap()
+            .get_path(Path::new("src/main.rs"), 0)
+            .unwrap();
+
+        // Same size and mtime as the index entry, different content, with the
+        // index itself written in that same second.
+        fs::write(&path, "fn nain() {}\n").unwrap();
+        let mtime =
+            UNIX_EPOCH + Duration::new(staged.mtime.seconds() as u64, staged.mtime.nanoseconds());
+        set_modified(&path, mtime);
+        set_modified(&repo.repo.path().join("index"), mtime);
+        let hashed = Oid::hash_file(ObjectType::Blob, &path).unwrap();
+        assert_ne!(hashed, staged.id);
+
+        let mut cache = Cache::load(repo.dir.path());
+        assert_eq!(cache.key(&path, "Rust"), Some(format!("{}:Rust", hashed)));
+
+        // An index written a second later would vouch for the stale blob.
+        cache.index_mtime = i64::from(staged.mtime.seconds()) + 1;
+        assert_eq!(
+            cache.key(&path, "Rust"),
+            Some(format!("{}:Rust", staged.id))
+        );
+    }
+
+    #[test]
+    fn history_is_reused_only_from_an_ancestor() {
+        let mut repo = TempRepo::new();
+        repo.write("a.txt", "a\n");
+        let first = repo.commit("first");
+        repo.write("a.txt", "b\n");
+        repo.commit("second");
+        assert_eq!(commit_count(&repo), 2);
+
+        // Inflate the cached totals so reuse shows in the result.
+        edit_cache(&repo, |data| data["history"]["commit_count"] = 100.into());
+        repo.write("a.txt", "c\n");
+        repo.commit("third");
+        assert_eq!(commit_count(&repo), 101);
+
+        // After a force-push the cached HEAD is no ancestor: walk it all again.
+        edit_cache(&repo, |data| data["history"]["commit_count"] = 100.into());
+        repo.reset(first);
+        repo.write("a.txt", "d\n");
+        repo.commit("rewritten");
+        assert_eq!(commit_count(&repo), 2);
+    }
+
+    #[test]
+    fn disabled_cache_neither_reads_nor_writes() {
+        let (repo, key) = cached_repo();
+        commit_count(&repo);
+        let saved = fs::read_to_string(cache_file(&repo)).unwrap();
+
+        let mut cache = Cache::disabled(repo.dir.path());
+        let path = repo.dir.path().join("src/main.rs");
+        assert!(cache.key(&path, "Rust").is_none());
+        assert!(cache.file(&key).is_none());
+        assert!(cache.history().is_none());
+
+        cache.insert_file(key, stats(2));
+        GitAnalyzer::new()
+            .analyze(repo.dir.path(), &mut cache)
+            .unwrap();
+        cache.save().unwrap();
+        assert_eq!(fs::read_to_string(cache_file(&repo)).unwrap(), saved);
+    }
+
+    #[test]
+    fn a_subdirectory_uses_the_repository_cache() {
+        let mut repo = TempRepo::new();
+        repo.write("app/src/main.rs", "fn main() {}\n");
+        repo.write("README.md", "# Project\n");
+        repo.commit("init");
+        let app = repo.dir.path().join("app");
+        let path = app.join("src/main.rs");
+        let staged = repo
+            .repo
+            .index()
+            .unwrap()
+            .get_path(Path::new("app/src/main.rs"), 0)
+            .unwrap();
+
+        let mut cache = Cache::load(&app);
+        assert_eq!(cache.prefix.as_deref(), Some(Path::new("app")));
+        // Index entries are found by their path from the top of the tree.
+        cache.index_mtime = i64::MAX;
+        assert_eq!(cache.indexed_oid(&path), Some(staged.id));
+
+        let key = cache.key(&path, "Rust").unwrap();
+        cache.insert_file(key.clone(), stats(1));
+        let stats = GitAnalyzer::new().analyze(&app, &mut cache).unwrap();
+        assert_eq!(stats.commit_count, 1);
+        cache.save().unwrap();
+
+        assert!(cache_file(&repo).is_file());
+        let reloaded = Cache::load(&app);
+        assert_eq!(reloaded.file(&key).map(|s| s.code), Some(1));
+        assert!(reloaded.history().is_some());
+    }
+}
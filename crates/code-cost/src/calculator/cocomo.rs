@@ -0,0 +1,686 @@
+//! Parametric models: COCOMO 81 (Boehm, 1981), COCOMO II.2000
+//! post-architecture, and a power law calibrated in configuration.
+//!
+//! Size is thousands of source lines (KSLOC), counting code lines only.
+
+use anyhow::{bail, Result};
+use cli_core::config::{
+    Cocomo2Config, Cocomo81Config, CocomoMode, CustomModelConfig, DriverValue, Rating,
+};
+use std::collections::BTreeMap;
+
+use super::model::{Assumption, Estimate, EstimationModel};
+use crate::analyzer::Analysis;
+
+/// Marks a rating a driver's table does not define.
+const UNDEFINED: f64 = f64::NAN;
+
+/// Intermediate COCOMO 81 cost drivers, rated very low to extra high.
+const COCOMO81_DRIVERS: &[(&str, [f64; 6])] = &[
+    ("RELY", [0.75, 0.88, 1.00, 1.15, 1.40, UNDEFINED]),
+    ("DATA", [UNDEFINED, 0.94, 1.00, 1.08, 1.16, UNDEFINED]),
+    ("CPLX", [0.70, 0.85, 1.00, 1.15, 1.30, 1.65]),
+    ("TIME", [UNDEFINED, UNDEFINED, 1.00, 1.11, 1.30, 1.66]),
+    ("STOR", [UNDEFINED, UNDEFINED, 1.00, 1.06, 1.21, 1.56]),
+    ("VIRT", [UNDEFINED, 0.87, 1.00, 1.15, 1.30, UNDEFINED]),
+    ("TURN", [UNDEFINED, 0.87, 1.00, 1.07, 1.15, UNDEFINED]),
+    ("ACAP", [1.46, 1.19, 1.00, 0.86, 0.71, UNDEFINED]),
+    ("AEXP", [1.29, 1.13, 1.00, 0.91, 0.82, UNDEFINED]),
+    ("PCAP", [1.42, 1.17, 1.00, 0.86, 0.70, UNDEFINED]),
+    ("VEXP", [1.21, 1.10, 1.00, 0.90, UNDEFINED, UNDEFINED]),
+    ("LEXP", [1.14, 1.07, 1.00, 0.95, UNDEFINED, UNDEFINED]),
+    ("MODP", [1.24, 1.10, 1.00, 0.91, 0.82, UNDEFINED]),
+    ("TOOL", [1.24, 1.10, 1.00, 0.91, 0.83, UNDEFINED]),
+    ("SCED", [1.23, 1.08, 1.00, 1.04, 1.10, UNDEFINED]),
+];
+
+/// COCOMO II scale factors, rated very low to extra high.
+const COCOMO2_SCALE_FACTORS: &[(&str, [f64; 6])] = &[
+    ("PREC", [6.20, 4.96, 3.72, 2.48, 1.24, 0.00]),
+    ("FLEX", [5.07, 4.05, 3.04, 2.03, 1.01, 0.00]),
+    ("RESL", [7.07, 5.65, 4.24, 2.83, 1.41, 0.00]),
+    ("TEAM", [5.48, 4.38, 3.29, 2.19, 1.10, 0.00]),
+    ("PMAT", [7.80, 6.24, 4.68, 3.12, 1.56, 0.00]),
+];
+
+/// COCOMO II post-architecture effort multipliers, rated very low to extra high.
+const COCOMO2_MULTIPLIERS: &[(&str, [f64; 6])] = &[
+    ("RELY", [0.82, 0.92, 1.00, 1.10, 1.26, UNDEFINED]),
+    ("DATA", [UNDEFINED, 0.90, 1.00, 1.14, 1.28, UNDEFINED]),
+    ("CPLX", [0.73, 0.87, 1.00, 1.17, 1.34, 1.74]),
+    ("RUSE", [UNDEFINED, 0.95, 1.00, 1.07, 1.15, 1.24]),
+    ("DOCU", [0.81, 0.91, 1.00, 1.11, 1.23, UNDEFINED]),
+    ("TIME", [UNDEFINED, UNDEFINED, 1.00, 1.11, 1.29, 1.63]),
+    ("STOR", [UNDEFINED, UNDEFINED, 1.00, 1.05, 1.17, 1.46]),
+    ("PVOL", [UNDEFINED, 0.87, 1.00, 1.15, 1.30, UNDEFINED]),
+    ("ACAP", [1.42, 1.19, 1.00, 0.85, 0.71, UNDEFINED]),
+    ("PCAP", [1.34, 1.15, 1.00, 0.88, 0.76, UNDEFINED]),
+    ("PCON", [1.29, 1.12, 1.00, 0.90, 0.81, UNDEFINED]),
+    ("APEX", [1.22, 1.10, 1.00, 0.88, 0.81, UNDEFINED]),
+    ("PLEX", [1.19, 1.09, 1.00, 0.91, 0.85, UNDEFINED]),
+    ("LTEX", [1.20, 1.09, 1.00, 0.91, 0.84, UNDEFINED]),
+    ("TOOL", [1.17, 1.09, 1.00, 0.90, 0.78, UNDEFINED]),
+    ("SITE", [1.22, 1.09, 1.00, 0.93, 0.86, 0.80]),
+    ("SCED", [1.43, 1.14, 1.00, 1.00, 1.00, UNDEFINED]),
+];
+
+/// Share of the nominal schedule allowed by each `SCED` rating.
+const COCOMO2_SCED_SCHEDULE: [f64; 6] = [0.75, 0.85, 1.00, 1.30, 1.60, UNDEFINED];
+
+const COCOMO2_A: f64 = 2.94;
+const COCOMO2_B: f64 = 0.91;
+const COCOMO2_C: f64 = 3.67;
+const COCOMO2_D: f64 = 0.28;
+
+/// A resolved cost driver, scale factor or custom multiplier.
+struct Driver {
+    name: String,
+    rating: Option<Rating>,
+    value: f64,
+}
+
+impl Driver {
+    fn describe(&self) -> String {
+        match self.rating {
+            Some(rating) => format!("{} ({:.2})", rating_label(rating), self.value),
+            None => format!("{:.2} (calibrated)", self.value),
+        }
+    }
+}
+
+/// Look up configured `drivers` in `table`, in table order. Names match
+/// case-insensitively because environment variable keys are lowercased.
+fn resolve(
+    kind: &str,
+    table: &[(&str, [f64; 6])],
+    drivers: &BTreeMap<String, DriverValue>,
+) -> Result<Vec<Driver>> {
+    if let Some(unknown) = drivers.keys().find(|name| {
+        !table
+            .iter()
+            .any(|(known, _)| known.eq_ignore_ascii_case(name))
+    }) {
+        let known: Vec<&str> = table.iter().map(|(name, _)| *name).collect();
+        bail!(
+            "Unknown {kind} `{unknown}`; expected one of {}",
+            known.join(", ")
+        );
+    }
+
+    let mut resolved = Vec::new();
+    for (name, ratings) in table {
+        let Some(value) = drivers
+            .iter()
+            .find(|(configured, _)| configured.eq_ignore_ascii_case(name))
+            .map(|(_, value)| *value)
+        else {
+            continue;
+        };
+        let (rating, value) = match value {
+            DriverValue::Rating(rating) => {
+                let value = ratings[rating as usize];
+                if value.is_nan() {
+                    bail!("{kind} {name} has no {} rating", rating_label(rating));
+                }
+                (Some(rating), value)
+            }
+            DriverValue::Value(value) => (None, value),
+        };
+        resolved.push(Driver {
+            name: name.to_string(),
+            rating,
+            value,
+        });
+    }
+    Ok(resolved)
+}
+
+fn rating_label(rating: Rating) -> &'static str {
+    match rating {
+        Rating::VeryLow => "very low",
+        Rating::Low => "low",
+        Rating::Nominal => "nominal",
+        Rating::High => "high",
+        Rating::VeryHigh => "very high",
+        Rating::ExtraHigh => "extra high",
+    }
+}
+
+fn size_assumption(analysis: &Analysis) -> Assumption {
+    Assumption::new(
+        "Size",
+        format!(
+            "{:.2} KSLOC (non-blank, non-comment lines)",
+            ksloc(analysis)
+        ),
+    )
+}
+
+fn ksloc(analysis: &Analysis) -> f64 {
+    analysis.code_lines as f64 / 1000.0
+}
+
+fn product(drivers: &[Driver]) -> f64 {
+    drivers.iter().map(|driver| driver.value).product()
+}
+
+/// Basic or intermediate COCOMO 81.
+pub(super) struct Cocomo81 {
+    mode: CocomoMode,
+    /// `None` for basic COCOMO, which has no cost drivers.
+    drivers: Option<Vec<Driver>>,
+    hours_per_month: f64,
+}
+
+impl Cocomo81 {
+    pub(super) fn basic(config: &Cocomo81Config, hours_per_month: f64) -> Self {
+        Self {
+            mode: config.mode,
+            drivers: None,
+            hours_per_month,
+        }
+    }
+
+    pub(super) fn intermediate(config: &Cocomo81Config, hours_per_month: f64) -> Result<Self> {
+        Ok(Self {
+            mode: config.mode,
+            drivers: Some(resolve(
+                "COCOMO 81 cost driver",
+                COCOMO81_DRIVERS,
+                &config.cost_drivers,
+            )?),
+            hours_per_month,
+        })
+    }
+
+    /// Effort `a`, `b` and schedule `c`, `d` coefficients for the mode.
+    fn coefficients(&self) -> (f64, f64, f64, f64) {
+        let intermediate = self.drivers.is_some();
+        match (self.mode, intermediate) {
+            (CocomoMode::Organic, false) => (2.4, 1.05, 2.5, 0.38),
+            (CocomoMode::SemiDetached, false) => (3.0, 1.12, 2.5, 0.35),
+            (CocomoMode::Embedded, false) => (3.6, 1.20, 2.5, 0.32),
+            (CocomoMode::Organic, true) => (3.2, 1.05, 2.5, 0.38),
+            (CocomoMode::SemiDetached, true) => (3.0, 1.12, 2.5, 0.35),
+            (CocomoMode::Embedded, true) => (2.8, 1.20, 2.5, 0.32),
+        }
+    }
+}
+
+impl EstimationModel for Cocomo81 {
+    fn name(&self) -> &'static str {
+        match self.drivers {
+            Some(_) => "COCOMO 81 intermediate",
+            None => "COCOMO 81 basic",
+        }
+    }
+
+    fn estimate(&self, analysis: &Analysis) -> Estimate {
+        let (a, b, c, d) = self.coefficients();
+        let eaf = self.drivers.as_deref().map_or(1.0, product);
+        let person_months = a * ksloc(analysis).powf(b) * eaf;
+        let schedule_months = c * person_months.powf(d);
+
+        let mode = match self.mode {
+            CocomoMode::Organic => "organic",
+            CocomoMode::SemiDetached => "semi-detached",
+            CocomoMode::Embedded => "embedded",
+        };
+        let mut assumptions = vec![size_assumption(analysis), Assumption::new("Mode", mode)];
+        match &self.drivers {
+            Some(drivers) => {
+                assumptions.push(Assumption::new(
+                    "Effort",
+                    format!("{a} x KSLOC^{b} x EAF = {person_months:.1} person-months"),
+                ));
+                assumptions.push(Assumption::new(
+                    "EAF",
+                    format!("{eaf:.3}, unlisted cost drivers nominal (1.00)"),
+                ));
+                assumptions.extend(
+                    drivers
+                        .iter()
+                        .map(|driver| Assumption::new(&driver.name, driver.describe())),
+                );
+            }
+            None => assumptions.push(Assumption::new(
+                "Effort",
+                format!("{a} x KSLOC^{b} = {person_months:.1} person-months"),
+            )),
+        }
+        assumptions.push(Assumption::new(
+            "Schedule",
+            format!("{c} x PM^{d} = {schedule_months:.1} months"),
+        ));
+
+        Estimate::new(
+            self,
+            person_months,
+            schedule_months,
+            self.hours_per_month,
+            assumptions,
+        )
+    }
+}
+
+/// COCOMO II.2000 post-architecture.
+pub(super) struct Cocomo2 {
+    scale_factors: Vec<Driver>,
+    multipliers: Vec<Driver>,
+    hours_per_month: f64,
+}
+
+impl Cocomo2 {
+    pub(super) fn new(config: &Cocomo2Config, hours_per_month: f64) -> Result<Self> {
+        // Every scale factor feeds the exponent, so unset ones are rated nominal.
+        let mut scale_factors = config.scale_factors.clone();
+        for (name, _) in COCOMO2_SCALE_FACTORS {
+            if !scale_factors
+                .keys()
+                .any(|key| key.eq_ignore_ascii_case(name))
+            {
+                scale_factors.insert(name.to_string(), DriverValue::Rating(Rating::Nominal));
+            }
+        }
+        Ok(Self {
+            scale_factors: resolve(
+                "COCOMO II scale factor",
+                COCOMO2_SCALE_FACTORS,
+                &scale_factors,
+            )?,
+            multipliers: resolve(
+                "COCOMO II effort multiplier",
+                COCOMO2_MULTIPLIERS,
+                &config.effort_multipliers,
+            )?,
+            hours_per_month,
+        })
+    }
+}
+
+impl EstimationModel for Cocomo2 {
+    fn name(&self) -> &'static str {
+        "COCOMO II post-architecture"
+    }
+
+    fn estimate(&self, analysis: &Analysis) -> Estimate {
+        let exponent = COCOMO2_B + 0.01 * self.scale_factors.iter().map(|sf| sf.value).sum::<f64>();
+        let multiplier = product(&self.multipliers);
+        let person_months = COCOMO2_A * ksloc(analysis).powf(exponent) * multiplier;
+
+        // The schedule uses effort without SCED, then compresses or stretches it.
+        let sced = self.multipliers.iter().find(|driver| driver.name == "SCED");
+        let nominal_effort = person_months / sced.map_or(1.0, |driver| driver.value);
+        let schedule_exponent = COCOMO2_D + 0.2 * (exponent - COCOMO2_B);
+        let sced_share = sced
+            .and_then(|driver| driver.rating)
+            .map_or(1.0, |rating| COCOMO2_SCED_SCHEDULE[rating as usize]);
+        let schedule_months = COCOMO2_C * nominal_effort.powf(schedule_exponent) * sced_share;
+
+        let mut assumptions = vec![size_assumption(analysis)];
+        assumptions.extend(
+            self.scale_factors
+                .iter()
+                .map(|sf| Assumption::new(&sf.name, sf.describe())),
+        );
+        assumptions.push(Assumption::new(
+            "Exponent E",
+            format!("{COCOMO2_B} + 0.01 x sum(scale factors) = {exponent:.4}"),
+        ));
+        assumptions.extend(
+            self.multipliers
+                .iter()
+                .map(|em| Assumption::new(&em.name, em.describe())),
+        );
+        assumptions.push(Assumption::new(
+            "Effort",
+            format!(
+                "{COCOMO2_A} x KSLOC^E x {multiplier:.3} (unlisted multipliers nominal) = {person_months:.1} person-months"
+            ),
+        ));
+        assumptions.push(Assumption::new(
+            "Schedule",
+            format!(
+                "{COCOMO2_C} x PM^{schedule_exponent:.4} x {:.0}% SCED = {schedule_months:.1} months",
+                sced_share * 100.0
+            ),
+        ));
+
+        Estimate::new(
+            self,
+            person_months,
+            schedule_months,
+            self.hours_per_month,
+            assumptions,
+        )
+    }
+}
+
+/// `a * KSLOC^b * multipliers` person-months over `c * PM^d` months.
+pub(super) struct Custom {
+    a: f64,
+    b: f64,
+    c: f64,
+    d: f64,
+    multipliers: Vec<Driver>,
+    hours_per_month: f64,
+}
+
+impl Custom {
+    pub(super) fn new(config: &CustomModelConfig, hours_per_month: f64) -> Self {
+        Self {
+            a: config.a,
+            b: config.b,
+            c: config.c,
+            d: config.d,
+            multipliers: config
+                .effort_multipliers
+                .iter()
+                .map(|(name, value)| Driver {
+                    name: name.clone(),
+                    rating: None,
+                    value: *value,
+                })
+                .collect(),
+            hours_per_month,
+        }
+    }
+}
+
+impl EstimationModel for Custom {
+    fn name(&self) -> &'static str {
+        "Custom"
+    }
+
+    fn estimate(&self, analysis: &Analysis) -> Estimate {
+        let multiplier = product(&self.multipliers);
+        let person_months = self.a * ksloc(analysis).powf(self.b) * multiplier;
+        let schedule_months = self.c * person_months.powf(self.d);
+
+        let mut assumptions = vec![size_assumption(analysis)];
+        assumptions.extend(
+            self.multipliers
+                .iter()
+                .map(|m| Assumption::new(&m.name, m.describe())),
+        );
+        assumptions.push(Assumption::new(
+            "Effort",
+            format!(
+                "{} x KSLOC^{} x {multiplier:.3} = {person_months:.1} person-months",
+                self.a, self.b
+            ),
+        ));
+        assumptions.push(Assumption::new(
+            "Schedule",
+            format!("{} x PM^{} = {schedule_months:.1} months", self.c, self.d),
+        ));
+
+        Estimate::new(
+            self,
+            person_months,
+            schedule_months,
+            self.hours_per_month,
+            assumptions,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOURS_PER_MONTH: f64 = 152.0;
+
+    fn analysis(ksloc: f64) -> Analysis {
+        Analysis {
+            code_lines: (ksloc * 1000.0) as usize,
+            ..Analysis::default()
+        }
+    }
+
+    fn ratings(drivers: &[(&str, Rating)]) -> BTreeMap<String, DriverValue> {
+        drivers
+            .iter()
+            .map(|(name, rating)| (name.to_string(), DriverValue::Rating(*rating)))
+            .collect()
+    }
+
+    fn assert_near(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{actual} is not within {tolerance} of {expected}"
+        );
+    }
+
+    /// Boehm's microprocessor communications example: 10 KDSI, embedded.
+    fn communications_drivers() -> BTreeMap<String, DriverValue> {
+        ratings(&[
+            ("RELY", Rating::High),
+            ("DATA", Rating::Low),
+            ("CPLX", Rating::VeryHigh),
+            ("TIME", Rating::High),
+            ("STOR", Rating::High),
+            ("VIRT", Rating::Nominal),
+            ("TURN", Rating::Low),
+            ("ACAP", Rating::High),
+            ("AEXP", Rating::Nominal),
+            ("PCAP", Rating::High),
+            ("VEXP", Rating::Low),
+            ("LEXP", Rating::Nominal),
+            ("MODP", Rating::High),
+            ("TOOL", Rating::Low),
+            ("SCED", Rating::Nominal),
+        ])
+    }
+
+    #[test]
+    fn basic_matches_published_32_kdsi_estimates() {
+        for (mode, effort, schedule) in [
+            (CocomoMode::Organic, 91.0, 13.9),
+            (CocomoMode::SemiDetached, 146.0, 14.3),
+            (CocomoMode::Embedded, 230.0, 14.3),
+        ] {
+            let config = Cocomo81Config {
+                mode,
+                ..Cocomo81Config::default()
+            };
+            let estimate = Cocomo81::basic(&config, HOURS_PER_MONTH).estimate(&analysis(32.0));
+
+            assert_near(estimate.effort_person_months, effort, 1.0);
+            assert_near(estimate.schedule_months, schedule, 0.1);
+            assert_near(
+                estimate.effort_hours,
+                estimate.effort_person_months * HOURS_PER_MONTH,
+                1e-9,
+            );
+        }
+    }
+
+    #[test]
+    fn intermediate_matches_published_communications_example() {
+        let config = Cocomo81Config {
+            mode: CocomoMode::Embedded,
+            cost_drivers: communications_drivers(),
+        };
+        let model = Cocomo81::intermediate(&config, HOURS_PER_MONTH).unwrap();
+        let estimate = model.estimate(&analysis(10.0));
+
+        assert_near(product(model.drivers.as_deref().unwrap()), 1.17, 0.005);
+        assert_near(estimate.effort_person_months, 52.0, 0.5);
+        assert_near(estimate.schedule_months, 8.85, 0.05);
+        assert_eq!(estimate.model, "COCOMO 81 intermediate");
+    }
+
+    #[test]
+    fn intermediate_with_nominal_drivers_uses_intermediate_coefficients() {
+        let config = Cocomo81Config::default();
+        let estimate = Cocomo81::intermediate(&config, HOURS_PER_MONTH)
+            .unwrap()
+            .estimate(&analysis(32.0));
+
+        assert_near(estimate.effort_person_months, 3.2 * 32f64.powf(1.05), 1e-9);
+    }
+
+    #[test]
+    fn eaf_is_the_product_of_the_configured_drivers() {
+        let mut drivers = ratings(&[("rely", Rating::VeryHigh), ("ACAP", Rating::VeryLow)]);
+        drivers.insert("TOOL".to_string(), DriverValue::Value(0.5));
+        let resolved = resolve("COCOMO 81 cost driver", COCOMO81_DRIVERS, &drivers).unwrap();
+
+        let names: Vec<&str> = resolved.iter().map(|driver| driver.name.as_str()).collect();
+        assert_eq!(names, ["RELY", "ACAP", "TOOL"]);
+        assert_near(product(&resolved), 1.40 * 1.46 * 0.5, 1e-9);
+        assert_near(product(&[]), 1.0, 0.0);
+    }
+
+    #[test]
+    fn cocomo81_sced_only_scales_effort() {
+        let nominal = Cocomo81::intermediate(&Cocomo81Config::default(), HOURS_PER_MONTH)
+            .unwrap()
+            .estimate(&analysis(20.0));
+        let compressed = Cocomo81::intermediate(
+            &Cocomo81Config {
+                cost_drivers: ratings(&[("SCED", Rating::VeryLow)]),
+                ..Cocomo81Config::default()
+            },
+            HOURS_PER_MONTH,
+        )
+        .unwrap()
+        .estimate(&analysis(20.0));
+
+        assert_near(
+            compressed.effort_person_months,
+            nominal.effort_person_months * 1.23,
+            1e-9,
+        );
+    }
+
+    #[test]
+    fn cocomo2_nominal_project() {
+        // Every scale factor nominal gives E = 0.91 + 0.01 x 18.97 = 1.0997.
+        let estimate = Cocomo2::new(&Cocomo2Config::default(), HOURS_PER_MONTH)
+            .unwrap()
+            .estimate(&analysis(100.0));
+
+        assert_near(estimate.effort_person_months, 465.3, 0.1);
+        assert_near(estimate.schedule_months, 25.9, 0.05);
+    }
+
+    #[test]
+    fn cocomo2_scale_factors_change_the_exponent() {
+        let config = Cocomo2Config {
+            scale_factors: ratings(&[
+                ("PREC", Rating::ExtraHigh),
+                ("FLEX", Rating::ExtraHigh),
+                ("RESL", Rating::ExtraHigh),
+                ("TEAM", Rating::ExtraHigh),
+                ("PMAT", Rating::ExtraHigh),
+            ]),
+            ..Cocomo2Config::default()
+        };
+        let estimate = Cocomo2::new(&config, HOURS_PER_MONTH)
+            .unwrap()
+            .estimate(&analysis(100.0));
+
+        assert_near(
+            estimate.effort_person_months,
+            COCOMO2_A * 100f64.powf(COCOMO2_B),
+            1e-9,
+        );
+        assert_near(
+            estimate.schedule_months,
+            COCOMO2_C * estimate.effort_person_months.powf(COCOMO2_D),
+            1e-9,
+        );
+    }
+
+    #[test]
+    fn cocomo2_sced_scales_effort_and_schedule() {
+        let nominal = Cocomo2::new(&Cocomo2Config::default(), HOURS_PER_MONTH)
+            .unwrap()
+            .estimate(&analysis(100.0));
+        for (rating, effort, schedule) in [
+            (Rating::VeryLow, 1.43, 0.75),
+            (Rating::Low, 1.14, 0.85),
+            (Rating::High, 1.00, 1.30),
+            (Rating::VeryHigh, 1.00, 1.60),
+        ] {
+            let config = Cocomo2Config {
+                effort_multipliers: ratings(&[("SCED", rating)]),
+                ..Cocomo2Config::default()
+            };
+            let estimate = Cocomo2::new(&config, HOURS_PER_MONTH)
+                .unwrap()
+                .estimate(&analysis(100.0));
+
+            assert_near(
+                estimate.effort_person_months,
+                nominal.effort_person_months * effort,
+                1e-6,
+            );
+            assert_near(
+                estimate.schedule_months,
+                nominal.schedule_months * schedule,
+                1e-6,
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_driver_names_are_errors() {
+        let config = Cocomo81Config {
+            cost_drivers: ratings(&[("RELY", Rating::High), ("SPEED", Rating::High)]),
+            ..Cocomo81Config::default()
+        };
+        let error = Cocomo81::intermediate(&config, HOURS_PER_MONTH)
+            .err()
+            .unwrap();
+        assert!(
+            error
+                .to_string()
+                .starts_with("Unknown COCOMO 81 cost driver `SPEED`; expected one of RELY, DATA"),
+            "{error}"
+        );
+
+        let config = Cocomo2Config {
+            scale_factors: ratings(&[("TURN", Rating::Low)]),
+            ..Cocomo2Config::default()
+        };
+        let error = Cocomo2::new(&config, HOURS_PER_MONTH).err().unwrap();
+        assert!(
+            error
+                .to_string()
+                .starts_with("Unknown COCOMO II scale factor `TURN`"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn undefined_ratings_are_errors() {
+        let config = Cocomo81Config {
+            cost_drivers: ratings(&[("DATA", Rating::VeryLow)]),
+            ..Cocomo81Config::default()
+        };
+        let error = Cocomo81::intermediate(&config, HOURS_PER_MONTH)
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "COCOMO 81 cost driver DATA has no very low rating"
+        );
+
+        for (name, rating) in [("TIME", Rating::Low), ("SCED", Rating::ExtraHigh)] {
+            let config = Cocomo2Config {
+                effort_multipliers: ratings(&[(name, rating)]),
+                ..Cocomo2Config::default()
+            };
+            let error = Cocomo2::new(&config, HOURS_PER_MONTH).err().unwrap();
+            assert!(
+                error
+                    .to_string()
+                    .starts_with(&format!("COCOMO II effort multiplier {name} has no")),
+                "{error}"
+            );
+        }
+    }
+}
@@ -0,0 +1,277 @@
+//! LLM pricing catalogue for the AI development simulation.
+//!
+//! Built-in list prices can be corrected, and models added, in a TOML
+//! catalogue keyed by model id:
+//!
+//! ```toml
+//! [models.claude-opus-4-7]
+//! name = "Claude Opus 4.7"
+//! input = 5.0         # per million tokens
+//! output = 25.0
+//! cache_write = 6.25  # optional, defaults to input
+//! cache_read = 0.5    # optional, no prompt caching without it
+//! currency = "USD"    # optional, defaults to USD
+//! tokenizer = "heuristic"
+//! ```
+
+use anyhow::{bail, Context, Result};
+use cli_core::config::{expand_home, user_config_path, CodeCostConfig};
+use cli_core::currency::Currency;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::tokenizer::Tokenizer;
+
+/// User catalogue, next to the user configuration.
+pub const CATALOGUE_FILE: &str = "llm-pricing.toml";
+
+/// List prices in USD per million tokens from each vendor's pricing page;
+/// prices that have since changed can be corrected in the user catalogue.
+const BUILTIN: &str = r#"
+[models.claude-opus-4-7]
+name = "Claude Opus 4.7"
+input = 5.0
+output = 25.0
+cache_write = 6.25
+cache_read = 0.5
+
+[models.claude-sonnet-4-5]
+name = "Claude Sonnet 4.5"
+input = 3.0
+output = 15.0
+cache_write = 3.75
+cache_read = 0.3
+
+[models.claude-haiku-4-5]
+name = "Claude Haiku 4.5"
+input = 1.0
+output = 5.0
+cache_write = 1.25
+cache_read = 0.1
+
+[models.gpt-5]
+name = "GPT-5"
+input = 1.25
+output = 10.0
+cache_read = 0.125
+tokenizer = "o200k_base"
+
+[models."gpt-4.1"]
+name = "GPT-4.1"
+input = 2.0
+output = 8.0
+cache_read = 0.5
+tokenizer = "o200k_base"
+
+[models.gpt-4o]
+name = "GPT-4o"
+input = 2.5
+output = 10.0
+cache_read = 1.25
+tokenizer = "o200k_base"
+
+[models.gpt-4-turbo]
+name = "GPT-4 Turbo"
+input = 10.0
+output = 30.0
+tokenizer = "cl100k_base"
+
+[models."gemini-2.5-pro"]
+name = "Gemini 2.5 Pro"
+input = 1.25
+output = 10.0
+cache_read = 0.31
+"#;
+
+/// A model's prices per million tokens.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LlmModel {
+    /// Catalogue id, such as `claude-opus-4-7`.
+    #[serde(skip)]
+    pub id: String,
+    pub name: String,
+    #[serde(default = "usd")]
+    pub currency: Currency,
+    pub input: f64,
+    pub output: f64,
+    /// Writing prompt context to the cache; the input price when unset.
+    pub cache_write: Option<f64>,
+    /// Reading cached context back; without it nothing is cached.
+    pub cache_read: Option<f64>,
+    #[serde(default)]
+    pub tokenizer: Tokenizer,
+}
+
+fn usd() -> Currency {
+    Currency::USD
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CatalogueFile {
+    #[serde(default)]
+    models: BTreeMap<String, LlmModel>,
+}
+
+pub struct Catalogue {
+    models: BTreeMap<String, LlmModel>,
+}
+
+impl Catalogue {
+    /// The built-in catalogue, extended by `code_cost.llm_pricing` or else the
+    /// user catalogue when it exists.
+    pub fn load(settings: &CodeCostConfig) -> Result<Self> {
+        let mut catalogue = Self::builtin();
+        let path = match &settings.llm_pricing {
+            Some(path) => Some(expand_home(path)),
+            None => catalogue_path().filter(|path| path.is_file()),
+        };
+        if let Some(path) = path {
+            catalogue.extend_from(&path)?;
+        }
+        Ok(catalogue)
+    }
+
+    fn builtin() -> Self {
+        let mut catalogue = Self {
+            models: BTreeMap::new(),
+        };
+        catalogue
+            .extend(BUILTIN, "built-in")
+            .expect("built-in LLM pricing is valid");
+        catalogue
+    }
+
+    /// Add the models in `path`, replacing built-in ones with the same id.
+    fn extend_from(&mut self, path: &Path) -> Result<()> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read LLM pricing {}", path.display()))?;
+        self.extend(&content, &path.display().to_string())
+    }
+
+    fn extend(&mut self, content: &str, origin: &str) -> Result<()> {
+        let file: CatalogueFile = toml::from_str(content)
+            .with_context(|| format!("Failed to parse LLM pricing {origin}"))?;
+        for (id, mut model) in file.models {
+            model.id = id.clone();
+            self.models.insert(id, model);
+        }
+        Ok(())
+    }
+
+    /// The models named by `ids`, in that order.
+    pub fn select(&self, ids: &[String]) -> Result<Vec<LlmModel>> {
+        if ids.is_empty() {
+            bail!("No LLM models selected; set code_cost.llm_models or pass --models");
+        }
+        ids.iter()
+            .map(|id| match self.models.get(id) {
+                Some(model) => Ok(model.clone()),
+                None => bail!(
+                    "Unknown LLM model '{id}'; known models: {}",
+                    self.models.keys().cloned().collect::<Vec<_>>().join(", ")
+                ),
+            })
+            .collect()
+    }
+}
+
+/// Path of the user catalogue.
+pub fn catalogue_path() -> Option<PathBuf> {
+    Some(user_config_path()?.with_file_name(CATALOGUE_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+
+    fn ids(models: &[LlmModel]) -> Vec<&str> {
+        models.iter().map(|model| model.id.as_str()).collect()
+    }
+
+    #[test]
+    fn builtin_catalogue_parses() {
+        let catalogue = Catalogue::builtin();
+        assert_eq!(catalogue.models.len(), 8);
+        for (id, model) in &catalogue.models {
+            assert_eq!(&model.id, id);
+            assert_eq!(model.currency, Currency::USD);
+            assert!(model.input > 0.0 && model.output > model.input, "{id}");
+        }
+
+        let opus = &catalogue.models["claude-opus-4-7"];
+        assert_eq!(opus.name, "Claude Opus 4.7");
+        assert_eq!(opus.cache_write, Some(6.25));
+        assert_eq!(opus.tokenizer, Tokenizer::Heuristic);
+        assert_eq!(catalogue.models["gpt-5"].tokenizer, Tokenizer::O200kBase);
+        assert_eq!(catalogue.models["gpt-4-turbo"].cache_read, None);
+    }
+
+    #[test]
+    fn user_catalogue_replaces_and_adds_models() {
+        let dir = TempDir::new();
+        dir.write(
+            "prices.toml",
+            "[models.gpt-5]\nname = \"GPT-5\"\ninput = 2.0\noutput = 12.0\n\n\
+             [models.local]\nname = \"Local\"\ninput = 1500.0\noutput = 3000.0\ncurrency = \"KRW\"\n",
+        );
+        let settings = CodeCostConfig {
+            llm_pricing: Some(dir.path().join("prices.toml")),
+            ..CodeCostConfig::default()
+        };
+        let catalogue = Catalogue::load(&settings).unwrap();
+
+        let gpt = &catalogue.models["gpt-5"];
+        assert_eq!((gpt.input, gpt.output), (2.0, 12.0));
+        // A replaced model takes nothing from the built-in entry.
+        assert_eq!(gpt.cache_read, None);
+        assert_eq!(gpt.tokenizer, Tokenizer::Heuristic);
+
+        let local = &catalogue.models["local"];
+        assert_eq!(local.id, "local");
+        assert_eq!(local.currency, Currency::KRW);
+        assert!(catalogue.models.contains_key("claude-opus-4-7"));
+    }
+
+    #[test]
+    fn invalid_user_catalogues_are_rejected() {
+        let dir = TempDir::new();
+        dir.write(
+            "prices.toml",
+            "[models.typo]\nname = \"Typo\"\ninput = 1.0\noutput = 2.0\ncache = 0.1\n",
+        );
+        for path in ["prices.toml", "missing.toml"] {
+            let settings = CodeCostConfig {
+                llm_pricing: Some(dir.path().join(path)),
+                ..CodeCostConfig::default()
+            };
+            let error = Catalogue::load(&settings).err().unwrap().to_string();
+            assert!(error.contains(path), "{error}");
+        }
+    }
+
+    #[test]
+    fn select_keeps_the_requested_order() {
+        let catalogue = Catalogue::builtin();
+        let selected = catalogue
+            .select(&["gpt-5".to_string(), "claude-haiku-4-5".to_string()])
+            .unwrap();
+        assert_eq!(ids(&selected), ["gpt-5", "claude-haiku-4-5"]);
+    }
+
+    #[test]
+    fn select_rejects_unknown_and_empty_selections() {
+        let catalogue = Catalogue::builtin();
+        let error = catalogue
+            .select(&["gpt-5".to_string(), "gpt-6".to_string()])
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.contains("'gpt-6'"), "{error}");
+        assert!(error.contains("claude-opus-4-7"), "{error}");
+        assert!(catalogue.select(&[]).is_err());
+    }
+}
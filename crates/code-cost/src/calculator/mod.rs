@@ -1,6 +1,19 @@
+use anyhow::Result;
+use cli_core::currency::{Currency, Pricing};
+use cli_core::config::{DependencyMaintenanceConfig, DuplicationConfig};
+use cli_core::Config;
 use serde::{Deserialize, Serialize};
 
-use crate::analyzer::Analysis;
+use crate::analyzer::{Analysis, LanguageStat};
+use crate::git::Signal;
+use crate::tokenizer::Tokenizer;
+use llm::{Catalogue, LlmModel};
+pub use model::Estimate;
+use model::{Assumption, EstimationModel};
+
+mod cocomo;
+mod llm;
+mod model;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeveloperLevel {
@@ -11,63 +24,158 @@ pub struct DeveloperLevel {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AIAnalysis {
+    /// Share of inserted lines from commits attributed to an AI tool.
     pub estimated_ai_usage: f64,
     pub code_quality_score: f64,
-    pub potential_ai_indicators: Vec<String>,
+    /// Commits a trailer, message or author attributes to an AI tool.
+    pub attributed_commits: usize,
+    /// Every signal found, with the commits it came from.
+    pub evidence: Vec<Signal>,
+}
+
+/// Upkeep of the locked dependencies, at `[code_cost.dependencies]` hours each.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyMaintenance {
+    pub direct: usize,
+    pub transitive: usize,
+    /// Versions locked beyond the first of each package.
+    pub extra_versions: usize,
+    pub hours: f64,
+    pub cost: f64,
 }
 
+/// Effort left out for code copied within the codebase, at
+/// `code_cost.duplication.discount` per duplicated line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicationDiscount {
+    pub duplicated_lines: usize,
+    /// Share of the analysed lines that are duplicated.
+    pub share: f64,
+    /// Code lines the estimation model did not count.
+    pub discounted_lines: usize,
+    pub hours: f64,
+    pub cost: f64,
+}
+
+/// What writing the codebase with one LLM would cost.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenCost {
+    /// Catalogue id
+    pub model: String,
+    pub model_name: String,
+    pub tokenizer: Tokenizer,
     pub base_tokens: usize,
     pub estimated_prompt_tokens: usize,
+    /// Part of the prompt read back from the prompt cache.
+    pub cached_prompt_tokens: usize,
     pub estimated_output_tokens: usize,
     pub total_estimated_tokens: usize,
-    pub input_cost_krw: f64,
-    pub output_cost_krw: f64,
-    pub total_cost_krw: f64,
-    pub model_name: String,
+    /// Prompt tokens sent fresh, at the input or cache-write price.
+    pub input_cost: f64,
+    pub cache_read_cost: f64,
+    pub output_cost: f64,
+    pub total_cost: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CostEstimate {
-    pub base_hours: f64,
-    pub complexity_multiplier: f64,
-    pub language_adjusted_hours: f64,
-    pub maturity_bonus_hours: f64,
+    /// Currency of every amount in the estimate.
+    pub currency: Currency,
+    /// The model's effort plus dependency maintenance.
     pub estimated_hours: f64,
     pub hourly_rate: f64,
     pub total_cost: f64,
+    /// Effort, schedule and team size with the selected model's assumptions.
+    pub estimate: Estimate,
     pub developer_levels: Vec<DeveloperLevel>,
+    pub duplication_discount: DuplicationDiscount,
+    pub dependency_maintenance: DependencyMaintenance,
     pub ai_analysis: AIAnalysis,
-    pub token_cost: TokenCost,
+    /// One per selected LLM; the first is the AI cost in summaries.
+    pub token_costs: Vec<TokenCost>,
+}
+
+impl CostEstimate {
+    /// Token cost of the first selected LLM.
+    pub fn token_cost(&self) -> &TokenCost {
+        &self.token_costs[0]
+    }
 }
 
+/// Developer level hourly rates in KRW (South Korea market rates as of 2025)
+const DEVELOPER_LEVELS: [(&str, f64); 5] = [
+    ("Junior", 15_000.0),      // 1-3년차
+    ("Mid-level", 25_000.0),   // 3-5년차
+    ("Senior", 40_000.0),      // 5-10년차
+    ("Lead", 60_000.0),        // 10+년차, 팀 리드
+    ("Principal", 100_000.0),  // 아키텍트, 시니어 엔지니어
+];
+
 pub struct CostCalculator {
+    currency: Currency,
     hourly_rate: f64,
+    /// `DEVELOPER_LEVELS` in `currency`.
+    developer_rates: Vec<(&'static str, f64)>,
+    /// Selected LLMs with their prices converted to `currency`.
+    llm_models: Vec<LlmModel>,
+    model: Box<dyn EstimationModel>,
+    dependencies: DependencyMaintenanceConfig,
+    duplication: DuplicationConfig,
 }
 
 impl CostCalculator {
-    pub fn new(hourly_rate: f64) -> Self {
-        Self { hourly_rate }
+    /// Fails when the configured model has an unknown driver or rating, an
+    /// LLM is missing from the pricing catalogue, or an amount cannot be
+    /// converted to the report currency.
+    pub fn new(config: &Config) -> Result<Self> {
+        let pricing = Pricing::from_config(config)?;
+        let developer_rates = DEVELOPER_LEVELS
+            .into_iter()
+            .map(|(level, rate)| Ok((level, pricing.convert(rate, Currency::KRW)?)))
+            .collect::<Result<_>>()?;
+        let llm_models = Catalogue::load(&config.code_cost)?
+            .select(&config.code_cost.llm_models)?
+            .into_iter()
+            .map(|model| {
+                let price = |amount: f64| pricing.convert(amount, model.currency);
+                Ok(LlmModel {
+                    currency: pricing.currency,
+                    input: price(model.input)?,
+                    output: price(model.output)?,
+                    cache_write: model.cache_write.map(price).transpose()?,
+                    cache_read: model.cache_read.map(price).transpose()?,
+                    ..model
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            currency: pricing.currency,
+            hourly_rate: pricing.hourly_rate,
+            developer_rates,
+            llm_models,
+            model: model::from_config(&config.code_cost)?,
+            dependencies: config.code_cost.dependencies.clone(),
+            duplication: config.code_cost.duplication.clone(),
+        })
     }
 
-    pub fn calculate(&self, analysis: &Analysis) -> CostEstimate {
-        // Base calculation: assume 20 lines per hour for average code
-        let base_hours = analysis.code_lines as f64 / 20.0;
-
-        // Apply language weights
-        let language_adjusted_hours = self.apply_language_weights(analysis, base_hours);
-
-        // Apply complexity multiplier (1.0 - 2.0)
-        let complexity_multiplier = self.calculate_complexity_multiplier(analysis);
-        let complexity_adjusted_hours = language_adjusted_hours * complexity_multiplier;
-
-        // Apply maturity bonus (up to 30% more)
-        let maturity_bonus_hours = complexity_adjusted_hours * analysis.maturity_score * 0.3;
+    /// Tokenizers the selected LLMs count with, besides the heuristic.
+    pub fn tokenizers(&self) -> Vec<Tokenizer> {
+        let mut tokenizers: Vec<Tokenizer> = self
+            .llm_models
+            .iter()
+            .map(|model| model.tokenizer)
+            .filter(|&tokenizer| tokenizer != Tokenizer::Heuristic)
+            .collect();
+        tokenizers.sort();
+        tokenizers.dedup();
+        tokenizers
+    }
 
-        // Total estimated hours including learning time
-        let learning_time = self.estimate_learning_time(analysis);
-        let estimated_hours = complexity_adjusted_hours + maturity_bonus_hours + learning_time;
+    pub fn calculate(&self, analysis: &Analysis) -> CostEstimate {
+        let (estimate, duplication_discount) = self.estimate(analysis);
+        let dependency_maintenance = self.calculate_dependency_maintenance(analysis);
+        let estimated_hours = estimate.effort_hours + dependency_maintenance.hours;
 
         // Calculate total cost
         let total_cost = estimated_hours * self.hourly_rate;
@@ -78,37 +186,104 @@ impl CostCalculator {
         // Analyze AI usage
         let ai_analysis = self.analyze_ai_usage(analysis);
 
-        // Calculate token cost (Claude 4.7 xhigh)
-        let token_cost = self.calculate_token_cost(analysis);
+        // Simulate writing the codebase with each selected LLM
+        let token_costs = self
+            .llm_models
+            .iter()
+            .map(|model| self.calculate_token_cost(analysis, model))
+            .collect();
 
         CostEstimate {
-            base_hours,
-            complexity_multiplier,
-            language_adjusted_hours,
-            maturity_bonus_hours,
+            currency: self.currency,
             estimated_hours,
             hourly_rate: self.hourly_rate,
             total_cost,
+            estimate,
             developer_levels,
+            duplication_discount,
+            dependency_maintenance,
             ai_analysis,
-            token_cost,
+            token_costs,
         }
     }
 
-    fn calculate_token_cost(&self, analysis: &Analysis) -> TokenCost {
-        // Claude Opus 4.7 xhigh pricing:
-        // Input: $5.00 / 1M tokens
-        // Output: $25.00 / 1M tokens
-        // 1 USD ≈ 1,400 KRW
-        let input_price_per_m_usd = 5.0;
-        let output_price_per_m_usd = 25.0;
-        let exchange_rate = 1400.0;
-        
-        let input_price_per_m_krw = input_price_per_m_usd * exchange_rate;
-        let output_price_per_m_krw = output_price_per_m_usd * exchange_rate;
-
-        // Base tokens from the current codebase
-        let base_tokens = analysis.total_tokens;
+    /// The model's estimate with duplicated code discounted. Models size the
+    /// codebase by its line counts, so every count is scaled down by the
+    /// discounted share of the lines.
+    fn estimate(&self, analysis: &Analysis) -> (Estimate, DuplicationDiscount) {
+        let duplication = &analysis.duplication;
+        let discount = self.duplication.discount.clamp(0.0, 1.0);
+        let kept = 1.0 - duplication.share() * discount;
+        let scale = |lines: usize| (lines as f64 * kept).round() as usize;
+        let discounted_lines = analysis.code_lines - scale(analysis.code_lines);
+        let full = self.model.estimate(analysis);
+        let mut discounted = DuplicationDiscount {
+            duplicated_lines: duplication.duplicated_lines,
+            share: duplication.share(),
+            discounted_lines,
+            hours: 0.0,
+            cost: 0.0,
+        };
+        if discounted_lines == 0 {
+            return (full, discounted);
+        }
+
+        let deduplicated = Analysis {
+            total_lines: scale(analysis.total_lines),
+            code_lines: scale(analysis.code_lines),
+            language_stats: analysis
+                .language_stats
+                .iter()
+                .map(|lang| LanguageStat {
+                    lines: scale(lang.lines),
+                    ..lang.clone()
+                })
+                .collect(),
+            ..analysis.clone()
+        };
+        let mut estimate = self.model.estimate(&deduplicated);
+        estimate.assumptions.push(Assumption::new(
+            "Duplicated code",
+            format!(
+                "{} lines ({:.1}%) in {} blocks, discounted by {:.0}%: {} code lines not counted",
+                duplication.duplicated_lines,
+                duplication.share() * 100.0,
+                duplication.blocks.len(),
+                discount * 100.0,
+                discounted_lines
+            ),
+        ));
+        discounted.hours = full.effort_hours - estimate.effort_hours;
+        discounted.cost = discounted.hours * self.hourly_rate;
+        (estimate, discounted)
+    }
+
+    fn calculate_dependency_maintenance(&self, analysis: &Analysis) -> DependencyMaintenance {
+        let inventory = &analysis.dependencies;
+        let direct = inventory.direct();
+        let transitive = inventory.transitive();
+        let extra_versions = inventory.extra_versions();
+        let hours = direct as f64 * self.dependencies.direct_hours
+            + transitive as f64 * self.dependencies.transitive_hours
+            + extra_versions as f64 * self.dependencies.duplicate_hours;
+        DependencyMaintenance {
+            direct,
+            transitive,
+            extra_versions,
+            hours,
+            cost: hours * self.hourly_rate,
+        }
+    }
+
+    fn calculate_token_cost(&self, analysis: &Analysis, model: &LlmModel) -> TokenCost {
+        // Base tokens from the current codebase, with the heuristic for a
+        // tokenizer that was not counted
+        let base_tokens = analysis
+            .total_tokens
+            .get(&model.tokenizer)
+            .or_else(|| analysis.total_tokens.get(&Tokenizer::Heuristic))
+            .copied()
+            .unwrap_or(0);
 
         // AI Development Simulation Heuristics:
         // 1. Output tokens: Usually developers iterate. Assume AI generated the code 1.5 times on average.
@@ -120,149 +295,209 @@ impl CostCalculator {
 
         let total_estimated_tokens = estimated_prompt_tokens + estimated_output_tokens;
 
-        let input_cost_krw = (estimated_prompt_tokens as f64 / 1_000_000.0) * input_price_per_m_krw;
-        let output_cost_krw = (estimated_output_tokens as f64 / 1_000_000.0) * output_price_per_m_krw;
-        let total_cost_krw = input_cost_krw + output_cost_krw;
+        // 3. With prompt caching, each generated token enters the context once
+        // and is read back from the cache after that.
+        let (cached_prompt_tokens, fresh_price) = match model.cache_read {
+            Some(_) => (
+                estimated_prompt_tokens - estimated_output_tokens,
+                model.cache_write.unwrap_or(model.input),
+            ),
+            None => (0, model.input),
+        };
+
+        let per_m = |tokens: usize, price: f64| tokens as f64 / 1_000_000.0 * price;
+        let input_cost = per_m(estimated_prompt_tokens - cached_prompt_tokens, fresh_price);
+        let cache_read_cost = per_m(cached_prompt_tokens, model.cache_read.unwrap_or(0.0));
+        let output_cost = per_m(estimated_output_tokens, model.output);
+        let total_cost = input_cost + cache_read_cost + output_cost;
 
         TokenCost {
+            model: model.id.clone(),
+            model_name: model.name.clone(),
+            tokenizer: model.tokenizer,
             base_tokens,
             estimated_prompt_tokens,
+            cached_prompt_tokens,
             estimated_output_tokens,
             total_estimated_tokens,
-            input_cost_krw,
-            output_cost_krw,
-            total_cost_krw,
-            model_name: "Claude Opus 4.7 xhigh".to_string(),
+            input_cost,
+            cache_read_cost,
+            output_cost,
+            total_cost,
         }
     }
 
-    fn apply_language_weights(&self, analysis: &Analysis, base_hours: f64) -> f64 {
-        if analysis.language_stats.is_empty() {
-            return base_hours;
-        }
-
-        let total_lines = analysis.total_lines.max(1) as f64;
-
-        analysis
-            .language_stats
+    fn calculate_developer_levels(&self, estimated_hours: f64) -> Vec<DeveloperLevel> {
+        self.developer_rates
             .iter()
-            .map(|lang| {
-                let ratio = lang.lines as f64 / total_lines;
-                let lang_hours = (lang.lines as f64 / 20.0) * lang.weight;
-                lang_hours * ratio
+            .map(|&(level, rate)| DeveloperLevel {
+                level: level.to_string(),
+                hourly_rate: rate,
+                estimated_cost: estimated_hours * rate,
             })
-            .sum::<f64>()
-            .max(base_hours)
+            .collect()
     }
 
-    fn calculate_complexity_multiplier(&self, analysis: &Analysis) -> f64 {
-        // Complexity score is 1.0 - 5.0
-        // Map it to multiplier 1.0 - 2.0
-        let base_multiplier = 1.0 + (analysis.complexity_score - 1.0) * 0.25;
+    /// AI usage from the evidence in the history: the share of inserted
+    /// lines that came from commits attributed to a tool.
+    fn analyze_ai_usage(&self, analysis: &Analysis) -> AIAnalysis {
+        let evidence = &analysis.ai_evidence;
 
-        // Adjust for file count (more files = better organization = slight reduction)
-        let file_factor = if analysis.total_files > 50 {
-            0.95
-        } else {
-            1.0
-        };
+        // Code quality score based on maturity and complexity balance
+        let code_quality_score = (analysis.maturity_score * 0.6
+            + (1.0 - (analysis.complexity_score - 1.0) / 4.0).max(0.0) * 0.4)
+            .clamp(0.0, 1.0);
 
-        // Adjust for test coverage
-        let test_factor = if analysis.maturity_score > 0.5 {
-            0.98
-        } else {
-            1.0
-        };
+        AIAnalysis {
+            estimated_ai_usage: evidence.attributed_share(),
+            code_quality_score,
+            attributed_commits: evidence.attributed_commits,
+            evidence: evidence.signals(),
+        }
+    }
+}
 
-        (base_multiplier * file_factor * test_factor).clamp(1.0, 2.0)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::duplication::Duplication;
+    use cli_core::config::CodeCostConfig;
+
+    /// A calculator for the default model, built without reading exchange
+    /// rates or an LLM catalogue.
+    fn calculator(discount: f64) -> CostCalculator {
+        CostCalculator {
+            currency: Currency::KRW,
+            hourly_rate: 50_000.0,
+            developer_rates: Vec::new(),
+            llm_models: Vec::new(),
+            model: model::from_config(&CodeCostConfig::default()).unwrap(),
+            dependencies: DependencyMaintenanceConfig::default(),
+            duplication: DuplicationConfig { discount },
+        }
     }
 
-    fn estimate_learning_time(&self, analysis: &Analysis) -> f64 {
-        // Estimate learning time based on language diversity and complexity
-        let language_count = analysis.language_stats.len() as f64;
-        let base_learning = language_count * 20.0; // 20 hours per language
+    fn analysis(code_lines: usize, duplicated_lines: usize) -> Analysis {
+        Analysis {
+            code_lines,
+            total_lines: code_lines,
+            duplication: Duplication {
+                duplicated_lines,
+                total_lines: code_lines,
+                blocks: Vec::new(),
+            },
+            ..Analysis::default()
+        }
+    }
 
-        // Adjust for complexity
-        let complexity_learning = analysis.complexity_score * 10.0;
+    #[test]
+    fn duplicated_lines_are_discounted_by_their_share() {
+        let calculator = calculator(0.75);
+        let (estimate, discount) = calculator.estimate(&analysis(10_000, 2_000));
+        let full = calculator.model.estimate(&analysis(10_000, 0));
+        // 20% duplicated, three quarters of which is left out.
+        let deduplicated = calculator.model.estimate(&analysis(8_500, 0));
+
+        assert_eq!(discount.share, 0.2);
+        assert_eq!(discount.discounted_lines, 1_500);
+        assert_eq!(estimate.effort_hours, deduplicated.effort_hours);
+        assert!(estimate.effort_hours < full.effort_hours);
+        assert_eq!(discount.hours, full.effort_hours - estimate.effort_hours);
+        assert_eq!(discount.cost, discount.hours * 50_000.0);
+        assert!(estimate
+            .assumptions
+            .iter()
+            .any(|a| a.name == "Duplicated code"));
+    }
 
-        // Adjust for project size
-        let size_learning = (analysis.code_lines as f64 / 1000.0).min(50.0);
+    #[test]
+    fn no_discount_leaves_the_estimate_alone() {
+        for (discount, duplicated_lines) in [(0.0, 2_000), (0.75, 0)] {
+            let calculator = calculator(discount);
+            let (estimate, discounted) = calculator.estimate(&analysis(10_000, duplicated_lines));
+            let full = calculator.model.estimate(&analysis(10_000, 0));
 
-        base_learning + complexity_learning + size_learning
+            assert_eq!(discounted.discounted_lines, 0);
+            assert_eq!(discounted.hours, 0.0);
+            assert_eq!(estimate.effort_hours, full.effort_hours);
+        }
     }
 
-    fn calculate_developer_levels(&self, estimated_hours: f64) -> Vec<DeveloperLevel> {
-        // Developer level hourly rates in KRW (South Korea market rates as of 2025)
-        let levels = vec![
-            ("Junior", 15_000.0),      // 1-3년차
-            ("Mid-level", 25_000.0),   // 3-5년차
-            ("Senior", 40_000.0),      // 5-10년차
-            ("Lead", 60_000.0),        // 10+년차, 팀 리드
-            ("Principal", 100_000.0),  // 아키텍트, 시니어 엔지니어
-        ];
-
-        levels
-            .into_iter()
-            .map(|(level, rate)| DeveloperLevel {
-                level: level.to_string(),
-                hourly_rate: rate,
-                estimated_cost: estimated_hours * rate,
-            })
-            .collect()
+    #[test]
+    fn the_discount_is_clamped_to_the_duplicated_lines() {
+        let (_, discount) = calculator(3.0).estimate(&analysis(10_000, 2_000));
+        assert_eq!(discount.discounted_lines, 2_000);
     }
 
-    fn analyze_ai_usage(&self, analysis: &Analysis) -> AIAnalysis {
-        let mut ai_indicators = Vec::new();
-        let mut ai_score: f64 = 0.0;
-
-        // Indicator 1: High code quality with low comment ratio might indicate AI assistance
-        let comment_ratio = analysis.comment_lines as f64 / analysis.code_lines.max(1) as f64;
-        if comment_ratio < 0.05 && analysis.maturity_score > 0.5 {
-            ai_indicators.push("Low comment ratio with high code quality".to_string());
-            ai_score += 0.2;
+    fn llm(cache_write: Option<f64>, cache_read: Option<f64>, tokenizer: Tokenizer) -> LlmModel {
+        LlmModel {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            currency: Currency::KRW,
+            input: 5.0,
+            output: 25.0,
+            cache_write,
+            cache_read,
+            tokenizer,
         }
+    }
 
-        // Indicator 2: Consistent file structure and organization
-        let avg_lines_per_file = analysis.code_lines / analysis.total_files.max(1);
-        if (50..=200).contains(&avg_lines_per_file) {
-            ai_indicators.push("Consistent file size distribution".to_string());
-            ai_score += 0.15;
+    fn tokens(counts: &[(Tokenizer, usize)]) -> Analysis {
+        Analysis {
+            total_tokens: counts.iter().copied().collect(),
+            ..Analysis::default()
         }
+    }
 
-        // Indicator 3: High complexity with good maturity (tests, docs)
-        if analysis.complexity_score > 3.0 && analysis.maturity_score > 0.6 {
-            ai_indicators.push("High complexity with comprehensive testing".to_string());
-            ai_score += 0.25;
-        }
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
 
-        // Indicator 4: Modern language usage
-        let has_modern_lang = analysis.language_stats.iter().any(|l| {
-            matches!(
-                l.name.as_str(),
-                "Rust" | "TypeScript" | "Go" | "Kotlin" | "Swift"
-            )
-        });
-        if has_modern_lang {
-            ai_indicators.push("Use of modern programming languages".to_string());
-            ai_score += 0.1;
-        }
+    #[test]
+    fn token_cost_reads_the_prompt_back_from_the_cache() {
+        let model = llm(Some(6.25), Some(0.5), Tokenizer::Heuristic);
+        let cost = calculator(0.0)
+            .calculate_token_cost(&tokens(&[(Tokenizer::Heuristic, 100_000)]), &model);
+
+        assert_eq!(cost.base_tokens, 100_000);
+        assert_eq!(cost.estimated_output_tokens, 150_000);
+        assert_eq!(cost.estimated_prompt_tokens, 1_500_000);
+        assert_eq!(cost.total_estimated_tokens, 1_650_000);
+        // Only the generated tokens are written to the cache.
+        assert_eq!(cost.cached_prompt_tokens, 1_350_000);
+        assert_close(cost.input_cost, 0.15 * 6.25);
+        assert_close(cost.cache_read_cost, 1.35 * 0.5);
+        assert_close(cost.output_cost, 0.15 * 25.0);
+        assert_close(cost.total_cost, 0.9375 + 0.675 + 3.75);
+    }
 
-        // Indicator 5: Good code-to-test ratio
-        let test_ratio = analysis.test_file_count as f64 / analysis.total_files.max(1) as f64;
-        if test_ratio > 0.2 {
-            ai_indicators.push("Strong test coverage".to_string());
-            ai_score += 0.2;
-        }
+    #[test]
+    fn token_cost_without_caching_pays_the_input_price() {
+        let model = llm(Some(6.25), None, Tokenizer::Heuristic);
+        let cost = calculator(0.0)
+            .calculate_token_cost(&tokens(&[(Tokenizer::Heuristic, 100_000)]), &model);
 
-        // Code quality score based on maturity and complexity balance
-        let code_quality_score = (analysis.maturity_score * 0.6
-            + (1.0 - (analysis.complexity_score - 1.0) / 4.0).max(0.0) * 0.4)
-            .clamp(0.0, 1.0);
+        assert_eq!(cost.cached_prompt_tokens, 0);
+        assert_close(cost.input_cost, 1.5 * 5.0);
+        assert_eq!(cost.cache_read_cost, 0.0);
+        assert_close(cost.total_cost, 7.5 + 3.75);
+    }
 
-        AIAnalysis {
-            estimated_ai_usage: ai_score.clamp(0.0, 1.0),
-            code_quality_score,
-            potential_ai_indicators: ai_indicators,
-        }
+    #[test]
+    fn token_cost_uses_the_model_tokenizer() {
+        let counts = tokens(&[
+            (Tokenizer::Heuristic, 100_000),
+            (Tokenizer::O200kBase, 80_000),
+        ]);
+        let calculator = calculator(0.0);
+
+        let exact =
+            calculator.calculate_token_cost(&counts, &llm(None, None, Tokenizer::O200kBase));
+        assert_eq!(exact.base_tokens, 80_000);
+        assert_eq!(exact.tokenizer, Tokenizer::O200kBase);
+        // A tokenizer that was not counted falls back to the heuristic.
+        let fallback =
+            calculator.calculate_token_cost(&counts, &llm(None, None, Tokenizer::Cl100kBase));
+        assert_eq!(fallback.base_tokens, 100_000);
     }
 }
@@ -0,0 +1,300 @@
+//! Effort estimation models.
+//!
+//! Every model turns an [`Analysis`] into effort, schedule and team size, and
+//! lists the assumptions behind them so a report can show how it got there.
+
+use anyhow::Result;
+use cli_core::config::{CodeCostConfig, EstimationModelKind};
+use serde::{Deserialize, Serialize};
+
+use super::cocomo::{Cocomo2, Cocomo81, Custom};
+use crate::analyzer::Analysis;
+
+pub trait EstimationModel {
+    /// Methodology name shown in reports.
+    fn name(&self) -> &'static str;
+
+    fn estimate(&self, analysis: &Analysis) -> Estimate;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Estimate {
+    pub model: String,
+    pub effort_hours: f64,
+    pub effort_person_months: f64,
+    pub schedule_months: f64,
+    /// Average number of people working through the schedule.
+    pub team_size: f64,
+    pub assumptions: Vec<Assumption>,
+}
+
+/// One input or equation an estimate depends on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Assumption {
+    pub name: String,
+    pub value: String,
+}
+
+impl Assumption {
+    pub(super) fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+}
+
+impl Estimate {
+    pub(super) fn new(
+        model: &dyn EstimationModel,
+        person_months: f64,
+        schedule_months: f64,
+        hours_per_month: f64,
+        mut assumptions: Vec<Assumption>,
+    ) -> Self {
+        assumptions.push(Assumption::new(
+            "Person-month",
+            format!("{hours_per_month} working hours"),
+        ));
+        Self {
+            model: model.name().to_string(),
+            effort_hours: person_months * hours_per_month,
+            effort_person_months: person_months,
+            schedule_months,
+            team_size: if schedule_months > 0.0 {
+                person_months / schedule_months
+            } else {
+                0.0
+            },
+            assumptions,
+        }
+    }
+}
+
+/// The model selected by `code_cost.model` (or `--model`), with its settings.
+pub fn from_config(config: &CodeCostConfig) -> Result<Box<dyn EstimationModel>> {
+    let hours_per_month = config.hours_per_month.max(f64::EPSILON);
+    Ok(match config.model {
+        EstimationModelKind::Heuristic => Box::new(Heuristic {
+            lines_per_hour: config.lines_per_hour.max(f64::EPSILON),
+            hours_per_month,
+        }),
+        EstimationModelKind::Cocomo81Basic => {
+            Box::new(Cocomo81::basic(&config.cocomo81, hours_per_month))
+        }
+        EstimationModelKind::Cocomo81Intermediate => {
+            Box::new(Cocomo81::intermediate(&config.cocomo81, hours_per_month)?)
+        }
+        EstimationModelKind::Cocomo2 => Box::new(Cocomo2::new(&config.cocomo2, hours_per_month)?),
+        EstimationModelKind::Custom => Box::new(Custom::new(&config.custom_model, hours_per_month)),
+    })
+}
+
+/// Lines per hour, adjusted for language, complexity and maturity, plus time
+/// to learn the codebase.
+struct Heuristic {
+    lines_per_hour: f64,
+    hours_per_month: f64,
+}
+
+impl EstimationModel for Heuristic {
+    fn name(&self) -> &'static str {
+        "Heuristic"
+    }
+
+    fn estimate(&self, analysis: &Analysis) -> Estimate {
+        // Base calculation: configured lines per hour (20 by default)
+        let base_hours = analysis.code_lines as f64 / self.lines_per_hour;
+
+        // Apply language weights
+        let language_adjusted_hours = self.apply_language_weights(analysis, base_hours);
+
+        // Apply complexity multiplier (1.0 - 2.0)
+        let complexity_multiplier = self.calculate_complexity_multiplier(analysis);
+        let complexity_adjusted_hours = language_adjusted_hours * complexity_multiplier;
+
+        // Apply maturity bonus (up to 30% more)
+        let maturity_bonus_hours = complexity_adjusted_hours * analysis.maturity_score * 0.3;
+
+        // Total estimated hours including learning time
+        let learning_time = self.estimate_learning_time(analysis);
+        let estimated_hours = complexity_adjusted_hours + maturity_bonus_hours + learning_time;
+
+        // The heuristic has no schedule of its own; borrow basic COCOMO's organic one.
+        let person_months = estimated_hours / self.hours_per_month;
+        let schedule_months = 2.5 * person_months.powf(0.38);
+
+        let assumptions = vec![
+            Assumption::new(
+                "Productivity",
+                format!(
+                    "{} lines per hour = {base_hours:.1} hours",
+                    self.lines_per_hour
+                ),
+            ),
+            Assumption::new(
+                "Language weights",
+                format!("{language_adjusted_hours:.1} hours"),
+            ),
+            Assumption::new(
+                "Complexity multiplier",
+                format!("{complexity_multiplier:.2}x"),
+            ),
+            Assumption::new(
+                "Maturity bonus",
+                format!(
+                    "30% x {:.0}% maturity = {maturity_bonus_hours:.1} hours",
+                    analysis.maturity_score * 100.0
+                ),
+            ),
+            Assumption::new(
+                "Learning time",
+                format!("{learning_time:.1} hours (20 per language, complexity and size)"),
+            ),
+            Assumption::new(
+                "Schedule",
+                format!("COCOMO 81 organic, 2.5 x PM^0.38 = {schedule_months:.1} months"),
+            ),
+        ];
+        Estimate::new(
+            self,
+            person_months,
+            schedule_months,
+            self.hours_per_month,
+            assumptions,
+        )
+    }
+}
+
+impl Heuristic {
+    fn apply_language_weights(&self, analysis: &Analysis, base_hours: f64) -> f64 {
+        if analysis.language_stats.is_empty() {
+            return base_hours;
+        }
+
+        let total_lines = analysis.total_lines.max(1) as f64;
+
+        analysis
+            .language_stats
+            .iter()
+            .map(|lang| {
+                let ratio = lang.lines as f64 / total_lines;
+                let lang_hours = (lang.lines as f64 / self.lines_per_hour) * lang.weight;
+                lang_hours * ratio
+            })
+            .sum::<f64>()
+            .max(base_hours)
+    }
+
+    fn calculate_complexity_multiplier(&self, analysis: &Analysis) -> f64 {
+        // Complexity score is 1.0 - 5.0
+        // Map it to multiplier 1.0 - 2.0
+        let base_multiplier = 1.0 + (analysis.complexity_score - 1.0) * 0.25;
+
+        // Adjust for file count (more files = better organization = slight reduction)
+        let file_factor = if analysis.total_files > 50 { 0.95 } else { 1.0 };
+
+        // Adjust for test coverage
+        let test_factor = if analysis.maturity_score > 0.5 {
+            0.98
+        } else {
+            1.0
+        };
+
+        (base_multiplier * file_factor * test_factor).clamp(1.0, 2.0)
+    }
+
+    fn estimate_learning_time(&self, analysis: &Analysis) -> f64 {
+        // Estimate learning time based on language diversity and complexity
+        let language_count = analysis.language_stats.len() as f64;
+        let base_learning = language_count * 20.0; // 20 hours per language
+
+        // Adjust for complexity
+        let complexity_learning = analysis.complexity_score * 10.0;
+
+        // Adjust for project size
+        let size_learning = (analysis.code_lines as f64 / 1000.0).min(50.0);
+
+        base_learning + complexity_learning + size_learning
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cli_core::config::{DriverValue, Rating};
+
+    fn analysis(code_lines: usize) -> Analysis {
+        Analysis {
+            code_lines,
+            total_lines: code_lines,
+            ..Analysis::default()
+        }
+    }
+
+    #[test]
+    fn from_config_builds_the_selected_model() {
+        for (kind, name) in [
+            (EstimationModelKind::Heuristic, "Heuristic"),
+            (EstimationModelKind::Cocomo81Basic, "COCOMO 81 basic"),
+            (
+                EstimationModelKind::Cocomo81Intermediate,
+                "COCOMO 81 intermediate",
+            ),
+            (EstimationModelKind::Cocomo2, "COCOMO II post-architecture"),
+            (EstimationModelKind::Custom, "Custom"),
+        ] {
+            let config = CodeCostConfig {
+                model: kind,
+                ..CodeCostConfig::default()
+            };
+            let estimate = from_config(&config).unwrap().estimate(&analysis(10_000));
+
+            assert_eq!(estimate.model, name);
+            assert!(estimate.effort_person_months > 0.0, "{name}");
+            assert_eq!(
+                estimate.assumptions.last().unwrap().value,
+                "152 working hours"
+            );
+        }
+    }
+
+    #[test]
+    fn from_config_rejects_invalid_drivers_for_the_selected_model_only() {
+        let mut config = CodeCostConfig::default();
+        config
+            .cocomo81
+            .cost_drivers
+            .insert("DATA".to_string(), DriverValue::Rating(Rating::ExtraHigh));
+
+        config.model = EstimationModelKind::Cocomo81Intermediate;
+        assert!(from_config(&config).is_err());
+        // Basic COCOMO has no cost drivers to check.
+        config.model = EstimationModelKind::Cocomo81Basic;
+        assert!(from_config(&config).is_ok());
+    }
+
+    #[test]
+    fn custom_model_applies_its_constants_and_multipliers() {
+        let mut config = CodeCostConfig {
+            model: EstimationModelKind::Custom,
+            hours_per_month: 160.0,
+            ..CodeCostConfig::default()
+        };
+        config.custom_model.a = 3.0;
+        config.custom_model.b = 1.0;
+        config.custom_model.c = 2.0;
+        config.custom_model.d = 0.5;
+        config
+            .custom_model
+            .effort_multipliers
+            .insert("legacy".to_string(), 1.5);
+
+        let estimate = from_config(&config).unwrap().estimate(&analysis(4_000));
+
+        assert_eq!(estimate.effort_person_months, 18.0);
+        assert_eq!(estimate.schedule_months, 2.0 * 18f64.sqrt());
+        assert_eq!(estimate.effort_hours, 18.0 * 160.0);
+        assert_eq!(estimate.team_size, 18.0 / estimate.schedule_months);
+    }
+}
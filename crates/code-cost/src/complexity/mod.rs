@@ -0,0 +1,846 @@
+//! Per-function structural metrics.
+//!
+//! Functions are found in brace-delimited languages by their signatures and in
+//! Python by indentation, working on code with comments and literals already
+//! blanked out. For each one we measure cyclomatic complexity (McCabe: one plus
+//! each branch point), cognitive complexity (SonarSource: branches weighted by
+//! how deeply they are nested), the deepest nesting of control structures, and
+//! its length in lines.
+
+use serde::{Deserialize, Serialize};
+
+/// Functions listed in reports when no other count is given.
+pub const DEFAULT_TOP: usize = 10;
+
+/// Structural metrics for one function.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FunctionStats {
+    pub name: String,
+    /// 1-based line of the function's name.
+    pub line: usize,
+    pub length: usize,
+    pub cyclomatic: usize,
+    pub cognitive: usize,
+    pub nesting: usize,
+}
+
+/// A function together with where it was found.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionReport {
+    pub path: String,
+    pub language: String,
+    #[serde(flatten)]
+    pub stats: FunctionStats,
+}
+
+/// How many functions fall in each cyclomatic risk band.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Distribution {
+    /// 1-5: simple.
+    pub low: usize,
+    /// 6-10: moderate.
+    pub moderate: usize,
+    /// 11-20: complex.
+    pub high: usize,
+    /// Over 20: untestable in practice.
+    pub very_high: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComplexityReport {
+    pub functions: usize,
+    pub mean_cyclomatic: f64,
+    pub p90_cyclomatic: usize,
+    pub max_cyclomatic: usize,
+    pub mean_cognitive: f64,
+    pub max_nesting: usize,
+    pub mean_length: f64,
+    pub distribution: Distribution,
+    /// 1.0-5.0, from the share of function lines in each risk band.
+    pub score: f64,
+    /// The most complex functions, by cyclomatic then cognitive complexity.
+    pub top: Vec<FunctionReport>,
+}
+
+impl ComplexityReport {
+    pub fn new(mut functions: Vec<FunctionReport>, top: usize) -> Self {
+        if functions.is_empty() {
+            return Self {
+                score: 1.0,
+                ..Self::default()
+            };
+        }
+        let count = functions.len();
+        let mean = |value: fn(&FunctionStats) -> usize| {
+            functions.iter().map(|f| value(&f.stats)).sum::<usize>() as f64 / count as f64
+        };
+        let mean_cyclomatic = mean(|f| f.cyclomatic);
+        let mean_cognitive = mean(|f| f.cognitive);
+        let mean_length = mean(|f| f.length);
+
+        let mut distribution = Distribution::default();
+        // Weight each band by lines, so one 500-line function is not outvoted
+        // by a hundred getters.
+        let mut weighted = 0.0;
+        let mut total_length = 0.0;
+        for function in &functions {
+            let (band, weight) = match function.stats.cyclomatic {
+                0..=5 => (&mut distribution.low, 1.0),
+                6..=10 => (&mut distribution.moderate, 2.5),
+                11..=20 => (&mut distribution.high, 4.0),
+                _ => (&mut distribution.very_high, 5.0),
+            };
+            *band += 1;
+            let length = function.stats.length.max(1) as f64;
+            weighted += weight * length;
+            total_length += length;
+        }
+
+        let mut cyclomatic: Vec<usize> = functions.iter().map(|f| f.stats.cyclomatic).collect();
+        cyclomatic.sort_unstable();
+        let p90_cyclomatic = cyclomatic[(count * 9 / 10).min(count - 1)];
+        let max_nesting = functions.iter().map(|f| f.stats.nesting).max().unwrap_or(0);
+
+        functions.sort_by(|a, b| {
+            b.stats
+                .cyclomatic
+                .cmp(&a.stats.cyclomatic)
+                .then(b.stats.cognitive.cmp(&a.stats.cognitive))
+                .then_with(|| a.path.cmp(&b.path))
+                .then(a.stats.line.cmp(&b.stats.line))
+        });
+        functions.truncate(top);
+
+        Self {
+            functions: count,
+            mean_cyclomatic,
+            p90_cyclomatic,
+            max_cyclomatic: cyclomatic[count - 1],
+            mean_cognitive,
+            max_nesting,
+            mean_length,
+            distribution,
+            score: (weighted / total_length).clamp(1.0, 5.0),
+            top: functions,
+        }
+    }
+}
+
+/// Whether functions can be found in `lang`.
+pub fn supports(lang: &str) -> bool {
+    lang == "Python" || braces::rules(lang).is_some()
+}
+
+/// Functions in `lines`, which must already have comments and literals blanked out.
+pub fn measure(lines: &[String], lang: &str) -> Vec<FunctionStats> {
+    match lang {
+        "Python" => python::measure(lines),
+        _ => match braces::rules(lang) {
+            Some(rules) => braces::measure(lines, lang, &rules),
+            None => Vec::new(),
+        },
+    }
+}
+
+/// A word or punctuation token on a stripped line.
+#[derive(Clone, Copy)]
+struct Token<'a> {
+    text: &'a str,
+    /// 0-based line index.
+    line: usize,
+}
+
+impl Token<'_> {
+    fn is_word(&self) -> bool {
+        self.text
+            .starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '$')
+    }
+}
+
+const OPERATORS: &[&str] = &[
+    "&&", "||", "=>", "->", "::", "??", "?.", "==", "!=", "<=", ">=",
+];
+
+fn tokenize(text: &str, line: usize) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        let end = if c.is_alphanumeric() || c == '_' || c == '$' {
+            let mut end = start + c.len_utf8();
+            while let Some(&(i, next)) = chars.peek() {
+                if !(next.is_alphanumeric() || next == '_' || next == '$') {
+                    break;
+                }
+                end = i + next.len_utf8();
+                chars.next();
+            }
+            end
+        } else if OPERATORS.iter().any(|op| text[start..].starts_with(op)) {
+            chars.next();
+            start + 2
+        } else {
+            start + c.len_utf8()
+        };
+        tokens.push(Token {
+            text: &text[start..end],
+            line,
+        });
+    }
+    tokens
+}
+
+/// Running totals for the function being measured.
+struct Counter {
+    stats: FunctionStats,
+    /// Last `&&`/`||` seen in the current expression, for cognitive sequences.
+    last_boolean: Option<&'static str>,
+}
+
+impl Counter {
+    fn new(name: &str, line: usize) -> Self {
+        Self {
+            stats: FunctionStats {
+                name: name.to_string(),
+                line: line + 1,
+                length: 0,
+                cyclomatic: 1,
+                cognitive: 0,
+                nesting: 0,
+            },
+            last_boolean: None,
+        }
+    }
+
+    /// A branch that nests, such as `if` or `for`, at `nesting` levels deep.
+    fn structural(&mut self, nesting: usize) {
+        self.stats.cyclomatic += 1;
+        self.stats.cognitive += 1 + nesting;
+        self.last_boolean = None;
+    }
+
+    /// A boolean operator; each switch between `and` and `or` adds cognitive load.
+    fn boolean(&mut self, op: &'static str) {
+        self.stats.cyclomatic += 1;
+        if self.last_boolean != Some(op) {
+            self.stats.cognitive += 1;
+            self.last_boolean = Some(op);
+        }
+    }
+
+    fn finish(mut self, end_line: usize) -> FunctionStats {
+        self.stats.length = end_line + 2 - self.stats.line;
+        self.stats
+    }
+}
+
+mod braces {
+    use super::{tokenize, Counter, FunctionStats, Token};
+
+    /// Function syntax of a brace-delimited language.
+    pub(super) struct Rules {
+        /// Keyword introducing a named function (`fn`, `func`, `function`).
+        keywords: &'static [&'static str],
+        /// Whether `Type name(args) {` declares a function (C family, Java, JS methods).
+        signatures: bool,
+        /// Whether `?` is the conditional operator.
+        ternary: bool,
+        /// Whether the language has a C preprocessor.
+        preprocessor: bool,
+    }
+
+    pub(super) fn rules(lang: &str) -> Option<Rules> {
+        let (keywords, signatures, ternary, preprocessor): (&[&str], _, _, _) = match lang {
+            "Rust" | "Zig" => (&["fn"], false, false, false),
+            "Go" => (&["func"], false, false, false),
+            "Swift" => (&["func", "init"], false, true, false),
+            "Kotlin" => (&["fun"], false, false, false),
+            "Scala" => (&["def"], false, false, false),
+            "PHP" => (&["function"], false, true, false),
+            "JavaScript" | "TypeScript" => (&["function"], true, true, false),
+            "Java" | "Dart" => (&[], true, true, false),
+            "C" | "C++" | "C/C++ Header" | "C#" => (&[], true, true, true),
+            _ => return None,
+        };
+        Some(Rules {
+            keywords,
+            signatures,
+            ternary,
+            preprocessor,
+        })
+    }
+
+    /// Words that start a control block rather than a declaration.
+    const CONTROL: &[&str] = &[
+        "if",
+        "else",
+        "for",
+        "foreach",
+        "while",
+        "do",
+        "switch",
+        "match",
+        "loop",
+        "try",
+        "catch",
+        "finally",
+        "when",
+        "select",
+        "guard",
+        "unless",
+        "using",
+        "lock",
+        "synchronized",
+    ];
+
+    /// Words that cannot name a function in a `name(args) {` signature.
+    const NOT_NAMES: &[&str] = &[
+        "if",
+        "for",
+        "foreach",
+        "while",
+        "switch",
+        "catch",
+        "return",
+        "sizeof",
+        "typeof",
+        "new",
+        "using",
+        "lock",
+        "synchronized",
+        "fixed",
+        "function",
+    ];
+
+    /// Tokens that, ending a line, mean the declaration continues on the next.
+    const CONTINUATIONS: &[&str] = &[
+        ",", "(", "where", "=>", "->", ":", "=", "&&", "||", "+", "|",
+    ];
+
+    enum Block {
+        Function(Counter),
+        Control,
+        /// A closure or lambda inside a function: nests but is not counted apart.
+        Nested,
+        Other,
+    }
+
+    pub(super) fn measure(lines: &[String], lang: &str, rules: &Rules) -> Vec<FunctionStats> {
+        let mut functions = Vec::new();
+        let mut stack: Vec<Block> = Vec::new();
+        let mut header: Vec<Token> = Vec::new();
+        let mut depth = 0usize;
+        let mut prev_last: Option<&str> = None;
+
+        for (index, text) in lines.iter().enumerate() {
+            if rules.preprocessor && text.trim_start().starts_with('#') {
+                header.clear();
+                continue;
+            }
+            let tokens = tokenize(text, index);
+            let Some(first) = tokens.first() else {
+                continue;
+            };
+            // A declaration ends at the line break unless the next line plainly
+            // continues it (`{` on its own line, wrapped arguments, `where`).
+            let continues = depth > 0
+                || prev_last.is_some_and(|last| CONTINUATIONS.contains(&last))
+                || ["{", ":", "->", ")", ".", "throws", "where"].contains(&first.text);
+            if !continues {
+                header.clear();
+            }
+            prev_last = tokens.last().map(|t| t.text);
+
+            for (i, token) in tokens.iter().enumerate() {
+                match token.text {
+                    "(" => depth += 1,
+                    ")" => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+                match token.text {
+                    "{" => {
+                        let block = open_block(&header, &stack, rules);
+                        if let (Block::Control, Some((counter, nesting))) =
+                            (&block, current(&mut stack))
+                        {
+                            counter.stats.nesting = counter.stats.nesting.max(nesting + 1);
+                        }
+                        stack.push(block);
+                        header.clear();
+                        depth = 0;
+                    }
+                    "}" => {
+                        if let Some(Block::Function(counter)) = stack.pop() {
+                            functions.push(counter.finish(index));
+                        }
+                        header.clear();
+                        depth = 0;
+                    }
+                    // `for (i = 0; i < n; i++)` keeps its header.
+                    ";" if depth == 0 => {
+                        header.clear();
+                        if let Some((counter, _)) = current(&mut stack) {
+                            counter.last_boolean = None;
+                        }
+                    }
+                    _ => {
+                        if let Some((counter, nesting)) = current(&mut stack) {
+                            count(counter, nesting, lang, rules, &tokens, i);
+                        }
+                        header.push(*token);
+                    }
+                }
+            }
+        }
+
+        let last = lines.len().saturating_sub(1);
+        while let Some(block) = stack.pop() {
+            if let Block::Function(counter) = block {
+                functions.push(counter.finish(last));
+            }
+        }
+        functions.sort_by_key(|f| f.line);
+        functions
+    }
+
+    /// The innermost open function and how many control blocks enclose the
+    /// current position inside it.
+    fn current(stack: &mut [Block]) -> Option<(&mut Counter, usize)> {
+        let position = stack
+            .iter()
+            .rposition(|b| matches!(b, Block::Function(_)))?;
+        let (outer, inner) = stack.split_at_mut(position + 1);
+        let nesting = inner
+            .iter()
+            .filter(|b| matches!(b, Block::Control | Block::Nested))
+            .count();
+        match &mut outer[position] {
+            Block::Function(counter) => Some((counter, nesting)),
+            _ => None,
+        }
+    }
+
+    fn open_block(header: &[Token], stack: &[Block], rules: &Rules) -> Block {
+        let in_function = stack.iter().any(|b| matches!(b, Block::Function(_)));
+        let anonymous = |line| {
+            if in_function {
+                Block::Nested
+            } else {
+                Block::Function(Counter::new("<anonymous>", line))
+            }
+        };
+        let Some(last) = header.last() else {
+            return Block::Other;
+        };
+
+        if let Some(k) = header
+            .iter()
+            .rposition(|t| rules.keywords.contains(&t.text))
+        {
+            // Swift initializers are named by their keyword.
+            let name = match header[k].text {
+                "init" => Some(header[k]),
+                _ => name_after_keyword(&header[k + 1..]),
+            };
+            return match name {
+                Some(name) => Block::Function(Counter::new(name.text, name.line)),
+                None => anonymous(header[k].line),
+            };
+        }
+        if matches!(last.text, "=>" | "->") {
+            // `const handler = (event) => {` is named by its binding.
+            let binding = header
+                .iter()
+                .position(|t| t.text == "=")
+                .and_then(|eq| eq.checked_sub(1))
+                .map(|i| header[i])
+                .filter(|t| t.is_word());
+            return match binding {
+                Some(name) if !in_function => Block::Function(Counter::new(name.text, name.line)),
+                _ => anonymous(last.line),
+            };
+        }
+        if header.iter().any(|t| CONTROL.contains(&t.text)) {
+            return Block::Control;
+        }
+        if rules.signatures {
+            if let Some(name) = signature_name(header) {
+                return Block::Function(Counter::new(name.text, name.line));
+            }
+        }
+        Block::Other
+    }
+
+    /// The name following `fn`/`func`, skipping a Go method receiver and a
+    /// generator `*`; `None` for an anonymous function.
+    fn name_after_keyword<'a>(mut rest: &[Token<'a>]) -> Option<Token<'a>> {
+        if rest.first()?.text == "(" {
+            let close = matching_paren(rest)?;
+            rest = &rest[close + 1..];
+        }
+        if rest.first()?.text == "*" {
+            rest = &rest[1..];
+        }
+        match rest {
+            [name, next, ..]
+                if name.is_word() && ["(", "<", "[", ":", "="].contains(&next.text) =>
+            {
+                Some(*name)
+            }
+            // Kotlin `fun <T> name(` and `fun Receiver.name(`.
+            _ => {
+                let open = rest.iter().position(|t| t.text == "(")?;
+                Some(rest[open.checked_sub(1)?]).filter(|t| t.is_word())
+            }
+        }
+    }
+
+    /// Index of the `)` closing the `(` that `tokens` starts with.
+    fn matching_paren(tokens: &[Token]) -> Option<usize> {
+        let mut depth = 0;
+        for (i, token) in tokens.iter().enumerate() {
+            match token.text {
+                "(" => depth += 1,
+                ")" => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                return Some(i);
+            }
+        }
+        None
+    }
+
+    /// The name in `modifiers Type name(args) qualifiers {`.
+    fn signature_name<'a>(header: &[Token<'a>]) -> Option<Token<'a>> {
+        let open = header.iter().position(|t| t.text == "(")?;
+        let name = header.get(open.checked_sub(1)?)?;
+        if !name.is_word() || NOT_NAMES.contains(&name.text) {
+            return None;
+        }
+        let before = &header[..open - 1];
+        if before
+            .iter()
+            .any(|t| matches!(t.text, "=" | "." | "class" | "struct" | "enum"))
+        {
+            return None;
+        }
+
+        let close = open + matching_paren(&header[open..])?;
+        let after = &header[close + 1..];
+        // C++ constructors may have an initializer list; anything else with a
+        // call or assignment after the parameters is an expression.
+        let initializer = after.first().is_some_and(|t| t.text == ":");
+        let expression = after.iter().any(|t| matches!(t.text, "=" | "(" | "."));
+        (initializer || !expression).then_some(*name)
+    }
+
+    /// Count the branch, if any, that `tokens[i]` adds to the function.
+    fn count(
+        counter: &mut Counter,
+        nesting: usize,
+        lang: &str,
+        rules: &Rules,
+        tokens: &[Token],
+        i: usize,
+    ) {
+        let word = tokens[i].text;
+        let prev = i.checked_sub(1).map(|p| tokens[p].text);
+        let next = tokens.get(i + 1).map(|t| t.text);
+        // `T?` is a nullable type unless a `:` follows on the line.
+        let ternary = rules.ternary && tokens[i + 1..].iter().any(|t| t.text == ":");
+        match word {
+            "if" if prev == Some("else") => {
+                counter.stats.cyclomatic += 1;
+                counter.stats.cognitive += 1;
+            }
+            "if" | "for" | "foreach" | "while" | "catch" => counter.structural(nesting),
+            "switch" | "select" => counter.stats.cognitive += 1 + nesting,
+            "case" => counter.stats.cyclomatic += 1,
+            // Kotlin's `else ->` is a `when` arm, not a branch of its own.
+            "else" if !matches!(next, Some("if" | "->")) => counter.stats.cognitive += 1,
+            "&&" => counter.boolean("&&"),
+            "||" => counter.boolean("||"),
+            "?" if ternary && !matches!(next, Some(":" | ")" | ",")) => counter.structural(nesting),
+            _ => match (lang, word) {
+                // Rust counts each match arm, less one for the match itself.
+                ("Rust", "match") => {
+                    counter.stats.cyclomatic = counter.stats.cyclomatic.saturating_sub(1);
+                    counter.stats.cognitive += 1 + nesting;
+                }
+                ("Rust", "=>") => counter.stats.cyclomatic += 1,
+                ("Rust", "loop") => counter.stats.cognitive += 1 + nesting,
+                ("Kotlin", "when") | ("Scala", "match") => counter.structural(nesting),
+                ("Swift", "guard") => counter.structural(nesting),
+                _ => {}
+            },
+        }
+    }
+}
+
+mod python {
+    use super::{tokenize, Counter, FunctionStats};
+
+    /// Statements that open an indented block and nest what follows.
+    const BLOCKS: &[&str] = &[
+        "if", "elif", "else", "for", "while", "except", "with", "match", "case",
+    ];
+
+    struct Open {
+        counter: Counter,
+        indent: usize,
+        /// Indents of the control blocks enclosing the current line.
+        controls: Vec<usize>,
+        last_line: usize,
+    }
+
+    pub(super) fn measure(lines: &[String]) -> Vec<FunctionStats> {
+        let mut functions = Vec::new();
+        let mut open: Vec<Open> = Vec::new();
+
+        for (index, text) in lines.iter().enumerate() {
+            let code = text.trim_start();
+            if code.trim().is_empty() {
+                continue;
+            }
+            let indent = text.len() - code.len();
+            while open.last().is_some_and(|f| indent <= f.indent) {
+                let function = open.pop().unwrap();
+                functions.push(function.counter.finish(function.last_line));
+            }
+
+            let tokens = tokenize(text, index);
+            let words: Vec<&str> = tokens.iter().map(|t| t.text).collect();
+            let def = match words.as_slice() {
+                ["def", name, ..] | ["async", "def", name, ..] => Some(*name),
+                _ => None,
+            };
+            if let Some(name) = def {
+                open.push(Open {
+                    counter: Counter::new(name, index),
+                    indent,
+                    controls: Vec::new(),
+                    last_line: index,
+                });
+                continue;
+            }
+            let Some(function) = open.last_mut() else {
+                continue;
+            };
+            function.last_line = index;
+            function.controls.retain(|&control| control < indent);
+            let nesting = function.controls.len();
+            let counter = &mut function.counter;
+            counter.last_boolean = None;
+
+            for (i, word) in words.iter().enumerate() {
+                let at_start = i == 0;
+                match *word {
+                    // Also conditional expressions and comprehensions.
+                    "if" | "for" => counter.structural(nesting),
+                    "while" | "except" if at_start => counter.structural(nesting),
+                    "elif" if at_start => {
+                        counter.stats.cyclomatic += 1;
+                        counter.stats.cognitive += 1;
+                    }
+                    "else" if at_start => counter.stats.cognitive += 1,
+                    "match" if at_start => counter.stats.cognitive += 1 + nesting,
+                    "case" if at_start => counter.stats.cyclomatic += 1,
+                    "and" => counter.boolean("&&"),
+                    "or" => counter.boolean("||"),
+                    _ => {}
+                }
+            }
+
+            if words.first().is_some_and(|w| BLOCKS.contains(w)) && words.last() == Some(&":") {
+                function.controls.push(indent);
+                let depth = function.controls.len();
+                function.counter.stats.nesting = function.counter.stats.nesting.max(depth);
+            }
+        }
+
+        while let Some(function) = open.pop() {
+            functions.push(function.counter.finish(function.last_line));
+        }
+        functions.sort_by_key(|f| f.line);
+        functions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::lexer;
+
+    /// Functions in `source`, stripped the way the scanner strips files.
+    fn functions(source: &str, lang: &str) -> Vec<FunctionStats> {
+        measure(&lexer::strip(source, lang), lang)
+    }
+
+    fn stats(name: &str, line: usize, length: usize, metrics: [usize; 3]) -> FunctionStats {
+        let [cyclomatic, cognitive, nesting] = metrics;
+        FunctionStats {
+            name: name.to_string(),
+            line,
+            length,
+            cyclomatic,
+            cognitive,
+            nesting,
+        }
+    }
+
+    fn report(cyclomatic: usize, length: usize) -> FunctionReport {
+        FunctionReport {
+            path: "src/lib.rs".to_string(),
+            language: "Rust".to_string(),
+            stats: stats(&format!("f{cyclomatic}"), 1, length, [cyclomatic, 0, 0]),
+        }
+    }
+
+    #[test]
+    fn closures_count_toward_the_enclosing_function() {
+        let source = "\
+function outer(items) {
+  items.forEach(function (item) {
+    if (item) {
+      log(item);
+    }
+  });
+  const inner = (x) => {
+    return x && ready;
+  };
+}
+";
+        // The `if` sits inside the callback, so it is nested once.
+        assert_eq!(
+            functions(source, "JavaScript"),
+            vec![stats("outer", 1, 10, [3, 3, 2])]
+        );
+    }
+
+    #[test]
+    fn else_if_is_a_branch_without_extra_nesting() {
+        let source = "\
+fn classify(n: i32) -> i32 {
+    if n < 0 {
+        -1
+    } else if n == 0 {
+        0
+    } else {
+        1
+    }
+}
+";
+        assert_eq!(
+            functions(source, "Rust"),
+            vec![stats("classify", 1, 9, [3, 3, 1])]
+        );
+    }
+
+    #[test]
+    fn rust_match_counts_each_arm() {
+        let source = "\
+fn describe(n: u8) -> u8 {
+    match n {
+        0 => 10,
+        1 | 2 => 20,
+        _ => 30,
+    }
+}
+";
+        assert_eq!(
+            functions(source, "Rust"),
+            vec![stats("describe", 1, 7, [3, 1, 1])]
+        );
+    }
+
+    #[test]
+    fn functions_in_strings_and_comments_are_ignored() {
+        let source = "\
+fn real() {
+    let s = \"fn fake() { if x { } }\";
+    // fn commented() { if y { } }
+}
+";
+        assert_eq!(
+            functions(source, "Rust"),
+            vec![stats("real", 1, 4, [1, 0, 0])]
+        );
+    }
+
+    #[test]
+    fn python_nested_defs_and_comprehensions() {
+        let source = "\
+def outer(items):
+    def inner(x):
+        if x:
+            return 1
+        return 0
+    for item in items:
+        inner(item)
+    return [i for i in items if i]
+";
+        // The comprehension's `for` and `if` are branches of `outer`.
+        assert_eq!(
+            functions(source, "Python"),
+            vec![
+                stats("outer", 1, 8, [4, 3, 1]),
+                stats("inner", 2, 4, [2, 1, 1]),
+            ]
+        );
+    }
+
+    #[test]
+    fn unsupported_languages_have_no_functions() {
+        assert!(!supports("Markdown"));
+        assert!(functions("fn main() { if x { } }", "Markdown").is_empty());
+    }
+
+    #[test]
+    fn empty_report_scores_one() {
+        let report = ComplexityReport::new(Vec::new(), DEFAULT_TOP);
+        assert_eq!(report.functions, 0);
+        assert_eq!(report.score, 1.0);
+        assert!(report.top.is_empty());
+    }
+
+    #[test]
+    fn report_takes_the_90th_percentile_and_ranks_the_top() {
+        let mut functions: Vec<_> = (0..18).map(|_| report(1, 1)).collect();
+        functions.push(report(30, 1));
+        functions.push(report(12, 1));
+
+        let report = ComplexityReport::new(functions, 2);
+
+        assert_eq!(report.functions, 20);
+        assert_eq!(report.p90_cyclomatic, 12);
+        assert_eq!(report.max_cyclomatic, 30);
+        assert_eq!(report.mean_cyclomatic, 3.0);
+        assert_eq!(
+            (
+                report.distribution.low,
+                report.distribution.high,
+                report.distribution.very_high
+            ),
+            (18, 1, 1)
+        );
+        let top: Vec<usize> = report.top.iter().map(|f| f.stats.cyclomatic).collect();
+        assert_eq!(top, vec![30, 12]);
+    }
+
+    #[test]
+    fn score_weights_bands_by_length() {
+        // A hundred one-line getters do not outvote one 500-line function.
+        let mut functions: Vec<_> = (0..100).map(|_| report(1, 1)).collect();
+        functions.push(report(25, 500));
+        let score = ComplexityReport::new(functions, DEFAULT_TOP).score;
+        assert!((score - 2600.0 / 600.0).abs() < 1e-9, "{score}");
+
+        // Zero-length functions still weigh one line.
+        let functions = vec![report(8, 0), report(15, 1)];
+        let score = ComplexityReport::new(functions, DEFAULT_TOP).score;
+        assert!((score - 3.25).abs() < 1e-9, "{score}");
+    }
+}
@@ -0,0 +1,118 @@
+//! lcov tracefiles, as written by `cargo llvm-cov --lcov`, genhtml tooling,
+//! c8, nyc and `coverage lcov`.
+
+use anyhow::Result;
+use std::path::PathBuf;
+
+use super::Counts;
+
+pub fn parse(content: &str) -> Result<Vec<(PathBuf, Counts)>> {
+    let mut files = Vec::new();
+    let mut current: Option<(PathBuf, Record)> = None;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line == "end_of_record" {
+            if let Some((path, record)) = current.take() {
+                files.push((path, record.counts()));
+            }
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        if key == "SF" {
+            current = Some((PathBuf::from(value), Record::default()));
+            continue;
+        }
+        let Some((_, record)) = current.as_mut() else {
+            continue;
+        };
+        let number = || value.parse::<usize>().ok();
+        match key {
+            "LF" => record.lines_found = number(),
+            "LH" => record.lines_hit = number(),
+            "BRF" => record.branches_found = number(),
+            "BRH" => record.branches_hit = number(),
+            // Line records back up totals that some writers leave out.
+            "DA" => {
+                record.lines.0 += 1;
+                let hits = value
+                    .split(',')
+                    .nth(1)
+                    .and_then(|hits| hits.parse::<u64>().ok());
+                if hits.unwrap_or(0) > 0 {
+                    record.lines.1 += 1;
+                }
+            }
+            "BRDA" => {
+                record.branches.0 += 1;
+                let taken = value
+                    .rsplit(',')
+                    .next()
+                    .and_then(|taken| taken.parse::<u64>().ok());
+                if taken.unwrap_or(0) > 0 {
+                    record.branches.1 += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(files)
+}
+
+/// One `SF:` to `end_of_record` block.
+#[derive(Default)]
+struct Record {
+    lines_found: Option<usize>,
+    lines_hit: Option<usize>,
+    branches_found: Option<usize>,
+    branches_hit: Option<usize>,
+    /// `DA` records seen and hit
+    lines: (usize, usize),
+    /// `BRDA` records seen and taken
+    branches: (usize, usize),
+}
+
+impl Record {
+    fn counts(&self) -> Counts {
+        Counts {
+            lines_found: self.lines_found.unwrap_or(self.lines.0),
+            lines_hit: self.lines_hit.unwrap_or(self.lines.1),
+            branches_found: self.branches_found.unwrap_or(self.branches.0),
+            branches_hit: self.branches_hit.unwrap_or(self.branches.1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn totals_come_from_lf_and_lh() {
+        let report = "TN:\nSF:/home/ci/app/src/lib.rs\nDA:1,1\nDA:2,0\nLF:10\nLH:7\nBRF:4\nBRH:1\nend_of_record\n";
+
+        let files = parse(report).unwrap();
+        assert_eq!(files.len(), 1);
+        let (path, counts) = &files[0];
+        assert_eq!(path, &PathBuf::from("/home/ci/app/src/lib.rs"));
+        assert_eq!((counts.lines_found, counts.lines_hit), (10, 7));
+        assert_eq!((counts.branches_found, counts.branches_hit), (4, 1));
+    }
+
+    #[test]
+    fn missing_totals_are_counted_from_records() {
+        let report =
+            "SF:src/a.js\nDA:1,3\nDA:2,0\nDA:3,1\nBRDA:2,0,0,1\nBRDA:2,0,1,-\nend_of_record\n\
+                      SF:src/b.js\nDA:1,0\nend_of_record\n";
+
+        let files = parse(report).unwrap();
+        assert_eq!(files.len(), 2);
+        let a = files[0].1;
+        assert_eq!((a.lines_found, a.lines_hit), (3, 2));
+        assert_eq!((a.branches_found, a.branches_hit), (2, 1));
+        let b = files[1].1;
+        assert_eq!((b.lines_found, b.lines_hit), (1, 0));
+    }
+}
@@ -0,0 +1,102 @@
+//! `llvm-cov export` JSON, as written by `cargo llvm-cov --json`.
+
+use anyhow::{bail, Result};
+use serde::Deserialize;
+use std::path::PathBuf;
+
+use super::Counts;
+
+#[derive(Deserialize)]
+struct Export {
+    #[serde(rename = "type")]
+    kind: String,
+    data: Vec<Data>,
+}
+
+#[derive(Deserialize)]
+struct Data {
+    files: Vec<File>,
+}
+
+#[derive(Deserialize)]
+struct File {
+    filename: String,
+    summary: Summary,
+}
+
+#[derive(Deserialize)]
+struct Summary {
+    lines: Summed,
+    #[serde(default)]
+    branches: Summed,
+}
+
+#[derive(Default, Deserialize)]
+struct Summed {
+    count: usize,
+    covered: usize,
+}
+
+pub fn parse(content: &str) -> Result<Vec<(PathBuf, Counts)>> {
+    let export: Export = serde_json::from_str(content)?;
+    if export.kind != "llvm.coverage.json.export" {
+        bail!("unexpected JSON type '{}'", export.kind);
+    }
+    Ok(export
+        .data
+        .into_iter()
+        .flat_map(|data| data.files)
+        .map(|file| {
+            let counts = Counts {
+                lines_found: file.summary.lines.count,
+                lines_hit: file.summary.lines.covered,
+                branches_found: file.summary.branches.count,
+                branches_hit: file.summary.branches.covered,
+            };
+            (PathBuf::from(file.filename), counts)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_file_summaries() {
+        let report = r#"{
+  "type": "llvm.coverage.json.export",
+  "version": "2.0.1",
+  "data": [{
+    "files": [
+      {
+        "filename": "/work/src/main.rs",
+        "segments": [],
+        "summary": {
+          "lines": {"count": 40, "covered": 30, "percent": 75.0},
+          "branches": {"count": 6, "covered": 3, "notcovered": 3, "percent": 50.0}
+        }
+      },
+      {
+        "filename": "/work/src/util.rs",
+        "summary": {"lines": {"count": 5, "covered": 5, "percent": 100.0}}
+      }
+    ],
+    "totals": {}
+  }]
+}"#;
+
+        let files = parse(report).unwrap();
+        assert_eq!(files.len(), 2);
+        let (path, main) = &files[0];
+        assert_eq!(path, &PathBuf::from("/work/src/main.rs"));
+        assert_eq!((main.lines_found, main.lines_hit), (40, 30));
+        assert_eq!((main.branches_found, main.branches_hit), (6, 3));
+        assert_eq!(files[1].1.branch_rate(), None);
+    }
+
+    #[test]
+    fn rejects_other_json() {
+        assert!(parse(r#"{"type": "istanbul", "data": []}"#).is_err());
+    }
+}
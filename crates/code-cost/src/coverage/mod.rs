@@ -0,0 +1,361 @@
+//! Test coverage from lcov, Cobertura, JaCoCo and llvm-cov reports.
+//!
+//! Report paths are matched to repository files by their trailing
+//! components, so absolute paths from another machine and JaCoCo's
+//! package-relative paths both resolve.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+mod lcov;
+mod llvm;
+mod xml;
+
+/// Where coverage tools write their reports, searched in order.
+const CANDIDATES: [&str; 11] = [
+    "lcov.info",
+    "coverage/lcov.info",
+    "target/llvm-cov/lcov.info",
+    "coverage.xml",
+    "cobertura.xml",
+    "coverage/cobertura-coverage.xml",
+    "coverage/cobertura.xml",
+    "target/site/jacoco/jacoco.xml",
+    "build/reports/jacoco/test/jacocoTestReport.xml",
+    "coverage.json",
+    "target/llvm-cov/coverage.json",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Format {
+    Lcov,
+    Cobertura,
+    Jacoco,
+    LlvmCov,
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Lcov => "lcov",
+            Self::Cobertura => "Cobertura",
+            Self::Jacoco => "JaCoCo",
+            Self::LlvmCov => "llvm-cov",
+        })
+    }
+}
+
+/// Instrumented and covered lines and branches.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Counts {
+    pub lines_found: usize,
+    pub lines_hit: usize,
+    pub branches_found: usize,
+    pub branches_hit: usize,
+}
+
+impl Counts {
+    fn add(&mut self, other: &Counts) {
+        self.lines_found += other.lines_found;
+        self.lines_hit += other.lines_hit;
+        self.branches_found += other.branches_found;
+        self.branches_hit += other.branches_hit;
+    }
+
+    pub fn line_rate(&self) -> f64 {
+        self.lines_hit as f64 / self.lines_found.max(1) as f64
+    }
+
+    /// `None` when the report recorded no branches.
+    pub fn branch_rate(&self) -> Option<f64> {
+        (self.branches_found > 0).then(|| self.branches_hit as f64 / self.branches_found as f64)
+    }
+
+    /// Line and branch rates, as "82.1% lines, 64.0% branches".
+    pub fn describe(&self) -> String {
+        match self.branch_rate() {
+            Some(branches) => format!(
+                "{:.1}% lines, {:.1}% branches",
+                self.line_rate() * 100.0,
+                branches * 100.0
+            ),
+            None => format!("{:.1}% lines", self.line_rate() * 100.0),
+        }
+    }
+}
+
+/// A parsed report: counts per source file as the report names it.
+#[derive(Debug)]
+pub struct CoverageReport {
+    pub path: PathBuf,
+    pub format: Format,
+    /// Keyed by file name, for matching by trailing components.
+    files: HashMap<String, Vec<(PathBuf, Counts)>>,
+}
+
+impl CoverageReport {
+    /// The first report in the usual output locations under `root`.
+    pub fn find(root: &Path) -> Option<PathBuf> {
+        CANDIDATES
+            .iter()
+            .map(|candidate| root.join(candidate))
+            .find(|path| path.is_file())
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read coverage report {}", path.display()))?;
+        let format = detect(path, &content)
+            .with_context(|| format!("Unrecognised coverage report {}", path.display()))?;
+        let files = match format {
+            Format::Lcov => lcov::parse(&content),
+            Format::Cobertura => xml::parse_cobertura(&content),
+            Format::Jacoco => xml::parse_jacoco(&content),
+            Format::LlvmCov => llvm::parse(&content),
+        }
+        .with_context(|| format!("Failed to parse {format} report {}", path.display()))?;
+        Ok(Self::new(path, format, files))
+    }
+
+    fn new(path: &Path, format: Format, files: Vec<(PathBuf, Counts)>) -> Self {
+        let mut by_name: HashMap<String, Vec<(PathBuf, Counts)>> = HashMap::new();
+        for (file, counts) in files {
+            if let Some(name) = file.file_name().and_then(|name| name.to_str()) {
+                by_name
+                    .entry(name.to_string())
+                    .or_default()
+                    .push((file, counts));
+            }
+        }
+        Self {
+            path: path.to_path_buf(),
+            format,
+            files: by_name,
+        }
+    }
+
+    /// Counts for `relative`, a path from the repository root: the report
+    /// entry with the same path, or else one whose path ends with it or that
+    /// it ends with.
+    pub fn lookup(&self, relative: &Path) -> Option<Counts> {
+        let name = relative.file_name()?.to_str()?;
+        let entries = self.files.get(name)?;
+        entries
+            .iter()
+            .find(|(path, _)| path == relative)
+            .or_else(|| {
+                entries
+                    .iter()
+                    .find(|(path, _)| path.ends_with(relative) || relative.ends_with(path))
+            })
+            .map(|&(_, counts)| counts)
+    }
+}
+
+/// Coverage of the analysed files a report covers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Coverage {
+    pub report: PathBuf,
+    pub format: Format,
+    /// Analysed files the report has counts for.
+    pub files: usize,
+    #[serde(flatten)]
+    pub total: Counts,
+    /// Largest languages by instrumented lines first.
+    pub languages: Vec<LanguageCoverage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageCoverage {
+    pub name: String,
+    pub files: usize,
+    #[serde(flatten)]
+    pub counts: Counts,
+}
+
+/// Totals per language of the files a report covers, while metrics are
+/// aggregated.
+pub struct Tally<'r> {
+    report: &'r CoverageReport,
+    languages: BTreeMap<String, (usize, Counts)>,
+}
+
+impl<'r> Tally<'r> {
+    pub fn new(report: &'r CoverageReport) -> Self {
+        Self {
+            report,
+            languages: BTreeMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, relative: &Path, language: &str) {
+        if let Some(counts) = self.report.lookup(relative) {
+            let entry = self.languages.entry(language.to_string()).or_default();
+            entry.0 += 1;
+            entry.1.add(&counts);
+        }
+    }
+
+    /// `None` when the report covers none of the files.
+    pub fn finish(self) -> Option<Coverage> {
+        let mut total = Counts::default();
+        let mut files = 0;
+        let mut languages: Vec<LanguageCoverage> = self
+            .languages
+            .into_iter()
+            .map(|(name, (count, counts))| {
+                total.add(&counts);
+                files += count;
+                LanguageCoverage {
+                    name,
+                    files: count,
+                    counts,
+                }
+            })
+            .collect();
+        languages.sort_by_key(|language| Reverse(language.counts.lines_found));
+        (files > 0).then(|| Coverage {
+            report: self.report.path.clone(),
+            format: self.report.format,
+            files,
+            total,
+            languages,
+        })
+    }
+}
+
+/// The report format, from the extension and the first markup.
+fn detect(path: &Path, content: &str) -> Result<Format> {
+    let head = content.trim_start();
+    if head.starts_with('{') {
+        return Ok(Format::LlvmCov);
+    }
+    if head.starts_with('<') {
+        let root = head
+            .split('<')
+            .map(str::trim_start)
+            .find(|tag| !tag.is_empty() && !tag.starts_with('?') && !tag.starts_with('!'));
+        return match root {
+            Some(tag) if tag.starts_with("coverage") => Ok(Format::Cobertura),
+            Some(tag) if tag.starts_with("report") => Ok(Format::Jacoco),
+            _ => bail!("expected a Cobertura <coverage> or JaCoCo <report> root element"),
+        };
+    }
+    let is_lcov = path
+        .extension()
+        .is_some_and(|ext| ext == "info" || ext == "lcov")
+        || head.starts_with("TN:")
+        || head.starts_with("SF:");
+    if is_lcov {
+        return Ok(Format::Lcov);
+    }
+    bail!("expected lcov, Cobertura XML, JaCoCo XML or llvm-cov JSON")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(lines_found: usize) -> Counts {
+        Counts {
+            lines_found,
+            ..Counts::default()
+        }
+    }
+
+    fn report(files: &[(&str, usize)]) -> CoverageReport {
+        let files = files
+            .iter()
+            .map(|&(path, lines)| (PathBuf::from(path), counts(lines)))
+            .collect();
+        CoverageReport::new(Path::new("lcov.info"), Format::Lcov, files)
+    }
+
+    fn lines(report: &CoverageReport, relative: &str) -> Option<usize> {
+        report
+            .lookup(Path::new(relative))
+            .map(|counts| counts.lines_found)
+    }
+
+    #[test]
+    fn lookup_matches_trailing_components() {
+        let report = report(&[
+            ("/home/ci/build/app/src/main.rs", 1),
+            ("com/example/App.java", 2),
+            ("src/util/mod.rs", 3),
+            ("tests/util/mod.rs", 4),
+        ]);
+
+        // An absolute path from another machine ends with the relative one.
+        assert_eq!(lines(&report, "src/main.rs"), Some(1));
+        // A package-relative JaCoCo path is a suffix of the relative one.
+        assert_eq!(
+            lines(&report, "app/src/main/java/com/example/App.java"),
+            Some(2)
+        );
+        // Files sharing a name resolve by their directories.
+        assert_eq!(lines(&report, "src/util/mod.rs"), Some(3));
+        assert_eq!(lines(&report, "tests/util/mod.rs"), Some(4));
+        assert_eq!(lines(&report, "benches/util/mod.rs"), None);
+        // Matching is by whole components, not characters.
+        assert_eq!(lines(&report, "rc/util/mod.rs"), None);
+        assert_eq!(lines(&report, "src/other.rs"), None);
+    }
+
+    #[test]
+    fn lookup_prefers_the_exact_path() {
+        let report = report(&[("a/src/lib.rs", 1), ("src/lib.rs", 2)]);
+        assert_eq!(lines(&report, "src/lib.rs"), Some(2));
+    }
+
+    #[test]
+    fn formats_are_detected_from_content() {
+        let detect = |name: &str, content: &str| detect(Path::new(name), content).ok();
+        assert_eq!(detect("lcov.info", "TN:\nSF:a.rs\n"), Some(Format::Lcov));
+        assert_eq!(detect("report.txt", "SF:a.rs\n"), Some(Format::Lcov));
+        assert_eq!(
+            detect(
+                "coverage.xml",
+                "<?xml version=\"1.0\" ?>\n<!DOCTYPE coverage>\n<coverage>"
+            ),
+            Some(Format::Cobertura)
+        );
+        assert_eq!(
+            detect("jacoco.xml", "<?xml?><report name=\"x\">"),
+            Some(Format::Jacoco)
+        );
+        assert_eq!(
+            detect("coverage.json", "  {\"type\": \"x\"}"),
+            Some(Format::LlvmCov)
+        );
+        assert_eq!(detect("coverage.xml", "<html>"), None);
+        assert_eq!(detect("notes.txt", "hello"), None);
+    }
+
+    #[test]
+    fn tally_totals_covered_files_by_language() {
+        let report = report(&[("src/a.rs", 10), ("src/b.rs", 30), ("web/c.ts", 20)]);
+        let mut tally = Tally::new(&report);
+        tally.add(Path::new("src/a.rs"), "Rust");
+        tally.add(Path::new("src/b.rs"), "Rust");
+        tally.add(Path::new("web/c.ts"), "TypeScript");
+        tally.add(Path::new("web/d.ts"), "TypeScript");
+
+        let coverage = tally.finish().unwrap();
+        assert_eq!(coverage.files, 3);
+        assert_eq!(coverage.total.lines_found, 60);
+        let languages: Vec<(&str, usize)> = coverage
+            .languages
+            .iter()
+            .map(|language| (language.name.as_str(), language.files))
+            .collect();
+        assert_eq!(languages, [("Rust", 2), ("TypeScript", 1)]);
+
+        assert!(Tally::new(&report).finish().is_none());
+    }
+}
@@ -0,0 +1,212 @@
+//! Cobertura and JaCoCo XML reports.
+
+use anyhow::Result;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use super::Counts;
+
+/// Cobertura, as written by coverage.py, gcovr, Istanbul and cargo-tarpaulin.
+/// Lines are counted from each class's own `<lines>`, not its methods', which
+/// repeat them.
+pub fn parse_cobertura(content: &str) -> Result<Vec<(PathBuf, Counts)>> {
+    let mut reader = Reader::from_str(content);
+    let mut files: BTreeMap<PathBuf, Counts> = BTreeMap::new();
+    let mut class: Option<PathBuf> = None;
+    let mut in_methods = false;
+
+    loop {
+        match reader.read_event()? {
+            // coverage.py writes `<methods/>` in every class; only an open
+            // element has method lines to skip.
+            Event::Start(element) if element.name().as_ref() == b"methods" => in_methods = true,
+            Event::Start(element) | Event::Empty(element) => match element.name().as_ref() {
+                b"class" => class = attribute(&element, "filename")?.map(PathBuf::from),
+                b"line" if !in_methods => {
+                    let Some(class) = &class else {
+                        continue;
+                    };
+                    let counts = files.entry(class.clone()).or_default();
+                    counts.lines_found += 1;
+                    if number(&element, "hits")? > 0 {
+                        counts.lines_hit += 1;
+                    }
+                    if attribute(&element, "branch")?.as_deref() == Some("true") {
+                        // "50% (1/2)"
+                        let coverage = attribute(&element, "condition-coverage")?;
+                        let taken = coverage.as_deref().and_then(|coverage| {
+                            let (hit, found) = coverage
+                                .split_once('(')?
+                                .1
+                                .trim_end_matches(')')
+                                .split_once('/')?;
+                            Some((
+                                hit.trim().parse::<usize>().ok()?,
+                                found.trim().parse::<usize>().ok()?,
+                            ))
+                        });
+                        if let Some((hit, found)) = taken {
+                            counts.branches_hit += hit;
+                            counts.branches_found += found;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Event::End(element) => match element.name().as_ref() {
+                b"class" => class = None,
+                b"methods" => in_methods = false,
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(files.into_iter().collect())
+}
+
+/// JaCoCo, from the `LINE` and `BRANCH` counters of each source file. Paths
+/// are relative to the source root, such as `com/example/App.java`.
+pub fn parse_jacoco(content: &str) -> Result<Vec<(PathBuf, Counts)>> {
+    let mut reader = Reader::from_str(content);
+    let mut files = Vec::new();
+    let mut package = String::new();
+    let mut source: Option<(PathBuf, Counts)> = None;
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) | Event::Empty(element) => match element.name().as_ref() {
+                b"package" => package = attribute(&element, "name")?.unwrap_or_default(),
+                b"sourcefile" => {
+                    let name = attribute(&element, "name")?.unwrap_or_default();
+                    source = Some((PathBuf::from(&package).join(name), Counts::default()));
+                }
+                b"counter" => {
+                    let Some((_, counts)) = source.as_mut() else {
+                        continue;
+                    };
+                    let missed = number(&element, "missed")?;
+                    let covered = number(&element, "covered")?;
+                    match attribute(&element, "type")?.as_deref() {
+                        Some("LINE") => {
+                            counts.lines_found += missed + covered;
+                            counts.lines_hit += covered;
+                        }
+                        Some("BRANCH") => {
+                            counts.branches_found += missed + covered;
+                            counts.branches_hit += covered;
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            },
+            Event::End(element) if element.name().as_ref() == b"sourcefile" => {
+                files.extend(source.take());
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(files)
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>> {
+    Ok(match element.try_get_attribute(name)? {
+        Some(attribute) => Some(attribute.unescape_value()?.into_owned()),
+        None => None,
+    })
+}
+
+/// A numeric attribute; missing or malformed counts as zero.
+fn number(element: &BytesStart, name: &str) -> Result<usize> {
+    Ok(attribute(element, name)?
+        .and_then(|value| value.parse::<f64>().ok())
+        .map_or(0, |value| value as usize))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cobertura_counts_class_lines_after_empty_methods() {
+        let report = r#"<?xml version="1.0" ?>
+<coverage version="7.4.0" line-rate="0.75" branch-rate="0.5">
+  <packages>
+    <package name="app">
+      <classes>
+        <class name="main.py" filename="app/main.py">
+          <methods/>
+          <lines>
+            <line number="1" hits="1"/>
+            <line number="2" hits="1" branch="true" condition-coverage="50% (1/2)"/>
+            <line number="3" hits="0"/>
+            <line number="4" hits="3"/>
+          </lines>
+        </class>
+      </classes>
+    </package>
+  </packages>
+</coverage>"#;
+
+        let files = parse_cobertura(report).unwrap();
+        assert_eq!(files.len(), 1);
+        let (path, counts) = &files[0];
+        assert_eq!(path, &PathBuf::from("app/main.py"));
+        assert_eq!((counts.lines_found, counts.lines_hit), (4, 3));
+        assert_eq!((counts.branches_found, counts.branches_hit), (2, 1));
+    }
+
+    #[test]
+    fn cobertura_skips_method_lines() {
+        let report = r#"<coverage>
+  <class filename="src/lib.rs">
+    <methods>
+      <method name="f">
+        <lines><line number="1" hits="1"/></lines>
+      </method>
+    </methods>
+    <lines>
+      <line number="1" hits="1"/>
+      <line number="2" hits="0"/>
+    </lines>
+  </class>
+</coverage>"#;
+
+        let files = parse_cobertura(report).unwrap();
+        let counts = files[0].1;
+        assert_eq!((counts.lines_found, counts.lines_hit), (2, 1));
+        assert_eq!(counts.branch_rate(), None);
+    }
+
+    #[test]
+    fn jacoco_reads_source_file_counters() {
+        let report = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<!DOCTYPE report PUBLIC "-//JACOCO//DTD Report 1.1//EN" "report.dtd">
+<report name="app">
+  <package name="com/example">
+    <class name="com/example/App" sourcefilename="App.java">
+      <counter type="LINE" missed="9" covered="1"/>
+    </class>
+    <sourcefile name="App.java">
+      <line nr="3" mi="0" ci="3" mb="0" cb="0"/>
+      <counter type="INSTRUCTION" missed="4" covered="12"/>
+      <counter type="BRANCH" missed="1" covered="3"/>
+      <counter type="LINE" missed="2" covered="6"/>
+    </sourcefile>
+    <counter type="LINE" missed="2" covered="6"/>
+  </package>
+  <counter type="LINE" missed="2" covered="6"/>
+</report>"#;
+
+        let files = parse_jacoco(report).unwrap();
+        assert_eq!(files.len(), 1);
+        let (path, counts) = &files[0];
+        assert_eq!(path, &PathBuf::from("com/example/App.java"));
+        assert_eq!((counts.lines_found, counts.lines_hit), (8, 6));
+        assert_eq!((counts.branches_found, counts.branches_hit), (4, 3));
+    }
+}
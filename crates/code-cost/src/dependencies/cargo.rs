@@ -0,0 +1,157 @@
+//! `Cargo.lock`, with licences from crate sources Cargo has downloaded.
+
+use anyhow::Result;
+use cli_core::config::expand_home;
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use super::{Locked, Package};
+
+#[derive(Deserialize)]
+struct Lock {
+    #[serde(default)]
+    package: Vec<LockedPackage>,
+}
+
+#[derive(Deserialize)]
+struct LockedPackage {
+    name: String,
+    version: String,
+    /// Unset for workspace members and path dependencies.
+    source: Option<String>,
+    /// "name", or "name version" when several versions are locked.
+    #[serde(default)]
+    dependencies: Vec<String>,
+}
+
+/// Direct dependencies are the ones workspace members and path crates list.
+pub fn parse(content: &str) -> Result<Locked> {
+    let lock: Lock = toml::from_str(content)?;
+    let (local, remote): (Vec<_>, Vec<_>) = lock
+        .package
+        .into_iter()
+        .partition(|package| package.source.is_none());
+
+    let local_names: BTreeSet<&str> = local.iter().map(|package| package.name.as_str()).collect();
+    let direct = local
+        .iter()
+        .flat_map(|package| &package.dependencies)
+        .filter_map(|dependency| dependency.split_whitespace().next())
+        .filter(|name| !local_names.contains(name))
+        .map(String::from)
+        .collect();
+
+    let packages = remote
+        .into_iter()
+        .map(|package| Package {
+            license: package
+                .source
+                .as_deref()
+                .filter(|source| source.starts_with("registry+") || source.starts_with("sparse+"))
+                .and_then(|_| registry_license(&package.name, &package.version)),
+            name: package.name,
+            version: package.version,
+        })
+        .collect();
+    Ok(Locked { packages, direct })
+}
+
+/// The `license` of a crate unpacked under `$CARGO_HOME/registry/src`, or
+/// "custom" for one that only ships a `license-file`.
+fn registry_license(name: &str, version: &str) -> Option<String> {
+    let crate_dir = format!("{name}-{version}");
+    registries().iter().find_map(|registry| {
+        let manifest =
+            std::fs::read_to_string(registry.join(&crate_dir).join("Cargo.toml")).ok()?;
+        let manifest: toml::Table = toml::from_str(&manifest).ok()?;
+        let package = manifest.get("package")?;
+        match package.get("license").and_then(|license| license.as_str()) {
+            Some(license) => Some(license.to_string()),
+            None => package.get("license-file").map(|_| "custom".to_string()),
+        }
+    })
+}
+
+/// One directory per registry index under `$CARGO_HOME/registry/src`.
+fn registries() -> &'static [PathBuf] {
+    static REGISTRIES: OnceLock<Vec<PathBuf>> = OnceLock::new();
+    REGISTRIES.get_or_init(|| {
+        let cargo_home = std::env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| expand_home(Path::new("~/.cargo")));
+        std::fs::read_dir(cargo_home.join("registry").join("src"))
+            .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+            .unwrap_or_default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workspace_and_path_crates_declare_the_direct_dependencies() {
+        let lock = r#"
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = ["log", "shared"]
+
+[[package]]
+name = "shared"
+version = "0.1.0"
+dependencies = ["log", "regex 99.1.0"]
+
+[[package]]
+name = "log"
+version = "99.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "regex"
+version = "99.1.0"
+source = "sparse+https://index.crates.io/"
+dependencies = ["memchr"]
+
+[[package]]
+name = "regex"
+version = "99.2.0"
+source = "sparse+https://index.crates.io/"
+
+[[package]]
+name = "memchr"
+version = "99.0.0"
+source = "git+https://github.com/BurntSushi/memchr#abc"
+"#;
+        let locked = parse(lock).unwrap();
+
+        assert_eq!(
+            locked.versions(),
+            [
+                "log@99.0.0",
+                "regex@99.1.0",
+                "regex@99.2.0",
+                "memchr@99.0.0"
+            ]
+        );
+        assert_eq!(locked.direct_names(), ["log", "regex"]);
+        // Only registry crates can have a downloaded manifest to read.
+        assert!(locked.packages[3].license.is_none());
+    }
+
+    #[test]
+    fn a_lockfile_without_packages_is_empty() {
+        let locked = parse("version = 4\n").unwrap();
+
+        assert!(locked.packages.is_empty());
+        assert!(locked.direct.is_empty());
+    }
+
+    #[test]
+    fn malformed_lockfiles_are_errors() {
+        assert!(parse("[[package]]\nname = \"a\"\n").is_err());
+        assert!(parse("package = 1").is_err());
+    }
+}
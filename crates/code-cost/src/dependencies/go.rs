@@ -0,0 +1,133 @@
+//! `go.sum`, with direct requirements from `go.mod`.
+
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{Locked, Package};
+
+/// Modules with a content hash in `go.sum`; entries hashing only a `go.mod`
+/// were consulted for version selection but never downloaded. Minimal version
+/// selection builds with one version of each module, so only the one `go.mod`
+/// requires, or else the last listed, is kept. Go records no licences.
+pub fn parse(content: &str, manifest: Option<String>) -> Result<Locked> {
+    let (required, direct) = manifest.as_deref().map(requirements).unwrap_or_default();
+
+    let mut modules: BTreeMap<&str, &str> = BTreeMap::new();
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        let (Some(module), Some(version)) = (fields.next(), fields.next()) else {
+            continue;
+        };
+        if version.ends_with("/go.mod") {
+            continue;
+        }
+        let selected = modules.entry(module).or_insert(version);
+        if required
+            .get(module)
+            .is_none_or(|required| required != *selected)
+        {
+            *selected = version;
+        }
+    }
+
+    let packages = modules
+        .into_iter()
+        .map(|(module, version)| Package {
+            name: module.to_string(),
+            version: version.to_string(),
+            license: None,
+        })
+        .collect();
+    Ok(Locked { packages, direct })
+}
+
+/// Required versions by module, and the modules required without an
+/// `// indirect` comment.
+fn requirements(manifest: &str) -> (BTreeMap<String, String>, BTreeSet<String>) {
+    let mut required = BTreeMap::new();
+    let mut direct = BTreeSet::new();
+    let mut in_block = false;
+    for line in manifest.lines() {
+        let line = line.trim();
+        let requirement = if in_block {
+            if line == ")" {
+                in_block = false;
+                continue;
+            }
+            line
+        } else if line == "require (" {
+            in_block = true;
+            continue;
+        } else if let Some(requirement) = line.strip_prefix("require ") {
+            requirement
+        } else {
+            continue;
+        };
+
+        let mut fields = requirement.split_whitespace();
+        let (Some(module), Some(version)) = (fields.next(), fields.next()) else {
+            continue;
+        };
+        if !requirement.contains("// indirect") {
+            direct.insert(module.to_string());
+        }
+        required.insert(module.to_string(), version.to_string());
+    }
+    (required, direct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GO_SUM: &str = "\
+github.com/a/x v1.0.0 h1:aaa=
+github.com/a/x v1.0.0/go.mod h1:bbb=
+github.com/a/x v1.1.0 h1:ccc=
+github.com/a/x v1.1.0/go.mod h1:ddd=
+github.com/b/y v0.2.0/go.mod h1:eee=
+golang.org/x/text v0.3.0 h1:fff=
+golang.org/x/text v0.3.0/go.mod h1:ggg=
+";
+
+    #[test]
+    fn keeps_the_version_go_mod_requires() {
+        let manifest = "\
+module example.com/app
+
+go 1.22
+
+require github.com/a/x v1.0.0
+
+require (
+\tgolang.org/x/text v0.3.0 // indirect
+)
+";
+        let locked = parse(GO_SUM, Some(manifest.to_string())).unwrap();
+
+        // y only has a go.mod hash: it was never downloaded.
+        assert_eq!(
+            locked.versions(),
+            ["github.com/a/x@v1.0.0", "golang.org/x/text@v0.3.0"]
+        );
+        assert_eq!(locked.direct_names(), ["github.com/a/x"]);
+    }
+
+    #[test]
+    fn without_go_mod_keeps_the_last_listed_version() {
+        let locked = parse(GO_SUM, None).unwrap();
+
+        assert_eq!(
+            locked.versions(),
+            ["github.com/a/x@v1.1.0", "golang.org/x/text@v0.3.0"]
+        );
+        assert!(locked.direct.is_empty());
+    }
+
+    #[test]
+    fn ignores_lines_that_are_not_entries() {
+        let locked = parse("\nnot-an-entry\ngithub.com/a/x v1.0.0 h1:aaa=\n", None).unwrap();
+
+        assert_eq!(locked.versions(), ["github.com/a/x@v1.0.0"]);
+    }
+}
@@ -0,0 +1,465 @@
+//! Dependency inventory from lockfiles, read offline.
+//!
+//! Each lockfile is counted on its own: direct dependencies are the ones its
+//! workspace declares, everything else it locks is transitive. Licences come
+//! from the lockfile where it records them, and for crates from the sources
+//! Cargo has already downloaded.
+
+use anyhow::{Context, Result};
+use cli_core::ui::Theme;
+use git2::{ObjectType, Repository, Tree, TreeWalkMode, TreeWalkResult};
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::metrics::IGNORE_FILE;
+
+mod cargo;
+mod go;
+mod npm;
+mod python;
+
+/// Licence counted for packages that declare none the lockfile or a local
+/// source records.
+pub const UNKNOWN_LICENSE: &str = "unknown";
+
+/// The tool that wrote a lockfile.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Ecosystem {
+    Cargo,
+    Npm,
+    Pnpm,
+    Poetry,
+    Uv,
+    Go,
+}
+
+impl Ecosystem {
+    const ALL: [Ecosystem; 6] = [
+        Self::Cargo,
+        Self::Npm,
+        Self::Pnpm,
+        Self::Poetry,
+        Self::Uv,
+        Self::Go,
+    ];
+
+    fn lockfile(&self) -> &'static str {
+        match self {
+            Self::Cargo => "Cargo.lock",
+            Self::Npm => "package-lock.json",
+            Self::Pnpm => "pnpm-lock.yaml",
+            Self::Poetry => "poetry.lock",
+            Self::Uv => "uv.lock",
+            Self::Go => "go.sum",
+        }
+    }
+
+    fn from_lockfile(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|ecosystem| ecosystem.lockfile() == name)
+    }
+}
+
+impl fmt::Display for Ecosystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Cargo => "Cargo",
+            Self::Npm => "npm",
+            Self::Pnpm => "pnpm",
+            Self::Poetry => "Poetry",
+            Self::Uv => "uv",
+            Self::Go => "Go",
+        })
+    }
+}
+
+/// A locked package as a parser reads it.
+struct Package {
+    name: String,
+    version: String,
+    license: Option<String>,
+}
+
+/// What a parser reads from one lockfile.
+struct Locked {
+    packages: Vec<Package>,
+    /// Names the workspace declares itself.
+    direct: BTreeSet<String>,
+}
+
+/// Every lockfile in a repository.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Inventory {
+    pub lockfiles: Vec<Lockfile>,
+}
+
+/// One lockfile's dependencies, counted by package name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lockfile {
+    /// Relative to the repository root.
+    pub path: PathBuf,
+    pub ecosystem: Ecosystem,
+    pub direct: usize,
+    pub transitive: usize,
+    /// Packages locked at more than one version.
+    pub duplicates: Vec<Duplicate>,
+    /// Locked package versions per declared licence.
+    pub licenses: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Duplicate {
+    pub name: String,
+    pub versions: Vec<String>,
+}
+
+impl Lockfile {
+    fn new(path: PathBuf, ecosystem: Ecosystem, locked: Locked) -> Self {
+        let mut versions: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let mut licenses: BTreeMap<String, usize> = BTreeMap::new();
+        let mut seen = BTreeSet::new();
+        for package in locked.packages {
+            if !seen.insert((package.name.clone(), package.version.clone())) {
+                continue;
+            }
+            let license = package
+                .license
+                .unwrap_or_else(|| UNKNOWN_LICENSE.to_string());
+            *licenses.entry(license).or_default() += 1;
+            versions
+                .entry(package.name)
+                .or_default()
+                .insert(package.version);
+        }
+
+        let direct = versions
+            .keys()
+            .filter(|name| locked.direct.contains(*name))
+            .count();
+        Self {
+            path,
+            ecosystem,
+            direct,
+            transitive: versions.len() - direct,
+            duplicates: versions
+                .into_iter()
+                .filter(|(_, versions)| versions.len() > 1)
+                .map(|(name, versions)| Duplicate {
+                    name,
+                    versions: versions.into_iter().collect(),
+                })
+                .collect(),
+            licenses,
+        }
+    }
+
+    /// Versions locked beyond the first of each package.
+    pub fn extra_versions(&self) -> usize {
+        self.duplicates
+            .iter()
+            .map(|duplicate| duplicate.versions.len() - 1)
+            .sum()
+    }
+}
+
+impl Inventory {
+    /// Lockfiles in the working tree at `root`. Ignored directories are not
+    /// searched, but a lockfile Git ignores in a searched directory still
+    /// counts, since libraries often leave `Cargo.lock` out of the repository.
+    pub fn scan(root: &Path) -> Result<Self> {
+        let walker = WalkBuilder::new(root)
+            .hidden(false)
+            .follow_links(false)
+            .add_custom_ignore_filename(IGNORE_FILE)
+            .filter_entry(|entry| entry.file_name() != ".git")
+            .build();
+        let mut paths = Vec::new();
+        for entry in walker {
+            let entry = entry?;
+            if !entry.file_type().is_some_and(|t| t.is_dir()) {
+                continue;
+            }
+            let dir = entry.path().strip_prefix(root)?;
+            for ecosystem in Ecosystem::ALL {
+                let path = dir.join(ecosystem.lockfile());
+                if root.join(&path).is_file() {
+                    paths.push(path);
+                }
+            }
+        }
+        Ok(Self::read(paths, |path| {
+            std::fs::read_to_string(root.join(path)).ok()
+        }))
+    }
+
+    /// Lockfiles committed in `tree`.
+    pub fn scan_tree(repo: &Repository, tree: &Tree) -> Result<Self> {
+        let mut paths = Vec::new();
+        tree.walk(TreeWalkMode::PreOrder, |dir, entry| {
+            let is_lockfile = entry.kind() == Some(ObjectType::Blob)
+                && entry.name().and_then(Ecosystem::from_lockfile).is_some();
+            if is_lockfile {
+                paths.push(Path::new(dir).join(entry.name().unwrap_or_default()));
+            }
+            TreeWalkResult::Ok
+        })?;
+        Ok(Self::read(paths, |path| {
+            let blob = tree
+                .get_path(path)
+                .ok()?
+                .to_object(repo)
+                .ok()?
+                .peel_to_blob()
+                .ok()?;
+            String::from_utf8(blob.content().to_vec()).ok()
+        }))
+    }
+
+    /// Parse each lockfile with `read`, which also supplies the manifests
+    /// beside it. A lockfile that cannot be parsed is skipped with a warning.
+    fn read(mut paths: Vec<PathBuf>, read: impl Fn(&Path) -> Option<String>) -> Self {
+        paths.sort();
+        let lockfiles = paths
+            .into_iter()
+            .filter_map(|path| {
+                let name = path.file_name()?.to_str()?;
+                let ecosystem = Ecosystem::from_lockfile(name)?;
+                match parse(ecosystem, &path, &read) {
+                    Ok(locked) => Some(Lockfile::new(path, ecosystem, locked)),
+                    Err(e) => {
+                        eprintln!("{} {:#}", Theme::warning("Lockfile skipped:"), e);
+                        None
+                    }
+                }
+            })
+            .collect();
+        Self { lockfiles }
+    }
+
+    /// The lockfiles `keep` accepts, by path relative to the repository root.
+    pub fn filter(&self, keep: impl Fn(&Path) -> bool) -> Self {
+        Self {
+            lockfiles: self
+                .lockfiles
+                .iter()
+                .filter(|lockfile| keep(&lockfile.path))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    pub fn direct(&self) -> usize {
+        self.lockfiles.iter().map(|lockfile| lockfile.direct).sum()
+    }
+
+    pub fn transitive(&self) -> usize {
+        self.lockfiles
+            .iter()
+            .map(|lockfile| lockfile.transitive)
+            .sum()
+    }
+
+    pub fn extra_versions(&self) -> usize {
+        self.lockfiles.iter().map(Lockfile::extra_versions).sum()
+    }
+
+    /// Package versions per licence over every lockfile, most common first.
+    pub fn licenses(&self) -> Vec<(String, usize)> {
+        let mut totals: BTreeMap<&str, usize> = BTreeMap::new();
+        for lockfile in &self.lockfiles {
+            for (license, count) in &lockfile.licenses {
+                *totals.entry(license).or_default() += count;
+            }
+        }
+        let mut licenses: Vec<(String, usize)> = totals
+            .into_iter()
+            .map(|(license, count)| (license.to_string(), count))
+            .collect();
+        licenses.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        licenses
+    }
+}
+
+fn parse(
+    ecosystem: Ecosystem,
+    path: &Path,
+    read: impl Fn(&Path) -> Option<String>,
+) -> Result<Locked> {
+    let content = read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let manifest = |name: &str| read(&path.with_file_name(name));
+    match ecosystem {
+        Ecosystem::Cargo => cargo::parse(&content),
+        Ecosystem::Npm => npm::parse_package_lock(&content, manifest("package.json")),
+        Ecosystem::Pnpm => npm::parse_pnpm_lock(&content),
+        Ecosystem::Poetry => python::parse_poetry(&content, manifest("pyproject.toml")),
+        Ecosystem::Uv => python::parse_uv(&content),
+        Ecosystem::Go => go::parse(&content, manifest("go.mod")),
+    }
+    .with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+#[cfg(test)]
+impl Locked {
+    /// `name@version` of every package, in lockfile order.
+    fn versions(&self) -> Vec<String> {
+        self.packages
+            .iter()
+            .map(|package| format!("{}@{}", package.name, package.version))
+            .collect()
+    }
+
+    fn direct_names(&self) -> Vec<&str> {
+        self.direct.iter().map(String::as_str).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CARGO_LOCK: &str = r#"
+version = 4
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = ["serde", "rand 0.8.5", "helper"]
+
+[[package]]
+name = "helper"
+version = "0.1.0"
+dependencies = ["rand 0.9.0"]
+
+[[package]]
+name = "serde"
+version = "99.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "rand"
+version = "0.8.5"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "rand"
+version = "0.9.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "libc"
+version = "0.2.0"
+source = "git+https://github.com/rust-lang/libc#abc123"
+"#;
+
+    fn inventory(files: &[(&str, &str)]) -> Inventory {
+        let files: BTreeMap<PathBuf, String> = files
+            .iter()
+            .map(|(path, content)| (PathBuf::from(path), content.to_string()))
+            .collect();
+        let paths = files
+            .keys()
+            .filter(|path| {
+                Ecosystem::from_lockfile(path.file_name().unwrap().to_str().unwrap()).is_some()
+            })
+            .cloned()
+            .collect();
+        Inventory::read(paths, |path| files.get(path).cloned())
+    }
+
+    #[test]
+    fn counts_direct_transitive_and_duplicate_versions() {
+        let inventory = inventory(&[("Cargo.lock", CARGO_LOCK)]);
+        let [lockfile] = inventory.lockfiles.as_slice() else {
+            panic!("expected one lockfile");
+        };
+
+        assert_eq!(lockfile.ecosystem, Ecosystem::Cargo);
+        // serde and rand are declared by workspace crates; libc only by rand.
+        assert_eq!((lockfile.direct, lockfile.transitive), (2, 1));
+        assert_eq!(lockfile.duplicates.len(), 1);
+        assert_eq!(lockfile.duplicates[0].name, "rand");
+        assert_eq!(lockfile.duplicates[0].versions, ["0.8.5", "0.9.0"]);
+        assert_eq!(lockfile.extra_versions(), 1);
+        assert_eq!(
+            lockfile.licenses,
+            BTreeMap::from([(UNKNOWN_LICENSE.to_string(), 4)])
+        );
+    }
+
+    #[test]
+    fn repeated_package_versions_count_once() {
+        let locked = Locked {
+            packages: ["a@1", "a@1", "b@2"]
+                .iter()
+                .map(|spec| {
+                    let (name, version) = spec.split_once('@').unwrap();
+                    Package {
+                        name: name.to_string(),
+                        version: version.to_string(),
+                        license: Some("MIT".to_string()),
+                    }
+                })
+                .collect(),
+            direct: BTreeSet::from(["a".to_string()]),
+        };
+        let lockfile = Lockfile::new(PathBuf::from("x"), Ecosystem::Npm, locked);
+
+        assert_eq!((lockfile.direct, lockfile.transitive), (1, 1));
+        assert!(lockfile.duplicates.is_empty());
+        assert_eq!(lockfile.licenses, BTreeMap::from([("MIT".to_string(), 2)]));
+    }
+
+    #[test]
+    fn malformed_lockfiles_are_skipped() {
+        let inventory = inventory(&[
+            ("Cargo.lock", CARGO_LOCK),
+            ("web/package-lock.json", "{ not json"),
+            ("api/go.sum", ""),
+            ("py/poetry.lock", "[[package]]\nname = "),
+        ]);
+
+        let paths: Vec<&Path> = inventory
+            .lockfiles
+            .iter()
+            .map(|lockfile| lockfile.path.as_path())
+            .collect();
+        assert_eq!(paths, [Path::new("Cargo.lock"), Path::new("api/go.sum")]);
+        assert_eq!(inventory.direct(), 2);
+        assert_eq!(inventory.transitive(), 1);
+    }
+
+    #[test]
+    fn licenses_are_totalled_most_common_first() {
+        let lockfile = |licenses: &[(&str, usize)]| Lockfile {
+            path: PathBuf::new(),
+            ecosystem: Ecosystem::Npm,
+            direct: 0,
+            transitive: 0,
+            duplicates: Vec::new(),
+            licenses: licenses
+                .iter()
+                .map(|(license, count)| (license.to_string(), *count))
+                .collect(),
+        };
+        let inventory = Inventory {
+            lockfiles: vec![
+                lockfile(&[("MIT", 2), ("ISC", 1)]),
+                lockfile(&[("Apache-2.0", 3), ("ISC", 2)]),
+            ],
+        };
+
+        assert_eq!(
+            inventory.licenses(),
+            [
+                ("Apache-2.0".to_string(), 3),
+                ("ISC".to_string(), 3),
+                ("MIT".to_string(), 2)
+            ]
+        );
+    }
+}
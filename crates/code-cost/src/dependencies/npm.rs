@@ -0,0 +1,323 @@
+//! `package-lock.json` and `pnpm-lock.yaml`.
+
+use anyhow::{bail, Result};
+use serde_json::Value as Json;
+use serde_yaml::Value as Yaml;
+use std::collections::BTreeSet;
+
+use super::{Locked, Package};
+
+/// Dependency lists a package manifest or lockfile importer declares.
+const DEPENDENCY_KEYS: [&str; 4] = [
+    "dependencies",
+    "devDependencies",
+    "optionalDependencies",
+    "peerDependencies",
+];
+
+/// Lockfile v2 and v3 `packages`, keyed by install path, with licences; v1
+/// nested `dependencies` otherwise, with direct ones from `package.json`.
+pub fn parse_package_lock(content: &str, manifest: Option<String>) -> Result<Locked> {
+    let lock: Json = serde_json::from_str(content)?;
+    let mut packages = Vec::new();
+    let mut direct = BTreeSet::new();
+
+    if let Some(entries) = lock.get("packages").and_then(Json::as_object) {
+        for (key, entry) in entries {
+            // The root and workspace packages are the ones outside node_modules.
+            let Some((_, name)) = key.rsplit_once("node_modules/") else {
+                direct.extend(json_keys(entry));
+                continue;
+            };
+            if entry.get("link").and_then(Json::as_bool) == Some(true) {
+                continue;
+            }
+            let Some(version) = entry.get("version").and_then(Json::as_str) else {
+                continue;
+            };
+            packages.push(Package {
+                name: name.to_string(),
+                version: version.to_string(),
+                license: entry
+                    .get("license")
+                    .and_then(Json::as_str)
+                    .map(String::from),
+            });
+        }
+    } else if let Some(dependencies) = lock.get("dependencies") {
+        collect_v1(dependencies, &mut packages);
+        if let Some(manifest) = manifest {
+            let manifest: Json = serde_json::from_str(&manifest)?;
+            direct.extend(json_keys(&manifest));
+        }
+    } else {
+        bail!("expected `packages` or `dependencies`");
+    }
+
+    Ok(Locked { packages, direct })
+}
+
+fn collect_v1(dependencies: &Json, packages: &mut Vec<Package>) {
+    let Some(dependencies) = dependencies.as_object() else {
+        return;
+    };
+    for (name, entry) in dependencies {
+        if let Some(version) = entry.get("version").and_then(Json::as_str) {
+            packages.push(Package {
+                name: name.clone(),
+                version: version.to_string(),
+                license: None,
+            });
+        }
+        if let Some(nested) = entry.get("dependencies") {
+            collect_v1(nested, packages);
+        }
+    }
+}
+
+/// Names in every dependency list of a manifest or lockfile entry.
+fn json_keys(entry: &Json) -> Vec<String> {
+    DEPENDENCY_KEYS
+        .iter()
+        .filter_map(|key| entry.get(key)?.as_object())
+        .flat_map(|dependencies| dependencies.keys().cloned())
+        .collect()
+}
+
+/// Lockfile versions 5 to 9. Direct dependencies are the importers' (or, before
+/// workspaces, the top level's); pnpm records no licences.
+pub fn parse_pnpm_lock(content: &str) -> Result<Locked> {
+    let lock: Yaml = serde_yaml::from_str(content)?;
+    let mut direct = BTreeSet::new();
+    direct.extend(yaml_keys(&lock));
+    if let Some(importers) = lock.get("importers").and_then(Yaml::as_mapping) {
+        for importer in importers.values() {
+            direct.extend(yaml_keys(importer));
+        }
+    }
+
+    // "5.4" is a number in version 5 lockfiles and a string after.
+    let version = match lock.get("lockfileVersion") {
+        Some(Yaml::String(version)) => version.parse::<f64>().ok(),
+        Some(version) => version.as_f64(),
+        None => None,
+    };
+    let slash_separated = version.is_some_and(|version| version < 6.0);
+    let packages = lock
+        .get("packages")
+        .and_then(Yaml::as_mapping)
+        .map(|entries| {
+            entries
+                .keys()
+                .filter_map(Yaml::as_str)
+                .filter_map(|key| pnpm_package(key, slash_separated))
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(Locked { packages, direct })
+}
+
+fn yaml_keys(entry: &Yaml) -> Vec<String> {
+    DEPENDENCY_KEYS
+        .iter()
+        .filter_map(|key| entry.get(*key)?.as_mapping())
+        .flat_map(|dependencies| {
+            dependencies
+                .keys()
+                .filter_map(Yaml::as_str)
+                .map(String::from)
+        })
+        .collect()
+}
+
+/// Name and version from a `packages` key: `name@1.0.0(peer@2.0.0)` since
+/// version 9, `/name@1.0.0` in 6 and `/name/1.0.0_peer@2.0.0` in 5.
+fn pnpm_package(key: &str, slash_separated: bool) -> Option<Package> {
+    let key = key.strip_prefix('/').unwrap_or(key);
+    let (name, version) = if slash_separated {
+        let (name, version) = key.rsplit_once('/')?;
+        (name, version.split('_').next()?)
+    } else {
+        let key = key.split('(').next()?;
+        // Skip the `@` of a scope.
+        let at = key[1..].rfind('@')? + 1;
+        (&key[..at], &key[at + 1..])
+    };
+    Some(Package {
+        name: name.to_string(),
+        version: version.to_string(),
+        license: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACKAGES: &str = r#"{
+        "": {
+            "name": "app",
+            "dependencies": { "left-pad": "^1.3.0" },
+            "devDependencies": { "jest": "^29.0.0" },
+            "workspaces": ["packages/*"]
+        },
+        "packages/lib": {
+            "name": "lib",
+            "version": "1.0.0",
+            "dependencies": { "@scope/util": "^2.0.0" }
+        },
+        "node_modules/lib": { "resolved": "packages/lib", "link": true },
+        "node_modules/left-pad": { "version": "1.3.0", "license": "WTFPL" },
+        "node_modules/jest": { "version": "29.7.0", "license": "MIT", "dev": true },
+        "node_modules/@scope/util": { "version": "2.0.0" },
+        "node_modules/jest/node_modules/left-pad": { "version": "1.1.0", "license": "WTFPL" }
+    }"#;
+
+    #[test]
+    fn package_lock_v3_reads_packages_and_workspaces() {
+        let lock = format!(r#"{{ "lockfileVersion": 3, "packages": {PACKAGES} }}"#);
+        let locked = parse_package_lock(&lock, None).unwrap();
+
+        assert_eq!(
+            locked.versions(),
+            [
+                "@scope/util@2.0.0",
+                "jest@29.7.0",
+                "left-pad@1.1.0",
+                "left-pad@1.3.0"
+            ]
+        );
+        assert_eq!(locked.direct_names(), ["@scope/util", "jest", "left-pad"]);
+        let licenses: Vec<Option<&str>> = locked
+            .packages
+            .iter()
+            .map(|package| package.license.as_deref())
+            .collect();
+        assert_eq!(licenses, [None, Some("MIT"), Some("WTFPL"), Some("WTFPL")]);
+    }
+
+    #[test]
+    fn package_lock_v2_prefers_packages_over_v1_dependencies() {
+        let lock = format!(
+            r#"{{
+                "lockfileVersion": 2,
+                "packages": {PACKAGES},
+                "dependencies": {{ "stale": {{ "version": "0.0.1" }} }}
+            }}"#
+        );
+        let locked = parse_package_lock(&lock, None).unwrap();
+
+        assert_eq!(locked.packages.len(), 4);
+        assert!(!locked.versions().contains(&"stale@0.0.1".to_string()));
+    }
+
+    #[test]
+    fn package_lock_v1_nests_dependencies_and_reads_the_manifest() {
+        let lock = r#"{
+            "lockfileVersion": 1,
+            "dependencies": {
+                "express": {
+                    "version": "4.18.0",
+                    "dependencies": { "debug": { "version": "2.6.9" } }
+                },
+                "debug": { "version": "4.3.4" }
+            }
+        }"#;
+        let manifest = r#"{ "dependencies": { "express": "^4" } }"#;
+        let locked = parse_package_lock(lock, Some(manifest.to_string())).unwrap();
+
+        assert_eq!(
+            locked.versions(),
+            ["debug@4.3.4", "express@4.18.0", "debug@2.6.9"]
+        );
+        assert_eq!(locked.direct_names(), ["express"]);
+    }
+
+    #[test]
+    fn malformed_package_locks_are_errors() {
+        assert!(parse_package_lock("{ \"packages\": ", None).is_err());
+        let error = parse_package_lock("{ \"name\": \"app\" }", None)
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "expected `packages` or `dependencies`");
+    }
+
+    #[test]
+    fn pnpm_lock_v9_reads_importers_and_peer_suffixes() {
+        let lock = r#"
+lockfileVersion: '9.0'
+importers:
+  .:
+    dependencies:
+      react:
+        specifier: ^18.2.0
+        version: 18.2.0
+  packages/web:
+    devDependencies:
+      '@types/node':
+        specifier: ^20.0.0
+        version: 20.1.0
+packages:
+  react@18.2.0:
+    resolution: {integrity: sha512-a}
+  react-dom@18.2.0(react@18.2.0):
+    resolution: {integrity: sha512-b}
+  '@types/node@20.1.0':
+    resolution: {integrity: sha512-c}
+  '@types/node@18.0.0':
+    resolution: {integrity: sha512-d}
+"#;
+        let locked = parse_pnpm_lock(lock).unwrap();
+
+        assert_eq!(
+            locked.versions(),
+            [
+                "react@18.2.0",
+                "react-dom@18.2.0",
+                "@types/node@20.1.0",
+                "@types/node@18.0.0"
+            ]
+        );
+        assert_eq!(locked.direct_names(), ["@types/node", "react"]);
+    }
+
+    #[test]
+    fn pnpm_lock_v5_and_v6_keys() {
+        let v5 = r#"
+lockfileVersion: 5.4
+dependencies:
+  react-dom: 18.2.0_react@18.2.0
+packages:
+  /react/18.2.0:
+    resolution: {integrity: sha512-a}
+  /react-dom/18.2.0_react@18.2.0:
+    resolution: {integrity: sha512-b}
+  /@babel/core/7.0.0:
+    resolution: {integrity: sha512-c}
+"#;
+        let locked = parse_pnpm_lock(v5).unwrap();
+        assert_eq!(
+            locked.versions(),
+            ["react@18.2.0", "react-dom@18.2.0", "@babel/core@7.0.0"]
+        );
+        assert_eq!(locked.direct_names(), ["react-dom"]);
+
+        let v6 = r#"
+lockfileVersion: '6.0'
+packages:
+  /react@18.2.0:
+    resolution: {integrity: sha512-a}
+  /@babel/core@7.0.0(supports-color@5.5.0):
+    resolution: {integrity: sha512-c}
+"#;
+        assert_eq!(
+            parse_pnpm_lock(v6).unwrap().versions(),
+            ["react@18.2.0", "@babel/core@7.0.0"]
+        );
+    }
+
+    #[test]
+    fn malformed_pnpm_locks_are_errors() {
+        assert!(parse_pnpm_lock("packages: [unclosed").is_err());
+    }
+}
@@ -0,0 +1,328 @@
+//! `poetry.lock` and `uv.lock`. Neither records licences.
+
+use anyhow::Result;
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use toml::Value;
+
+use super::{Locked, Package};
+
+#[derive(Deserialize)]
+struct Lock {
+    #[serde(default)]
+    package: Vec<LockedPackage>,
+}
+
+#[derive(Deserialize)]
+struct LockedPackage {
+    name: String,
+    /// uv leaves it out for virtual workspace roots.
+    #[serde(default)]
+    version: String,
+    /// uv only: `{ editable = "." }` or `{ virtual = "." }` for the workspace.
+    source: Option<toml::Table>,
+    #[serde(default)]
+    dependencies: Vec<Value>,
+    #[serde(default, rename = "optional-dependencies")]
+    optional_dependencies: BTreeMap<String, Vec<Value>>,
+    #[serde(default, rename = "dev-dependencies")]
+    dev_dependencies: BTreeMap<String, Vec<Value>>,
+}
+
+/// Direct dependencies come from `pyproject.toml`: Poetry's own tables, PEP
+/// 621 `[project]` and PEP 735 `[dependency-groups]`.
+pub fn parse_poetry(content: &str, manifest: Option<String>) -> Result<Locked> {
+    let lock: Lock = toml::from_str(content)?;
+    let mut direct = BTreeSet::new();
+    if let Some(manifest) = manifest {
+        let manifest: toml::Table = toml::from_str(&manifest)?;
+        let poetry = manifest.get("tool").and_then(|tool| tool.get("poetry"));
+        let mut tables: Vec<&Value> = ["dependencies", "dev-dependencies"]
+            .iter()
+            .filter_map(|key| poetry?.get(key))
+            .collect();
+        if let Some(groups) = poetry
+            .and_then(|poetry| poetry.get("group"))
+            .and_then(Value::as_table)
+        {
+            tables.extend(
+                groups
+                    .values()
+                    .filter_map(|group| group.get("dependencies")),
+            );
+        }
+        for table in tables.iter().filter_map(|table| table.as_table()) {
+            direct.extend(
+                table
+                    .keys()
+                    .filter(|name| *name != "python")
+                    .map(|name| normalize(name)),
+            );
+        }
+
+        let project = manifest.get("project");
+        let mut requirements: Vec<&Value> = Vec::new();
+        requirements.extend(project.and_then(|project| project.get("dependencies")));
+        let extras = project.and_then(|project| project.get("optional-dependencies"));
+        if let Some(extras) = extras.and_then(Value::as_table) {
+            requirements.extend(extras.values());
+        }
+        if let Some(groups) = manifest.get("dependency-groups").and_then(Value::as_table) {
+            requirements.extend(groups.values());
+        }
+        direct.extend(
+            requirements
+                .into_iter()
+                .filter_map(Value::as_array)
+                .flatten()
+                .filter_map(Value::as_str)
+                .map(requirement_name),
+        );
+    }
+
+    let packages = lock
+        .package
+        .into_iter()
+        .map(|package| Package {
+            name: normalize(&package.name),
+            version: package.version,
+            license: None,
+        })
+        .collect();
+    Ok(Locked { packages, direct })
+}
+
+/// Direct dependencies are the ones the workspace packages list.
+pub fn parse_uv(content: &str) -> Result<Locked> {
+    let lock: Lock = toml::from_str(content)?;
+    let is_workspace = |package: &LockedPackage| {
+        package
+            .source
+            .as_ref()
+            .is_some_and(|source| source.contains_key("editable") || source.contains_key("virtual"))
+    };
+    let (workspace, locked): (Vec<_>, Vec<_>) = lock.package.into_iter().partition(is_workspace);
+
+    let members: BTreeSet<String> = workspace
+        .iter()
+        .map(|package| normalize(&package.name))
+        .collect();
+    let direct = workspace
+        .iter()
+        .flat_map(|package| {
+            package
+                .dependencies
+                .iter()
+                .chain(package.optional_dependencies.values().flatten())
+                .chain(package.dev_dependencies.values().flatten())
+        })
+        .filter_map(|dependency| dependency.get("name")?.as_str())
+        .map(normalize)
+        .filter(|name| !members.contains(name))
+        .collect();
+
+    let packages = locked
+        .into_iter()
+        .map(|package| Package {
+            name: normalize(&package.name),
+            version: package.version,
+            license: None,
+        })
+        .collect();
+    Ok(Locked { packages, direct })
+}
+
+/// The distribution name of a PEP 508 requirement such as `requests[socks]>=2`.
+fn requirement_name(requirement: &str) -> String {
+    let end = requirement
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(requirement.len());
+    normalize(&requirement[..end])
+}
+
+/// PEP 503 normalised name: lowercase, with runs of `-`, `_` and `.` as `-`.
+fn normalize(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for c in name.chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+        } else {
+            normalized.push(c.to_ascii_lowercase());
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poetry_reads_direct_dependencies_from_pyproject() {
+        let lock = r#"
+[[package]]
+name = "Requests"
+version = "2.31.0"
+
+[[package]]
+name = "urllib3"
+version = "2.0.0"
+
+[[package]]
+name = "pytest"
+version = "8.0.0"
+
+[[package]]
+name = "typing_extensions"
+version = "4.9.0"
+
+[[package]]
+name = "rich"
+version = "13.0.0"
+
+[[package]]
+name = "ruff"
+version = "0.4.0"
+"#;
+        let manifest = r#"
+[tool.poetry.dependencies]
+python = "^3.11"
+requests = "^2.31"
+
+[tool.poetry.group.dev.dependencies]
+pytest = "^8"
+
+[project]
+dependencies = ["typing-extensions>=4"]
+
+[project.optional-dependencies]
+cli = ["Rich[jupyter]~=13.0"]
+
+[dependency-groups]
+lint = ["ruff"]
+"#;
+        let locked = parse_poetry(lock, Some(manifest.to_string())).unwrap();
+
+        assert_eq!(
+            locked.versions(),
+            [
+                "requests@2.31.0",
+                "urllib3@2.0.0",
+                "pytest@8.0.0",
+                "typing-extensions@4.9.0",
+                "rich@13.0.0",
+                "ruff@0.4.0"
+            ]
+        );
+        assert_eq!(
+            locked.direct_names(),
+            ["pytest", "requests", "rich", "ruff", "typing-extensions"]
+        );
+    }
+
+    #[test]
+    fn poetry_without_pyproject_has_no_direct_dependencies() {
+        let lock = "[[package]]\nname = \"six\"\nversion = \"1.16.0\"\n";
+        let locked = parse_poetry(lock, None).unwrap();
+
+        assert_eq!(locked.versions(), ["six@1.16.0"]);
+        assert!(locked.direct.is_empty());
+    }
+
+    #[test]
+    fn uv_direct_dependencies_come_from_workspace_members() {
+        let lock = r#"
+version = 1
+
+[[package]]
+name = "app"
+version = "0.1.0"
+source = { editable = "." }
+dependencies = [{ name = "httpx" }, { name = "lib" }]
+
+[package.optional-dependencies]
+cli = [{ name = "Click" }]
+
+[package.dev-dependencies]
+dev = [{ name = "pytest" }]
+
+[[package]]
+name = "lib"
+version = "0.1.0"
+source = { editable = "packages/lib" }
+dependencies = [{ name = "anyio" }]
+
+[[package]]
+name = "root"
+source = { virtual = "." }
+
+[[package]]
+name = "httpx"
+version = "0.27.0"
+source = { registry = "https://pypi.org/simple" }
+dependencies = [{ name = "anyio" }, { name = "h11" }]
+
+[[package]]
+name = "anyio"
+version = "4.0.0"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "anyio"
+version = "3.7.1"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "h11"
+version = "0.14.0"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "click"
+version = "8.1.7"
+source = { registry = "https://pypi.org/simple" }
+
+[[package]]
+name = "pytest"
+version = "8.0.0"
+source = { registry = "https://pypi.org/simple" }
+"#;
+        let locked = parse_uv(lock).unwrap();
+
+        assert_eq!(
+            locked.versions(),
+            [
+                "httpx@0.27.0",
+                "anyio@4.0.0",
+                "anyio@3.7.1",
+                "h11@0.14.0",
+                "click@8.1.7",
+                "pytest@8.0.0"
+            ]
+        );
+        assert_eq!(locked.direct_names(), ["anyio", "click", "httpx", "pytest"]);
+    }
+
+    #[test]
+    fn malformed_python_locks_are_errors() {
+        assert!(parse_poetry("[[package]]\nversion = \"1\"\n", None).is_err());
+        assert!(parse_uv("[[package]\n").is_err());
+        let lock = "[[package]]\nname = \"a\"\nversion = \"1\"\n";
+        assert!(parse_poetry(lock, Some("[project".to_string())).is_err());
+    }
+
+    #[test]
+    fn requirement_names_are_normalised() {
+        assert_eq!(
+            requirement_name("Flask_SQLAlchemy[async]>=3"),
+            "flask-sqlalchemy"
+        );
+        assert_eq!(
+            requirement_name("zope.interface ; python_version<'4'"),
+            "zope-interface"
+        );
+        assert_eq!(normalize("A__b.-C"), "a-b-c");
+    }
+}
@@ -0,0 +1,386 @@
+//! Value added between two revisions (`code-cost diff`).
+
+use anyhow::{Context, Result};
+use git2::{Delta, DiffFindOptions, DiffOptions, Oid, Patch, Repository, Tree};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::analyzer::RepositoryAnalyzer;
+use crate::calculator::CostCalculator;
+use crate::group;
+use crate::history::{Point, Revision};
+use crate::metrics::TreeScanner;
+
+/// Directories shown by default.
+pub const DEFAULT_DIRECTORIES: usize = 10;
+/// Path components that name a directory by default (`crates/code-cost`).
+pub const DEFAULT_DEPTH: usize = 2;
+
+/// Diff lines of counted files. A hunk's replaced lines count as changed,
+/// and only the remainder as added or removed.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct LineDelta {
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+}
+
+impl LineDelta {
+    fn add(&mut self, other: LineDelta) {
+        self.added += other.added;
+        self.removed += other.removed;
+        self.changed += other.changed;
+    }
+
+    /// Lines written or rewritten, which is what the new value pays for.
+    pub fn touched(&self) -> usize {
+        self.added + self.changed
+    }
+
+    /// Change in line count.
+    pub fn net(&self) -> i64 {
+        self.added as i64 - self.removed as i64
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LanguageDelta {
+    pub language: String,
+    #[serde(flatten)]
+    pub lines: LineDelta,
+}
+
+/// A directory's lines and its share of the estimate delta, in proportion
+/// to the lines it touched.
+#[derive(Debug, Clone, Serialize)]
+pub struct DirectoryDelta {
+    pub directory: String,
+    #[serde(flatten)]
+    pub lines: LineDelta,
+    pub share: f64,
+    pub hours: f64,
+    pub cost: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RevisionDiff {
+    pub path: PathBuf,
+    pub from: Point,
+    pub to: Point,
+    pub files_changed: usize,
+    pub lines: LineDelta,
+    pub hours: f64,
+    pub cost: f64,
+    pub languages: Vec<LanguageDelta>,
+    pub directories: Vec<DirectoryDelta>,
+}
+
+/// Options for [`compare`].
+pub struct DiffScope {
+    /// Path components that name a directory.
+    pub depth: usize,
+    /// Most directories to list; the rest are left out.
+    pub directories: usize,
+}
+
+/// Value both revisions from git objects and attribute the difference.
+pub async fn compare(
+    path: &Path,
+    from: &str,
+    to: &str,
+    scope: &DiffScope,
+    analyzer: &RepositoryAnalyzer,
+    calculator: &CostCalculator,
+) -> Result<RevisionDiff> {
+    let repo = Repository::open(path)?;
+    let revisions = [
+        Revision::resolve(&repo, from)?,
+        Revision::resolve(&repo, to)?,
+    ];
+
+    let mut analyses = analyzer
+        .analyze_history(path, &revisions)
+        .await?
+        .into_iter();
+    let mut point = |revision: &Revision| {
+        let analysis = analyses.next().context("Missing revision analysis")?;
+        let cost = calculator.calculate(&analysis);
+        anyhow::Ok(Point::new(revision, analysis, cost))
+    };
+    let from_point = point(&revisions[0])?;
+    let to_point = point(&revisions[1])?;
+
+    let old_tree = repo.find_commit(revisions[0].commit)?.tree()?;
+    let new_tree = repo.find_commit(revisions[1].commit)?.tree()?;
+    let files = changed_files(&repo, path, &old_tree, &new_tree)?;
+
+    let mut lines = LineDelta::default();
+    let mut languages: HashMap<String, LineDelta> = HashMap::new();
+    let mut directories: HashMap<String, LineDelta> = HashMap::new();
+    for file in &files {
+        lines.add(file.lines);
+        languages
+            .entry(file.language.clone())
+            .or_default()
+            .add(file.lines);
+        directories
+            .entry(group::directory(&file.path, scope.depth).join("/"))
+            .or_default()
+            .add(file.lines);
+    }
+
+    let hours = to_point.cost.estimated_hours - from_point.cost.estimated_hours;
+    let cost = to_point.cost.total_cost - from_point.cost.total_cost;
+    let touched = lines.touched().max(1) as f64;
+
+    let mut languages: Vec<LanguageDelta> = languages
+        .into_iter()
+        .map(|(language, lines)| LanguageDelta { language, lines })
+        .collect();
+    languages.sort_by(|a, b| {
+        b.lines
+            .touched()
+            .cmp(&a.lines.touched())
+            .then_with(|| a.language.cmp(&b.language))
+    });
+
+    let mut directories: Vec<DirectoryDelta> = directories
+        .into_iter()
+        .map(|(directory, lines)| {
+            let share = lines.touched() as f64 / touched;
+            DirectoryDelta {
+                directory,
+                lines,
+                share,
+                hours: hours * share,
+                cost: cost * share,
+            }
+        })
+        .collect();
+    directories.sort_by(|a, b| {
+        b.lines
+            .touched()
+            .cmp(&a.lines.touched())
+            .then_with(|| b.lines.removed.cmp(&a.lines.removed))
+            .then_with(|| a.directory.cmp(&b.directory))
+    });
+    directories.truncate(scope.directories);
+
+    Ok(RevisionDiff {
+        path: path.to_path_buf(),
+        from: from_point,
+        to: to_point,
+        files_changed: files.len(),
+        lines,
+        hours,
+        cost,
+        languages,
+        directories,
+    })
+}
+
+/// One counted file that differs between the trees.
+struct ChangedFile {
+    path: PathBuf,
+    language: String,
+    lines: LineDelta,
+}
+
+/// Files whose language is counted, with renames followed so a moved file
+/// only contributes the lines that changed.
+fn changed_files(
+    repo: &Repository,
+    root: &Path,
+    old_tree: &Tree,
+    new_tree: &Tree,
+) -> Result<Vec<ChangedFile>> {
+    let mut options = DiffOptions::new();
+    options.context_lines(0);
+    let mut diff = repo.diff_tree_to_tree(Some(old_tree), Some(new_tree), Some(&mut options))?;
+    diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+
+    // Deleted files take their language from the old tree, everything else
+    // from the new one; each side is classified in one pass.
+    let mut sides: [Vec<(PathBuf, Oid, LineDelta)>; 2] = [Vec::new(), Vec::new()];
+    for index in 0..diff.deltas().len() {
+        let Some(delta) = diff.get_delta(index) else {
+            continue;
+        };
+        let (side, file) = match delta.status() {
+            Delta::Deleted => (0, delta.old_file()),
+            _ => (1, delta.new_file()),
+        };
+        let Some(path) = file.path() else {
+            continue;
+        };
+        let Some(patch) = Patch::from_diff(&diff, index)? else {
+            continue;
+        };
+        let lines = hunk_lines(&patch)?;
+        if lines.added + lines.removed + lines.changed > 0 {
+            sides[side].push((path.to_path_buf(), file.id(), lines));
+        }
+    }
+
+    let mut scanner = TreeScanner::new(repo, root);
+    let mut files = Vec::new();
+    for (tree, side) in [old_tree, new_tree].into_iter().zip(sides) {
+        for (path, oid, lines) in side {
+            if let Some(language) = scanner.language(tree, &path, oid)? {
+                files.push(ChangedFile {
+                    path,
+                    language,
+                    lines,
+                });
+            }
+        }
+    }
+    Ok(files)
+}
+
+fn hunk_lines(patch: &Patch) -> Result<LineDelta> {
+    let mut lines = LineDelta::default();
+    for index in 0..patch.num_hunks() {
+        let (hunk, _) = patch.hunk(index)?;
+        let (added, removed) = (hunk.new_lines() as usize, hunk.old_lines() as usize);
+        let changed = added.min(removed);
+        lines.add(LineDelta {
+            added: added - changed,
+            removed: removed - changed,
+            changed,
+        });
+    }
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempRepo;
+    use cli_core::config::Config;
+
+    fn numbered(prefix: &str, count: usize) -> String {
+        (1..=count).map(|n| format!("{prefix}{n}\n")).collect()
+    }
+
+    /// Two commits touching Rust and Python files, a rename with one edited
+    /// line, a deletion and a binary file.
+    fn history() -> TempRepo {
+        let mut repo = TempRepo::new();
+        repo.write("src/lib.rs", "fn a() {}\nfn b() {}\nfn c() {}\nfn d() {}\n");
+        repo.write("app/main.py", "x = 1\ny = 2\nz = 3\n");
+        repo.write("old/name.rs", &numbered("const C: u8 = ", 10));
+        repo.write("gone.py", "a = 1\nb = 2\n");
+        repo.write("data.bin", "\0\u{1}\u{2}binary");
+        repo.commit("first");
+
+        repo.write(
+            "src/lib.rs",
+            "fn a() {}\nfn b2() {}\nfn c() {}\nfn d() {}\nfn e() {}\nfn f() {}\n",
+        );
+        repo.write("app/main.py", "x = 1\nz = 3\n");
+        repo.remove("old/name.rs");
+        repo.write(
+            "new/name.rs",
+            &numbered("const C: u8 = ", 10).replace("= 5\n", "= 50\n"),
+        );
+        repo.write("src/extra.rs", "fn g() {}\nfn h() {}\nfn i() {}\n");
+        repo.remove("gone.py");
+        repo.write("data.bin", "\0\u{3}\u{4}other");
+        repo.commit("second");
+        repo
+    }
+
+    async fn diff(repo: &TempRepo, directories: usize) -> RevisionDiff {
+        let config = Config::default();
+        let analyzer = RepositoryAnalyzer::new(&config.code_cost).with_cache(false);
+        let calculator = CostCalculator::new(&config).unwrap();
+        let scope = DiffScope {
+            depth: 1,
+            directories,
+        };
+        compare(
+            repo.dir.path(),
+            "HEAD~1",
+            "HEAD",
+            &scope,
+            &analyzer,
+            &calculator,
+        )
+        .await
+        .unwrap()
+    }
+
+    fn lines(delta: LineDelta) -> [usize; 3] {
+        [delta.added, delta.removed, delta.changed]
+    }
+
+    #[tokio::test]
+    async fn counts_lines_per_language() {
+        let repo = history();
+        let diff = diff(&repo, DEFAULT_DIRECTORIES).await;
+
+        // lib.rs, main.py, name.rs, extra.rs and gone.py; not data.bin.
+        assert_eq!(diff.files_changed, 5);
+        assert_eq!(lines(diff.lines), [5, 3, 2]);
+        let languages: Vec<(&str, [usize; 3])> = diff
+            .languages
+            .iter()
+            .map(|language| (language.language.as_str(), lines(language.lines)))
+            .collect();
+        assert_eq!(languages, [("Rust", [5, 0, 2]), ("Python", [0, 3, 0])]);
+    }
+
+    #[tokio::test]
+    async fn renamed_files_only_count_their_edits() {
+        let repo = history();
+        let diff = diff(&repo, DEFAULT_DIRECTORIES).await;
+
+        let renamed = diff
+            .directories
+            .iter()
+            .find(|directory| directory.directory == "new")
+            .unwrap();
+        assert_eq!(lines(renamed.lines), [0, 0, 1]);
+        assert!(diff.directories.iter().all(|d| d.directory != "old"));
+    }
+
+    #[tokio::test]
+    async fn binary_files_are_not_counted() {
+        let mut repo = TempRepo::new();
+        repo.write("src/lib.rs", "fn a() {}\n");
+        repo.write("image.bin", "\0\u{1}");
+        repo.commit("first");
+        repo.write("image.bin", "\0\u{2}\u{3}");
+        repo.commit("binary only");
+
+        let diff = diff(&repo, DEFAULT_DIRECTORIES).await;
+        assert_eq!(diff.files_changed, 0);
+        assert_eq!(lines(diff.lines), [0, 0, 0]);
+        assert!(diff.directories.is_empty());
+    }
+
+    #[tokio::test]
+    async fn ranks_directories_by_lines_touched_then_removed() {
+        let repo = history();
+        let diff = diff(&repo, 3).await;
+
+        let ranked: Vec<(&str, [usize; 3])> = diff
+            .directories
+            .iter()
+            .map(|directory| (directory.directory.as_str(), lines(directory.lines)))
+            .collect();
+        // app (one line removed) is cut off by the limit.
+        assert_eq!(
+            ranked,
+            [("src", [5, 0, 1]), ("new", [0, 0, 1]), (".", [0, 2, 0])]
+        );
+
+        let src = &diff.directories[0];
+        assert!((src.share - 6.0 / 7.0).abs() < 1e-9);
+        assert!((src.cost - diff.cost * src.share).abs() < 1e-6);
+        assert!((src.hours - diff.hours * src.share).abs() < 1e-9);
+        assert_eq!(diff.directories[2].share, 0.0);
+    }
+}
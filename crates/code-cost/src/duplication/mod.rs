@@ -0,0 +1,379 @@
+//! Copy-paste detection over normalised tokens.
+//!
+//! Each file's code, with comments and literals already blanked out by the
+//! lexer, becomes a stream of tokens in which all numbers look alike. Every
+//! run of `KGRAM` tokens gets a rolling hash, and winnowing keeps the smallest
+//! of each `WINDOW` consecutive hashes as a fingerprint. That is enough to
+//! find every match of `MIN_TOKENS` tokens while caching only a few hashes per
+//! file. Fingerprints two places share at a steady token offset are merged
+//! into duplicated blocks.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Tokens hashed together.
+const KGRAM: usize = 25;
+/// Consecutive k-gram hashes that winnowing keeps one of.
+const WINDOW: usize = 26;
+/// The shortest block reported; every match this long shares a fingerprint.
+pub const MIN_TOKENS: usize = KGRAM + WINDOW - 1;
+/// Fingerprints found in more places than this are boilerplate rather than
+/// copies, and pairing them all would be quadratic.
+const MAX_OCCURRENCES: usize = 64;
+/// Multiplier of the polynomial rolling hash.
+const BASE: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Languages that hold data or prose rather than code.
+const DATA_LANGUAGES: [&str; 5] = ["JSON", "Markdown", "TOML", "XML", "YAML"];
+
+/// A winnowed k-gram: its hash, the index of its first token, and the first
+/// and last line (from 1) it spans. Cached with each file's counts.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Fingerprint(u64, u32, u32, u32);
+
+/// Whether files in `lang` are searched for copies.
+pub fn supports(lang: &str) -> bool {
+    !DATA_LANGUAGES.contains(&lang)
+}
+
+/// Fingerprints of `lines`, which must already have comments and literals blanked out.
+pub fn fingerprint(lines: &[String]) -> Vec<Fingerprint> {
+    let tokens = tokenize(lines);
+    if tokens.len() < KGRAM {
+        return Vec::new();
+    }
+
+    let high = BASE.wrapping_pow(KGRAM as u32 - 1);
+    let mut hash = 0u64;
+    let mut kgrams = Vec::with_capacity(tokens.len() - KGRAM + 1);
+    for (i, &(token, _)) in tokens.iter().enumerate() {
+        if i >= KGRAM {
+            hash = hash.wrapping_sub(tokens[i - KGRAM].0.wrapping_mul(high));
+        }
+        hash = hash.wrapping_mul(BASE).wrapping_add(token);
+        if i + 1 >= KGRAM {
+            kgrams.push(hash);
+        }
+    }
+
+    winnow(&kgrams)
+        .into_iter()
+        .map(|start| {
+            Fingerprint(
+                kgrams[start],
+                start as u32,
+                tokens[start].1,
+                tokens[start + KGRAM - 1].1,
+            )
+        })
+        .collect()
+}
+
+/// Token hashes with their line numbers. Words and numbers are one token
+/// each, and every other visible character is a token of its own.
+fn tokenize(lines: &[String]) -> Vec<(u64, u32)> {
+    let mut tokens = Vec::new();
+    for (number, line) in lines.iter().enumerate() {
+        let line_number = number as u32 + 1;
+        let mut chars = line.char_indices().peekable();
+        while let Some((start, c)) = chars.next() {
+            if c.is_whitespace() {
+                continue;
+            }
+            let mut end = start + c.len_utf8();
+            if is_word(c) {
+                while let Some(&(i, next)) = chars.peek() {
+                    if !is_word(next) {
+                        break;
+                    }
+                    end = i + next.len_utf8();
+                    chars.next();
+                }
+            }
+            // Copies often differ only in their constants.
+            let text = if c.is_ascii_digit() {
+                "0"
+            } else {
+                &line[start..end]
+            };
+            tokens.push((hash(text), line_number));
+        }
+    }
+    tokens
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// FNV-1a, which unlike `DefaultHasher` is stable across Rust releases, as
+/// cached fingerprints need.
+fn hash(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Positions of the rightmost smallest hash in every window of `WINDOW`.
+fn winnow(hashes: &[u64]) -> Vec<usize> {
+    let mut selected: Vec<usize> = Vec::new();
+    let windows = hashes.len().saturating_sub(WINDOW) + 1;
+    for start in 0..windows {
+        let end = (start + WINDOW).min(hashes.len());
+        let Some(pick) = (start..end).rev().min_by_key(|&i| hashes[i]) else {
+            continue;
+        };
+        if selected.last() != Some(&pick) {
+            selected.push(pick);
+        }
+    }
+    selected
+}
+
+/// Duplicated code among the analysed files.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Duplication {
+    /// Lines inside blocks that repeat code from a file earlier in path
+    /// order, or from earlier in the same file; the first copy is not counted.
+    pub duplicated_lines: usize,
+    /// Lines of every file searched.
+    pub total_lines: usize,
+    /// Largest first.
+    pub blocks: Vec<Block>,
+}
+
+impl Duplication {
+    /// Share of the lines that are duplicated.
+    pub fn share(&self) -> f64 {
+        self.duplicated_lines as f64 / self.total_lines.max(1) as f64
+    }
+}
+
+/// A run of at least `MIN_TOKENS` tokens found in two places.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Block {
+    pub tokens: usize,
+    /// Lines of the copy.
+    pub lines: usize,
+    pub original: Location,
+    pub copy: Location,
+}
+
+/// Lines from 1, inclusive, in a file relative to the repository root.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Location {
+    pub path: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}-{}", self.path, self.start, self.end)
+    }
+}
+
+/// Collects fingerprints file by file, then pairs them up.
+#[derive(Default)]
+pub struct Index {
+    files: Vec<(String, Vec<Fingerprint>)>,
+    lines: usize,
+}
+
+impl Index {
+    /// Add a file of `lines` lines; files without fingerprints still count
+    /// toward the total.
+    pub fn add(&mut self, path: String, lines: usize, fingerprints: Vec<Fingerprint>) {
+        self.lines += lines;
+        if !fingerprints.is_empty() {
+            self.files.push((path, fingerprints));
+        }
+    }
+
+    pub fn finish(mut self) -> Duplication {
+        self.files.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut places: HashMap<u64, Vec<(usize, usize)>> = HashMap::new();
+        for (file, (_, fingerprints)) in self.files.iter().enumerate() {
+            for (index, fingerprint) in fingerprints.iter().enumerate() {
+                places.entry(fingerprint.0).or_default().push((file, index));
+            }
+        }
+
+        // Shared fingerprints by the pair of files and the token offset
+        // between them; the first of each pair comes first in path order.
+        let token = |(file, index): (usize, usize)| i64::from(self.files[file].1[index].1);
+        let mut matches: HashMap<(usize, usize, i64), Vec<(usize, usize)>> = HashMap::new();
+        for places in places.values() {
+            if !(2..=MAX_OCCURRENCES).contains(&places.len()) {
+                continue;
+            }
+            for (i, &a) in places.iter().enumerate() {
+                for &b in &places[i + 1..] {
+                    let offset = token(b) - token(a);
+                    matches
+                        .entry((a.0, b.0, offset))
+                        .or_default()
+                        .push((a.1, b.1));
+                }
+            }
+        }
+
+        let mut blocks = Vec::new();
+        let mut copied: Vec<Vec<(usize, usize)>> = vec![Vec::new(); self.files.len()];
+        for ((file_a, file_b, offset), mut pairs) in matches {
+            pairs.sort_unstable();
+            let token = |index: usize| self.files[file_a].1[index].1;
+            // Winnowing picks a fingerprint in every window, so the ones of
+            // a single block are never further apart than that.
+            for run in pairs.chunk_by(|x, y| token(y.0) - token(x.0) <= WINDOW as u32) {
+                let (first, last) = (run[0], run[run.len() - 1]);
+                let tokens = (token(last.0) - token(first.0)) as usize + KGRAM;
+                let overlaps = file_a == file_b && offset < tokens as i64;
+                if tokens < MIN_TOKENS || overlaps {
+                    continue;
+                }
+                let copy = self.location(file_b, first.1, last.1);
+                copied[file_b].push((copy.start, copy.end));
+                blocks.push(Block {
+                    tokens,
+                    lines: copy.end - copy.start + 1,
+                    original: self.location(file_a, first.0, last.0),
+                    copy,
+                });
+            }
+        }
+
+        blocks.sort_by(|a, b| {
+            b.lines
+                .cmp(&a.lines)
+                .then_with(|| a.copy.path.cmp(&b.copy.path))
+                .then_with(|| a.copy.start.cmp(&b.copy.start))
+                .then_with(|| a.original.path.cmp(&b.original.path))
+                .then_with(|| a.original.start.cmp(&b.original.start))
+        });
+        Duplication {
+            duplicated_lines: copied.into_iter().map(covered_lines).sum(),
+            total_lines: self.lines,
+            blocks,
+        }
+    }
+
+    /// Lines of `file` from its `first` fingerprint to its `last`.
+    fn location(&self, file: usize, first: usize, last: usize) -> Location {
+        let (path, fingerprints) = &self.files[file];
+        Location {
+            path: path.clone(),
+            start: fingerprints[first].2 as usize,
+            end: fingerprints[last].3 as usize,
+        }
+    }
+}
+
+/// Lines inside any of the inclusive `ranges`.
+fn covered_lines(mut ranges: Vec<(usize, usize)>) -> usize {
+    ranges.sort_unstable();
+    let mut lines = 0;
+    let mut next = 0;
+    for (start, end) in ranges {
+        let start = start.max(next);
+        if end >= start {
+            lines += end - start + 1;
+            next = end + 1;
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `count` lines of twelve tokens each, with names unique to `prefix`.
+    fn code(prefix: &str, count: usize) -> Vec<String> {
+        (0..count)
+            .map(|i| format!("let {prefix}_value_{i} = {prefix}_compute({prefix}_input, {i}) + {prefix}_offset;"))
+            .collect()
+    }
+
+    fn duplication(files: &[(&str, Vec<String>)]) -> Duplication {
+        let mut index = Index::default();
+        for (path, lines) in files {
+            index.add(path.to_string(), lines.len(), fingerprint(lines));
+        }
+        index.finish()
+    }
+
+    #[test]
+    fn identical_blocks_in_two_files_are_found() {
+        let a = [code("alpha", 5), code("shared", 10)].concat();
+        let b = [code("beta", 8), code("shared", 10)].concat();
+        let duplication = duplication(&[("b.rs", b), ("a.rs", a)]);
+
+        assert_eq!(duplication.blocks.len(), 1);
+        let block = &duplication.blocks[0];
+        assert!(block.tokens >= MIN_TOKENS);
+        assert_eq!(block.original.path, "a.rs");
+        assert!(block.original.start >= 6 && block.original.end <= 15);
+        assert_eq!(block.copy.path, "b.rs");
+        assert!(block.copy.start >= 9 && block.copy.end <= 18);
+        assert_eq!(duplication.duplicated_lines, block.lines);
+        assert_eq!(duplication.total_lines, 33);
+    }
+
+    #[test]
+    fn blocks_below_the_threshold_are_ignored() {
+        // Three lines are 36 tokens, enough to share a fingerprint but
+        // short of `MIN_TOKENS`.
+        let a = [code("alpha", 5), code("shared", 3), code("gamma", 5)].concat();
+        let b = [code("beta", 5), code("shared", 3), code("delta", 5)].concat();
+        let duplication = duplication(&[("a.rs", a), ("b.rs", b)]);
+
+        assert!(duplication.blocks.is_empty());
+        assert_eq!(duplication.duplicated_lines, 0);
+        assert_eq!(duplication.share(), 0.0);
+    }
+
+    #[test]
+    fn whitespace_differences_do_not_hide_a_copy() {
+        let a = [code("alpha", 5), code("shared", 10)].concat();
+        let reformatted: Vec<String> = code("shared", 10)
+            .chunks(2)
+            .map(|pair| format!("        {}   {}", pair[0], pair[1]).replace(", ", " ,"))
+            .collect();
+        let b = [code("beta", 5), reformatted].concat();
+        let duplication = duplication(&[("a.rs", a), ("b.rs", b)]);
+
+        assert_eq!(duplication.blocks.len(), 1);
+        let block = &duplication.blocks[0];
+        assert_eq!(block.copy.path, "b.rs");
+        assert!(block.copy.start >= 6 && block.copy.end <= 10);
+    }
+
+    #[test]
+    fn a_block_is_not_its_own_copy() {
+        let duplication = duplication(&[("a.rs", code("alpha", 20))]);
+        assert!(duplication.blocks.is_empty());
+    }
+
+    #[test]
+    fn share_is_the_duplicated_part_of_all_lines() {
+        let duplication = Duplication {
+            duplicated_lines: 25,
+            total_lines: 200,
+            blocks: Vec::new(),
+        };
+        assert_eq!(duplication.share(), 0.125);
+        assert_eq!(Duplication::default().share(), 0.0);
+    }
+
+    #[test]
+    fn covered_lines_counts_overlaps_once() {
+        assert_eq!(
+            covered_lines(vec![(10, 20), (1, 5), (15, 25), (25, 25)]),
+            21
+        );
+        assert_eq!(covered_lines(Vec::new()), 0);
+    }
+}
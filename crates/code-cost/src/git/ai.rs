@@ -0,0 +1,500 @@
+//! Evidence of AI assistance in commit metadata.
+//!
+//! A commit is attributed to a tool when a `Co-Authored-By` trailer or the
+//! author is one of the tool's accounts, or when the message carries a footer
+//! the tool writes. Accounts are recognised by their addresses and exact
+//! names only: Claude, Jules or Devin may just as well be a colleague. Agent
+//! instruction files and unusually large commits are circumstantial and only
+//! listed.
+
+use git2::{Commit, Delta, DiffOptions, Patch, Repository, Tree};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::path::Path;
+
+/// Non-merge commits adding at least this many lines are listed.
+const LARGE_COMMIT_LINES: usize = 1000;
+
+/// Addresses tools sign commits and trailers with.
+const TOOL_EMAILS: [(&str, &str); 5] = [
+    ("noreply@anthropic.com", "Claude"),
+    ("cursoragent@cursor.com", "Cursor"),
+    ("noreply@aider.chat", "Aider"),
+    ("noreply@openai.com", "OpenAI Codex"),
+    ("codex@openai.com", "OpenAI Codex"),
+];
+
+/// GitHub accounts of agents, from `{id}+{login}@users.noreply.github.com`.
+const GITHUB_LOGINS: [(&str, &str); 9] = [
+    ("claude[bot]", "Claude"),
+    ("copilot", "GitHub Copilot"),
+    ("copilot-swe-agent[bot]", "GitHub Copilot"),
+    ("cursor[bot]", "Cursor"),
+    ("chatgpt-codex-connector[bot]", "OpenAI Codex"),
+    ("gemini-code-assist[bot]", "Gemini"),
+    ("google-labs-jules[bot]", "Jules"),
+    ("devin-ai-integration[bot]", "Devin"),
+    ("amazon-q-developer[bot]", "Amazon Q"),
+];
+
+/// Names tools sign with, compared whole and ignoring case.
+const TOOL_NAMES: [(&str, &str); 8] = [
+    ("Claude Code", "Claude"),
+    ("GitHub Copilot", "GitHub Copilot"),
+    ("Copilot", "GitHub Copilot"),
+    ("Cursor Agent", "Cursor"),
+    ("ChatGPT", "ChatGPT"),
+    ("OpenAI Codex", "OpenAI Codex"),
+    ("Gemini Code Assist", "Gemini"),
+    ("Devin AI", "Devin"),
+];
+
+/// Domain of the addresses GitHub gives accounts that hide their own.
+const GITHUB_NOREPLY: &str = "@users.noreply.github.com";
+
+/// Lines tools append to the messages they write.
+const MESSAGE_MARKERS: [(&str, &str); 4] = [
+    ("generated with [claude code]", "Claude Code"),
+    ("claude.ai/code", "Claude Code"),
+    ("generated with copilot", "GitHub Copilot"),
+    ("🤖 generated with", "AI tool"),
+];
+
+/// Files that instruct coding agents, matched by name anywhere in the tree.
+const INSTRUCTION_FILES: [&str; 7] = [
+    "CLAUDE.md",
+    "GEMINI.md",
+    "AGENTS.md",
+    ".cursorrules",
+    ".windsurfrules",
+    ".clinerules",
+    "copilot-instructions.md",
+];
+
+/// Lockfiles are regenerated wholesale and would drown out real insertions.
+const LOCKFILES: [&str; 8] = [
+    "Cargo.lock",
+    "package-lock.json",
+    "pnpm-lock.yaml",
+    "yarn.lock",
+    "go.sum",
+    "poetry.lock",
+    "Gemfile.lock",
+    "composer.lock",
+];
+
+/// A commit some evidence came from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceCommit {
+    pub id: String,
+    pub time: i64,
+    pub summary: String,
+    /// The tool named, when the evidence names one.
+    pub tool: Option<String>,
+    /// The trailer, author or file that matched, or the size of the commit.
+    pub detail: String,
+}
+
+impl EvidenceCommit {
+    fn new(commit: &Commit, tool: Option<&str>, detail: impl Into<String>) -> Self {
+        Self {
+            id: commit.id().to_string(),
+            time: commit.time().seconds(),
+            summary: commit.summary().unwrap_or_default().to_string(),
+            tool: tool.map(String::from),
+            detail: detail.into(),
+        }
+    }
+
+    pub fn short_id(&self) -> &str {
+        &self.id[..self.id.len().min(7)]
+    }
+
+    pub fn date(&self) -> String {
+        chrono::DateTime::from_timestamp(self.time, 0)
+            .map(|time| time.format("%Y-%m-%d").to_string())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignalKind {
+    CoAuthorTrailer,
+    ToolMessage,
+    AgentAuthor,
+    InstructionFile,
+    LargeCommit,
+}
+
+impl SignalKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::CoAuthorTrailer => "Co-Authored-By trailers",
+            Self::ToolMessage => "Tool-generated messages",
+            Self::AgentAuthor => "Commits by coding agents",
+            Self::InstructionFile => "Agent instruction files",
+            Self::LargeCommit => "Large single commits",
+        }
+    }
+}
+
+/// One kind of evidence with the commits it came from, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Signal {
+    pub kind: SignalKind,
+    pub commits: Vec<EvidenceCommit>,
+}
+
+/// Evidence over every commit walked so far; cached with the history.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AiEvidence {
+    /// Lines added by non-merge commits, lockfiles excluded.
+    pub insertions: usize,
+    /// Of those, lines added by commits attributed to a tool.
+    pub attributed_insertions: usize,
+    pub attributed_commits: usize,
+    co_author_trailers: Vec<EvidenceCommit>,
+    tool_messages: Vec<EvidenceCommit>,
+    agent_authors: Vec<EvidenceCommit>,
+    /// The commit that added each instruction file still in the tree.
+    instruction_files: Vec<EvidenceCommit>,
+    large_commits: Vec<EvidenceCommit>,
+}
+
+impl AiEvidence {
+    /// Look for evidence in `commit`. Merges only repeat their parents' changes
+    /// and are skipped.
+    pub(super) fn add(&mut self, repo: &Repository, commit: &Commit) {
+        if commit.parent_count() > 1 {
+            return;
+        }
+        let message = commit.message().unwrap_or_default();
+        let author = commit.author();
+        let (name, email) = (
+            author.name().unwrap_or_default(),
+            author.email().unwrap_or_default(),
+        );
+
+        let mut attributed = false;
+        for trailer in co_author_trailers(message) {
+            let (name, email) = split_identity(trailer);
+            if let Some(tool) = identify(name, email) {
+                self.co_author_trailers
+                    .push(EvidenceCommit::new(commit, Some(tool), trailer));
+                attributed = true;
+            }
+        }
+        if let Some(line) = message
+            .lines()
+            .find(|line| find(&MESSAGE_MARKERS, line).is_some())
+        {
+            let tool = find(&MESSAGE_MARKERS, line);
+            self.tool_messages
+                .push(EvidenceCommit::new(commit, tool, line.trim()));
+            attributed = true;
+        }
+        if let Some(tool) = identify(name, email) {
+            self.agent_authors.push(EvidenceCommit::new(
+                commit,
+                Some(tool),
+                format!("{name} <{email}>"),
+            ));
+            attributed = true;
+        }
+
+        let Some((insertions, files, added)) = changes(repo, commit) else {
+            return;
+        };
+        self.insertions += insertions;
+        if attributed {
+            self.attributed_insertions += insertions;
+            self.attributed_commits += 1;
+        }
+        for path in added {
+            self.instruction_files
+                .push(EvidenceCommit::new(commit, None, path));
+        }
+        // A root commit is usually an import of code written elsewhere.
+        if insertions >= LARGE_COMMIT_LINES && commit.parent_count() == 1 {
+            self.large_commits.push(EvidenceCommit::new(
+                commit,
+                None,
+                format!("+{insertions} lines in {files} files"),
+            ));
+        }
+    }
+
+    /// Keep the earliest addition of each instruction file `tree` still has.
+    pub(super) fn retain_instruction_files(&mut self, tree: &Tree) {
+        self.instruction_files.sort_by_key(|file| file.time);
+        let mut seen = Vec::new();
+        self.instruction_files.retain(|file| {
+            let keep =
+                !seen.contains(&file.detail) && tree.get_path(Path::new(&file.detail)).is_ok();
+            seen.push(file.detail.clone());
+            keep
+        });
+    }
+
+    /// Every signal with evidence, attributing ones first, commits newest first.
+    pub fn signals(&self) -> Vec<Signal> {
+        [
+            (SignalKind::CoAuthorTrailer, &self.co_author_trailers),
+            (SignalKind::ToolMessage, &self.tool_messages),
+            (SignalKind::AgentAuthor, &self.agent_authors),
+            (SignalKind::InstructionFile, &self.instruction_files),
+            (SignalKind::LargeCommit, &self.large_commits),
+        ]
+        .into_iter()
+        .filter(|(_, commits)| !commits.is_empty())
+        .map(|(kind, commits)| {
+            let mut commits = commits.clone();
+            commits.sort_by_key(|commit| Reverse(commit.time));
+            Signal { kind, commits }
+        })
+        .collect()
+    }
+
+    /// Share of inserted lines from attributed commits.
+    pub fn attributed_share(&self) -> f64 {
+        self.attributed_insertions as f64 / self.insertions.max(1) as f64
+    }
+}
+
+/// Values of the `Co-Authored-By` trailers in `message`.
+fn co_author_trailers(message: &str) -> impl Iterator<Item = &str> {
+    message.lines().filter_map(|line| {
+        let (key, value) = line.trim().split_once(':')?;
+        key.eq_ignore_ascii_case("co-authored-by")
+            .then(|| value.trim())
+    })
+}
+
+/// Name and email of a `Name <email>` identity; the email is empty when there
+/// are no angle brackets.
+fn split_identity(identity: &str) -> (&str, &str) {
+    match identity.split_once('<') {
+        Some((name, rest)) => (name.trim(), rest.trim_end().trim_end_matches('>').trim()),
+        None => (identity.trim(), ""),
+    }
+}
+
+/// The tool whose account `name <email>` is. Aider marks the commits it makes
+/// by appending "(aider)" to the author's name.
+fn identify(name: &str, email: &str) -> Option<&'static str> {
+    let email = email.to_lowercase();
+    let login = email
+        .strip_suffix(GITHUB_NOREPLY)
+        .map(|local| local.split_once('+').map_or(local, |(_, login)| login));
+    let lookup = |table: &[(&str, &'static str)], key: &str| {
+        table
+            .iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(key))
+            .map(|&(_, tool)| tool)
+    };
+    lookup(&TOOL_EMAILS, &email)
+        .or_else(|| lookup(&GITHUB_LOGINS, login?))
+        .or_else(|| lookup(&TOOL_NAMES, name))
+        .or_else(|| name.ends_with("(aider)").then_some("Aider"))
+}
+
+/// The tool of the first pattern found in `text`, ignoring case.
+fn find(patterns: &[(&str, &'static str)], text: &str) -> Option<&'static str> {
+    let text = text.to_lowercase();
+    patterns
+        .iter()
+        .find(|(pattern, _)| text.contains(pattern))
+        .map(|&(_, tool)| tool)
+}
+
+/// Lines added by `commit` and files it touched, lockfiles excluded, with the
+/// instruction files it added. `None` when the diff cannot be computed.
+fn changes(repo: &Repository, commit: &Commit) -> Option<(usize, usize, Vec<String>)> {
+    let tree = commit.tree().ok()?;
+    let parent = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree().ok()?),
+        Err(_) => None,
+    };
+    let mut options = DiffOptions::new();
+    options.ignore_submodules(true);
+    let diff = repo
+        .diff_tree_to_tree(parent.as_ref(), Some(&tree), Some(&mut options))
+        .ok()?;
+
+    let mut insertions = 0;
+    let mut files = 0;
+    let mut added = Vec::new();
+    for (index, delta) in diff.deltas().enumerate() {
+        let Some(path) = delta.new_file().path() else {
+            continue;
+        };
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        if delta.status() == Delta::Added
+            && (INSTRUCTION_FILES.contains(&name) || path.starts_with(".cursor/rules"))
+        {
+            added.push(path.to_string_lossy().into_owned());
+        }
+        if LOCKFILES.contains(&name) {
+            continue;
+        }
+        if let Ok(Some(patch)) = Patch::from_diff(&diff, index) {
+            if let Ok((_, additions, _)) = patch.line_stats() {
+                insertions += additions;
+                files += 1;
+            }
+        }
+    }
+    Some((insertions, files, added))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempRepo;
+
+    fn identify_trailer(trailer: &str) -> Option<&'static str> {
+        let (name, email) = split_identity(trailer);
+        identify(name, email)
+    }
+
+    #[test]
+    fn co_author_trailers_are_read_from_any_line() {
+        let message = "Add parser\n\nBody text: not a trailer.\n\n\
+                       Co-authored-by: Jane Doe <jane@example.com>\n\
+                       CO-AUTHORED-BY:Claude <noreply@anthropic.com>\n\
+                       Signed-off-by: Dev <dev@example.com>\n";
+        let trailers: Vec<&str> = co_author_trailers(message).collect();
+        assert_eq!(
+            trailers,
+            [
+                "Jane Doe <jane@example.com>",
+                "Claude <noreply@anthropic.com>"
+            ]
+        );
+    }
+
+    #[test]
+    fn tools_are_identified_by_account() {
+        let cases = [
+            ("Claude <noreply@anthropic.com>", Some("Claude")),
+            ("Claude Opus <NoReply@Anthropic.com>", Some("Claude")),
+            (
+                "Copilot <198982749+Copilot@users.noreply.github.com>",
+                Some("GitHub Copilot"),
+            ),
+            (
+                "google-labs-jules[bot] <161369871+google-labs-jules[bot]@users.noreply.github.com>",
+                Some("Jules"),
+            ),
+            ("aider (gpt-4o) <noreply@aider.chat>", Some("Aider")),
+            ("Jane Doe (aider) <jane@example.com>", Some("Aider")),
+            ("Cursor Agent <cursoragent@cursor.com>", Some("Cursor")),
+            ("GitHub Copilot", Some("GitHub Copilot")),
+        ];
+        for (trailer, tool) in cases {
+            assert_eq!(identify_trailer(trailer), tool, "{trailer}");
+        }
+    }
+
+    #[test]
+    fn people_named_like_tools_are_not_attributed() {
+        let people = [
+            "Devin Jules <devin.jules@example.com>",
+            "Claude Monet <claude@example.com>",
+            "Gemini Cursor <gemini@cursor.example>",
+            "Jules <jules@anthropic.com>",
+            "codex-fan <12345+codex-fan@users.noreply.github.com>",
+            "dependabot[bot] <49699333+dependabot[bot]@users.noreply.github.com>",
+            "Ann Copilot <ann@example.com>",
+        ];
+        for person in people {
+            assert_eq!(identify_trailer(person), None, "{person}");
+        }
+    }
+
+    #[test]
+    fn message_markers_ignore_case() {
+        assert_eq!(
+            find(
+                &MESSAGE_MARKERS,
+                "🤖 Generated with [Claude Code](https://claude.ai/code)"
+            ),
+            Some("Claude Code")
+        );
+        assert_eq!(
+            find(&MESSAGE_MARKERS, "Generated with Copilot"),
+            Some("GitHub Copilot")
+        );
+        assert_eq!(find(&MESSAGE_MARKERS, "Generated the parser tables"), None);
+    }
+
+    #[test]
+    fn only_tool_trailers_attribute_insertions() {
+        let mut repo = TempRepo::new();
+        repo.write("README.md", "start\n");
+        repo.commit("Initial commit");
+        repo.write("a.txt", "1\n2\n3\n");
+        repo.commit("Add a\n\nCo-authored-by: Devin Jules <devin.jules@example.com>\n");
+        repo.write("b.txt", "1\n2\n");
+        repo.commit("Add b\n\nCo-Authored-By: Claude <noreply@anthropic.com>\n");
+
+        let mut evidence = AiEvidence::default();
+        let mut walk = repo.repo.revwalk().unwrap();
+        walk.push_head().unwrap();
+        for oid in walk {
+            let commit = repo.repo.find_commit(oid.unwrap()).unwrap();
+            evidence.add(&repo.repo, &commit);
+        }
+
+        assert_eq!(evidence.insertions, 6);
+        assert_eq!(evidence.attributed_insertions, 2);
+        assert_eq!(evidence.attributed_commits, 1);
+        let signals = evidence.signals();
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].kind, SignalKind::CoAuthorTrailer);
+        assert_eq!(signals[0].commits[0].tool.as_deref(), Some("Claude"));
+    }
+
+    #[test]
+    fn instruction_files_keep_the_first_addition_still_in_the_tree() {
+        let mut repo = TempRepo::new();
+        repo.write("CLAUDE.md", "v1\n");
+        repo.write("docs/AGENTS.md", "v1\n");
+        repo.commit("Add agent files");
+        repo.remove("CLAUDE.md");
+        repo.commit("Drop CLAUDE.md");
+        repo.write("CLAUDE.md", "v2\n");
+        repo.write(".cursorrules", "rules\n");
+        repo.commit("Restore CLAUDE.md and add .cursorrules");
+        repo.remove(".cursorrules");
+        repo.commit("Drop .cursorrules");
+
+        let mut evidence = AiEvidence::default();
+        let mut walk = repo.repo.revwalk().unwrap();
+        walk.push_head().unwrap();
+        for oid in walk {
+            let commit = repo.repo.find_commit(oid.unwrap()).unwrap();
+            evidence.add(&repo.repo, &commit);
+        }
+        let tree = repo.repo.head().unwrap().peel_to_tree().unwrap();
+        evidence.retain_instruction_files(&tree);
+
+        let mut kept: Vec<(String, String)> = evidence
+            .instruction_files
+            .iter()
+            .map(|file| (file.detail.clone(), file.summary.clone()))
+            .collect();
+        kept.sort();
+        assert_eq!(
+            kept,
+            [
+                ("CLAUDE.md".to_string(), "Add agent files".to_string()),
+                ("docs/AGENTS.md".to_string(), "Add agent files".to_string()),
+            ]
+        );
+    }
+}
@@ -1,13 +1,63 @@
 use anyhow::Result;
-use git2::Repository;
-use std::collections::HashSet;
+use git2::{Oid, Repository};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 use std::path::Path;
 
+use crate::cache::Cache;
+
+pub use ai::{AiEvidence, Signal};
+
+mod ai;
+
 #[derive(Debug, Clone)]
 pub struct GitStats {
     pub commit_count: usize,
     pub contributor_count: usize,
     pub age_in_days: i64,
+    pub ai: AiEvidence,
+}
+
+/// Running totals over every commit reachable from `head`; cached by HEAD id.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct History {
+    head: String,
+    commit_count: usize,
+    contributors: BTreeSet<String>,
+    first_commit_time: Option<i64>,
+    last_commit_time: Option<i64>,
+    ai: AiEvidence,
+}
+
+impl History {
+    fn add(&mut self, repo: &Repository, commit: &git2::Commit) {
+        self.commit_count += 1;
+        self.ai.add(repo, commit);
+
+        if let Some(author) = commit.author().email() {
+            self.contributors.insert(author.to_string());
+        }
+
+        let commit_time = commit.time().seconds();
+        self.first_commit_time = Some(self.first_commit_time.map_or(commit_time, |t| t.min(commit_time)));
+        self.last_commit_time = Some(self.last_commit_time.map_or(commit_time, |t| t.max(commit_time)));
+    }
+
+    pub fn stats(&self) -> GitStats {
+        let age_in_days = if let (Some(first), Some(last)) = (self.first_commit_time, self.last_commit_time)
+        {
+            ((last - first) / 86400).max(0)
+        } else {
+            0
+        };
+
+        GitStats {
+            commit_count: self.commit_count,
+            contributor_count: self.contributors.len(),
+            age_in_days,
+            ai: self.ai.clone(),
+        }
+    }
 }
 
 pub struct GitAnalyzer;
@@ -17,50 +67,64 @@ impl GitAnalyzer {
         Self
     }
 
-    pub fn analyze(&self, path: &Path) -> Result<GitStats> {
-        let repo = Repository::open(path)?;
-
-        let mut revwalk = repo.revwalk()?;
-        revwalk.push_head()?;
-
-        let mut commit_count = 0;
-        let mut contributors = HashSet::new();
-        let mut first_commit_time: Option<i64> = None;
-        let mut last_commit_time: Option<i64> = None;
-
-        for oid in revwalk {
-            let oid = oid?;
-            let commit = repo.find_commit(oid)?;
+    /// Walk history from HEAD of the repository `path` is in, starting from the
+    /// cached totals when HEAD only moved forward.
+    pub fn analyze(&self, path: &Path, cache: &mut Cache) -> Result<GitStats> {
+        let repo = Repository::discover(path)?;
+        let head = repo.head()?.peel_to_commit()?.id();
+        self.analyze_commit(&repo, head, cache)
+    }
 
-            commit_count += 1;
+    /// Totals over every commit reachable from `commit`, continuing from the
+    /// cached history when it ends at an ancestor.
+    pub fn analyze_commit(
+        &self,
+        repo: &Repository,
+        commit: Oid,
+        cache: &mut Cache,
+    ) -> Result<GitStats> {
+        let history = self.history_at(repo, commit, cache.history())?;
+        let stats = history.stats();
+        cache.set_history(history);
+        Ok(stats)
+    }
 
-            if let Some(author) = commit.author().email() {
-                contributors.insert(author.to_string());
-            }
+    /// Totals over every commit reachable from `commit`, walking only the
+    /// commits `previous` does not cover when it ends at an ancestor.
+    pub fn history_at(
+        &self,
+        repo: &Repository,
+        commit: Oid,
+        previous: Option<&History>,
+    ) -> Result<History> {
+        let previous = previous
+            .and_then(|history| Some((history, Oid::from_str(&history.head).ok()?)))
+            .filter(|(_, previous_head)| {
+                *previous_head == commit
+                    || repo.graph_descendant_of(commit, *previous_head).unwrap_or(false)
+            });
 
-            let commit_time = commit.time().seconds();
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(commit)?;
 
-            if first_commit_time.is_none() || commit_time < first_commit_time.unwrap() {
-                first_commit_time = Some(commit_time);
+        let mut history = match previous {
+            Some((history, previous_head)) => {
+                revwalk.hide(previous_head)?;
+                history.clone()
             }
+            None => History::default(),
+        };
 
-            if last_commit_time.is_none() || commit_time > last_commit_time.unwrap() {
-                last_commit_time = Some(commit_time);
-            }
+        for oid in revwalk {
+            let commit = repo.find_commit(oid?)?;
+            history.add(repo, &commit);
         }
 
-        let age_in_days = if let (Some(first), Some(last)) = (first_commit_time, last_commit_time)
-        {
-            ((last - first) / 86400).max(0)
-        } else {
-            0
-        };
-
-        Ok(GitStats {
-            commit_count,
-            contributor_count: contributors.len(),
-            age_in_days,
-        })
+        history
+            .ai
+            .retain_instruction_files(&repo.find_commit(commit)?.tree()?);
+        history.head = commit.to_string();
+        Ok(history)
     }
 }
 
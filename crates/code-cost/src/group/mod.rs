@@ -0,0 +1,433 @@
+//! Monorepo breakdown (`--by`): one valuation per directory or workspace
+//! member, with a subtotal for every directory above them. Each group is
+//! estimated on its own, then given its pro rata share of the subtotal above
+//! it so that the shares add up.
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::analyzer::{Analysis, RepositoryAnalyzer};
+use crate::calculator::{CostCalculator, CostEstimate};
+use crate::get_repository_name;
+use workspace::Member;
+
+pub use workspace::Ecosystem;
+
+mod workspace;
+
+/// Path components that name a group for a bare `--by dir`.
+pub const DEFAULT_DEPTH: usize = 1;
+
+/// How `--by` splits a repository.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Grouping {
+    /// The first `n` directories of every path
+    Directory(usize),
+    /// Cargo, npm and pnpm workspace members and Go modules
+    Workspace,
+}
+
+impl FromStr for Grouping {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "dir" => Ok(Self::Directory(DEFAULT_DEPTH)),
+            None if s == "workspace" => Ok(Self::Workspace),
+            Some(("dir", depth)) => match depth.parse() {
+                Ok(depth) if depth > 0 => Ok(Self::Directory(depth)),
+                _ => Err(format!(
+                    "invalid depth '{depth}': expected a positive number"
+                )),
+            },
+            _ => Err(format!("expected dir, dir:<depth> or workspace, got '{s}'")),
+        }
+    }
+}
+
+impl fmt::Display for Grouping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Directory(depth) => write!(f, "dir:{depth}"),
+            Self::Workspace => f.write_str("workspace"),
+        }
+    }
+}
+
+/// One repository valued per group.
+#[derive(Debug, Clone, Serialize)]
+pub struct Breakdown {
+    pub path: PathBuf,
+    pub grouping: String,
+    /// The total first, then every subtotal followed by the groups below it.
+    pub groups: Vec<Group>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupKind {
+    Total,
+    Subtotal,
+    Group,
+}
+
+/// A group or subtotal, analysed and estimated from its own files alone.
+#[derive(Debug, Clone, Serialize)]
+pub struct Group {
+    /// Directory relative to the repository root. "." is both the total and
+    /// the group of files that belong to no other group.
+    pub path: String,
+    pub kind: GroupKind,
+    /// Levels below the total.
+    pub depth: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub ecosystems: Vec<Ecosystem>,
+    /// Name drawn as a tree, e.g. "│  └─ code-cost".
+    #[serde(skip)]
+    pub label: String,
+    pub metrics: Analysis,
+    /// Standalone estimate, as if the group were a repository of its own.
+    pub cost: CostEstimate,
+    /// Part of the total this group accounts for.
+    pub share: Share,
+}
+
+/// A group's part of the subtotal above it, split in proportion to the
+/// standalone estimates of the groups under that subtotal.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Share {
+    pub estimated_hours: f64,
+    pub total_cost: f64,
+}
+
+impl Group {
+    /// The path, with the files outside every other group called "(root)".
+    pub fn name(&self) -> &str {
+        match (self.kind, self.path.as_str()) {
+            (GroupKind::Group, ".") => "(root)",
+            (_, path) => path,
+        }
+    }
+}
+
+/// Value the repository at `path` as a whole and per group.
+pub async fn breakdown(
+    path: &Path,
+    grouping: Grouping,
+    analyzer: &RepositoryAnalyzer,
+    calculator: &CostCalculator,
+) -> Result<Breakdown> {
+    let members = match grouping {
+        Grouping::Directory(_) => Vec::new(),
+        Grouping::Workspace => workspace::members(path)?,
+    };
+    if grouping == Grouping::Workspace && members.is_empty() {
+        bail!("No Cargo, npm or pnpm workspace or Go module found; try --by dir");
+    }
+
+    let analyses = analyzer
+        .analyze_groups(path, |relative| {
+            let group = match grouping {
+                Grouping::Directory(depth) => directory(relative, depth),
+                Grouping::Workspace => member_of(&members, relative),
+            };
+            // A file counts toward its group and every subtotal above it.
+            (0..=group.len()).map(|len| group[..len].to_vec()).collect()
+        })
+        .await?;
+
+    let keys: Vec<&[String]> = analyses.keys().map(Vec::as_slice).collect();
+    let labels = tree_labels(&keys, &get_repository_name(&path.to_path_buf()));
+    let kinds: Vec<GroupKind> = keys
+        .iter()
+        .enumerate()
+        .map(|(index, key)| match keys.get(index + 1) {
+            _ if key.is_empty() => GroupKind::Total,
+            Some(next) if next.starts_with(key) => GroupKind::Subtotal,
+            _ => GroupKind::Group,
+        })
+        .collect();
+
+    let mut groups: Vec<Group> = analyses
+        .into_iter()
+        .zip(labels.into_iter().zip(kinds))
+        .map(|((key, metrics), (label, kind))| Group {
+            path: match key.is_empty() {
+                true => ".".to_string(),
+                false => key.join("/"),
+            },
+            kind,
+            depth: key.len(),
+            ecosystems: members
+                .iter()
+                .find(|member| member.path == key)
+                .map(|member| member.ecosystems.clone())
+                .unwrap_or_default(),
+            label,
+            cost: calculator.calculate(&metrics),
+            share: Share {
+                estimated_hours: 0.0,
+                total_cost: 0.0,
+            },
+            metrics,
+        })
+        .collect();
+
+    let nodes = |value: fn(&CostEstimate) -> f64| -> Vec<Node> {
+        groups
+            .iter()
+            .map(|group| Node {
+                depth: group.depth,
+                files: group.metrics.total_files,
+                value: value(&group.cost),
+            })
+            .collect()
+    };
+    let hours = shares(&nodes(|cost| cost.estimated_hours));
+    let costs = shares(&nodes(|cost| cost.total_cost));
+    for (group, (estimated_hours, total_cost)) in
+        groups.iter_mut().zip(hours.into_iter().zip(costs))
+    {
+        group.share = Share {
+            estimated_hours,
+            total_cost,
+        };
+    }
+
+    Ok(Breakdown {
+        path: path.to_path_buf(),
+        grouping: grouping.to_string(),
+        groups,
+    })
+}
+
+/// A group as `shares` sees it.
+struct Node {
+    depth: usize,
+    files: usize,
+    /// Standalone estimate.
+    value: f64,
+}
+
+/// Split the total down the tree: every node's share goes to the nodes
+/// directly below it in proportion to their standalone values. `nodes` are in
+/// depth-first order with the total first. A node with files of its own,
+/// outside all its children, keeps what its standalone value exceeds theirs
+/// by, so its children never add up to more than its share.
+fn shares(nodes: &[Node]) -> Vec<f64> {
+    let mut shares = vec![0.0; nodes.len()];
+    for (index, node) in nodes.iter().enumerate() {
+        if node.depth == 0 {
+            shares[index] = node.value;
+        }
+        let children: Vec<usize> = nodes[index + 1..]
+            .iter()
+            .take_while(|next| next.depth > node.depth)
+            .enumerate()
+            .filter(|(_, next)| next.depth == node.depth + 1)
+            .map(|(offset, _)| index + 1 + offset)
+            .collect();
+        let files: usize = children.iter().map(|&child| nodes[child].files).sum();
+        let values: f64 = children.iter().map(|&child| nodes[child].value).sum();
+        let whole = match node.files > files {
+            true => values.max(node.value),
+            false => values,
+        };
+        for child in children {
+            if whole > 0.0 {
+                shares[child] = shares[index] * nodes[child].value / whole;
+            }
+        }
+    }
+    shares
+}
+
+/// The first `depth` directories of `path`; files at the root belong to ".".
+pub fn directory(path: &Path, depth: usize) -> Vec<String> {
+    let parent = path.parent().unwrap_or(Path::new(""));
+    let components: Vec<String> = parent
+        .components()
+        .take(depth.max(1))
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    if components.is_empty() {
+        vec![".".to_string()]
+    } else {
+        components
+    }
+}
+
+/// The innermost member containing `path`, or "." for files outside them all.
+fn member_of(members: &[Member], path: &Path) -> Vec<String> {
+    let parent: Vec<String> = path
+        .parent()
+        .unwrap_or(Path::new(""))
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    members
+        .iter()
+        .filter(|member| parent.starts_with(&member.path))
+        .max_by_key(|member| member.path.len())
+        .map(|member| member.path.clone())
+        .unwrap_or_else(|| vec![".".to_string()])
+}
+
+/// Tree drawing for `keys` in depth-first order, with `root` naming the total.
+fn tree_labels(keys: &[&[String]], root: &str) -> Vec<String> {
+    // A node is the last of its siblings when nothing at its depth follows
+    // before the walk leaves its parent.
+    let mut last: HashMap<&[String], bool> = HashMap::new();
+    for (index, key) in keys.iter().enumerate() {
+        let is_last = keys[index + 1..]
+            .iter()
+            .find(|next| next.len() <= key.len())
+            .is_none_or(|next| next.len() < key.len());
+        last.insert(key, is_last);
+    }
+
+    keys.iter()
+        .map(|key| {
+            let Some(name) = key.last() else {
+                return root.to_string();
+            };
+            let mut label = String::new();
+            for depth in 1..key.len() {
+                label.push_str(match last.get(&key[..depth]) {
+                    Some(true) => "\u{a0}\u{a0}\u{a0}",
+                    _ => "│\u{a0}\u{a0}",
+                });
+            }
+            label.push_str(match last.get(key) {
+                Some(true) => "└─\u{a0}",
+                _ => "├─\u{a0}",
+            });
+            label.push_str(match name.as_str() {
+                "." => "(root)",
+                name => name,
+            });
+            label
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(depth: usize, files: usize, value: f64) -> Node {
+        Node {
+            depth,
+            files,
+            value,
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn parses_groupings() {
+        assert_eq!("dir".parse(), Ok(Grouping::Directory(DEFAULT_DEPTH)));
+        assert_eq!("dir:3".parse(), Ok(Grouping::Directory(3)));
+        assert_eq!("workspace".parse(), Ok(Grouping::Workspace));
+        assert!("dir:0".parse::<Grouping>().is_err());
+        assert!("dir:x".parse::<Grouping>().is_err());
+        assert!("crate".parse::<Grouping>().is_err());
+    }
+
+    #[test]
+    fn buckets_files_by_leading_directories() {
+        let dir = |path: &str, depth| directory(Path::new(path), depth);
+        assert_eq!(dir("README.md", 1), ["."]);
+        assert_eq!(dir("README.md", 3), ["."]);
+        assert_eq!(dir("src/main.rs", 1), ["src"]);
+        assert_eq!(dir("src/group/mod.rs", 1), ["src"]);
+        assert_eq!(dir("src/group/mod.rs", 2), ["src", "group"]);
+        // Files above the depth stay in the shallower directory.
+        assert_eq!(dir("src/main.rs", 2), ["src"]);
+        assert_eq!(dir("a/b/c/d/e.rs", 3), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn assigns_files_to_the_innermost_member() {
+        let members = vec![
+            Member {
+                path: vec!["crates".into(), "app".into()],
+                ecosystems: vec![Ecosystem::Cargo],
+            },
+            Member {
+                path: vec!["crates".into(), "app".into(), "plugin".into()],
+                ecosystems: vec![Ecosystem::Cargo],
+            },
+        ];
+        let member = |path: &str| member_of(&members, Path::new(path));
+        assert_eq!(member("crates/app/src/main.rs"), ["crates", "app"]);
+        assert_eq!(
+            member("crates/app/plugin/src/lib.rs"),
+            ["crates", "app", "plugin"]
+        );
+        assert_eq!(member("crates/application/lib.rs"), ["."]);
+        assert_eq!(member("build.rs"), ["."]);
+    }
+
+    #[test]
+    fn shares_add_up_to_their_subtotal() {
+        // total
+        // ├─ (root)  10
+        // ├─ a       60 = b + c, no files of its own
+        // │  ├─ b    30
+        // │  └─ c    50
+        // └─ z       40
+        let split = shares(&[
+            node(0, 10, 100.0),
+            node(1, 1, 10.0),
+            node(1, 6, 60.0),
+            node(2, 3, 30.0),
+            node(2, 3, 50.0),
+            node(1, 3, 40.0),
+        ]);
+        assert_close(split[0], 100.0);
+        assert_close(split[1], 100.0 * 10.0 / 110.0);
+        assert_close(split[2], 100.0 * 60.0 / 110.0);
+        assert_close(split[5], 100.0 * 40.0 / 110.0);
+        assert_close(split[1] + split[2] + split[5], split[0]);
+        assert_close(split[3], split[2] * 30.0 / 80.0);
+        assert_close(split[3] + split[4], split[2]);
+    }
+
+    #[test]
+    fn own_files_keep_the_remainder() {
+        // a holds a file besides b and c and is worth more than both.
+        let split = shares(&[
+            node(0, 7, 100.0),
+            node(1, 7, 100.0),
+            node(2, 3, 30.0),
+            node(2, 3, 50.0),
+        ]);
+        assert_close(split[1], 100.0);
+        assert_close(split[2], 30.0);
+        assert_close(split[3], 50.0);
+
+        // Children worth more than their parent are scaled down to it.
+        let split = shares(&[
+            node(0, 7, 100.0),
+            node(1, 7, 100.0),
+            node(2, 3, 75.0),
+            node(2, 3, 50.0),
+        ]);
+        assert_close(split[2], 60.0);
+        assert_close(split[3], 40.0);
+    }
+
+    #[test]
+    fn zero_valued_groups_share_nothing() {
+        let split = shares(&[node(0, 2, 0.0), node(1, 1, 0.0), node(1, 1, 0.0)]);
+        assert_eq!(split, [0.0, 0.0, 0.0]);
+    }
+}
@@ -0,0 +1,357 @@
+//! Workspace members for `--by workspace`.
+
+use anyhow::{Context, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Component, Path, PathBuf};
+
+use crate::metrics::IGNORE_FILE;
+
+/// The tool whose manifest declares a member.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Ecosystem {
+    Cargo,
+    Npm,
+    Pnpm,
+    Go,
+}
+
+/// A member directory, as path components relative to the repository root.
+pub struct Member {
+    pub path: Vec<String>,
+    pub ecosystems: Vec<Ecosystem>,
+}
+
+/// A manifest that lists members by glob, relative to its own directory.
+struct Declaration {
+    dir: Vec<String>,
+    ecosystem: Ecosystem,
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+/// Members of every Cargo, npm and pnpm workspace under `root`, plus every
+/// Go module below it. Ignored directories (`.gitignore`, `.codecostignore`)
+/// are not searched, so `node_modules` and vendored code never count.
+pub fn members(root: &Path) -> Result<Vec<Member>> {
+    let mut cargo = BTreeSet::new();
+    let mut npm = BTreeSet::new();
+    let mut go = BTreeSet::new();
+    let mut declarations = Vec::new();
+    let mut pnpm_roots = BTreeSet::new();
+    let mut npm_declarations = Vec::new();
+
+    let walker = WalkBuilder::new(root)
+        .hidden(false)
+        .follow_links(false)
+        .add_custom_ignore_filename(IGNORE_FILE)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build();
+    for entry in walker {
+        let entry = entry?;
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        let path = entry.path();
+        let dir = components(path.parent().unwrap_or(root).strip_prefix(root)?);
+        match entry.file_name().to_str() {
+            Some("Cargo.toml") => {
+                if let Some(declaration) = cargo_workspace(path, &dir)? {
+                    declarations.push(declaration);
+                }
+                cargo.insert(dir);
+            }
+            Some("package.json") => {
+                if let Some(declaration) = npm_workspace(path, &dir)? {
+                    npm_declarations.push(declaration);
+                }
+                npm.insert(dir);
+            }
+            Some("pnpm-workspace.yaml") => {
+                declarations.push(pnpm_workspace(path, &dir)?);
+                pnpm_roots.insert(dir);
+            }
+            Some("go.mod") => {
+                go.insert(dir);
+            }
+            _ => {}
+        }
+    }
+    // pnpm reads its own file and ignores `workspaces` in package.json.
+    declarations.extend(
+        npm_declarations
+            .into_iter()
+            .filter(|declaration| !pnpm_roots.contains(&declaration.dir)),
+    );
+
+    let mut members: BTreeMap<Vec<String>, BTreeSet<Ecosystem>> = BTreeMap::new();
+    for declaration in &declarations {
+        let candidates = match declaration.ecosystem {
+            Ecosystem::Cargo => &cargo,
+            _ => &npm,
+        };
+        let include = glob_set(&declaration.include)?;
+        let exclude = glob_set(&declaration.exclude)?;
+        for dir in candidates {
+            let Some(relative) = dir.strip_prefix(declaration.dir.as_slice()) else {
+                continue;
+            };
+            let relative = relative.join("/");
+            if !relative.is_empty() && include.is_match(&relative) && !exclude.is_match(&relative) {
+                members
+                    .entry(dir.clone())
+                    .or_default()
+                    .insert(declaration.ecosystem);
+            }
+        }
+    }
+    for dir in go {
+        members.entry(dir).or_default().insert(Ecosystem::Go);
+    }
+
+    Ok(members
+        .into_iter()
+        .filter(|(path, _)| !path.is_empty())
+        .map(|(path, ecosystems)| Member {
+            path,
+            ecosystems: ecosystems.into_iter().collect(),
+        })
+        .collect())
+}
+
+/// `[workspace] members` and `exclude` of a Cargo manifest.
+fn cargo_workspace(path: &Path, dir: &[String]) -> Result<Option<Declaration>> {
+    let content = read(path)?;
+    let manifest: toml::Table =
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))?;
+    let Some(workspace) = manifest.get("workspace") else {
+        return Ok(None);
+    };
+    let strings = |key: &str| -> Vec<String> {
+        workspace
+            .get(key)
+            .and_then(|value| value.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|value| value.as_str().map(String::from))
+            .collect()
+    };
+    Ok(Some(Declaration {
+        dir: dir.to_vec(),
+        ecosystem: Ecosystem::Cargo,
+        include: strings("members"),
+        exclude: strings("exclude"),
+    }))
+}
+
+/// `workspaces` of a package.json, either a list or `{ "packages": [...] }`.
+fn npm_workspace(path: &Path, dir: &[String]) -> Result<Option<Declaration>> {
+    let content = read(path)?;
+    let manifest: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    let patterns = match manifest.get("workspaces") {
+        Some(serde_json::Value::Array(patterns)) => patterns,
+        Some(serde_json::Value::Object(workspaces)) => match workspaces.get("packages") {
+            Some(serde_json::Value::Array(patterns)) => patterns,
+            _ => return Ok(None),
+        },
+        _ => return Ok(None),
+    };
+    let patterns = patterns.iter().filter_map(|value| value.as_str());
+    Ok(Some(declaration(dir, Ecosystem::Npm, patterns)))
+}
+
+/// `packages` of a pnpm-workspace.yaml.
+fn pnpm_workspace(path: &Path, dir: &[String]) -> Result<Declaration> {
+    let content = read(path)?;
+    let manifest: serde_yaml::Value = serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    let patterns = manifest
+        .get("packages")
+        .and_then(|value| value.as_sequence())
+        .into_iter()
+        .flatten()
+        .filter_map(|value| value.as_str());
+    Ok(declaration(dir, Ecosystem::Pnpm, patterns))
+}
+
+/// npm and pnpm exclude with `!pattern` in the same list.
+fn declaration<'a>(
+    dir: &[String],
+    ecosystem: Ecosystem,
+    patterns: impl Iterator<Item = &'a str>,
+) -> Declaration {
+    let (exclude, include): (Vec<_>, Vec<_>) = patterns.partition(|p| p.starts_with('!'));
+    Declaration {
+        dir: dir.to_vec(),
+        ecosystem,
+        include: include.into_iter().map(String::from).collect(),
+        exclude: exclude
+            .into_iter()
+            .map(|pattern| pattern[1..].to_string())
+            .collect(),
+    }
+}
+
+fn read(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+/// Member globs match whole path components, as in Cargo, npm and pnpm.
+fn glob_set(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let pattern = components(&PathBuf::from(pattern)).join("/");
+        let glob = GlobBuilder::new(&pattern)
+            .literal_separator(true)
+            .build()
+            .with_context(|| format!("Invalid workspace member pattern: {pattern}"))?;
+        builder.add(glob);
+    }
+    Ok(builder.build()?)
+}
+
+/// Normal components of a relative path, without `.` and trailing slashes.
+fn components(path: &Path) -> Vec<String> {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+
+    fn detect(dir: &TempDir) -> Vec<(String, Vec<Ecosystem>)> {
+        members(dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|member| (member.path.join("/"), member.ecosystems))
+            .collect()
+    }
+
+    #[test]
+    fn cargo_members_and_excludes() {
+        let dir = TempDir::new();
+        dir.write(
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\", \"tools/cli/\"]\nexclude = [\"crates/skip\"]\n",
+        );
+        dir.write("crates/core/Cargo.toml", "[package]\nname = \"core\"\n");
+        dir.write("crates/skip/Cargo.toml", "[package]\nname = \"skip\"\n");
+        dir.write(
+            "crates/nested/deep/Cargo.toml",
+            "[package]\nname = \"deep\"\n",
+        );
+        dir.write("tools/cli/Cargo.toml", "[package]\nname = \"cli\"\n");
+        dir.write("examples/demo/Cargo.toml", "[package]\nname = \"demo\"\n");
+
+        assert_eq!(
+            detect(&dir),
+            [
+                ("crates/core".to_string(), vec![Ecosystem::Cargo]),
+                ("tools/cli".to_string(), vec![Ecosystem::Cargo]),
+            ]
+        );
+    }
+
+    #[test]
+    fn npm_workspaces_as_list_or_packages() {
+        let dir = TempDir::new();
+        dir.write(
+            "package.json",
+            r#"{"workspaces": ["packages/*", "!packages/legacy"]}"#,
+        );
+        dir.write("packages/ui/package.json", "{}");
+        dir.write("packages/legacy/package.json", "{}");
+        dir.write(
+            "site/package.json",
+            r#"{"workspaces": {"packages": ["apps/*"]}}"#,
+        );
+        dir.write("site/apps/web/package.json", "{}");
+
+        assert_eq!(
+            detect(&dir),
+            [
+                ("packages/ui".to_string(), vec![Ecosystem::Npm]),
+                ("site/apps/web".to_string(), vec![Ecosystem::Npm]),
+            ]
+        );
+    }
+
+    #[test]
+    fn pnpm_overrides_package_json_workspaces() {
+        let dir = TempDir::new();
+        dir.write("pnpm-workspace.yaml", "packages:\n  - 'libs/**'\n");
+        dir.write("package.json", r#"{"workspaces": ["ignored/*"]}"#);
+        dir.write("libs/a/package.json", "{}");
+        dir.write("libs/a/b/package.json", "{}");
+        dir.write("ignored/x/package.json", "{}");
+
+        assert_eq!(
+            detect(&dir),
+            [
+                ("libs/a".to_string(), vec![Ecosystem::Pnpm]),
+                ("libs/a/b".to_string(), vec![Ecosystem::Pnpm]),
+            ]
+        );
+    }
+
+    #[test]
+    fn go_modules_below_the_root() {
+        let dir = TempDir::new();
+        dir.write("go.mod", "module example.com/root\n");
+        dir.write("services/api/go.mod", "module example.com/api\n");
+        dir.write("services/api/main.go", "package main\n");
+
+        assert_eq!(
+            detect(&dir),
+            [("services/api".to_string(), vec![Ecosystem::Go])]
+        );
+    }
+
+    #[test]
+    fn one_directory_in_several_ecosystems() {
+        let dir = TempDir::new();
+        dir.write("Cargo.toml", "[workspace]\nmembers = [\"bindings\"]\n");
+        dir.write("package.json", r#"{"workspaces": ["bindings"]}"#);
+        dir.write("bindings/Cargo.toml", "[package]\nname = \"bindings\"\n");
+        dir.write("bindings/package.json", "{}");
+
+        assert_eq!(
+            detect(&dir),
+            [(
+                "bindings".to_string(),
+                vec![Ecosystem::Cargo, Ecosystem::Npm]
+            )]
+        );
+    }
+
+    #[test]
+    fn ignored_directories_are_not_searched() {
+        let dir = TempDir::new();
+        dir.write(IGNORE_FILE, "vendor/\n");
+        dir.write("vendor/lib/go.mod", "module example.com/lib\n");
+        dir.write("cmd/tool/go.mod", "module example.com/tool\n");
+
+        assert_eq!(
+            detect(&dir),
+            [("cmd/tool".to_string(), vec![Ecosystem::Go])]
+        );
+    }
+
+    #[test]
+    fn malformed_manifests_are_errors() {
+        let dir = TempDir::new();
+        dir.write("Cargo.toml", "[workspace\n");
+        let error = members(dir.path()).err().unwrap();
+        assert!(error.to_string().contains("Failed to parse"), "{error}");
+    }
+}
@@ -0,0 +1,292 @@
+//! Valuation over time: the past revisions `--history` measures.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, NaiveDate};
+use clap::ValueEnum;
+use git2::{Oid, Repository, Sort};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::analyzer::{Analysis, RepositoryAnalyzer};
+use crate::calculator::{CostCalculator, CostEstimate};
+
+/// Which revisions `--history` values.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Granularity {
+    /// The last first-parent commit of every month
+    Monthly,
+    /// Every tag, oldest first
+    Tags,
+}
+
+impl Granularity {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Monthly => "monthly",
+            Self::Tags => "tags",
+        }
+    }
+}
+
+/// One repository valued at every selected revision.
+#[derive(Debug, Clone, Serialize)]
+pub struct Timeline {
+    pub path: PathBuf,
+    pub granularity: Granularity,
+    pub points: Vec<Point>,
+}
+
+/// A past revision to value.
+#[derive(Debug, Clone)]
+pub struct Revision {
+    pub label: String,
+    pub commit: Oid,
+    pub time: i64,
+}
+
+/// The repository as it was at one revision.
+#[derive(Debug, Clone, Serialize)]
+pub struct Point {
+    pub label: String,
+    pub commit: String,
+    pub date: String,
+    pub metrics: Analysis,
+    pub cost: CostEstimate,
+}
+
+impl Revision {
+    /// The commit a tag, branch or commit id names.
+    pub fn resolve(repo: &Repository, rev: &str) -> Result<Self> {
+        let commit = repo
+            .revparse_single(rev)
+            .and_then(|object| object.peel_to_commit())
+            .with_context(|| format!("Unknown revision: {rev}"))?;
+        Ok(Self {
+            label: rev.to_string(),
+            commit: commit.id(),
+            time: commit.time().seconds(),
+        })
+    }
+}
+
+impl Point {
+    pub fn new(revision: &Revision, metrics: Analysis, cost: CostEstimate) -> Self {
+        Self {
+            label: revision.label.clone(),
+            commit: revision.commit.to_string(),
+            date: DateTime::from_timestamp(revision.time, 0)
+                .map(|time| time.format("%Y-%m-%d").to_string())
+                .unwrap_or_default(),
+            metrics,
+            cost,
+        }
+    }
+}
+
+/// Value the repository at `path` at each revision `granularity` selects.
+pub async fn timeline(
+    path: &Path,
+    granularity: Granularity,
+    analyzer: &RepositoryAnalyzer,
+    calculator: &CostCalculator,
+) -> Result<Timeline> {
+    let repo = Repository::open(path)?;
+    let revisions = revisions(&repo, granularity)?;
+    let analyses = analyzer.analyze_history(path, &revisions).await?;
+    let points = revisions
+        .iter()
+        .zip(analyses)
+        .map(|(revision, analysis)| {
+            let cost = calculator.calculate(&analysis);
+            Point::new(revision, analysis, cost)
+        })
+        .collect();
+    Ok(Timeline {
+        path: path.to_path_buf(),
+        granularity,
+        points,
+    })
+}
+
+/// Revisions to value, oldest first.
+fn revisions(repo: &Repository, granularity: Granularity) -> Result<Vec<Revision>> {
+    match granularity {
+        Granularity::Monthly => monthly(repo),
+        Granularity::Tags => tags(repo),
+    }
+}
+
+/// The last commit of each calendar month (UTC) on HEAD's first-parent
+/// chain. Months without commits repeat the previous month's commit so the
+/// series stays evenly spaced.
+fn monthly(repo: &Repository) -> Result<Vec<Revision>> {
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push_head()?;
+    revwalk.simplify_first_parent()?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+    let commits = revwalk
+        .map(|oid| {
+            let oid = oid?;
+            Ok((oid, repo.find_commit(oid)?.time().seconds()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let (Some(first), Some(last)) = (
+        commits.iter().map(|(_, time)| *time).min(),
+        commits.iter().map(|(_, time)| *time).max(),
+    ) else {
+        return Ok(Vec::new());
+    };
+
+    let mut revisions = Vec::new();
+    let mut month = month_of(first);
+    let mut pending = commits.iter().peekable();
+    let mut latest = None;
+    while month <= month_of(last) {
+        let next = next_month(month);
+        let boundary = next
+            .and_hms_opt(0, 0, 0)
+            .unwrap_or_default()
+            .and_utc()
+            .timestamp();
+        while let Some(commit) = pending.next_if(|(_, time)| *time < boundary) {
+            latest = Some(*commit);
+        }
+        if let Some((commit, time)) = latest {
+            revisions.push(Revision {
+                label: month.format("%Y-%m").to_string(),
+                commit,
+                time,
+            });
+        }
+        month = next;
+    }
+    Ok(revisions)
+}
+
+/// Every tag that points at a commit, ordered by commit time.
+fn tags(repo: &Repository) -> Result<Vec<Revision>> {
+    let names = repo.tag_names(None)?;
+    let mut revisions: Vec<Revision> = names
+        .iter()
+        .flatten()
+        .filter_map(|name| {
+            let commit = repo
+                .revparse_single(&format!("refs/tags/{name}"))
+                .ok()?
+                .peel_to_commit()
+                .ok()?;
+            Some(Revision {
+                label: name.to_string(),
+                commit: commit.id(),
+                time: commit.time().seconds(),
+            })
+        })
+        .collect();
+    revisions.sort_by(|a, b| a.time.cmp(&b.time).then_with(|| a.label.cmp(&b.label)));
+    Ok(revisions)
+}
+
+fn month_of(time: i64) -> NaiveDate {
+    DateTime::from_timestamp(time, 0)
+        .map(|time| time.date_naive())
+        .unwrap_or_default()
+        .with_day(1)
+        .unwrap_or_default()
+}
+
+fn next_month(month: NaiveDate) -> NaiveDate {
+    let (year, month) = match month.month() {
+        12 => (month.year() + 1, 1),
+        m => (month.year(), m + 1),
+    };
+    NaiveDate::from_ymd_opt(year, month, 1).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempRepo;
+
+    fn at(time: &str) -> i64 {
+        DateTime::parse_from_rfc3339(time).unwrap().timestamp()
+    }
+
+    fn labels(revisions: &[Revision]) -> Vec<(&str, Oid)> {
+        revisions
+            .iter()
+            .map(|revision| (revision.label.as_str(), revision.commit))
+            .collect()
+    }
+
+    #[test]
+    fn monthly_picks_the_last_commit_of_each_month() {
+        let mut repo = TempRepo::new();
+        let mut commit = |time: &str| {
+            repo.write("log.txt", time);
+            repo.commit_at(at(time), time)
+        };
+        commit("2023-12-15T12:00:00Z");
+        let december = commit("2023-12-31T23:59:00Z");
+        commit("2024-01-02T09:00:00Z");
+        let january = commit("2024-01-20T09:00:00Z");
+        let march = commit("2024-03-31T23:59:59Z");
+        let april = commit("2024-04-01T00:00:00Z");
+
+        let revisions = revisions(&repo.repo, Granularity::Monthly).unwrap();
+        assert_eq!(
+            labels(&revisions),
+            [
+                ("2023-12", december),
+                ("2024-01", january),
+                // A month without commits repeats the one before.
+                ("2024-02", january),
+                ("2024-03", march),
+                ("2024-04", april),
+            ]
+        );
+        assert_eq!(revisions[3].time, at("2024-03-31T23:59:59Z"));
+    }
+
+    #[test]
+    fn tags_are_ordered_by_commit_time() {
+        let mut repo = TempRepo::new();
+        repo.write("a.txt", "a\n");
+        let first = repo.commit("first");
+        repo.write("a.txt", "b\n");
+        let second = repo.commit("second");
+        for (name, oid) in [("v2.0", first), ("v1.0", second), ("v1.0-rc", second)] {
+            let commit = repo.repo.find_object(oid, None).unwrap();
+            repo.repo.tag_lightweight(name, &commit, false).unwrap();
+        }
+
+        let revisions = revisions(&repo.repo, Granularity::Tags).unwrap();
+        assert_eq!(
+            labels(&revisions),
+            [("v2.0", first), ("v1.0", second), ("v1.0-rc", second)]
+        );
+    }
+
+    #[test]
+    fn resolve_names_tags_branches_and_ids() {
+        let mut repo = TempRepo::new();
+        repo.write("a.txt", "a\n");
+        let oid = repo.commit_at(at("2024-05-01T10:00:00Z"), "first");
+        // An annotated tag, which has to be peeled to its commit.
+        let commit = repo.repo.find_object(oid, None).unwrap();
+        let tagger = git2::Signature::now("Dev", "dev@example.com").unwrap();
+        repo.repo
+            .tag("v1.0", &commit, &tagger, "release", false)
+            .unwrap();
+
+        for rev in ["v1.0", "main", "HEAD", &oid.to_string()[..8]] {
+            let revision = Revision::resolve(&repo.repo, rev).unwrap();
+            assert_eq!(revision.label, rev);
+            assert_eq!(revision.commit, oid);
+            assert_eq!(revision.time, at("2024-05-01T10:00:00Z"));
+        }
+        let error = Revision::resolve(&repo.repo, "v9.9").err().unwrap();
+        assert_eq!(error.to_string(), "Unknown revision: v9.9");
+    }
+}
@@ -1,16 +1,43 @@
 mod analyzer;
+mod cache;
 mod calculator;
+mod complexity;
+mod coverage;
+mod dependencies;
+mod diff;
+mod duplication;
 mod git;
+mod group;
+mod history;
 mod metrics;
+mod report;
+#[cfg(test)]
+mod testing;
+mod tokenizer;
 
-use anyhow::Result;
-use clap::Parser;
-use cli_core::output::format_integer;
+use anyhow::{bail, Result};
+use clap::{Parser, Subcommand};
+use cli_core::currency::{Amount, Currency};
+use cli_core::output::{format_integer, FormatArg, OutputFormat};
 use cli_core::ui::Theme;
-use std::path::PathBuf;
+use cli_core::{Config, LayeredConfig};
+use std::path::{Path, PathBuf};
 
 use crate::analyzer::RepositoryAnalyzer;
 use crate::calculator::CostCalculator;
+use crate::diff::{DiffScope, RevisionDiff};
+use crate::group::{Breakdown, GroupKind, Grouping};
+use crate::history::{Granularity, Timeline};
+
+/// Most recent commits listed per AI evidence signal in the terminal.
+const AI_EVIDENCE_SHOWN: usize = 5;
+
+/// Duplicated packages listed per lockfile, and licences listed, in the terminal.
+const DUPLICATES_SHOWN: usize = 8;
+const LICENSES_SHOWN: usize = 6;
+
+/// Largest duplicated blocks listed in the terminal.
+const DUPLICATED_BLOCKS_SHOWN: usize = 5;
 
 #[derive(serde::Serialize)]
 struct ExportRow {
@@ -18,23 +45,75 @@ struct ExportRow {
     lines: usize,
     files: usize,
     commits: usize,
+    /// Empty without a coverage report
+    line_coverage: Option<f64>,
+    branch_coverage: Option<f64>,
+    duplicated_lines: usize,
+    duplication_discount_hours: f64,
+    direct_dependencies: usize,
+    transitive_dependencies: usize,
+    dependency_hours: f64,
+    /// LLM the token counts and `total_api_cost` are for
+    api_model: String,
     base_tokens: usize,
     estimated_prompt_tokens: usize,
     estimated_output_tokens: usize,
     estimated_hours: f64,
-    total_cost_krw: f64,
-    total_api_cost_krw: f64,
+    model: String,
+    effort_person_months: f64,
+    schedule_months: f64,
+    team_size: f64,
+    currency: Currency,
+    total_cost: f64,
+    total_api_cost: f64,
+}
+
+/// One `--history` point in CSV exports.
+#[derive(serde::Serialize)]
+struct HistoryRow {
+    path: String,
+    revision: String,
+    date: String,
+    commit: String,
+    lines: usize,
+    code_lines: usize,
+    files: usize,
+    languages: String,
+    estimated_hours: f64,
+    currency: Currency,
+    total_cost: f64,
+}
+
+/// One `--by` group or subtotal in CSV exports.
+#[derive(serde::Serialize)]
+struct GroupRow {
+    path: String,
+    group: String,
+    kind: GroupKind,
+    depth: usize,
+    lines: usize,
+    code_lines: usize,
+    files: usize,
+    languages: String,
+    complexity_score: f64,
+    estimated_hours: f64,
+    currency: Currency,
+    total_cost: f64,
+    standalone_hours: f64,
+    standalone_cost: f64,
 }
 
 fn get_repository_name(path: &PathBuf) -> String {
     // Try to get git repository name first
     if let Ok(repo) = git2::Repository::open(path) {
-        if let Some(name) = repo
-            .workdir()
-            .and_then(|p| p.file_name())
-            .and_then(|n| n.to_str())
-        {
-            return name.to_string();
+        // Bare repositories are conventionally named `name.git`.
+        let dir = if repo.is_bare() {
+            Some(repo.path())
+        } else {
+            repo.workdir()
+        };
+        if let Some(name) = dir.and_then(|p| p.file_name()).and_then(|n| n.to_str()) {
+            return name.strip_suffix(".git").unwrap_or(name).to_string();
         }
     }
 
@@ -45,6 +124,24 @@ fn get_repository_name(path: &PathBuf) -> String {
         .unwrap_or_else(|| path.to_string_lossy().to_string())
 }
 
+/// A repository path, or the `file://` URL a local clone or mirror was made from.
+fn repository_path(arg: &str) -> Result<PathBuf, String> {
+    match arg.split_once("://") {
+        None => Ok(PathBuf::from(arg)),
+        Some(("file", rest)) => {
+            let path = rest.strip_prefix("localhost").unwrap_or(rest);
+            if path.starts_with('/') {
+                Ok(PathBuf::from(path))
+            } else {
+                Err(format!("expected file:///<absolute path>, got {arg}"))
+            }
+        }
+        Some((scheme, _)) => Err(format!(
+            "{scheme} URLs are not supported; clone the repository first (git clone --mirror)"
+        )),
+    }
+}
+
 #[derive(Parser, Debug)]
 #[command(
     name = "code-cost",
@@ -55,21 +152,27 @@ fn get_repository_name(path: &PathBuf) -> String {
                   complexity, commit history, and project maturity."
 )]
 struct Cli {
-    /// Paths to repositories to analyze
-    #[arg(value_name = "PATH", default_value = ".")]
+    #[command(subcommand)]
+    command: Option<Commands>,
+
+    /// Paths to repositories to analyze: working trees, bare repositories or file:// URLs
+    #[arg(value_name = "PATH", default_value = ".", value_parser = repository_path)]
     paths: Vec<PathBuf>,
 
-    /// Output format
-    #[arg(short, long, value_name = "FORMAT", default_value = "table")]
-    format: String,
+    #[command(flatten)]
+    output: FormatArg,
 
     /// Export results to a file (supports .csv, .html, .md)
-    #[arg(short, long, value_name = "FILE")]
+    #[arg(short, long, value_name = "FILE", global = true)]
     export: Option<PathBuf>,
 
-    /// Hourly rate in KRW (default: 10030 - 2025 minimum wage)
-    #[arg(long, value_name = "RATE", default_value = "10030")]
-    hourly_rate: f64,
+    /// Hourly rate, optionally with its currency such as 45USD (default: `hourly_rate` from config, else 10030 KRW - 2025 minimum wage)
+    #[arg(long, value_name = "RATE", global = true)]
+    hourly_rate: Option<Amount>,
+
+    /// Currency to report in, converting with the offline exchange rates (default: `report_currency` or `currency` from config)
+    #[arg(long, value_name = "CODE", global = true)]
+    currency: Option<Currency>,
 
     /// Simple output mode (hide detailed analysis)
     #[arg(short, long)]
@@ -78,68 +181,764 @@ struct Cli {
     /// Show developer level breakdown
     #[arg(long)]
     dev_levels: bool,
+
+    /// Re-read every file and walk all history instead of using the analysis cache
+    #[arg(long, global = true)]
+    no_cache: bool,
+
+    /// Number of most complex functions to list
+    #[arg(long, value_name = "N", default_value_t = complexity::DEFAULT_TOP)]
+    top: usize,
+
+    /// Estimation model (default: `code_cost.model` from config, else heuristic)
+    #[arg(
+        long,
+        value_name = "MODEL",
+        global = true,
+        value_parser = [
+            "heuristic",
+            "cocomo81-basic",
+            "cocomo81-intermediate",
+            "cocomo2",
+            "custom",
+        ]
+    )]
+    model: Option<String>,
+
+    /// LLMs from the pricing catalogue to simulate token costs for, comma-separated (default: `code_cost.llm_models` from config, else claude-opus-4-7)
+    #[arg(long, value_name = "IDS", value_delimiter = ',', global = true)]
+    models: Vec<String>,
+
+    /// Coverage report (lcov, Cobertura, JaCoCo or llvm-cov JSON) to score test coverage with (default: `code_cost.coverage` from config, else the first found in the usual output locations)
+    #[arg(long, value_name = "FILE", conflicts_with = "history")]
+    coverage: Option<PathBuf>,
+
+    /// Value the repository at every month or tag instead of only its current state
+    #[arg(long, value_name = "POINTS")]
+    history: Option<Granularity>,
+
+    /// Break the estimate down by directory (dir, dir:<depth>) or workspace member (workspace)
+    #[arg(long, value_name = "GROUPING", conflicts_with = "history")]
+    by: Option<Grouping>,
+
+    /// Analyze the tree at a commit, branch or tag from git objects instead of the working tree (bare repositories default to HEAD)
+    #[arg(long, value_name = "REV", conflicts_with_all = ["history", "by"])]
+    rev: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Compare two revisions: lines added, removed and changed, and the value added
+    Diff {
+        /// Older revision (tag, branch or commit)
+        from: String,
+
+        /// Newer revision (tag, branch or commit)
+        to: String,
+
+        /// Repository to compare
+        #[arg(short, long, value_name = "PATH", default_value = ".", value_parser = repository_path)]
+        path: PathBuf,
+
+        /// Path components that name a directory
+        #[arg(long, value_name = "N", default_value_t = diff::DEFAULT_DEPTH)]
+        depth: usize,
+
+        /// Number of directories to list
+        #[arg(long, value_name = "N", default_value_t = diff::DEFAULT_DIRECTORIES)]
+        dirs: usize,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    let format = cli.output.format;
+    // Progress text would corrupt structured output, so it goes to stderr there.
+    let progress = |line: String| {
+        if format.is_table() {
+            println!("{line}");
+        } else {
+            eprintln!("{line}");
+        }
+    };
 
-    println!("{}", Theme::header("🔍 Code Cost Analyzer"));
-    println!();
+    progress(Theme::header("🔍 Code Cost Analyzer"));
+    progress(String::new());
 
-    let analyzer = RepositoryAnalyzer::new(cli.hourly_rate);
-    let calculator = CostCalculator::new(cli.hourly_rate);
+    if let Some(Commands::Diff {
+        from,
+        to,
+        path,
+        depth,
+        dirs,
+    }) = &cli.command
+    {
+        let scope = DiffScope {
+            depth: *depth,
+            directories: *dirs,
+        };
+        return run_diff(&cli, format, path, (from, to), &scope, progress).await;
+    }
+
+    if let Some(granularity) = cli.history {
+        return run_history(&cli, format, granularity, progress).await;
+    }
+
+    if let Some(grouping) = cli.by {
+        return run_breakdown(&cli, format, grouping, progress).await;
+    }
 
     let mut results = Vec::new();
 
     for path in &cli.paths {
-        println!("{} {}", Theme::info("Analyzing:"), path.display());
+        match &cli.rev {
+            Some(rev) => progress(format!(
+                "{} {} at {}",
+                Theme::info("Analyzing:"),
+                path.display(),
+                rev
+            )),
+            None => progress(format!("{} {}", Theme::info("Analyzing:"), path.display())),
+        }
+
+        let config = load_config(path, &cli)?;
+        let calculator = CostCalculator::new(&config)?;
+        let analyzer = RepositoryAnalyzer::new(&config.code_cost)
+            .with_cache(!cli.no_cache)
+            .with_top_functions(cli.top)
+            .with_tokenizers(calculator.tokenizers());
 
-        match analyzer.analyze(path).await {
+        let analysis = match &cli.rev {
+            Some(rev) => analyzer.analyze_revision(path, rev).await,
+            None => analyzer.analyze(path).await,
+        };
+        match analysis {
             Ok(analysis) => {
                 let cost = calculator.calculate(&analysis);
-                results.push((path, analysis, cost));
-                println!("{}", Theme::success("Analysis completed"));
+                results.push(report::RepositoryCost {
+                    path: path.clone(),
+                    analysis,
+                    cost,
+                });
+                progress(Theme::success("Analysis completed"));
             }
             Err(e) => {
-                println!("{} {}", Theme::error("Analysis failed:"), e);
+                progress(format!("{} {}", Theme::error("Analysis failed:"), e));
             }
         }
-        println!();
+        progress(String::new());
     }
 
     if results.is_empty() {
-        println!("{}", Theme::warning("No repositories were successfully analyzed"));
-        return Ok(());
+        bail!("No repositories were successfully analyzed");
     }
 
     // Display results
-    display_results(&results, &cli)?;
+    display_results(&results, &cli, format)?;
 
     // Export if requested
-    if let Some(export_path) = cli.export {
-        export_results(&results, &export_path)?;
+    if let Some(export_path) = &cli.export {
+        export_results(&results, export_path)?;
+        progress(format!(
+            "{} {}",
+            Theme::success("Exported to:"),
+            export_path.display()
+        ));
+    }
+
+    Ok(())
+}
+
+/// `--history`: value each repository at past revisions read straight from git.
+async fn run_history(
+    cli: &Cli,
+    format: OutputFormat,
+    granularity: Granularity,
+    progress: impl Fn(String),
+) -> Result<()> {
+    let mut timelines = Vec::new();
+
+    for path in &cli.paths {
+        progress(format!("{} {}", Theme::info("Analyzing history:"), path.display()));
+
+        let config = load_config(path, cli)?;
+        let calculator = CostCalculator::new(&config)?;
+        let analyzer = RepositoryAnalyzer::new(&config.code_cost)
+            .with_cache(!cli.no_cache)
+            .with_top_functions(cli.top)
+            .with_tokenizers(calculator.tokenizers());
+
+        match history::timeline(path, granularity, &analyzer, &calculator).await {
+            Ok(timeline) if timeline.points.is_empty() => {
+                progress(Theme::warning(match granularity {
+                    Granularity::Monthly => "No commits to value",
+                    Granularity::Tags => "No tags to value",
+                }));
+            }
+            Ok(timeline) => {
+                progress(Theme::success(match timeline.points.len() {
+                    1 => "Valued 1 revision".to_string(),
+                    n => format!("Valued {n} revisions"),
+                }));
+                timelines.push(timeline);
+            }
+            Err(e) => {
+                progress(format!("{} {}", Theme::error("Analysis failed:"), e));
+            }
+        }
+        progress(String::new());
+    }
+
+    if timelines.is_empty() {
+        bail!("No repositories were successfully analyzed");
+    }
+
+    match format {
+        OutputFormat::Table => display_history(&timelines),
+        OutputFormat::Csv => format.print(&history_rows(&timelines))?,
+        _ => format.print(&timelines)?,
+    }
+
+    if let Some(export_path) = &cli.export {
+        export_history(&timelines, export_path)?;
+        progress(format!(
+            "{} {}",
+            Theme::success("Exported to:"),
+            export_path.display()
+        ));
+    }
+
+    Ok(())
+}
+
+fn display_history(timelines: &[Timeline]) {
+    use cli_core::output::TableFormatter;
+    use cli_core::ui::sparkline;
+    use comfy_table::{Cell, Color};
+
+    for timeline in timelines {
+        let currency = timeline
+            .points
+            .first()
+            .map(|point| point.cost.currency)
+            .unwrap_or_default();
+        println!(
+            "{}",
+            Theme::header(format!(
+                "📈 {} ({} history)",
+                get_repository_name(&timeline.path),
+                timeline.granularity.name()
+            ))
+        );
+
+        let mut table = TableFormatter::create_table();
+        table.set_header(vec![
+            TableFormatter::header_cell("Revision"),
+            TableFormatter::header_cell("Date"),
+            TableFormatter::header_cell("Commit"),
+            TableFormatter::header_cell("Code Lines"),
+            TableFormatter::header_cell("Files"),
+            TableFormatter::header_cell("Languages"),
+            TableFormatter::header_cell("Est. Hours"),
+            TableFormatter::header_cell(format!("Value ({currency})")),
+        ]);
+        for point in &timeline.points {
+            table.add_row(vec![
+                Cell::new(&point.label),
+                Cell::new(&point.date),
+                Cell::new(&point.commit[..7]),
+                Cell::new(format!("{:>10}", format_integer(point.metrics.code_lines as i64))),
+                Cell::new(format!("{:>6}", point.metrics.total_files)),
+                Cell::new(point.metrics.language_mix(3)),
+                Cell::new(format!("{:>10.1}", point.cost.estimated_hours)),
+                Cell::new(format!("{:>14}", currency.format(point.cost.total_cost)))
+                    .fg(Color::Green),
+            ]);
+        }
+        println!("{table}");
+
+        let (Some(first), Some(last)) = (timeline.points.first(), timeline.points.last()) else {
+            continue;
+        };
+        let values: Vec<f64> = timeline.points.iter().map(|p| p.cost.total_cost).collect();
+        let lines: Vec<f64> = timeline
+            .points
+            .iter()
+            .map(|p| p.metrics.code_lines as f64)
+            .collect();
         println!(
+            "  {} {} {} → {}",
+            Theme::dim("Value:"),
+            Theme::highlight(sparkline(&values)),
+            currency.format(first.cost.total_cost),
+            currency.format(last.cost.total_cost)
+        );
+        println!(
+            "  {} {} {} → {} lines",
+            Theme::dim("Code: "),
+            Theme::cyan(sparkline(&lines)),
+            format_integer(first.metrics.code_lines as i64),
+            format_integer(last.metrics.code_lines as i64)
+        );
+        println!();
+    }
+}
+
+fn history_rows(timelines: &[Timeline]) -> Vec<HistoryRow> {
+    timelines
+        .iter()
+        .flat_map(|timeline| {
+            timeline.points.iter().map(|point| HistoryRow {
+                path: timeline.path.to_string_lossy().to_string(),
+                revision: point.label.clone(),
+                date: point.date.clone(),
+                commit: point.commit.clone(),
+                lines: point.metrics.total_lines,
+                code_lines: point.metrics.code_lines,
+                files: point.metrics.total_files,
+                languages: point.metrics.language_lines(),
+                estimated_hours: point.cost.estimated_hours,
+                currency: point.cost.currency,
+                total_cost: point.cost.total_cost,
+            })
+        })
+        .collect()
+}
+
+fn export_history(timelines: &[Timeline], export_path: &Path) -> Result<()> {
+    use cli_core::output::{CsvExporter, ExportFormat, HtmlExporter, MarkdownExporter, ToReport};
+
+    let ext = export_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .ok_or_else(|| anyhow::anyhow!("No file extension provided"))?;
+    let output = export_path.to_str().unwrap();
+
+    match ExportFormat::from_extension(ext)? {
+        ExportFormat::Csv => CsvExporter::new().export(&history_rows(timelines), output)?,
+        ExportFormat::Html => {
+            HtmlExporter::new().export(&report::HistoryReport(timelines).to_report(), output)?
+        }
+        ExportFormat::Markdown => {
+            MarkdownExporter::new().export(&report::HistoryReport(timelines).to_report(), output)?
+        }
+    }
+
+    Ok(())
+}
+
+/// `--by`: value each repository per directory or workspace member.
+async fn run_breakdown(
+    cli: &Cli,
+    format: OutputFormat,
+    grouping: Grouping,
+    progress: impl Fn(String),
+) -> Result<()> {
+    let mut breakdowns = Vec::new();
+
+    for path in &cli.paths {
+        progress(format!("{} {}", Theme::info("Analyzing:"), path.display()));
+
+        let config = load_config(path, cli)?;
+        let calculator = CostCalculator::new(&config)?;
+        let analyzer = RepositoryAnalyzer::new(&config.code_cost)
+            .with_cache(!cli.no_cache)
+            .with_top_functions(cli.top)
+            .with_tokenizers(calculator.tokenizers());
+
+        match group::breakdown(path, grouping, &analyzer, &calculator).await {
+            Ok(breakdown) => {
+                let groups = breakdown
+                    .groups
+                    .iter()
+                    .filter(|group| group.kind == GroupKind::Group)
+                    .count();
+                progress(Theme::success(match groups {
+                    1 => "Valued 1 group".to_string(),
+                    n => format!("Valued {n} groups"),
+                }));
+                breakdowns.push(breakdown);
+            }
+            Err(e) => {
+                progress(format!("{} {}", Theme::error("Analysis failed:"), e));
+            }
+        }
+        progress(String::new());
+    }
+
+    if breakdowns.is_empty() {
+        bail!("No repositories were successfully analyzed");
+    }
+
+    match format {
+        OutputFormat::Table => display_breakdowns(&breakdowns),
+        OutputFormat::Csv => format.print(&group_rows(&breakdowns))?,
+        _ => format.print(&breakdowns)?,
+    }
+
+    if let Some(export_path) = &cli.export {
+        export_breakdowns(&breakdowns, export_path)?;
+        progress(format!(
             "{} {}",
             Theme::success("Exported to:"),
             export_path.display()
+        ));
+    }
+
+    Ok(())
+}
+
+fn display_breakdowns(breakdowns: &[Breakdown]) {
+    use cli_core::output::TableFormatter;
+    use comfy_table::{Attribute, Cell, Color};
+
+    for breakdown in breakdowns {
+        let currency = breakdown
+            .groups
+            .first()
+            .map(|group| group.cost.currency)
+            .unwrap_or_default();
+        println!(
+            "{}",
+            Theme::header(format!(
+                "🗂️  {} (by {})",
+                get_repository_name(&breakdown.path),
+                breakdown.grouping
+            ))
+        );
+
+        let mut table = TableFormatter::create_table();
+        table.set_header(vec![
+            TableFormatter::header_cell("Group"),
+            TableFormatter::header_cell("Files"),
+            TableFormatter::header_cell("Code Lines"),
+            TableFormatter::header_cell("Languages"),
+            TableFormatter::header_cell("Complexity"),
+            TableFormatter::header_cell("Est. Hours"),
+            TableFormatter::header_cell(format!("Value ({currency})")),
+        ]);
+        for group in &breakdown.groups {
+            let cells = vec![
+                Cell::new(&group.label),
+                Cell::new(format!("{:>6}", group.metrics.total_files)),
+                Cell::new(format!("{:>10}", format_integer(group.metrics.code_lines as i64))),
+                Cell::new(group.metrics.language_mix(3)),
+                Cell::new(format!("{:>6.2}", group.metrics.complexity_score)),
+                Cell::new(format!("{:>10.1}", group.share.estimated_hours)),
+                Cell::new(format!("{:>14}", currency.format(group.share.total_cost)))
+                    .fg(Color::Green),
+            ];
+            table.add_row(match group.kind {
+                GroupKind::Group => cells,
+                _ => cells
+                    .into_iter()
+                    .map(|cell| cell.add_attribute(Attribute::Bold))
+                    .collect(),
+            });
+        }
+        println!("{table}");
+        println!(
+            "  {}",
+            Theme::dim("Each total is split among the groups below it by standalone estimate.")
         );
+        println!();
+    }
+}
+
+fn group_rows(breakdowns: &[Breakdown]) -> Vec<GroupRow> {
+    breakdowns
+        .iter()
+        .flat_map(|breakdown| {
+            breakdown.groups.iter().map(|group| GroupRow {
+                path: breakdown.path.to_string_lossy().to_string(),
+                group: group.path.clone(),
+                kind: group.kind,
+                depth: group.depth,
+                lines: group.metrics.total_lines,
+                code_lines: group.metrics.code_lines,
+                files: group.metrics.total_files,
+                languages: group.metrics.language_lines(),
+                complexity_score: group.metrics.complexity_score,
+                estimated_hours: group.share.estimated_hours,
+                currency: group.cost.currency,
+                total_cost: group.share.total_cost,
+                standalone_hours: group.cost.estimated_hours,
+                standalone_cost: group.cost.total_cost,
+            })
+        })
+        .collect()
+}
+
+fn export_breakdowns(breakdowns: &[Breakdown], export_path: &Path) -> Result<()> {
+    use cli_core::output::{CsvExporter, ExportFormat, HtmlExporter, MarkdownExporter, ToReport};
+
+    let ext = export_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .ok_or_else(|| anyhow::anyhow!("No file extension provided"))?;
+    let output = export_path.to_str().unwrap();
+
+    match ExportFormat::from_extension(ext)? {
+        ExportFormat::Csv => CsvExporter::new().export(&group_rows(breakdowns), output)?,
+        ExportFormat::Html => {
+            HtmlExporter::new().export(&report::BreakdownReport(breakdowns).to_report(), output)?
+        }
+        ExportFormat::Markdown => MarkdownExporter::new()
+            .export(&report::BreakdownReport(breakdowns).to_report(), output)?,
+    }
+
+    Ok(())
+}
+
+/// `code-cost diff`: what changed between two revisions and what it was worth.
+async fn run_diff(
+    cli: &Cli,
+    format: OutputFormat,
+    path: &Path,
+    (from, to): (&str, &str),
+    scope: &DiffScope,
+    progress: impl Fn(String),
+) -> Result<()> {
+    progress(format!(
+        "{} {} {from}..{to}",
+        Theme::info("Comparing:"),
+        path.display()
+    ));
+    let config = load_config(path, cli)?;
+    let calculator = CostCalculator::new(&config)?;
+    let analyzer = RepositoryAnalyzer::new(&config.code_cost)
+        .with_cache(!cli.no_cache)
+        .with_tokenizers(calculator.tokenizers());
+
+    let diff = diff::compare(path, from, to, scope, &analyzer, &calculator).await?;
+    progress(String::new());
+
+    match format {
+        OutputFormat::Table => display_diff(&diff),
+        OutputFormat::Csv => format.print(&diff_rows(&diff))?,
+        _ => format.print(&diff)?,
+    }
+
+    if let Some(export_path) = &cli.export {
+        export_diff(&diff, export_path)?;
+        progress(format!(
+            "{} {}",
+            Theme::success("Exported to:"),
+            export_path.display()
+        ));
+    }
+
+    Ok(())
+}
+
+fn display_diff(diff: &RevisionDiff) {
+    use cli_core::output::TableFormatter;
+    use comfy_table::Cell;
+
+    let side = |point: &history::Point| {
+        format!("{} ({}, {})", point.label, &point.commit[..7], point.date)
+    };
+    println!(
+        "{} {} → {}",
+        Theme::header(format!("📦 {}:", get_repository_name(&diff.path))),
+        side(&diff.from),
+        side(&diff.to)
+    );
+    println!();
+
+    let signed = |value: i64| match value {
+        v if v > 0 => format!("+{}", format_integer(v)),
+        v => format_integer(v),
+    };
+    let currency = diff.to.cost.currency;
+    let signed_money = |value: f64| match currency.format(value) {
+        money if value > 0.0 && money != currency.format(0.0) => format!("+{money}"),
+        money => money,
+    };
+
+    if !diff.languages.is_empty() {
+        let mut table = TableFormatter::create_table();
+        table.set_header(vec![
+            TableFormatter::header_cell("Language"),
+            TableFormatter::header_cell("Added"),
+            TableFormatter::header_cell("Removed"),
+            TableFormatter::header_cell("Changed"),
+            TableFormatter::header_cell("Net"),
+        ]);
+        for language in &diff.languages {
+            table.add_row(vec![
+                Cell::new(&language.language),
+                Cell::new(format!("{:>8}", format_integer(language.lines.added as i64))),
+                Cell::new(format!("{:>8}", format_integer(language.lines.removed as i64))),
+                Cell::new(format!("{:>8}", format_integer(language.lines.changed as i64))),
+                Cell::new(format!("{:>8}", signed(language.lines.net()))),
+            ]);
+        }
+        println!("{table}");
+        println!();
+    }
+
+    if !diff.directories.is_empty() {
+        println!("{}", Theme::info("Top Directories:"));
+        let mut table = TableFormatter::create_table();
+        table.set_header(vec![
+            TableFormatter::header_cell("Directory"),
+            TableFormatter::header_cell("Added"),
+            TableFormatter::header_cell("Removed"),
+            TableFormatter::header_cell("Changed"),
+            TableFormatter::header_cell("Share"),
+            TableFormatter::header_cell("Est. Hours"),
+            TableFormatter::header_cell(format!("Value ({currency})")),
+        ]);
+        for directory in &diff.directories {
+            table.add_row(vec![
+                Cell::new(&directory.directory),
+                Cell::new(format!("{:>8}", format_integer(directory.lines.added as i64))),
+                Cell::new(format!("{:>8}", format_integer(directory.lines.removed as i64))),
+                Cell::new(format!("{:>8}", format_integer(directory.lines.changed as i64))),
+                Cell::new(format!("{:>5.1}%", directory.share * 100.0)),
+                Cell::new(format!("{:>9.1}", directory.hours)),
+                Cell::new(format!("{:>13}", signed_money(directory.cost))),
+            ]);
+        }
+        println!("{table}");
+        println!();
+    }
+
+    println!("{}", Theme::header("📊 Delta"));
+    println!(
+        "  {} {} ({} added, {} removed, {} changed)",
+        Theme::dim("Files changed:"),
+        Theme::highlight(diff.files_changed.to_string()),
+        format_integer(diff.lines.added as i64),
+        format_integer(diff.lines.removed as i64),
+        format_integer(diff.lines.changed as i64)
+    );
+    println!(
+        "  {} {} → {} ({})",
+        Theme::dim("Code lines:"),
+        format_integer(diff.from.metrics.code_lines as i64),
+        format_integer(diff.to.metrics.code_lines as i64),
+        signed(diff.to.metrics.code_lines as i64 - diff.from.metrics.code_lines as i64)
+    );
+    println!(
+        "  {} {:.1} → {:.1} ({})",
+        Theme::dim("Estimated hours:"),
+        diff.from.cost.estimated_hours,
+        diff.to.cost.estimated_hours,
+        Theme::highlight(format!("{:+.1}", diff.hours))
+    );
+    println!(
+        "  {} {} → {} ({})",
+        Theme::dim("Estimated cost:"),
+        currency.format(diff.from.cost.total_cost),
+        currency.format(diff.to.cost.total_cost),
+        Theme::highlight(signed_money(diff.cost))
+    );
+}
+
+fn export_diff(diff: &RevisionDiff, export_path: &Path) -> Result<()> {
+    use cli_core::output::{CsvExporter, ExportFormat, HtmlExporter, MarkdownExporter, ToReport};
+
+    let ext = export_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .ok_or_else(|| anyhow::anyhow!("No file extension provided"))?;
+    let output = export_path.to_str().unwrap();
+
+    match ExportFormat::from_extension(ext)? {
+        ExportFormat::Csv => CsvExporter::new().export(&diff_rows(diff), output)?,
+        ExportFormat::Html => {
+            HtmlExporter::new().export(&report::DiffReport(diff).to_report(), output)?
+        }
+        ExportFormat::Markdown => {
+            MarkdownExporter::new().export(&report::DiffReport(diff).to_report(), output)?
+        }
     }
 
     Ok(())
 }
 
+/// One language, directory or total line of a diff in CSV exports.
+#[derive(serde::Serialize)]
+struct DiffRow {
+    kind: &'static str,
+    name: String,
+    added: usize,
+    removed: usize,
+    changed: usize,
+    estimated_hours: Option<f64>,
+    currency: Currency,
+    cost: Option<f64>,
+}
+
+fn diff_rows(diff: &RevisionDiff) -> Vec<DiffRow> {
+    let languages = diff.languages.iter().map(|language| DiffRow {
+        kind: "language",
+        name: language.language.clone(),
+        added: language.lines.added,
+        removed: language.lines.removed,
+        changed: language.lines.changed,
+        estimated_hours: None,
+        currency: diff.to.cost.currency,
+        cost: None,
+    });
+    let directories = diff.directories.iter().map(|directory| DiffRow {
+        kind: "directory",
+        name: directory.directory.clone(),
+        added: directory.lines.added,
+        removed: directory.lines.removed,
+        changed: directory.lines.changed,
+        estimated_hours: Some(directory.hours),
+        currency: diff.to.cost.currency,
+        cost: Some(directory.cost),
+    });
+    let total = DiffRow {
+        kind: "total",
+        name: format!("{}..{}", diff.from.label, diff.to.label),
+        added: diff.lines.added,
+        removed: diff.lines.removed,
+        changed: diff.lines.changed,
+        estimated_hours: Some(diff.hours),
+        currency: diff.to.cost.currency,
+        cost: Some(diff.cost),
+    };
+    languages
+        .chain(directories)
+        .chain(std::iter::once(total))
+        .collect()
+}
+
+/// Resolve the shared configuration for `path`, with command-line flags on top.
+fn load_config(path: &Path, cli: &Cli) -> Result<Config> {
+    let mut layered = LayeredConfig::load(path)?;
+    cli_core::currency::set_flags(&mut layered, cli.hourly_rate, cli.currency);
+    if let Some(model) = &cli.model {
+        layered.set_flag("code_cost.model", model.as_str());
+    }
+    if !cli.models.is_empty() {
+        layered.set_flag("code_cost.llm_models", cli.models.clone());
+    }
+    if let Some(coverage) = &cli.coverage {
+        // Relative to the working directory, not the repository.
+        let coverage = std::path::absolute(coverage)?;
+        layered.set_flag("code_cost.coverage", coverage.to_string_lossy().into_owned());
+    }
+    layered.config()
+}
+
 fn display_results(
-    results: &[(&PathBuf, analyzer::Analysis, calculator::CostEstimate)],
+    results: &[report::RepositoryCost],
     cli: &Cli,
+    format: OutputFormat,
 ) -> Result<()> {
-    use cli_core::output::{OutputFormat, TableFormatter};
+    use cli_core::output::TableFormatter;
     use comfy_table::{Cell, Color};
 
-    let format: OutputFormat = cli.format.parse()?;
-
     match format {
         OutputFormat::Table => {
+            let currency = results.first().map(|r| r.cost.currency).unwrap_or_default();
             let mut table = TableFormatter::create_table();
 
             table.set_header(vec![
@@ -148,10 +947,15 @@ fn display_results(
                 TableFormatter::header_cell("Files"),
                 TableFormatter::header_cell("Commits"),
                 TableFormatter::header_cell("Est. Hours"),
-                TableFormatter::header_cell("Total Cost (KRW)"),
+                TableFormatter::header_cell(format!("Total Cost ({currency})")),
             ]);
 
-            for (path, analysis, cost) in results {
+            for report::RepositoryCost {
+                path,
+                analysis,
+                cost,
+            } in results
+            {
                 let repo_name = get_repository_name(path);
 
                 table.add_row(vec![
@@ -160,7 +964,7 @@ fn display_results(
                     Cell::new(format!("{:>6}", analysis.total_files)),
                     Cell::new(format!("{:>7}", analysis.commit_count)),
                     Cell::new(format!("{:>10.1}", cost.estimated_hours)),
-                    Cell::new(format!("₩{:>12}", format_integer(cost.total_cost as i64)))
+                    Cell::new(format!("{:>14}", cost.currency.format(cost.total_cost)))
                         .fg(Color::Green),
                 ]);
             }
@@ -170,7 +974,12 @@ fn display_results(
             // Detailed analysis (unless --simple)
             if !cli.simple {
                 println!();
-                for (path, analysis, cost) in results {
+                for report::RepositoryCost {
+                    path,
+                    analysis,
+                    cost,
+                } in results
+                {
                     let repo_name = get_repository_name(path);
                     println!("{}", Theme::header(&format!("📁 {}", repo_name)));
                     println!();
@@ -195,6 +1004,13 @@ fn display_results(
 
                     // Project metrics
                     println!("{}", Theme::info("Project Metrics:"));
+                    println!(
+                        "  • Lines: {} code, {} comments, {} docs, {} blank",
+                        Theme::highlight(format_integer(analysis.code_lines as i64)),
+                        format_integer(analysis.comment_lines as i64),
+                        format_integer(analysis.doc_lines as i64),
+                        format_integer(analysis.blank_lines as i64)
+                    );
                     println!(
                         "  • Complexity Score: {}",
                         Theme::highlight(&format!("{:.2}/5.0", analysis.complexity_score))
@@ -212,42 +1028,219 @@ fn display_results(
                         Theme::highlight(&analysis.test_file_count.to_string()),
                         (analysis.test_file_count as f64 / analysis.total_files as f64) * 100.0
                     );
-                    println!();
-
-                    // AI Analysis
-                    println!("{}", Theme::info("AI Usage Analysis:"));
+                    if let Some(coverage) = &analysis.coverage {
+                        println!(
+                            "  • Coverage: {} {}",
+                            Theme::highlight(coverage.total.describe()),
+                            Theme::dim(format!(
+                                "({} files from {} report {})",
+                                coverage.files,
+                                coverage.format,
+                                coverage.report.display()
+                            ))
+                        );
+                        for language in &coverage.languages {
+                            println!(
+                                "    - {}: {} {}",
+                                language.name,
+                                language.counts.describe(),
+                                Theme::dim(format!("({} files)", language.files))
+                            );
+                        }
+                    }
+                    let duplication = &cost.duplication_discount;
                     println!(
-                        "  • Estimated AI Usage: {}",
-                        Theme::highlight(&format!("{:.1}%", cost.ai_analysis.estimated_ai_usage * 100.0))
+                        "  • Duplication: {} {}",
+                        Theme::highlight(format!("{:.1}%", duplication.share * 100.0)),
+                        Theme::dim(format!(
+                            "({} lines in {} blocks, {} code lines discounted)",
+                            format_integer(duplication.duplicated_lines as i64),
+                            analysis.duplication.blocks.len(),
+                            format_integer(duplication.discounted_lines as i64)
+                        ))
                     );
-                    if !cost.ai_analysis.potential_ai_indicators.is_empty() {
-                        println!("  {} Indicators:", Theme::dim("•"));
-                        for indicator in &cost.ai_analysis.potential_ai_indicators {
-                            println!("    - {}", Theme::dim(indicator));
-                        }
+                    let complexity = &analysis.complexity;
+                    if complexity.functions > 0 {
+                        println!(
+                            "  • Functions: {} {}",
+                            Theme::highlight(format_integer(complexity.functions as i64)),
+                            Theme::dim(format!(
+                                "(cyclomatic mean {:.1}, p90 {}, max {}; cognitive mean {:.1})",
+                                complexity.mean_cyclomatic,
+                                complexity.p90_cyclomatic,
+                                complexity.max_cyclomatic,
+                                complexity.mean_cognitive
+                            ))
+                        );
                     }
                     println!();
 
-                    // Token Cost Analysis
-                    println!("{}", Theme::info("AI Dev Simulation Token Cost (Claude Opus 4.7 xhigh):"));
+                    if !complexity.top.is_empty() {
+                        println!("{}", Theme::info("Most Complex Functions:"));
+                        for function in &complexity.top {
+                            println!(
+                                "  • {} {} cyclomatic {}, cognitive {}, nesting {}, {} lines",
+                                Theme::value(&function.stats.name),
+                                Theme::dim(format!("{}:{}", function.path, function.stats.line)),
+                                Theme::highlight(function.stats.cyclomatic.to_string()),
+                                function.stats.cognitive,
+                                function.stats.nesting,
+                                function.stats.length
+                            );
+                        }
+                        println!();
+                    }
+
+                    let blocks = &analysis.duplication.blocks;
+                    if !blocks.is_empty() {
+                        println!("{}", Theme::info("Largest Duplicated Blocks:"));
+                        for block in blocks.iter().take(DUPLICATED_BLOCKS_SHOWN) {
+                            println!(
+                                "  • {} {} {}",
+                                Theme::value(block.copy.to_string()),
+                                Theme::dim("copies"),
+                                block.original
+                            );
+                            println!(
+                                "    - {}",
+                                Theme::dim(format!("{} lines, {} tokens", block.lines, block.tokens))
+                            );
+                        }
+                        println!();
+                    }
+
+                    let dependencies = &analysis.dependencies;
+                    if !dependencies.lockfiles.is_empty() {
+                        println!("{}", Theme::info("Dependencies:"));
+                        for lockfile in &dependencies.lockfiles {
+                            println!(
+                                "  • {} {} {} direct, {} transitive, {} duplicated",
+                                Theme::value(lockfile.path.display().to_string()),
+                                Theme::dim(format!("({})", lockfile.ecosystem)),
+                                Theme::highlight(lockfile.direct.to_string()),
+                                lockfile.transitive,
+                                lockfile.duplicates.len()
+                            );
+                            if !lockfile.duplicates.is_empty() {
+                                let duplicates: Vec<String> = lockfile
+                                    .duplicates
+                                    .iter()
+                                    .take(DUPLICATES_SHOWN)
+                                    .map(|duplicate| format!("{} {}", duplicate.name, duplicate.versions.join("/")))
+                                    .collect();
+                                println!("    - {}", Theme::dim(duplicates.join(", ")));
+                            }
+                        }
+                        let licenses: Vec<String> = dependencies
+                            .licenses()
+                            .into_iter()
+                            .take(LICENSES_SHOWN)
+                            .map(|(license, count)| format!("{license} ({count})"))
+                            .collect();
+                        println!("  • Licences: {}", Theme::dim(licenses.join(", ")));
+                        let maintenance = &cost.dependency_maintenance;
+                        println!(
+                            "  • Maintenance: {} {}",
+                            Theme::highlight(format!("{:.1} hours", maintenance.hours)),
+                            Theme::dim(format!("({})", cost.currency.format(maintenance.cost)))
+                        );
+                        println!();
+                    }
+
+                    let estimate = &cost.estimate;
                     println!(
-                        "  • Base Codebase Tokens: {}",
-                        Theme::value(&format_integer(cost.token_cost.base_tokens as i64))
+                        "{}",
+                        Theme::info(format!("Estimation Model ({}):", estimate.model))
                     );
                     println!(
-                        "  • Estimated Prompt Tokens: {} {}",
-                        Theme::value(&format_integer(cost.token_cost.estimated_prompt_tokens as i64)),
-                        Theme::dim("(10x context multiplier)")
+                        "  • Effort: {} {}",
+                        Theme::highlight(format!("{:.1} person-months", estimate.effort_person_months)),
+                        Theme::dim(format!("({:.1} hours)", estimate.effort_hours))
                     );
                     println!(
-                        "  • Estimated Output Tokens: {} {}",
-                        Theme::value(&format_integer(cost.token_cost.estimated_output_tokens as i64)),
-                        Theme::dim("(1.5x revision multiplier)")
+                        "  • Schedule: {} with {} people",
+                        Theme::highlight(format!("{:.1} months", estimate.schedule_months)),
+                        Theme::highlight(format!("{:.1}", estimate.team_size))
                     );
+                    println!("  {} Assumptions:", Theme::dim("•"));
+                    for assumption in &estimate.assumptions {
+                        println!(
+                            "    - {}: {}",
+                            assumption.name,
+                            Theme::dim(&assumption.value)
+                        );
+                    }
+                    println!();
+
+                    // AI Analysis
+                    println!("{}", Theme::info("AI Usage Analysis:"));
                     println!(
-                        "  • Total API Cost: ₩{}",
-                        Theme::highlight(&format_integer(cost.token_cost.total_cost_krw.round() as i64))
+                        "  • Estimated AI Usage: {} {}",
+                        Theme::highlight(&format!("{:.1}%", cost.ai_analysis.estimated_ai_usage * 100.0)),
+                        Theme::dim(format!(
+                            "of inserted lines, {} attributed commits",
+                            cost.ai_analysis.attributed_commits
+                        ))
                     );
+                    if cost.ai_analysis.evidence.is_empty() {
+                        println!("  • {}", Theme::dim("No evidence of AI assistance in the history"));
+                    }
+                    for signal in &cost.ai_analysis.evidence {
+                        println!("  • {} ({}):", signal.kind.name(), signal.commits.len());
+                        for commit in signal.commits.iter().take(AI_EVIDENCE_SHOWN) {
+                            let tool = commit.tool.as_deref().map(|tool| format!("[{tool}] ")).unwrap_or_default();
+                            println!(
+                                "    - {} {} {}{}",
+                                Theme::highlight(commit.short_id()),
+                                Theme::dim(commit.date()),
+                                tool,
+                                commit.detail
+                            );
+                        }
+                        if signal.commits.len() > AI_EVIDENCE_SHOWN {
+                            println!(
+                                "    {}",
+                                Theme::dim(format!("… {} more", signal.commits.len() - AI_EVIDENCE_SHOWN))
+                            );
+                        }
+                    }
+                    println!();
+
+                    // Token Cost Analysis
+                    if let [token] = cost.token_costs.as_slice() {
+                        println!(
+                            "{}",
+                            Theme::info(format!("AI Dev Simulation Token Cost ({}):", token.model_name))
+                        );
+                        println!(
+                            "  • Base Codebase Tokens: {} {}",
+                            Theme::value(format_integer(token.base_tokens as i64)),
+                            Theme::dim(format!("({})", token.tokenizer))
+                        );
+                        println!(
+                            "  • Estimated Prompt Tokens: {} {}",
+                            Theme::value(format_integer(token.estimated_prompt_tokens as i64)),
+                            Theme::dim("(10x context multiplier)")
+                        );
+                        if token.cached_prompt_tokens > 0 {
+                            println!(
+                                "  • Read from Prompt Cache: {}",
+                                Theme::value(format_integer(token.cached_prompt_tokens as i64))
+                            );
+                        }
+                        println!(
+                            "  • Estimated Output Tokens: {} {}",
+                            Theme::value(format_integer(token.estimated_output_tokens as i64)),
+                            Theme::dim("(1.5x revision multiplier)")
+                        );
+                        println!(
+                            "  • Total API Cost: {}",
+                            Theme::highlight(cost.currency.format(token.total_cost))
+                        );
+                    } else {
+                        println!("{}", Theme::info("AI Dev Simulation Token Cost:"));
+                        println!("{}", token_cost_table(cost));
+                    }
                     println!();
 
                     // Developer level breakdown (if requested)
@@ -255,10 +1248,10 @@ fn display_results(
                         println!("{}", Theme::info("Developer Level Breakdown:"));
                         for level in &cost.developer_levels {
                             println!(
-                                "  • {:<12} ₩{:>8}/hr → ₩{}",
+                                "  • {:<12} {:>10}/hr → {}",
                                 Theme::value(&level.level),
-                                format_integer(level.hourly_rate as i64),
-                                Theme::highlight(&format_integer(level.estimated_cost as i64))
+                                cost.currency.format(level.hourly_rate),
+                                Theme::highlight(cost.currency.format(level.estimated_cost))
                             );
                         }
                         println!();
@@ -267,8 +1260,8 @@ fn display_results(
             }
 
             // Summary
-            let total_cost: f64 = results.iter().map(|(_, _, c)| c.total_cost).sum();
-            let total_hours: f64 = results.iter().map(|(_, _, c)| c.estimated_hours).sum();
+            let total_cost: f64 = results.iter().map(|r| r.cost.total_cost).sum();
+            let total_hours: f64 = results.iter().map(|r| r.cost.estimated_hours).sum();
 
             println!();
             println!("{}", Theme::header("📊 Summary"));
@@ -285,37 +1278,94 @@ fn display_results(
             println!(
                 "  {} {}",
                 Theme::dim("Total estimated cost:"),
-                Theme::highlight(&format!("₩{}", format_integer(total_cost as i64)))
+                Theme::highlight(currency.format(total_cost))
             );
         }
-        OutputFormat::Json | OutputFormat::JsonPretty => {
-            use cli_core::output::{Formatter, JsonFormatter};
-
-            let json_results: Vec<_> = results
-                .iter()
-                .map(|(path, analysis, cost)| {
-                    serde_json::json!({
-                        "path": path.to_string_lossy(),
-                        "metrics": analysis,
-                        "cost": cost,
-                    })
-                })
-                .collect();
-
-            let formatter = JsonFormatter::new(format == OutputFormat::JsonPretty);
-            let output = formatter.format(&json_results)?;
-            println!("{}", output);
+        OutputFormat::Csv => {
+            format.print(&export_rows(results))?;
+        }
+        OutputFormat::Json | OutputFormat::JsonPretty | OutputFormat::Yaml | OutputFormat::Ndjson => {
+            format.print(&results)?;
         }
     }
 
     Ok(())
 }
 
-fn export_results(
-    results: &[(&PathBuf, analyzer::Analysis, calculator::CostEstimate)],
-    export_path: &PathBuf,
-) -> Result<()> {
-    use cli_core::output::ExportFormat;
+/// `--models` side by side: tokens with each model's tokenizer and what they cost.
+fn token_cost_table(cost: &calculator::CostEstimate) -> comfy_table::Table {
+    use cli_core::output::TableFormatter;
+    use comfy_table::{Cell, Color};
+
+    let mut table = TableFormatter::create_table();
+    table.set_header(vec![
+        TableFormatter::header_cell("Model"),
+        TableFormatter::header_cell("Tokenizer"),
+        TableFormatter::header_cell("Base Tokens"),
+        TableFormatter::header_cell("Prompt"),
+        TableFormatter::header_cell("Cached"),
+        TableFormatter::header_cell("Output"),
+        TableFormatter::header_cell(format!("API Cost ({})", cost.currency)),
+    ]);
+    for token in &cost.token_costs {
+        table.add_row(vec![
+            Cell::new(&token.model_name),
+            Cell::new(token.tokenizer),
+            Cell::new(format!("{:>10}", format_integer(token.base_tokens as i64))),
+            Cell::new(format!("{:>12}", format_integer(token.estimated_prompt_tokens as i64))),
+            Cell::new(format!("{:>12}", format_integer(token.cached_prompt_tokens as i64))),
+            Cell::new(format!("{:>10}", format_integer(token.estimated_output_tokens as i64))),
+            Cell::new(format!("{:>12}", cost.currency.format(token.total_cost))).fg(Color::Green),
+        ]);
+    }
+    table
+}
+
+fn export_rows(results: &[report::RepositoryCost]) -> Vec<ExportRow> {
+    results
+        .iter()
+        .map(
+            |report::RepositoryCost {
+                 path,
+                 analysis,
+                 cost,
+             }| ExportRow {
+                path: path.to_string_lossy().to_string(),
+                lines: analysis.total_lines,
+                files: analysis.total_files,
+                commits: analysis.commit_count,
+                line_coverage: analysis
+                    .coverage
+                    .as_ref()
+                    .map(|coverage| coverage.total.line_rate()),
+                branch_coverage: analysis
+                    .coverage
+                    .as_ref()
+                    .and_then(|coverage| coverage.total.branch_rate()),
+                duplicated_lines: analysis.duplication.duplicated_lines,
+                duplication_discount_hours: cost.duplication_discount.hours,
+                direct_dependencies: cost.dependency_maintenance.direct,
+                transitive_dependencies: cost.dependency_maintenance.transitive,
+                dependency_hours: cost.dependency_maintenance.hours,
+                api_model: cost.token_cost().model.clone(),
+                base_tokens: cost.token_cost().base_tokens,
+                estimated_prompt_tokens: cost.token_cost().estimated_prompt_tokens,
+                estimated_output_tokens: cost.token_cost().estimated_output_tokens,
+                estimated_hours: cost.estimated_hours,
+                model: cost.estimate.model.clone(),
+                effort_person_months: cost.estimate.effort_person_months,
+                schedule_months: cost.estimate.schedule_months,
+                team_size: cost.estimate.team_size,
+                currency: cost.currency,
+                total_cost: cost.total_cost,
+                total_api_cost: cost.token_cost().total_cost,
+            },
+        )
+        .collect()
+}
+
+fn export_results(results: &[report::RepositoryCost], export_path: &PathBuf) -> Result<()> {
+    use cli_core::output::{ExportFormat, ToReport};
 
     let ext = export_path
         .extension()
@@ -324,39 +1374,50 @@ fn export_results(
 
     let format = ExportFormat::from_extension(ext)?;
 
-    let export_data: Vec<ExportRow> = results
-        .iter()
-        .map(|(path, analysis, cost)| ExportRow {
-            path: path.to_string_lossy().to_string(),
-            lines: analysis.total_lines,
-            files: analysis.total_files,
-            commits: analysis.commit_count,
-            base_tokens: cost.token_cost.base_tokens,
-            estimated_prompt_tokens: cost.token_cost.estimated_prompt_tokens,
-            estimated_output_tokens: cost.token_cost.estimated_output_tokens,
-            estimated_hours: cost.estimated_hours,
-            total_cost_krw: cost.total_cost,
-            total_api_cost_krw: cost.token_cost.total_cost_krw,
-        })
-        .collect();
-
     match format {
         ExportFormat::Csv => {
             use cli_core::output::CsvExporter;
             let exporter = CsvExporter::new();
-            exporter.export(&export_data, export_path.to_str().unwrap())?;
+            exporter.export(&export_rows(results), export_path.to_str().unwrap())?;
         }
         ExportFormat::Html => {
             use cli_core::output::HtmlExporter;
             let exporter = HtmlExporter::new();
-            exporter.export(&export_data, export_path.to_str().unwrap())?;
+            exporter.export(
+                &report::CostReport(results).to_report(),
+                export_path.to_str().unwrap(),
+            )?;
         }
         ExportFormat::Markdown => {
             use cli_core::output::MarkdownExporter;
             let exporter = MarkdownExporter::new();
-            exporter.export(&export_data, export_path.to_str().unwrap())?;
+            exporter.export(
+                &report::CostReport(results).to_report(),
+                export_path.to_str().unwrap(),
+            )?;
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repository_paths_accept_local_file_urls() {
+        for (arg, path) in [
+            ("repo", "repo"),
+            ("/srv/git/repo.git", "/srv/git/repo.git"),
+            ("file:///srv/git/repo.git", "/srv/git/repo.git"),
+            ("file://localhost/srv/git/repo.git", "/srv/git/repo.git"),
+        ] {
+            assert_eq!(repository_path(arg), Ok(PathBuf::from(path)), "{arg}");
+        }
+        assert!(repository_path("file://repo.git").is_err());
+        assert!(repository_path("https://example.com/repo.git")
+            .unwrap_err()
+            .contains("not supported"));
+    }
+}
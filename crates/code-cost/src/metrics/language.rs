@@ -0,0 +1,480 @@
+//! Language detection.
+//!
+//! A file's language comes from, in order: `.gitattributes` `linguist-language`
+//! overrides, well-known filenames, the shebang line, the extension, and finally
+//! content heuristics for ambiguous extensions and extensionless files. Files
+//! marked `linguist-vendored` or `linguist-generated` are not counted.
+
+use globset::{GlobBuilder, GlobMatcher};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Bytes read from the start of a file for shebang and content checks.
+const HEAD_LEN: usize = 1024;
+
+/// Every language the detector can report, in its canonical spelling.
+const LANGUAGES: &[&str] = &[
+    "C",
+    "C#",
+    "C++",
+    "C/C++ Header",
+    "CMake",
+    "CSS",
+    "Dart",
+    "Dockerfile",
+    "Go",
+    "HCL",
+    "HTML",
+    "JSON",
+    "Java",
+    "JavaScript",
+    "Just",
+    "Kotlin",
+    "Lua",
+    "Makefile",
+    "Markdown",
+    "PHP",
+    "Perl",
+    "Protocol Buffer",
+    "Python",
+    "Ruby",
+    "Rust",
+    "SCSS",
+    "SQL",
+    "Scala",
+    "Shell",
+    "Svelte",
+    "Swift",
+    "TOML",
+    "TypeScript",
+    "Vue",
+    "XML",
+    "YAML",
+    "Zig",
+];
+
+/// A `.gitattributes` line that sets at least one linguist attribute.
+struct AttributeRule {
+    matcher: GlobMatcher,
+    /// `Some(None)` unsets a language given by an earlier line.
+    language: Option<Option<String>>,
+    vendored: Option<bool>,
+    generated: Option<bool>,
+}
+
+/// Linguist attributes in effect for one path.
+#[derive(Default)]
+struct Attributes {
+    language: Option<String>,
+    vendored: bool,
+    generated: bool,
+}
+
+/// Detects languages under one root, applying its top-level `.gitattributes`.
+pub(super) struct Detector {
+    root: PathBuf,
+    rules: Vec<AttributeRule>,
+}
+
+impl Detector {
+    pub(super) fn load(root: &Path) -> Self {
+        let content = fs::read_to_string(root.join(".gitattributes")).unwrap_or_default();
+        Self::parse(root, &content)
+    }
+
+    /// A detector for `root` using the given `.gitattributes` content.
+    pub(super) fn parse(root: &Path, gitattributes: &str) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            rules: gitattributes.lines().filter_map(parse_rule).collect(),
+        }
+    }
+
+    /// Language of `path`, or `None` when it is not source or is vendored or generated.
+    pub(super) fn detect(&self, path: &Path) -> Option<String> {
+        self.detect_with(path, || read_head(path))
+    }
+
+    /// Like [`detect`](Self::detect), for a blob whose content is already in memory.
+    pub(super) fn detect_blob(&self, path: &Path, content: &[u8]) -> Option<String> {
+        self.detect_with(path, || Some(head_of(content)))
+    }
+
+    fn detect_with(&self, path: &Path, head: impl FnOnce() -> Option<String>) -> Option<String> {
+        let attributes = self.attributes(path);
+        if attributes.vendored || attributes.generated {
+            return None;
+        }
+        match attributes.language {
+            Some(language) => Some(canonical_name(&language)),
+            None => identify(path, head),
+        }
+    }
+
+    fn attributes(&self, path: &Path) -> Attributes {
+        let mut attributes = Attributes::default();
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return attributes;
+        };
+        // Like git, later lines override earlier ones attribute by attribute.
+        for rule in self
+            .rules
+            .iter()
+            .filter(|rule| rule.matcher.is_match(relative))
+        {
+            if let Some(language) = &rule.language {
+                attributes.language = language.clone();
+            }
+            if let Some(vendored) = rule.vendored {
+                attributes.vendored = vendored;
+            }
+            if let Some(generated) = rule.generated {
+                attributes.generated = generated;
+            }
+        }
+        attributes
+    }
+}
+
+fn parse_rule(line: &str) -> Option<AttributeRule> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') || line.starts_with("[attr]") {
+        return None;
+    }
+    let mut fields = line.split_whitespace();
+    let pattern = fields.next()?;
+
+    let mut rule = AttributeRule {
+        matcher: pattern_matcher(pattern)?,
+        language: None,
+        vendored: None,
+        generated: None,
+    };
+    for field in fields {
+        let (name, value) = match field.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (field, None),
+        };
+        let (name, set) = match name.strip_prefix(['-', '!']) {
+            Some(name) => (name, false),
+            None => (name, true),
+        };
+        let flag = set && value.is_none_or(|v| v != "false");
+        match name {
+            "linguist-language" => rule.language = Some(value.filter(|_| set).map(String::from)),
+            "linguist-vendored" => rule.vendored = Some(flag),
+            "linguist-generated" => rule.generated = Some(flag),
+            _ => {}
+        }
+    }
+
+    let sets_any = rule.language.is_some() || rule.vendored.is_some() || rule.generated.is_some();
+    sets_any.then_some(rule)
+}
+
+/// Compile a gitattributes pattern: patterns without a slash match a file name
+/// at any depth, others are anchored to the repository root.
+fn pattern_matcher(pattern: &str) -> Option<GlobMatcher> {
+    let pattern = pattern.strip_suffix('/').unwrap_or(pattern);
+    let glob = match pattern.strip_prefix('/') {
+        Some(anchored) => anchored.to_string(),
+        None if pattern.contains('/') => pattern.to_string(),
+        None => format!("**/{}", pattern),
+    };
+    // Git ignores lines it cannot parse, so an unusable pattern is skipped too.
+    GlobBuilder::new(&glob)
+        .literal_separator(true)
+        .build()
+        .ok()
+        .map(|glob| glob.compile_matcher())
+}
+
+/// Map a `linguist-language` value to our spelling. Values cannot contain
+/// spaces, so `Protocol-Buffer` and `protocol_buffer` both name "Protocol Buffer".
+fn canonical_name(value: &str) -> String {
+    let normalized = value.replace(['-', '_'], " ");
+    let alias = match normalized.to_lowercase().as_str() {
+        "js" | "node" => Some("JavaScript"),
+        "ts" => Some("TypeScript"),
+        "sh" | "bash" | "zsh" => Some("Shell"),
+        "py" => Some("Python"),
+        "rb" => Some("Ruby"),
+        "cpp" => Some("C++"),
+        "csharp" => Some("C#"),
+        "terraform" => Some("HCL"),
+        "docker" => Some("Dockerfile"),
+        "make" => Some("Makefile"),
+        "protobuf" => Some("Protocol Buffer"),
+        _ => None,
+    };
+    alias
+        .or_else(|| {
+            LANGUAGES
+                .iter()
+                .copied()
+                .find(|lang| lang.eq_ignore_ascii_case(&normalized))
+        })
+        .map(String::from)
+        .unwrap_or(normalized)
+}
+
+/// Language of `path` from its name and, when that is not enough, the first
+/// [`HEAD_LEN`] bytes of content.
+fn identify(path: &Path, head: impl FnOnce() -> Option<String>) -> Option<String> {
+    let name = path.file_name()?.to_str()?;
+    if let Some(lang) = by_filename(name) {
+        return Some(lang.to_string());
+    }
+
+    let head = head();
+    if let Some(lang) = head.as_deref().and_then(by_shebang) {
+        return Some(lang.to_string());
+    }
+
+    let ext = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+    let by_ext = ext.as_deref().and_then(by_extension);
+    by_content(ext.as_deref(), head.as_deref().unwrap_or(""))
+        .or(by_ext)
+        .map(String::from)
+}
+
+fn read_head(path: &Path) -> Option<String> {
+    let mut buf = Vec::with_capacity(HEAD_LEN);
+    File::open(path)
+        .ok()?
+        .take(HEAD_LEN as u64)
+        .read_to_end(&mut buf)
+        .ok()?;
+    Some(head_of(&buf))
+}
+
+fn head_of(content: &[u8]) -> String {
+    String::from_utf8_lossy(&content[..content.len().min(HEAD_LEN)]).into_owned()
+}
+
+fn by_filename(name: &str) -> Option<&'static str> {
+    let lang = match name {
+        "Dockerfile" | "Containerfile" => "Dockerfile",
+        "Makefile" | "makefile" | "GNUmakefile" => "Makefile",
+        "justfile" | "Justfile" | ".justfile" => "Just",
+        "CMakeLists.txt" => "CMake",
+        "Rakefile" | "Gemfile" | "Podfile" | "Vagrantfile" | "Brewfile" | "Guardfile" => "Ruby",
+        ".bashrc" | ".bash_profile" | ".zshrc" | ".zprofile" | ".profile" => "Shell",
+        _ if name.starts_with("Dockerfile.") || name.starts_with("Containerfile.") => "Dockerfile",
+        _ => return None,
+    };
+    Some(lang)
+}
+
+/// Language named by a `#!` line, looking through `env` to the real interpreter.
+fn by_shebang(head: &str) -> Option<&'static str> {
+    let line = head.strip_prefix("#!")?.lines().next()?;
+    let mut words = line.split_whitespace();
+    let mut interpreter = words.next()?.rsplit('/').next()?;
+    if interpreter == "env" {
+        interpreter = words.find(|word| !word.starts_with('-'))?;
+    }
+    // `python3.12` and `python3` are both Python.
+    let interpreter = interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+
+    let lang = match interpreter {
+        "python" | "pypy" => "Python",
+        "node" | "nodejs" | "deno" | "bun" => "JavaScript",
+        "ts-node" | "tsx" => "TypeScript",
+        "ruby" => "Ruby",
+        "sh" | "bash" | "zsh" | "dash" | "ksh" | "ash" => "Shell",
+        "perl" => "Perl",
+        "php" => "PHP",
+        "lua" | "luajit" => "Lua",
+        "dart" => "Dart",
+        "make" => "Makefile",
+        "just" => "Just",
+        _ => return None,
+    };
+    Some(lang)
+}
+
+fn by_extension(ext: &str) -> Option<&'static str> {
+    let lang = match ext {
+        "rs" => "Rust",
+        "py" | "pyi" => "Python",
+        "js" | "jsx" | "mjs" | "cjs" => "JavaScript",
+        "ts" | "tsx" | "mts" | "cts" => "TypeScript",
+        "go" => "Go",
+        "java" => "Java",
+        "c" => "C",
+        "cpp" | "cc" | "cxx" => "C++",
+        "h" | "hpp" => "C/C++ Header",
+        "cs" => "C#",
+        "rb" => "Ruby",
+        "php" => "PHP",
+        "swift" => "Swift",
+        "kt" | "kts" => "Kotlin",
+        "scala" => "Scala",
+        "sh" | "bash" | "zsh" => "Shell",
+        "sql" => "SQL",
+        "html" => "HTML",
+        "css" => "CSS",
+        "scss" | "sass" => "SCSS",
+        "md" | "markdown" => "Markdown",
+        "yaml" | "yml" => "YAML",
+        "json" => "JSON",
+        "toml" => "TOML",
+        "xml" => "XML",
+        "vue" => "Vue",
+        "svelte" => "Svelte",
+        "lua" => "Lua",
+        "dart" => "Dart",
+        "zig" => "Zig",
+        "proto" => "Protocol Buffer",
+        "tf" | "tfvars" | "hcl" => "HCL",
+        "pl" | "pm" => "Perl",
+        "mk" => "Makefile",
+        "just" => "Just",
+        "cmake" => "CMake",
+        "dockerfile" => "Dockerfile",
+        _ => return None,
+    };
+    Some(lang)
+}
+
+/// Content checks for extensions shared between languages and for files
+/// without a recognised extension.
+fn by_content(ext: Option<&str>, head: &str) -> Option<&'static str> {
+    let start = head.trim_start();
+    match ext {
+        // Qt Linguist translation files share `.ts` with TypeScript.
+        Some("ts") if start.starts_with("<?xml") || start.starts_with("<TS") => Some("XML"),
+        None => {
+            let lower: String = start
+                .chars()
+                .take(64)
+                .collect::<String>()
+                .to_ascii_lowercase();
+            if lower.starts_with("<?php") {
+                Some("PHP")
+            } else if lower.starts_with("<!doctype html") || lower.starts_with("<html") {
+                Some("HTML")
+            } else if lower.starts_with("<?xml") {
+                Some("XML")
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(detector: &Detector, cases: &[(&str, &str, Option<&str>)]) {
+        for (path, content, expected) in cases {
+            let path = Path::new("/repo").join(path);
+            assert_eq!(
+                detector.detect_blob(&path, content.as_bytes()).as_deref(),
+                *expected,
+                "{}",
+                path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn filename_then_shebang_then_extension_then_content() {
+        detect(
+            &Detector::parse(Path::new("/repo"), ""),
+            &[
+                // Well-known filenames win over everything else.
+                ("Dockerfile", "#!/bin/bash\n", Some("Dockerfile")),
+                ("Rakefile", "#!/usr/bin/env python\n", Some("Ruby")),
+                ("docker/Dockerfile.dev", "FROM rust\n", Some("Dockerfile")),
+                // Then the shebang, even over a known extension.
+                ("script.js", "#!/usr/bin/env python3\n", Some("Python")),
+                (
+                    "tool",
+                    "#!/usr/bin/env -S node --no-warnings\n",
+                    Some("JavaScript"),
+                ),
+                ("run", "#!/usr/local/bin/python3.12\n", Some("Python")),
+                ("install", "#!/bin/sh\nset -e\n", Some("Shell")),
+                // Then the extension, in any case.
+                ("src/main.rs", "fn main() {}\n", Some("Rust")),
+                ("LOUD.RS", "", Some("Rust")),
+                ("app.ts", "const x = 1;\n", Some("TypeScript")),
+                // Content decides ambiguous extensions and extensionless files.
+                ("i18n/de.ts", "<?xml version=\"1.0\"?>\n<TS>\n", Some("XML")),
+                ("i18n/fr.ts", "<TS version=\"2.1\">\n", Some("XML")),
+                ("index", "  <!DOCTYPE html>\n<html>\n", Some("HTML")),
+                ("page", "<?php echo 1;\n", Some("PHP")),
+                ("feed", "<?xml version=\"1.0\"?>\n", Some("XML")),
+                // Content is not consulted for other extensions.
+                ("notes.xyz", "<?php\n", None),
+                ("README", "Plain text\n", None),
+                ("run.txt", "#!/opt/custom/interp\n", None),
+            ],
+        );
+    }
+
+    #[test]
+    fn gitattributes_override_detection() {
+        let gitattributes = "\
+# Linguist overrides
+*.inc linguist-language=PHP
+*.h linguist-language=cpp
+*.proto linguist-language=protocol_buffer
+*.f90 linguist-language=Fortran-Free
+build/Makefile linguist-language=Just
+scripts/* linguist-language=Shell
+scripts/legacy.py -linguist-language
+vendor/** linguist-vendored
+vendor/keep.rs -linguist-vendored
+gen/** linguist-generated
+*.pb.go linguist-generated=true
+*.md linguist-generated
+docs/*.md linguist-generated=false
+";
+        detect(
+            &Detector::parse(Path::new("/repo"), gitattributes),
+            &[
+                // linguist-language beats every other rule, and is canonicalised.
+                ("lib/header.inc", "", Some("PHP")),
+                ("src/util.h", "", Some("C++")),
+                ("api/service.proto", "", Some("Protocol Buffer")),
+                ("solver.f90", "", Some("Fortran Free")),
+                ("build/Makefile", "", Some("Just")),
+                ("scripts/deploy", "#!/usr/bin/env python\n", Some("Shell")),
+                ("scripts/build.py", "", Some("Shell")),
+                // A later line unsets it again.
+                ("scripts/legacy.py", "", Some("Python")),
+                // Patterns with a slash are anchored to the root.
+                ("tools/scripts/run.py", "", Some("Python")),
+                // Vendored and generated files are not counted.
+                ("vendor/dep/lib.rs", "", None),
+                ("vendor/keep.rs", "", Some("Rust")),
+                ("gen/api.rs", "", None),
+                ("api/service.pb.go", "", None),
+                ("api/service.go", "", Some("Go")),
+                ("CHANGELOG.md", "", None),
+                ("docs/guide.md", "", Some("Markdown")),
+            ],
+        );
+    }
+
+    #[test]
+    fn gitattributes_only_apply_under_their_root() {
+        let detector = Detector::parse(Path::new("/repo"), "*.rs linguist-generated\n");
+        assert_eq!(detector.detect_blob(Path::new("/repo/a.rs"), b""), None);
+        assert_eq!(
+            detector
+                .detect_blob(Path::new("/elsewhere/a.rs"), b"")
+                .as_deref(),
+            Some("Rust")
+        );
+    }
+}
@@ -1,210 +1,523 @@
 use anyhow::Result;
-use std::collections::HashMap;
-use std::path::Path;
-use walkdir::WalkDir;
+use git2::{ObjectType, Oid, Repository, Tree, TreeWalkMode, TreeWalkResult};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::{WalkBuilder, WalkState};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
 
 use crate::analyzer::LanguageStat;
+use crate::cache::Cache;
+use crate::complexity::{self, FunctionReport, FunctionStats};
+use crate::coverage::{Coverage, CoverageReport, Tally};
+use crate::duplication::{self, Duplication, Fingerprint};
+use crate::tokenizer::{self, TokenCounts, Tokenizer};
+use language::Detector;
+
+mod language;
+pub(crate) mod lexer;
+
+/// Per-repository ignore file, using `.gitignore` syntax.
+pub(crate) const IGNORE_FILE: &str = ".codecostignore";
 
 #[derive(Debug, Clone)]
 pub struct Metrics {
     pub total_lines: usize,
     pub code_lines: usize,
     pub comment_lines: usize,
+    pub doc_lines: usize,
     pub blank_lines: usize,
     pub total_files: usize,
     pub test_file_count: usize,
-    pub total_tokens: usize,
+    pub total_tokens: TokenCounts,
     pub has_readme: bool,
     pub language_stats: Vec<LanguageStat>,
+    /// Every function found, with paths relative to the repository root.
+    pub functions: Vec<FunctionReport>,
+    /// Coverage of these files, when a report covers any of them.
+    pub coverage: Option<Coverage>,
+    /// Code copied between or within these files.
+    pub duplication: Duplication,
+}
+
+/// Counts for a single file; cached by blob id.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileStats {
+    pub lines: usize,
+    pub code: usize,
+    pub comments: usize,
+    pub docs: usize,
+    pub blanks: usize,
+    /// Counted with the heuristic and every tokenizer asked for when measured.
+    pub tokens: TokenCounts,
+    pub functions: Vec<FunctionStats>,
+    /// Winnowed token hashes, matched against other files for copies.
+    pub fingerprints: Vec<Fingerprint>,
 }
 
-pub struct MetricsCollector;
+impl FileStats {
+    fn measure(content: &str, lang: &str, tokenizers: &[Tokenizer]) -> Self {
+        let counts = lexer::count_lines(content, lang);
+        let (functions, fingerprints) = if duplication::supports(lang) {
+            let code = lexer::strip(content, lang);
+            let functions = if complexity::supports(lang) {
+                complexity::measure(&code, lang)
+            } else {
+                Vec::new()
+            };
+            (functions, duplication::fingerprint(&code))
+        } else {
+            (Vec::new(), Vec::new())
+        };
+        Self {
+            lines: content.lines().count(),
+            code: counts.code,
+            comments: counts.comments,
+            docs: counts.docs,
+            blanks: counts.blanks,
+            tokens: tokenizer::count_all(content, tokenizers),
+            functions,
+            fingerprints,
+        }
+    }
+
+    /// Whether these counts include every tokenizer in `tokenizers`.
+    fn has_tokens(&self, tokenizers: &[Tokenizer]) -> bool {
+        tokenizers.iter().all(|t| self.tokens.contains_key(t))
+    }
+}
+
+pub struct MetricsCollector {
+    language_weights: BTreeMap<String, f64>,
+    tokenizers: Vec<Tokenizer>,
+    coverage: Option<CoverageReport>,
+}
 
 impl MetricsCollector {
     pub fn new() -> Self {
-        Self
+        Self {
+            language_weights: BTreeMap::new(),
+            tokenizers: Vec::new(),
+            coverage: None,
+        }
+    }
+
+    /// Override the built-in weight for the given languages.
+    pub fn with_language_weights(mut self, weights: BTreeMap<String, f64>) -> Self {
+        self.language_weights = weights;
+        self
+    }
+
+    /// Also count tokens with these tokenizers; the heuristic is always counted.
+    pub fn with_tokenizers(mut self, tokenizers: Vec<Tokenizer>) -> Self {
+        self.tokenizers = tokenizers;
+        self
+    }
+
+    /// Match files against a coverage report of the working tree.
+    pub fn with_coverage(mut self, coverage: Option<CoverageReport>) -> Self {
+        self.coverage = coverage;
+        self
+    }
+
+    pub fn collect(&self, path: &Path, cache: &mut Cache) -> Result<Metrics> {
+        let files = scan_files(path, cache, &self.tokenizers)?;
+        Ok(self.aggregate(path, files, cache))
+    }
+
+    /// Like [`collect`](Self::collect), with separate metrics for every group
+    /// that `groups` puts a file in, given its path relative to `path`.
+    pub fn collect_groups<K: Ord + Clone>(
+        &self,
+        path: &Path,
+        cache: &mut Cache,
+        groups: impl Fn(&Path) -> Vec<K>,
+    ) -> Result<BTreeMap<K, Metrics>> {
+        let files = scan_files(path, cache, &self.tokenizers)?;
+
+        let mut buckets: BTreeMap<K, Vec<ScannedFile>> = BTreeMap::new();
+        for file in files {
+            let relative = file.path.strip_prefix(path).unwrap_or(&file.path);
+            for key in groups(relative) {
+                buckets.entry(key).or_default().push(file.clone());
+            }
+        }
+        Ok(buckets
+            .into_iter()
+            .map(|(key, files)| (key, self.aggregate(path, files, cache)))
+            .collect())
     }
 
-    pub fn collect(&self, path: &Path) -> Result<Metrics> {
+    /// Like [`collect`](Self::collect), for the files in a commit's `tree`.
+    pub fn collect_tree(
+        &self,
+        scanner: &mut TreeScanner,
+        tree: &Tree,
+        cache: &mut Cache,
+    ) -> Result<Metrics> {
+        let files = scanner.scan(tree, cache, &self.tokenizers)?;
+        Ok(self.aggregate(&scanner.root, files, cache))
+    }
+
+    fn aggregate(&self, path: &Path, files: Vec<ScannedFile>, cache: &mut Cache) -> Metrics {
         let mut total_lines = 0;
         let mut code_lines = 0;
         let mut comment_lines = 0;
+        let mut doc_lines = 0;
         let mut blank_lines = 0;
         let mut total_files = 0;
         let mut test_file_count = 0;
-        let mut total_tokens = 0;
+        let mut total_tokens = TokenCounts::new();
         let mut has_readme = false;
         let mut language_map: HashMap<String, (usize, usize)> = HashMap::new();
+        let mut functions = Vec::new();
+        let mut coverage = self.coverage.as_ref().map(Tally::new);
+        let mut duplication = duplication::Index::default();
 
-        for entry in WalkDir::new(path)
-            .follow_links(false)
-            .into_iter()
-            .filter_entry(|e| !is_ignored(e.path()))
-        {
-            let entry = entry?;
-            let path = entry.path();
-
-            if !path.is_file() {
-                continue;
-            }
-
+        for file in files {
             // Check for README
-            if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
+            if let Some(file_name) = file.path.file_name().and_then(|n| n.to_str()) {
                 if file_name.to_lowercase().starts_with("readme") {
                     has_readme = true;
                 }
             }
 
-            // Detect language
-            let lang = detect_language(path);
-            if lang.is_none() {
+            let Some(lang_name) = file.language else {
                 continue;
-            }
-
-            let lang_name = lang.unwrap();
+            };
             total_files += 1;
 
-            // Check if test file
-            if is_test_file(path) {
+            if file.is_test {
                 test_file_count += 1;
             }
 
-            // Count lines and tokens
-            if let Ok(content) = std::fs::read_to_string(path) {
-                let char_count = content.chars().count();
-                // Approximation for Claude 4.7 xhigh tokens: 
-                // 1 token ≈ 3.5 chars for code, with 1.35x inflation factor
-                total_tokens += ((char_count as f64 / 3.5) * 1.35) as usize;
-
-                let lines: Vec<&str> = content.lines().collect();
-                let line_count = lines.len();
-
-                total_lines += line_count;
-
-                let (code, comments, blanks) = count_line_types(&lines, &lang_name);
-                code_lines += code;
-                comment_lines += comments;
-                blank_lines += blanks;
+            let Some(stats) = file.stats else {
+                continue;
+            };
+            if let Some(key) = file.cache_key {
+                cache.insert_file(key, stats.clone());
+            }
 
-                let entry = language_map.entry(lang_name.clone()).or_insert((0, 0));
-                entry.0 += line_count;
-                entry.1 += 1;
+            for (&tokenizer, &tokens) in &stats.tokens {
+                *total_tokens.entry(tokenizer).or_default() += tokens;
+            }
+            total_lines += stats.lines;
+            code_lines += stats.code;
+            comment_lines += stats.comments;
+            doc_lines += stats.docs;
+            blank_lines += stats.blanks;
+
+            let relative = file.path.strip_prefix(path).unwrap_or(&file.path);
+            if let Some(coverage) = coverage.as_mut() {
+                coverage.add(relative, &lang_name);
             }
+            duplication.add(
+                relative.to_string_lossy().into_owned(),
+                stats.lines,
+                stats.fingerprints,
+            );
+            functions.extend(stats.functions.into_iter().map(|stats| FunctionReport {
+                path: relative.to_string_lossy().into_owned(),
+                language: lang_name.clone(),
+                stats,
+            }));
+
+            let entry = language_map.entry(lang_name).or_insert((0, 0));
+            entry.0 += stats.lines;
+            entry.1 += 1;
         }
 
         let mut language_stats: Vec<LanguageStat> = language_map
             .into_iter()
             .map(|(name, (lines, files))| LanguageStat {
-                weight: get_language_weight(&name),
+                weight: self
+                    .language_weights
+                    .get(&name)
+                    .copied()
+                    .unwrap_or_else(|| get_language_weight(&name)),
                 name,
                 lines,
                 files,
             })
             .collect();
 
-        language_stats.sort_by(|a, b| b.lines.cmp(&a.lines));
+        language_stats.sort_by(|a, b| b.lines.cmp(&a.lines).then_with(|| a.name.cmp(&b.name)));
 
-        Ok(Metrics {
+        Metrics {
             total_lines,
             code_lines,
             comment_lines,
+            doc_lines,
             blank_lines,
             total_files,
             test_file_count,
             total_tokens,
             has_readme,
             language_stats,
-        })
+            functions,
+            coverage: coverage.and_then(Tally::finish),
+            duplication: duplication.finish(),
+        }
     }
 }
 
-fn is_ignored(path: &Path) -> bool {
-    let ignored_dirs = [
-        // Build outputs
-        "target",
-        "dist",
-        "build",
-        "out",
-        // Dependencies
-        "node_modules",
-        "vendor",
-        // Python virtual environments
-        ".venv",
-        "venv",
-        "env",
-        "virtualenv",
-        "__pycache__",
-        ".pytest_cache",
-        ".mypy_cache",
-        ".tox",
-        // Version control
-        ".git",
-        ".svn",
-        ".hg",
-        // Framework-specific
-        ".next",
-        ".nuxt",
-        ".cache",
-        // IDE/Editor
-        ".idea",
-        ".vscode",
-        ".vs",
-    ];
-
-    path.components().any(|c| {
-        if let Some(s) = c.as_os_str().to_str() {
-            ignored_dirs.contains(&s)
-        } else {
-            false
+/// Per-file result produced by a walker thread.
+#[derive(Clone)]
+struct ScannedFile {
+    path: PathBuf,
+    language: Option<String>,
+    is_test: bool,
+    /// `None` when the file could not be read as UTF-8 text.
+    stats: Option<FileStats>,
+    cache_key: Option<String>,
+}
+
+/// Walk `root` on every core, honouring `.gitignore` and `.codecostignore`, and
+/// count each source file, reusing cached counts for unchanged content. Files
+/// come back sorted by path, so totals and cache writes are reproducible.
+fn scan_files(root: &Path, cache: &Cache, tokenizers: &[Tokenizer]) -> Result<Vec<ScannedFile>> {
+    let detector = Detector::load(root);
+    let (tx, rx) = mpsc::channel();
+
+    WalkBuilder::new(root)
+        .hidden(false)
+        .follow_links(false)
+        .add_custom_ignore_filename(IGNORE_FILE)
+        .filter_entry(|entry| !is_vcs_dir(entry.file_name()))
+        .build_parallel()
+        .run(|| {
+            let tx = tx.clone();
+            let detector = &detector;
+            Box::new(move |entry| {
+                let result = entry.map(|entry| {
+                    entry
+                        .file_type()
+                        .filter(|t| t.is_file())
+                        .map(|_| scan_file(entry.into_path(), detector, cache, tokenizers))
+                });
+                let keep_going = result.is_ok();
+                if let Some(result) = result.transpose() {
+                    if tx.send(result).is_err() {
+                        return WalkState::Quit;
+                    }
+                }
+                if keep_going {
+                    WalkState::Continue
+                } else {
+                    WalkState::Quit
+                }
+            })
+        });
+    drop(tx);
+
+    // Threads finish in any order.
+    let mut files = rx.into_iter().collect::<Result<Vec<_>, _>>()?;
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+fn scan_file(
+    path: PathBuf,
+    detector: &Detector,
+    cache: &Cache,
+    tokenizers: &[Tokenizer],
+) -> ScannedFile {
+    let language = detector.detect(&path);
+    let is_test = is_test_file(&path);
+
+    let (stats, cache_key) = match &language {
+        Some(lang) => {
+            let key = cache.key(&path, lang);
+            let cached = key
+                .as_deref()
+                .and_then(|key| cache.file(key))
+                .filter(|stats| stats.has_tokens(tokenizers));
+            match cached {
+                Some(stats) => (Some(stats), key),
+                None => {
+                    let stats = std::fs::read_to_string(&path)
+                        .ok()
+                        .map(|content| FileStats::measure(&content, lang, tokenizers));
+                    (stats, key)
+                }
+            }
         }
-    })
+        None => (None, None),
+    };
+
+    ScannedFile {
+        path,
+        language,
+        is_test,
+        stats,
+        cache_key,
+    }
 }
 
-fn detect_language(path: &Path) -> Option<String> {
-    path.extension()?.to_str().and_then(|ext| {
-        match ext {
-            "rs" => Some("Rust"),
-            "py" => Some("Python"),
-            "js" | "jsx" => Some("JavaScript"),
-            "ts" | "tsx" => Some("TypeScript"),
-            "go" => Some("Go"),
-            "java" => Some("Java"),
-            "c" => Some("C"),
-            "cpp" | "cc" | "cxx" => Some("C++"),
-            "h" | "hpp" => Some("C/C++ Header"),
-            "cs" => Some("C#"),
-            "rb" => Some("Ruby"),
-            "php" => Some("PHP"),
-            "swift" => Some("Swift"),
-            "kt" | "kts" => Some("Kotlin"),
-            "scala" => Some("Scala"),
-            "sh" | "bash" => Some("Shell"),
-            "sql" => Some("SQL"),
-            "html" => Some("HTML"),
-            "css" => Some("CSS"),
-            "scss" | "sass" => Some("SCSS"),
-            "md" | "markdown" => Some("Markdown"),
-            "yaml" | "yml" => Some("YAML"),
-            "json" => Some("JSON"),
-            "toml" => Some("TOML"),
-            "xml" => Some("XML"),
-            _ => None,
+/// Reads files from git trees instead of the working tree, so past revisions
+/// can be measured without a checkout. Blobs already seen at an earlier
+/// revision keep their language and counts, so only changed files are read.
+pub struct TreeScanner<'r> {
+    repo: &'r Repository,
+    root: PathBuf,
+    detector: Detector,
+    ignore: Gitignore,
+    /// Blob ids of the `.gitattributes` and `.codecostignore` in effect.
+    rules: (Option<Oid>, Option<Oid>),
+    seen: HashMap<(PathBuf, Oid), (Option<String>, Option<FileStats>)>,
+}
+
+impl<'r> TreeScanner<'r> {
+    /// A scanner for `repo`, reporting paths under its working directory `root`.
+    pub fn new(repo: &'r Repository, root: &Path) -> Self {
+        Self {
+            repo,
+            root: root.to_path_buf(),
+            detector: Detector::parse(root, ""),
+            ignore: Gitignore::empty(),
+            rules: (None, None),
+            seen: HashMap::new(),
+        }
+    }
+
+    fn scan(
+        &mut self,
+        tree: &Tree,
+        cache: &Cache,
+        tokenizers: &[Tokenizer],
+    ) -> Result<Vec<ScannedFile>> {
+        self.load_rules(tree);
+
+        let mut blobs = Vec::new();
+        tree.walk(TreeWalkMode::PreOrder, |dir, entry| {
+            // Symlinks are skipped like in the working-tree walk.
+            if entry.kind() == Some(ObjectType::Blob) && entry.filemode() != 0o120000 {
+                if let Some(name) = entry.name() {
+                    blobs.push((self.root.join(format!("{dir}{name}")), entry.id()));
+                }
+            }
+            TreeWalkResult::Ok
+        })?;
+        blobs.sort();
+
+        let mut files = Vec::with_capacity(blobs.len());
+        for (path, oid) in blobs {
+            if self
+                .ignore
+                .matched_path_or_any_parents(&path, false)
+                .is_ignore()
+            {
+                continue;
+            }
+            let (language, stats) = match self.seen.get(&(path.clone(), oid)) {
+                Some(seen) => seen.clone(),
+                None => {
+                    let seen = self.measure(&path, oid, cache, tokenizers)?;
+                    self.seen.insert((path.clone(), oid), seen.clone());
+                    seen
+                }
+            };
+            files.push(ScannedFile {
+                is_test: is_test_file(&path),
+                cache_key: language
+                    .as_deref()
+                    .and_then(|lang| cache.blob_key(oid, lang)),
+                path,
+                language,
+                stats,
+            });
+        }
+        Ok(files)
+    }
+
+    /// Language `tree` gives the blob `oid` at `relative`, or `None` when the
+    /// file is not counted (unrecognised, ignored, vendored or generated).
+    pub fn language(&mut self, tree: &Tree, relative: &Path, oid: Oid) -> Result<Option<String>> {
+        self.load_rules(tree);
+        let path = self.root.join(relative);
+        if self
+            .ignore
+            .matched_path_or_any_parents(&path, false)
+            .is_ignore()
+        {
+            return Ok(None);
+        }
+        if let Some((language, _)) = self.seen.get(&(path.clone(), oid)) {
+            return Ok(language.clone());
+        }
+        let blob = self.repo.find_blob(oid)?;
+        Ok(self.detector.detect_blob(&path, blob.content()))
+    }
+
+    fn measure(
+        &self,
+        path: &Path,
+        oid: Oid,
+        cache: &Cache,
+        tokenizers: &[Tokenizer],
+    ) -> Result<(Option<String>, Option<FileStats>)> {
+        let blob = self.repo.find_blob(oid)?;
+        let language = self.detector.detect_blob(path, blob.content());
+        let stats = language.as_deref().and_then(|lang| {
+            cache
+                .blob_key(oid, lang)
+                .and_then(|key| cache.file(&key))
+                .filter(|stats| stats.has_tokens(tokenizers))
+                .or_else(|| {
+                    std::str::from_utf8(blob.content())
+                        .ok()
+                        .map(|content| FileStats::measure(content, lang, tokenizers))
+                })
+        });
+        Ok((language, stats))
+    }
+
+    /// Reload `.gitattributes` and `.codecostignore` from `tree` when they
+    /// changed, forgetting languages detected under the old rules.
+    fn load_rules(&mut self, tree: &Tree) {
+        let blob_id = |name: &str| tree.get_name(name).map(|entry| entry.id());
+        let rules = (blob_id(".gitattributes"), blob_id(IGNORE_FILE));
+        if rules == self.rules && !self.seen.is_empty() {
+            return;
         }
-        .map(String::from)
-    })
+
+        let text = |oid: Option<Oid>| {
+            oid.and_then(|oid| self.repo.find_blob(oid).ok())
+                .map(|blob| String::from_utf8_lossy(blob.content()).into_owned())
+                .unwrap_or_default()
+        };
+        self.detector = Detector::parse(&self.root, &text(rules.0));
+
+        let mut ignore = GitignoreBuilder::new(&self.root);
+        for line in text(rules.1).lines() {
+            // Same as git: a line that is not a valid pattern is skipped.
+            let _ = ignore.add_line(None, line);
+        }
+        self.ignore = ignore.build().unwrap_or_else(|_| Gitignore::empty());
+        self.rules = rules;
+        self.seen.clear();
+    }
+}
+
+fn is_vcs_dir(name: &OsStr) -> bool {
+    [".git", ".svn", ".hg"].iter().any(|dir| name == *dir)
 }
 
 fn get_language_weight(lang: &str) -> f64 {
     match lang {
         "Rust" => 1.5,
-        "C++" | "C" => 1.4,
+        "C++" | "C" | "Zig" => 1.4,
         "Go" => 1.3,
         "Java" | "C#" | "Scala" => 1.2,
-        "TypeScript" | "Swift" | "Kotlin" => 1.2,
-        "Python" | "Ruby" | "PHP" => 1.1,
-        "JavaScript" => 1.0,
+        "TypeScript" | "Swift" | "Kotlin" | "Dart" => 1.2,
+        "Python" | "Ruby" | "PHP" | "Perl" => 1.1,
+        "JavaScript" | "Lua" | "Vue" | "Svelte" => 1.0,
         "Shell" | "SQL" => 0.9,
+        "Dockerfile" | "Makefile" | "Just" | "CMake" | "HCL" => 0.8,
         "HTML" | "CSS" | "SCSS" => 0.7,
+        "Protocol Buffer" => 0.6,
         "Markdown" | "YAML" | "JSON" | "TOML" | "XML" => 0.5,
         _ => 1.0,
     }
@@ -217,60 +530,70 @@ fn is_test_file(path: &Path) -> bool {
     lower.contains("test") || lower.contains("spec") || lower.contains("__tests__")
 }
 
-fn count_line_types(lines: &[&str], lang: &str) -> (usize, usize, usize) {
-    let mut code = 0;
-    let mut comments = 0;
-    let mut blanks = 0;
-
-    let (single_comment, multi_start, multi_end) = match lang {
-        "Rust" | "C++" | "C" | "Go" | "Java" | "C#" | "JavaScript" | "TypeScript"
-        | "Swift" | "Kotlin" | "Scala" | "PHP" => ("//", "/*", "*/"),
-        "Python" | "Ruby" | "Shell" => ("#", "", ""),
-        "HTML" | "XML" => ("", "<!--", "-->"),
-        "CSS" | "SCSS" => ("", "/*", "*/"),
-        _ => ("", "", ""),
-    };
-
-    let mut in_multiline_comment = false;
+impl Default for MetricsCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-    for line in lines {
-        let trimmed = line.trim();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{TempDir, TempRepo};
 
-        if trimmed.is_empty() {
-            blanks += 1;
-            continue;
-        }
-
-        if !multi_start.is_empty() && trimmed.contains(multi_start) {
-            in_multiline_comment = true;
-            comments += 1;
-            if trimmed.contains(multi_end) {
-                in_multiline_comment = false;
-            }
-            continue;
-        }
+    fn scanned(root: &Path) -> Vec<String> {
+        scan_files(root, &Cache::disabled(root), &[])
+            .unwrap()
+            .into_iter()
+            .map(|file| {
+                let relative = file.path.strip_prefix(root).unwrap();
+                relative.to_string_lossy().replace('\\', "/")
+            })
+            .collect()
+    }
 
-        if in_multiline_comment {
-            comments += 1;
-            if !multi_end.is_empty() && trimmed.contains(multi_end) {
-                in_multiline_comment = false;
-            }
-            continue;
+    #[test]
+    fn scan_honours_ignore_files_in_path_order() {
+        let repo = TempRepo::new();
+        repo.write(".gitignore", "build/\n*.log\n");
+        repo.write(IGNORE_FILE, "vendor/\n");
+        repo.write("src/tools/.gitignore", "generated.rs\n");
+        for path in [
+            "src/main.rs",
+            "src/lib.rs",
+            "src/a/b.rs",
+            "src/tools/mod.rs",
+            "src/tools/generated.rs",
+            "build/out.rs",
+            "vendor/dep/lib.rs",
+            "debug.log",
+            "README.md",
+        ] {
+            repo.write(path, "fn main() {}\n");
         }
 
-        if !single_comment.is_empty() && trimmed.starts_with(single_comment) {
-            comments += 1;
-            continue;
+        let expected = [
+            ".codecostignore",
+            ".gitignore",
+            "README.md",
+            "src/a/b.rs",
+            "src/lib.rs",
+            "src/main.rs",
+            "src/tools/.gitignore",
+            "src/tools/mod.rs",
+        ];
+        for _ in 0..5 {
+            assert_eq!(scanned(repo.dir.path()), expected);
         }
-
-        code += 1;
     }
 
-    (code, comments, blanks)
-}
+    #[test]
+    fn codecostignore_applies_outside_git() {
+        let dir = TempDir::new();
+        dir.write(IGNORE_FILE, "fixtures/\n");
+        dir.write("fixtures/big.json", "{}\n");
+        dir.write("main.py", "print()\n");
 
-impl Default for MetricsCollector {
-    fn default() -> Self {
-        Self::new()
+        assert_eq!(scanned(dir.path()), [IGNORE_FILE, "main.py"]);
     }
 }
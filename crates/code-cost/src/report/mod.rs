@@ -0,0 +1,654 @@
+use cli_core::output::{
+    format_integer, Card, Histogram, Report, ReportTable, SectionContent, ToReport,
+};
+use cli_core::currency::Currency;
+use serde::Serialize;
+use std::path::PathBuf;
+
+use crate::analyzer::Analysis;
+use crate::calculator::{CostEstimate, TokenCost};
+use crate::diff::RevisionDiff;
+use crate::get_repository_name;
+use crate::group::{Breakdown, GroupKind};
+use crate::history::Timeline;
+
+/// Largest duplicated blocks listed per repository.
+const DUPLICATED_BLOCKS_LISTED: usize = 25;
+
+/// One analysed repository and its cost estimate.
+#[derive(Debug, Clone, Serialize)]
+pub struct RepositoryCost {
+    pub path: PathBuf,
+    #[serde(rename = "metrics")]
+    pub analysis: Analysis,
+    pub cost: CostEstimate,
+}
+
+/// Exported report covering every analysed repository.
+pub struct CostReport<'a>(pub &'a [RepositoryCost]);
+
+impl ToReport for CostReport<'_> {
+    fn to_report(&self) -> Report {
+        build(self.0)
+    }
+}
+
+fn build(results: &[RepositoryCost]) -> Report {
+    let total_cost: f64 = results.iter().map(|r| r.cost.total_cost).sum();
+    let total_hours: f64 = results.iter().map(|r| r.cost.estimated_hours).sum();
+    let total_lines: usize = results.iter().map(|r| r.analysis.total_lines).sum();
+    let total_files: usize = results.iter().map(|r| r.analysis.total_files).sum();
+    let total_commits: usize = results.iter().map(|r| r.analysis.commit_count).sum();
+    let total_api_cost: f64 = results.iter().map(|r| r.cost.token_cost().total_cost).sum();
+    let currency = results.first().map(|r| r.cost.currency).unwrap_or_default();
+
+    let mut report = Report::new("Code Cost Analysis Report", "code-cost")
+        .with_subtitle(match results.len() {
+            1 => "1 repository analysed".to_string(),
+            n => format!("{n} repositories analysed"),
+        });
+
+    report.push(
+        "Summary",
+        SectionContent::Cards(vec![
+            Card::new("Repositories", results.len().to_string()),
+            Card::new("Lines of Code", format_integer(total_lines as i64)),
+            Card::new("Estimated Hours", format!("{total_hours:.1}")),
+            Card::new("Estimated Cost", currency.format(total_cost)),
+            Card::new("AI Simulation Cost", currency.format(total_api_cost)).with_detail(
+                results
+                    .first()
+                    .map(|r| r.cost.token_cost().model_name.clone())
+                    .unwrap_or_default(),
+            ),
+        ]),
+    );
+
+    let mut overview = ReportTable::new(&[
+        "Repository",
+        ">Lines",
+        ">Files",
+        ">Commits",
+        ">Est. Hours",
+        ">Total Cost",
+    ]);
+    for RepositoryCost {
+        path,
+        analysis,
+        cost,
+    } in results
+    {
+        overview.row(vec![
+            get_repository_name(path),
+            format_integer(analysis.total_lines as i64),
+            format_integer(analysis.total_files as i64),
+            format_integer(analysis.commit_count as i64),
+            format!("{:.1}", cost.estimated_hours),
+            currency.format(cost.total_cost),
+        ]);
+    }
+    if results.len() > 1 {
+        overview = overview.with_footer(vec![
+            "Total".to_string(),
+            format_integer(total_lines as i64),
+            format_integer(total_files as i64),
+            format_integer(total_commits as i64),
+            format!("{total_hours:.1}"),
+            currency.format(total_cost),
+        ]);
+    }
+    report.push("Repositories", SectionContent::Table(overview));
+
+    for RepositoryCost {
+        path,
+        analysis,
+        cost,
+    } in results
+    {
+        push_repository(&mut report, get_repository_name(path), analysis, cost);
+    }
+
+    report
+}
+
+fn push_repository(report: &mut Report, name: String, analysis: &Analysis, cost: &CostEstimate) {
+    let currency = cost.currency;
+    let mut cards = vec![
+            Card::new("Complexity", format!("{:.2}/5.0", analysis.complexity_score)).with_detail(
+                format!("{} functions", format_integer(analysis.complexity.functions as i64)),
+            ),
+            Card::new("Maturity", format!("{:.1}%", analysis.maturity_score * 100.0)),
+            Card::new(
+                "Code Quality",
+                format!("{:.1}%", cost.ai_analysis.code_quality_score * 100.0),
+            ),
+            Card::new("Test Files", analysis.test_file_count.to_string())
+                .with_detail(format!("{} files total", analysis.total_files)),
+        Card::new("Contributors", analysis.contributor_count.to_string())
+            .with_detail(format!("{} days old", analysis.age_in_days)),
+    ];
+    if !analysis.duplication.blocks.is_empty() {
+        cards.push(
+            Card::new(
+                "Duplication",
+                format!("{:.1}%", analysis.duplication.share() * 100.0),
+            )
+            .with_detail(format!("{} blocks", analysis.duplication.blocks.len())),
+        );
+    }
+    if let Some(coverage) = &analysis.coverage {
+        cards.insert(
+            4,
+            Card::new("Line Coverage", format!("{:.1}%", coverage.total.line_rate() * 100.0))
+                .with_detail(format!("{} report", coverage.format)),
+        );
+    }
+    report.push(name, SectionContent::Cards(cards));
+
+    if !analysis.language_stats.is_empty() {
+        let mut languages = ReportTable::new(&["Language", ">Lines", ">Files", ">Share", ">Weight"]);
+        for lang in &analysis.language_stats {
+            let share = if analysis.total_lines > 0 {
+                lang.lines as f64 / analysis.total_lines as f64 * 100.0
+            } else {
+                0.0
+            };
+            languages.row(vec![
+                lang.name.clone(),
+                format_integer(lang.lines as i64),
+                format_integer(lang.files as i64),
+                format!("{share:.1}%"),
+                format!("{:.1}x", lang.weight),
+            ]);
+        }
+        report.push_sub("Languages", SectionContent::Table(languages));
+    }
+
+    if let Some(coverage) = &analysis.coverage {
+        let mut languages = ReportTable::new(&[
+            "Language",
+            ">Files",
+            ">Lines",
+            ">Line Coverage",
+            ">Branches",
+            ">Branch Coverage",
+        ]);
+        let rows = coverage
+            .languages
+            .iter()
+            .map(|language| (language.name.as_str(), language.files, &language.counts))
+            .chain([("Total", coverage.files, &coverage.total)]);
+        for (name, files, counts) in rows {
+            languages.row(vec![
+                name.to_string(),
+                format_integer(files as i64),
+                format_integer(counts.lines_found as i64),
+                format!("{:.1}%", counts.line_rate() * 100.0),
+                format_integer(counts.branches_found as i64),
+                counts
+                    .branch_rate()
+                    .map(|rate| format!("{:.1}%", rate * 100.0))
+                    .unwrap_or_else(|| "-".to_string()),
+            ]);
+        }
+        report.push_sub(
+            format!("Coverage ({})", coverage.report.display()),
+            SectionContent::Table(languages),
+        );
+    }
+
+    let dependencies = &analysis.dependencies;
+    if !dependencies.lockfiles.is_empty() {
+        let mut lockfiles =
+            ReportTable::new(&["Lockfile", "Ecosystem", ">Direct", ">Transitive", ">Duplicated"]);
+        for lockfile in &dependencies.lockfiles {
+            lockfiles.row(vec![
+                lockfile.path.display().to_string(),
+                lockfile.ecosystem.to_string(),
+                format_integer(lockfile.direct as i64),
+                format_integer(lockfile.transitive as i64),
+                format_integer(lockfile.duplicates.len() as i64),
+            ]);
+        }
+        report.push_sub("Dependencies", SectionContent::Table(lockfiles));
+
+        let mut licenses = ReportTable::new(&["Licence", ">Packages"]);
+        for (license, count) in dependencies.licenses() {
+            licenses.row(vec![license, format_integer(count as i64)]);
+        }
+        report.push_sub("Dependency Licences", SectionContent::Table(licenses));
+
+        let mut duplicates = ReportTable::new(&["Lockfile", "Package", "Versions"]);
+        for lockfile in &dependencies.lockfiles {
+            for duplicate in &lockfile.duplicates {
+                duplicates.row(vec![
+                    lockfile.path.display().to_string(),
+                    duplicate.name.clone(),
+                    duplicate.versions.join(", "),
+                ]);
+            }
+        }
+        if !duplicates.rows.is_empty() {
+            report.push_sub("Duplicate Versions", SectionContent::Table(duplicates));
+        }
+    }
+
+    if !analysis.duplication.blocks.is_empty() {
+        let mut blocks = ReportTable::new(&["Copy", "Original", ">Lines", ">Tokens"]);
+        for block in analysis.duplication.blocks.iter().take(DUPLICATED_BLOCKS_LISTED) {
+            blocks.row(vec![
+                block.copy.to_string(),
+                block.original.to_string(),
+                format_integer(block.lines as i64),
+                format_integer(block.tokens as i64),
+            ]);
+        }
+        report.push_sub("Duplicated Blocks", SectionContent::Table(blocks));
+    }
+
+    if !analysis.complexity.top.is_empty() {
+        let mut functions = ReportTable::new(&[
+            "Function",
+            "Location",
+            ">Cyclomatic",
+            ">Cognitive",
+            ">Nesting",
+            ">Lines",
+        ]);
+        for function in &analysis.complexity.top {
+            functions.row(vec![
+                function.stats.name.clone(),
+                format!("{}:{}", function.path, function.stats.line),
+                function.stats.cyclomatic.to_string(),
+                function.stats.cognitive.to_string(),
+                function.stats.nesting.to_string(),
+                format_integer(function.stats.length as i64),
+            ]);
+        }
+        report.push_sub("Most Complex Functions", SectionContent::Table(functions));
+    }
+
+    let estimate = &cost.estimate;
+    let mut model = ReportTable::new(&["Assumption", "Value"]);
+    model.row(vec![
+        "Effort".to_string(),
+        format!(
+            "{:.1} person-months ({:.1} hours)",
+            estimate.effort_person_months, estimate.effort_hours
+        ),
+    ]);
+    model.row(vec![
+        "Schedule".to_string(),
+        format!(
+            "{:.1} months with {:.1} people",
+            estimate.schedule_months, estimate.team_size
+        ),
+    ]);
+    for assumption in &estimate.assumptions {
+        model.row(vec![assumption.name.clone(), assumption.value.clone()]);
+    }
+    let maintenance = &cost.dependency_maintenance;
+    if maintenance.hours > 0.0 {
+        model.row(vec![
+            "Dependency maintenance".to_string(),
+            format!(
+                "{:.1} hours for {} direct, {} transitive and {} duplicate versions ({})",
+                maintenance.hours,
+                maintenance.direct,
+                maintenance.transitive,
+                maintenance.extra_versions,
+                currency.format(maintenance.cost)
+            ),
+        ]);
+    }
+    report.push_sub(
+        format!("Estimation Model ({})", estimate.model),
+        SectionContent::Table(model),
+    );
+
+    let mut levels = ReportTable::new(&["Level", ">Hourly Rate", ">Estimated Cost"]);
+    for level in &cost.developer_levels {
+        levels.row(vec![
+            level.level.clone(),
+            currency.format(level.hourly_rate),
+            currency.format(level.estimated_cost),
+        ]);
+    }
+    report.push_sub("Developer Levels", SectionContent::Table(levels));
+
+    if let [token] = cost.token_costs.as_slice() {
+        push_token_cost(report, currency, token);
+    } else {
+        let mut models = ReportTable::new(&[
+            "Model",
+            "Tokenizer",
+            ">Base Tokens",
+            ">Prompt",
+            ">Cached",
+            ">Output",
+            ">Cost",
+        ]);
+        for token in &cost.token_costs {
+            models.row(vec![
+                token.model_name.clone(),
+                token.tokenizer.to_string(),
+                format_integer(token.base_tokens as i64),
+                format_integer(token.estimated_prompt_tokens as i64),
+                format_integer(token.cached_prompt_tokens as i64),
+                format_integer(token.estimated_output_tokens as i64),
+                currency.format(token.total_cost),
+            ]);
+        }
+        report.push_sub("AI Dev Simulation Token Cost", SectionContent::Table(models));
+    }
+
+    if !cost.ai_analysis.evidence.is_empty() {
+        report.push_sub(
+            "AI Usage",
+            SectionContent::Text(format!(
+                "Estimated AI usage {:.1}% of inserted lines, from {} attributed commits. \
+                 Agent instruction files and large commits are listed but not attributed.",
+                cost.ai_analysis.estimated_ai_usage * 100.0,
+                cost.ai_analysis.attributed_commits
+            )),
+        );
+        let mut evidence = ReportTable::new(&["Signal", "Commit", "Date", "Tool", "Evidence", "Summary"]);
+        for signal in &cost.ai_analysis.evidence {
+            for commit in &signal.commits {
+                evidence.row(vec![
+                    signal.kind.name().to_string(),
+                    commit.short_id().to_string(),
+                    commit.date(),
+                    commit.tool.clone().unwrap_or_default(),
+                    commit.detail.clone(),
+                    commit.summary.clone(),
+                ]);
+            }
+        }
+        report.push_sub("AI Evidence", SectionContent::Table(evidence));
+    }
+}
+
+/// One model's simulated token cost, item by item.
+fn push_token_cost(report: &mut Report, currency: Currency, token: &TokenCost) {
+    let mut tokens = ReportTable::new(&["Item", ">Tokens", ">Cost"]);
+    tokens.row(vec![
+        format!("Base codebase ({})", token.tokenizer),
+        format_integer(token.base_tokens as i64),
+        String::new(),
+    ]);
+    tokens.row(vec![
+        match token.cached_prompt_tokens {
+            0 => "Prompt (10x context)".to_string(),
+            _ => "Prompt written to cache".to_string(),
+        },
+        format_integer((token.estimated_prompt_tokens - token.cached_prompt_tokens) as i64),
+        currency.format(token.input_cost),
+    ]);
+    if token.cached_prompt_tokens > 0 {
+        tokens.row(vec![
+            "Prompt read from cache".to_string(),
+            format_integer(token.cached_prompt_tokens as i64),
+            currency.format(token.cache_read_cost),
+        ]);
+    }
+    tokens.row(vec![
+        "Output (1.5x revisions)".to_string(),
+        format_integer(token.estimated_output_tokens as i64),
+        currency.format(token.output_cost),
+    ]);
+    let tokens = tokens.with_footer(vec![
+        "Total".to_string(),
+        format_integer(token.total_estimated_tokens as i64),
+        currency.format(token.total_cost),
+    ]);
+    report.push_sub(
+        format!("AI Dev Simulation Token Cost ({})", token.model_name),
+        SectionContent::Table(tokens),
+    );
+}
+
+/// Exported `--history` report with one timeline per repository.
+pub struct HistoryReport<'a>(pub &'a [Timeline]);
+
+impl ToReport for HistoryReport<'_> {
+    fn to_report(&self) -> Report {
+        let mut report = Report::new("Code Value History", "code-cost").with_subtitle(
+            match self.0.len() {
+                1 => "1 repository".to_string(),
+                n => format!("{n} repositories"),
+            },
+        );
+        for timeline in self.0 {
+            push_timeline(&mut report, timeline);
+        }
+        report
+    }
+}
+
+fn push_timeline(report: &mut Report, timeline: &Timeline) {
+    let (Some(first), Some(last)) = (timeline.points.first(), timeline.points.last()) else {
+        return;
+    };
+    let currency = last.cost.currency;
+    report.push(
+        format!(
+            "{} ({} history)",
+            get_repository_name(&timeline.path),
+            timeline.granularity.name()
+        ),
+        SectionContent::Cards(vec![
+            Card::new("Revisions", timeline.points.len().to_string())
+                .with_detail(format!("{} to {}", first.label, last.label)),
+            Card::new("Value", currency.format(last.cost.total_cost))
+                .with_detail(format!("from {}", currency.format(first.cost.total_cost))),
+            Card::new("Code Lines", format_integer(last.metrics.code_lines as i64)).with_detail(
+                format!("from {}", format_integer(first.metrics.code_lines as i64)),
+            ),
+        ]),
+    );
+
+    let labels: Vec<String> = timeline.points.iter().map(|p| p.label.clone()).collect();
+    report.push_sub(
+        "Estimated Value",
+        SectionContent::Histogram(Histogram::new(
+            labels.clone(),
+            timeline.points.iter().map(|p| p.cost.total_cost).collect(),
+        )),
+    );
+    report.push_sub(
+        "Code Lines",
+        SectionContent::Histogram(Histogram::new(
+            labels,
+            timeline
+                .points
+                .iter()
+                .map(|p| p.metrics.code_lines as f64)
+                .collect(),
+        )),
+    );
+
+    let mut points = ReportTable::new(&[
+        "Revision",
+        "Date",
+        "Commit",
+        ">Code Lines",
+        ">Files",
+        "Languages",
+        ">Est. Hours",
+        ">Value",
+    ]);
+    for point in &timeline.points {
+        points.row(vec![
+            point.label.clone(),
+            point.date.clone(),
+            point.commit[..7].to_string(),
+            format_integer(point.metrics.code_lines as i64),
+            format_integer(point.metrics.total_files as i64),
+            point.metrics.language_mix(3),
+            format!("{:.1}", point.cost.estimated_hours),
+            currency.format(point.cost.total_cost),
+        ]);
+    }
+    report.push_sub("Revisions", SectionContent::Table(points));
+}
+
+/// Exported `--by` report with one group tree per repository.
+pub struct BreakdownReport<'a>(pub &'a [Breakdown]);
+
+impl ToReport for BreakdownReport<'_> {
+    fn to_report(&self) -> Report {
+        let mut report = Report::new("Code Cost Breakdown", "code-cost").with_subtitle(
+            match self.0.len() {
+                1 => "1 repository".to_string(),
+                n => format!("{n} repositories"),
+            },
+        );
+        for breakdown in self.0 {
+            push_breakdown(&mut report, breakdown);
+        }
+        report
+    }
+}
+
+fn push_breakdown(report: &mut Report, breakdown: &Breakdown) {
+    let Some(total) = breakdown.groups.first() else {
+        return;
+    };
+    let currency = total.cost.currency;
+    let groups: Vec<_> = breakdown
+        .groups
+        .iter()
+        .filter(|group| group.kind == GroupKind::Group)
+        .collect();
+    let mut cards = vec![
+        Card::new("Groups", groups.len().to_string())
+            .with_detail(format!("by {}", breakdown.grouping)),
+        Card::new("Value", currency.format(total.share.total_cost))
+            .with_detail(format!("{:.1} hours", total.share.estimated_hours)),
+    ];
+    if let Some(largest) = groups
+        .iter()
+        .max_by(|a, b| a.share.total_cost.total_cmp(&b.share.total_cost))
+    {
+        cards.push(
+            Card::new("Largest Group", largest.name())
+                .with_detail(currency.format(largest.share.total_cost)),
+        );
+    }
+    report.push(
+        format!(
+            "{} (by {})",
+            get_repository_name(&breakdown.path),
+            breakdown.grouping
+        ),
+        SectionContent::Cards(cards),
+    );
+
+    report.push_sub(
+        "Value by Group",
+        SectionContent::Histogram(Histogram::new(
+            groups.iter().map(|group| group.name().to_string()).collect(),
+            groups.iter().map(|group| group.share.total_cost).collect(),
+        )),
+    );
+
+    let mut table = ReportTable::new(&[
+        "Group",
+        ">Files",
+        ">Code Lines",
+        "Languages",
+        ">Complexity",
+        ">Est. Hours",
+        ">Value",
+        ">Standalone Value",
+    ]);
+    for group in &breakdown.groups {
+        table.row(vec![
+            group.label.clone(),
+            format_integer(group.metrics.total_files as i64),
+            format_integer(group.metrics.code_lines as i64),
+            group.metrics.language_mix(3),
+            format!("{:.2}", group.metrics.complexity_score),
+            format!("{:.1}", group.share.estimated_hours),
+            currency.format(group.share.total_cost),
+            currency.format(group.cost.total_cost),
+        ]);
+    }
+    report.push_sub("Groups", SectionContent::Table(table));
+}
+
+/// Exported `code-cost diff` report.
+pub struct DiffReport<'a>(pub &'a RevisionDiff);
+
+impl ToReport for DiffReport<'_> {
+    fn to_report(&self) -> Report {
+        let diff = self.0;
+        let (from, to) = (&diff.from, &diff.to);
+        let currency = to.cost.currency;
+        let mut report = Report::new("Code Value Diff", "code-cost").with_subtitle(format!(
+            "{}: {} ({}) to {} ({})",
+            get_repository_name(&diff.path),
+            from.label,
+            from.date,
+            to.label,
+            to.date
+        ));
+        report.push(
+            "Summary",
+            SectionContent::Cards(vec![
+                Card::new("Value Added", currency.format(diff.cost)).with_detail(format!(
+                    "{} to {}",
+                    currency.format(from.cost.total_cost),
+                    currency.format(to.cost.total_cost)
+                )),
+                Card::new("Est. Hours", format!("{:+.1}", diff.hours)).with_detail(format!(
+                    "{:.1} to {:.1}",
+                    from.cost.estimated_hours, to.cost.estimated_hours
+                )),
+                Card::new("Files Changed", diff.files_changed.to_string()).with_detail(format!(
+                    "+{} -{} ~{}",
+                    format_integer(diff.lines.added as i64),
+                    format_integer(diff.lines.removed as i64),
+                    format_integer(diff.lines.changed as i64)
+                )),
+            ]),
+        );
+
+        let mut languages =
+            ReportTable::new(&["Language", ">Added", ">Removed", ">Changed", ">Net"]);
+        for language in &diff.languages {
+            languages.row(vec![
+                language.language.clone(),
+                format_integer(language.lines.added as i64),
+                format_integer(language.lines.removed as i64),
+                format_integer(language.lines.changed as i64),
+                format_integer(language.lines.net()),
+            ]);
+        }
+        report.push("Languages", SectionContent::Table(languages));
+
+        let mut directories = ReportTable::new(&[
+            "Directory",
+            ">Added",
+            ">Removed",
+            ">Changed",
+            ">Share",
+            ">Est. Hours",
+            ">Value",
+        ]);
+        for directory in &diff.directories {
+            directories.row(vec![
+                directory.directory.clone(),
+                format_integer(directory.lines.added as i64),
+                format_integer(directory.lines.removed as i64),
+                format_integer(directory.lines.changed as i64),
+                format!("{:.1}%", directory.share * 100.0),
+                format!("{:.1}", directory.hours),
+                currency.format(directory.cost),
+            ]);
+        }
+        report.push("Top Directories", SectionContent::Table(directories));
+        report
+    }
+}
@@ -0,0 +1,126 @@
+//! Scratch directories and repositories for unit tests.
+
+use git2::{Oid, Repository, RepositoryInitOptions, Signature, Time};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A directory under the system temp dir, removed when dropped.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new() -> Self {
+        let dir = std::env::temp_dir().join(format!(
+            "code-cost-test-{}-{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+
+    /// Write `content` to `relative`, creating its directories.
+    pub fn write(&self, relative: &str, content: &str) {
+        let path = self.0.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    pub fn remove(&self, relative: &str) {
+        fs::remove_file(self.0.join(relative)).unwrap();
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// A repository on `main` whose commits are a minute apart, so their order
+/// does not depend on the clock.
+pub struct TempRepo {
+    pub dir: TempDir,
+    pub repo: Repository,
+    time: i64,
+}
+
+impl TempRepo {
+    pub fn new() -> Self {
+        let dir = TempDir::new();
+        let repo = Repository::init_opts(
+            dir.path(),
+            RepositoryInitOptions::new().initial_head("main"),
+        )
+        .unwrap();
+        Self {
+            dir,
+            repo,
+            time: 1_700_000_000,
+        }
+    }
+
+    pub fn write(&self, relative: &str, content: &str) {
+        self.dir.write(relative, content);
+    }
+
+    pub fn remove(&self, relative: &str) {
+        self.dir.remove(relative);
+    }
+
+    /// Commit the whole working tree on HEAD.
+    pub fn commit(&mut self, message: &str) -> Oid {
+        self.commit_as("Dev", "dev@example.com", message)
+    }
+
+    /// Commit the whole working tree on HEAD at `time`, in seconds since the
+    /// epoch; later commits follow a minute apart.
+    pub fn commit_at(&mut self, time: i64, message: &str) -> Oid {
+        self.time = time - 60;
+        self.commit(message)
+    }
+
+    /// Move HEAD's branch to `oid` and check it out, like `git reset --hard`.
+    pub fn reset(&self, oid: Oid) {
+        let commit = self.repo.find_object(oid, None).unwrap();
+        self.repo
+            .reset(&commit, git2::ResetType::Hard, None)
+            .unwrap();
+    }
+
+    pub fn commit_as(&mut self, name: &str, email: &str, message: &str) -> Oid {
+        let mut index = self.repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.update_all(["*"], None).unwrap();
+        index.write().unwrap();
+        let tree = self.repo.find_tree(index.write_tree().unwrap()).unwrap();
+
+        self.time += 60;
+        let signature = Signature::new(name, email, &Time::new(self.time, 0)).unwrap();
+        let parent = self
+            .repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<_> = parent.iter().collect();
+        self.repo
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                message,
+                &tree,
+                &parents,
+            )
+            .unwrap()
+    }
+}
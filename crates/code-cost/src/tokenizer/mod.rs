@@ -0,0 +1,125 @@
+//! Token counting for the AI development simulation.
+//!
+//! OpenAI vocabularies ship with the binary, so GPT models are counted
+//! exactly. Models without a public vocabulary, such as Claude and Gemini, use
+//! a character heuristic calibrated on source code.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::OnceLock;
+use tiktoken_rs::CoreBPE;
+
+/// Token counts of the same text, per tokenizer.
+pub type TokenCounts = BTreeMap<Tokenizer, usize>;
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Tokenizer {
+    /// About 3.5 characters per token, inflated by 1.35 for code
+    #[default]
+    Heuristic,
+    /// GPT-4 and GPT-3.5 vocabulary
+    Cl100kBase,
+    /// GPT-4o, GPT-4.1 and GPT-5 vocabulary
+    O200kBase,
+}
+
+impl Tokenizer {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Heuristic => "heuristic",
+            Self::Cl100kBase => "cl100k_base",
+            Self::O200kBase => "o200k_base",
+        }
+    }
+
+    /// Tokens in `text`. A vocabulary that fails to load falls back to the
+    /// heuristic rather than failing the analysis.
+    pub fn count(&self, text: &str) -> usize {
+        match self.bpe() {
+            Some(bpe) => bpe.encode_ordinary(text).len(),
+            None => ((text.chars().count() as f64 / 3.5) * 1.35) as usize,
+        }
+    }
+
+    fn bpe(&self) -> Option<&'static CoreBPE> {
+        static CL100K_BASE: OnceLock<Option<CoreBPE>> = OnceLock::new();
+        static O200K_BASE: OnceLock<Option<CoreBPE>> = OnceLock::new();
+        match self {
+            Self::Heuristic => None,
+            Self::Cl100kBase => CL100K_BASE
+                .get_or_init(|| tiktoken_rs::cl100k_base().ok())
+                .as_ref(),
+            Self::O200kBase => O200K_BASE
+                .get_or_init(|| tiktoken_rs::o200k_base().ok())
+                .as_ref(),
+        }
+    }
+}
+
+impl fmt::Display for Tokenizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Count `text` with every tokenizer in `tokenizers`, and always with the
+/// heuristic so a model can fall back to it.
+pub fn count_all(text: &str, tokenizers: &[Tokenizer]) -> TokenCounts {
+    let mut counts = TokenCounts::new();
+    counts.insert(Tokenizer::Heuristic, Tokenizer::Heuristic.count(text));
+    for &tokenizer in tokenizers {
+        counts
+            .entry(tokenizer)
+            .or_insert_with(|| tokenizer.count(text));
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vocabularies_count_exactly() {
+        assert_eq!(Tokenizer::Cl100kBase.count("hello world"), 2);
+        assert_eq!(Tokenizer::O200kBase.count("hello world"), 2);
+        assert_eq!(Tokenizer::O200kBase.count(""), 0);
+    }
+
+    #[test]
+    fn heuristic_counts_characters() {
+        // 35 characters are 10 heuristic tokens, 13.5 with the code factor.
+        assert_eq!(Tokenizer::Heuristic.count(&"a".repeat(35)), 13);
+        assert_eq!(Tokenizer::Heuristic.count("한글 텍스트"), 2);
+        assert_eq!(Tokenizer::Heuristic.count(""), 0);
+    }
+
+    #[test]
+    fn count_all_always_includes_the_heuristic() {
+        let counts = count_all(
+            "fn main() {}",
+            &[Tokenizer::O200kBase, Tokenizer::O200kBase],
+        );
+        assert_eq!(
+            counts.keys().copied().collect::<Vec<_>>(),
+            [Tokenizer::Heuristic, Tokenizer::O200kBase]
+        );
+        assert_eq!(counts[&Tokenizer::Heuristic], 4);
+    }
+
+    #[test]
+    fn names_match_the_serialized_form() {
+        for tokenizer in [
+            Tokenizer::Heuristic,
+            Tokenizer::Cl100kBase,
+            Tokenizer::O200kBase,
+        ] {
+            let json = serde_json::to_string(&tokenizer).unwrap();
+            assert_eq!(json, format!("\"{tokenizer}\""));
+        }
+    }
+}
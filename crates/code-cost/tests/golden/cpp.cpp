@@ -0,0 +1,15 @@
+// 15 lines 7 code 5 comments 1 docs 2 blanks
+#include <string>
+
+/// Returns a raw string.
+std::string raw() {
+    return R"x(// not a comment
+/* still not */
+)x";
+}
+
+/* block
+   comment */
+int main() { /* inline */ return 0; }
+/**/
+// done
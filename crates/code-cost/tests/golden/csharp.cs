@@ -0,0 +1,12 @@
+// 12 lines 6 code 2 comments 3 docs 1 blanks
+/// <summary>
+/// Docs.
+/// </summary>
+class A {
+    string p = @"C:\path\";
+    string q = "// not a comment";
+
+    /* block */
+    string r = """
+        raw /* text */
+        """;
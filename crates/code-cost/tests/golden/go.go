@@ -0,0 +1,12 @@
+// 12 lines 7 code 3 comments 0 docs 2 blanks
+package main
+
+/* Package docs
+   are comments in Go. */
+var raw = `first
+// not a comment
+last`
+
+func main() {
+	s := "\" // still string"; _ = s
+}
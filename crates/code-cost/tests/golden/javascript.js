@@ -0,0 +1,12 @@
+// 12 lines 5 code 3 comments 3 docs 1 blanks
+/**
+ * Docs.
+ */
+const url = 'http://x/*';
+const tpl = `line one
+// still template
+${url}`;
+
+/* c1
+   c2 */
+function f() { return "/*"; } // trailing
@@ -0,0 +1,11 @@
+-- 11 lines 5 code 3 comments 2 docs 1 blanks
+--- Adds one.
+--- @param x number
+local function add(x)
+  return x + 1 -- trailing
+end
+
+--[[ block
+comment ]]
+local s = [[ -- not a comment
+]]
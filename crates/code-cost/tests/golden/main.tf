@@ -0,0 +1,7 @@
+# 7 lines 3 code 3 comments 0 docs 1 blanks
+// also a comment
+resource "aws_s3_bucket" "b" {
+  bucket = "my#bucket" /* inline */
+}
+
+/* block */
@@ -0,0 +1,23 @@
+# 23 lines 8 code 5 comments 5 docs 5 blanks
+"""Module docstring."""
+
+import os  # trailing comment
+
+
+def greet(name):
+    """Greet someone.
+
+    Multi-line docstring.
+    """
+    # A real comment
+    url = "http://example.com/#anchor"
+    s = '''not a
+docstring'''
+    return f"{name} # not a comment"
+
+class Thing:
+    '''Class docstring.'''
+    x = 1
+# last
+#
+# comment block
@@ -0,0 +1,10 @@
+# 10 lines 4 code 5 comments 0 docs 1 blanks
+=begin
+block comment
+=end
+def hello
+  puts "# not a comment" # comment
+end
+
+# trailing
+x = 1
@@ -0,0 +1,27 @@
+// 27 lines 13 code 5 comments 6 docs 3 blanks
+//! Crate docs.
+
+/// Adds one.
+/// Second line.
+fn add_one(x: i32) -> i32 {
+    x + 1 // trailing comment
+}
+
+/* outer /* nested */
+   still a comment */
+fn strings() {
+    let a = "/* not a comment";
+    let b = r#"// not "a" comment"#;
+    let c = '"'; // char, not a string
+    let d = '\''; let e: &'static str = "x";
+    let f = "multi
+// inside a string
+line";
+}
+
+//// banner, not a doc
+/**
+ * Block docs.
+ */
+fn lifetimes<'a>(s: &'a str) -> &'a str { s }
+/***/
@@ -0,0 +1,10 @@
+# 10 lines 5 code 3 comments 0 docs 2 blanks
+#!/bin/bash
+
+echo $# "not # comment" 'raw \'
+echo "x"#notcomment
+echo hi # comment
+# comment
+
+cat <<<'a#b'
+x=1
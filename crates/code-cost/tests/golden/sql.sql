@@ -0,0 +1,8 @@
+-- 8 lines 4 code 3 comments 0 docs 1 blanks
+SELECT '-- not a comment', "/* nor this */"
+FROM t; -- trailing
+
+/* block
+   comment */
+SELECT 'it''s'
+FROM u;
@@ -0,0 +1,9 @@
+// 9 lines 5 code 2 comments 1 docs 1 blanks
+/// Docs.
+func f() -> String {
+    /* outer /* nested */ still */
+    return """
+    // text
+    """
+}
+
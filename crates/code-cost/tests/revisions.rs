@@ -0,0 +1,144 @@
+use git2::build::RepoBuilder;
+use git2::{Repository, RepositoryInitOptions, Signature, Time};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A repository with a `v1` tag on a one-file commit and a second file on
+/// HEAD, plus a bare clone of it; both removed when dropped.
+struct Fixture {
+    dir: PathBuf,
+}
+
+impl Fixture {
+    fn new() -> Self {
+        let dir = std::env::temp_dir().join(format!(
+            "code-cost-revisions-{}-{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        let work = dir.join("work");
+        let repo = Repository::init_opts(&work, RepositoryInitOptions::new().initial_head("main"))
+            .unwrap();
+
+        fs::write(work.join("lib.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+        let first = commit(&repo, "first", 1_700_000_000);
+        repo.tag_lightweight("v1", &repo.find_object(first, None).unwrap(), false)
+            .unwrap();
+        fs::write(work.join("main.py"), "print('hi')\n").unwrap();
+        commit(&repo, "second", 1_700_000_060);
+
+        RepoBuilder::new()
+            .bare(true)
+            .clone(work.to_str().unwrap(), &dir.join("bare.git"))
+            .unwrap();
+        Self { dir }
+    }
+
+    fn work(&self) -> PathBuf {
+        self.dir.join("work")
+    }
+
+    fn bare(&self) -> PathBuf {
+        self.dir.join("bare.git")
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn commit(repo: &Repository, message: &str, time: i64) -> git2::Oid {
+    let mut index = repo.index().unwrap();
+    index
+        .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+        .unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = Signature::new("Dev", "dev@example.com", &Time::new(time, 0)).unwrap();
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    let parents: Vec<_> = parent.iter().collect();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        message,
+        &tree,
+        &parents,
+    )
+    .unwrap()
+}
+
+fn code_cost(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_code-cost"))
+        .args(["--no-cache", "--format", "json"])
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Files and commits of the only repository in a successful JSON run.
+fn analysed(output: &Output) -> (u64, u64) {
+    assert!(output.status.success(), "code-cost failed: {output:?}");
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let metrics = &results[0]["metrics"];
+    (
+        metrics["total_files"].as_u64().unwrap(),
+        metrics["commit_count"].as_u64().unwrap(),
+    )
+}
+
+fn path(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+#[test]
+fn unknown_revision_fails() {
+    let fixture = Fixture::new();
+    let output = code_cost(&["--rev", "nonexistent", path(&fixture.work())]);
+
+    assert!(!output.status.success(), "code-cost succeeded: {output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Unknown revision: nonexistent"), "{stderr}");
+    assert!(
+        stderr.contains("No repositories were successfully analyzed"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn revision_can_be_a_tag() {
+    let fixture = Fixture::new();
+    assert_eq!(analysed(&code_cost(&[path(&fixture.work())])), (2, 2));
+    assert_eq!(
+        analysed(&code_cost(&["--rev", "v1", path(&fixture.work())])),
+        (1, 1)
+    );
+}
+
+#[test]
+fn bare_repositories_default_to_head() {
+    let fixture = Fixture::new();
+    assert_eq!(analysed(&code_cost(&[path(&fixture.bare())])), (2, 2));
+    assert_eq!(
+        analysed(&code_cost(&["--rev", "v1", path(&fixture.bare())])),
+        (1, 1)
+    );
+}
+
+#[test]
+fn file_urls_name_local_repositories() {
+    let fixture = Fixture::new();
+    let url = format!("file://{}", path(&fixture.bare()));
+    assert_eq!(analysed(&code_cost(&[&url])), (2, 2));
+
+    let output = code_cost(&["https://example.com/repo.git"]);
+    assert!(!output.status.success(), "code-cost succeeded: {output:?}");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("URLs are not supported"));
+}
@@ -1,9 +1,29 @@
 use anyhow::Result;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use cli_core::ui::Theme;
 use comfy_table::{Cell, Color};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct AsciiChar {
+    dec: u8,
+    hex: String,
+    char: String,
+}
+
+pub fn show(format: OutputFormat) -> Result<()> {
+    if !format.is_table() {
+        // The table is laid out in four side-by-side blocks; emit one record per code instead.
+        let chars: Vec<AsciiChar> = (0..128u8)
+            .map(|i| AsciiChar {
+                dec: i,
+                hex: format!("{:02X}", i),
+                char: char_name(i).trim().to_string(),
+            })
+            .collect();
+        return format.print(&chars);
+    }
 
-pub fn show() -> Result<()> {
     println!("{}", Theme::header(" ASCII Table (0-127) "));
     println!();
     
@@ -60,7 +80,17 @@ fn add_cols(row: &mut Vec<Cell>, i: u8) {
 }
 
 fn get_char_cell(i: u8) -> Cell {
-    let char_repr = match i {
+    let char_repr = char_name(i);
+
+    if i <= 32 || i == 127 {
+        Cell::new(char_repr).fg(Color::Grey)
+    } else {
+        TableFormatter::highlight_cell(char_repr)
+    }
+}
+
+fn char_name(i: u8) -> String {
+    match i {
         0 => "NUL".to_string(),
         1 => "SOH".to_string(),
         2 => "STX".to_string(),
@@ -96,11 +126,5 @@ fn get_char_cell(i: u8) -> Cell {
         32 => "SPC".to_string(),
         127 => "DEL".to_string(),
         _ => format!(" {} ", i as char),
-    };
-    
-    if i <= 32 || i == 127 {
-        Cell::new(char_repr).fg(Color::Grey)
-    } else {
-        TableFormatter::highlight_cell(char_repr)
     }
 }
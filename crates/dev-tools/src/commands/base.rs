@@ -1,8 +1,22 @@
 use anyhow::{Result, anyhow};
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
+use serde::Serialize;
 
-pub fn convert(value: &str, from_base: u32, to_base: Option<u32>, all: bool) -> Result<()> {
+#[derive(Serialize)]
+struct Radix {
+    base: u32,
+    name: &'static str,
+    value: String,
+}
+
+pub fn convert(
+    value: &str,
+    from_base: u32,
+    to_base: Option<u32>,
+    all: bool,
+    format: OutputFormat,
+) -> Result<()> {
     if from_base < 2 || from_base > 36 {
         return Err(anyhow!("Source base must be between 2 and 36"));
     }
@@ -20,8 +34,35 @@ pub fn convert(value: &str, from_base: u32, to_base: Option<u32>, all: bool) ->
     }
 
     // Dashboard view
+    let targets = if all {
+        vec![2, 8, 10, 16, 32, 36]
+    } else {
+        vec![2, 8, 10, 16]
+    };
+
+    let radixes: Vec<Radix> = targets
+        .into_iter()
+        .map(|t| Radix {
+            base: t,
+            name: match t {
+                2 => "Binary",
+                8 => "Octal",
+                10 => "Decimal",
+                16 => "Hexadecimal",
+                32 => "Base32",
+                36 => "Base36",
+                _ => "Custom",
+            },
+            value: to_radix(num, t),
+        })
+        .collect();
+
+    if !format.is_table() {
+        return format.print(&radixes);
+    }
+
     println!("\n{}", Theme::header(format!("Base Conversion: {} (base {})", value, from_base)));
-    
+
     let mut table = TableFormatter::create_table();
     table.set_header(vec![
         TableFormatter::header_cell("Base"),
@@ -29,26 +70,11 @@ pub fn convert(value: &str, from_base: u32, to_base: Option<u32>, all: bool) ->
         TableFormatter::header_cell("Value"),
     ]);
 
-    let targets = if all {
-        vec![2, 8, 10, 16, 32, 36]
-    } else {
-        vec![2, 8, 10, 16]
-    };
-
-    for t in targets {
-        let name = match t {
-            2 => "Binary",
-            8 => "Octal",
-            10 => "Decimal",
-            16 => "Hexadecimal",
-            32 => "Base32",
-            36 => "Base36",
-            _ => "Custom",
-        };
+    for radix in radixes {
         table.add_row(vec![
-            TableFormatter::value_cell(t),
-            TableFormatter::value_cell(name),
-            TableFormatter::highlight_cell(to_radix(num, t)),
+            TableFormatter::value_cell(radix.base),
+            TableFormatter::value_cell(radix.name),
+            TableFormatter::highlight_cell(radix.value),
         ]);
     }
 
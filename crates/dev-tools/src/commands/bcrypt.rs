@@ -1,10 +1,29 @@
 use anyhow::Result;
 use bcrypt::{hash, verify, DEFAULT_COST};
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
+use comfy_table::Color;
+use cli_core::output::{OutputFormat, TableFormatter};
+use serde::Serialize;
 
-pub fn hash_password(password: &str, cost: Option<u32>) -> Result<()> {
+#[derive(Serialize)]
+struct Hashed {
+    hash: String,
+    cost: u32,
+}
+
+#[derive(Serialize)]
+struct Verification<'a> {
+    matches: bool,
+    algorithm: &'static str,
+    cost: Option<&'a str>,
+}
+
+pub fn hash_password(password: &str, cost: Option<u32>, format: OutputFormat) -> Result<()> {
     let cost = cost.unwrap_or(DEFAULT_COST);
+
+    if !format.is_table() {
+        return format.print(&Hashed { hash: hash(password, cost)?, cost });
+    }
     
     println!("{}", Theme::info(format!("Hashing password with cost factor: {}", Theme::highlight(cost.to_string()))));
     
@@ -16,8 +35,19 @@ pub fn hash_password(password: &str, cost: Option<u32>) -> Result<()> {
     Ok(())
 }
 
-pub fn verify_password(password: &str, hashed: &str) -> Result<()> {
+pub fn verify_password(password: &str, hashed: &str, format: OutputFormat) -> Result<()> {
     let valid = verify(password, hashed)?;
+
+    // Extract cost if possible (bcrypt hashes usually start with $2b$cost$...)
+    let cost = if hashed.starts_with("$2") {
+        hashed.split('$').nth(2)
+    } else {
+        None
+    };
+
+    if !format.is_table() {
+        return format.print(&Verification { matches: valid, algorithm: "Bcrypt", cost });
+    }
     
     let mut table = TableFormatter::create_table();
     table.set_header(vec![
@@ -30,7 +60,7 @@ pub fn verify_password(password: &str, hashed: &str) -> Result<()> {
         if valid {
             TableFormatter::highlight_cell("MATCH")
         } else {
-            TableFormatter::value_cell(Theme::red("MISMATCH"))
+            TableFormatter::value_cell("MISMATCH").fg(Color::Red)
         },
     ]);
     
@@ -39,15 +69,11 @@ pub fn verify_password(password: &str, hashed: &str) -> Result<()> {
         TableFormatter::value_cell("Bcrypt"),
     ]);
 
-    // Extract cost if possible (bcrypt hashes usually start with $2b$cost$...)
-    if hashed.starts_with("$2") {
-        let parts: Vec<&str> = hashed.split('$').collect();
-        if parts.len() >= 3 {
-            table.add_row(vec![
-                TableFormatter::value_cell("Cost Factor"),
-                TableFormatter::value_cell(parts[2]),
-            ]);
-        }
+    if let Some(cost) = cost {
+        table.add_row(vec![
+            TableFormatter::value_cell("Cost Factor"),
+            TableFormatter::value_cell(cost),
+        ]);
     }
 
     println!("\n{}", Theme::header("Verification Results:"));
@@ -1,11 +1,26 @@
 use anyhow::Result;
 use std::process::Command;
 use std::time::Instant;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use cli_core::ui::Theme;
+use comfy_table::Color;
+use serde::Serialize;
 
-pub fn run(command: &str, args: Vec<String>, count: usize) -> Result<()> {
-    println!("{}", Theme::info(format!("Benchmarking '{}' ({} runs)...", command, count)));
+/// Durations in milliseconds.
+#[derive(Serialize)]
+struct BenchResult<'a> {
+    command: &'a str,
+    runs: usize,
+    failures: usize,
+    average_ms: f64,
+    min_ms: f64,
+    max_ms: f64,
+}
+
+pub fn run(command: &str, args: Vec<String>, count: usize, format: OutputFormat) -> Result<()> {
+    if format.is_table() {
+        println!("{}", Theme::info(format!("Benchmarking '{}' ({} runs)...", command, count)));
+    }
 
     let mut total_duration = std::time::Duration::default();
     let mut min_duration = std::time::Duration::from_secs(u64::MAX);
@@ -32,12 +47,23 @@ pub fn run(command: &str, args: Vec<String>, count: usize) -> Result<()> {
 
         if !status.success() {
             failures += 1;
-            println!("{}", Theme::warning(format!("Run {} failed with status: {}", i + 1, status)));
+            eprintln!("{}", Theme::warning(format!("Run {} failed with status: {}", i + 1, status)));
         }
     }
 
     let avg_duration = total_duration / count as u32;
 
+    if !format.is_table() {
+        return format.print(&BenchResult {
+            command,
+            runs: count,
+            failures,
+            average_ms: avg_duration.as_secs_f64() * 1000.0,
+            min_ms: min_duration.as_secs_f64() * 1000.0,
+            max_ms: max_duration.as_secs_f64() * 1000.0,
+        });
+    }
+
     println!("\n{}", Theme::header("Benchmark Results"));
     
     let mut table = TableFormatter::create_table();
@@ -52,7 +78,11 @@ pub fn run(command: &str, args: Vec<String>, count: usize) -> Result<()> {
     ]);
     table.add_row(vec![
         TableFormatter::value_cell("Failures"),
-        TableFormatter::value_cell(if failures > 0 { Theme::error(failures.to_string()) } else { "0".to_string() }),
+        if failures > 0 {
+            TableFormatter::value_cell(failures).fg(Color::Red)
+        } else {
+            TableFormatter::value_cell(0)
+        },
     ]);
     table.add_row(vec![
         TableFormatter::value_cell("Average"),
@@ -68,6 +98,5 @@ pub fn run(command: &str, args: Vec<String>, count: usize) -> Result<()> {
     ]);
 
     println!("{}", table);
-
     Ok(())
 }
@@ -1,9 +1,37 @@
 use anyhow::Result;
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use owo_colors::OwoColorize;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Byte {
+    char: char,
+    hex: String,
+    decimal: u8,
+    binary: String,
+}
+
+#[derive(Serialize)]
+struct Decoded {
+    binary: String,
+    text: String,
+}
+
+pub fn to_binary(input: &str, format: OutputFormat) -> Result<()> {
+    if !format.is_table() {
+        let bytes: Vec<Byte> = input
+            .bytes()
+            .map(|b| Byte {
+                char: b as char,
+                hex: format!("0x{:02X}", b),
+                decimal: b,
+                binary: format!("{:08b}", b),
+            })
+            .collect();
+        return format.print(&bytes);
+    }
 
-pub fn to_binary(input: &str) -> Result<()> {
     println!("{}", Theme::header("Text to Binary Conversion"));
     
     let mut table = TableFormatter::create_table();
@@ -36,7 +64,7 @@ pub fn to_binary(input: &str) -> Result<()> {
     Ok(())
 }
 
-pub fn from_binary(input: &str) -> Result<()> {
+pub fn from_binary(input: &str, format: OutputFormat) -> Result<()> {
     let clean_input = input.replace(' ', "");
     if clean_input.len() % 8 != 0 {
         anyhow::bail!("Invalid binary string length. Must be a multiple of 8 (each byte is 8 bits).");
@@ -51,6 +79,10 @@ pub fn from_binary(input: &str) -> Result<()> {
     }
     
     let text = String::from_utf8_lossy(&bytes);
+
+    if !format.is_table() {
+        return format.print(&Decoded { binary: clean_input, text: text.into_owned() });
+    }
     
     println!("{}", Theme::header("Binary to Text Conversion"));
     println!("{} {}", Theme::info("Input:  "), input.bright_white());
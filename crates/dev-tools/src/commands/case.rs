@@ -1,9 +1,20 @@
 use anyhow::Result;
 use heck::{ToKebabCase, ToLowerCamelCase, ToPascalCase, ToSnakeCase, ToShoutySnakeCase, ToTrainCase};
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use cli_core::ui::Theme;
+use serde::Serialize;
 
-pub fn convert(text: &str, target_case: Option<&str>) -> Result<()> {
+#[derive(Serialize)]
+struct Conversions {
+    snake: String,
+    camel: String,
+    pascal: String,
+    kebab: String,
+    shouty_snake: String,
+    train: String,
+}
+
+pub fn convert(text: &str, target_case: Option<&str>, format: OutputFormat) -> Result<()> {
     if let Some(target) = target_case {
         let result = match target.to_lowercase().as_str() {
             "snake" => text.to_snake_case(),
@@ -16,6 +27,18 @@ pub fn convert(text: &str, target_case: Option<&str>) -> Result<()> {
         };
         println!("{}", result);
     } else {
+        let conversions = Conversions {
+            snake: text.to_snake_case(),
+            camel: text.to_lower_camel_case(),
+            pascal: text.to_pascal_case(),
+            kebab: text.to_kebab_case(),
+            shouty_snake: text.to_shouty_snake_case(),
+            train: text.to_train_case(),
+        };
+        if !format.is_table() {
+            return format.print(&conversions);
+        }
+
         println!("{}", Theme::header(format!("Case Conversions for: \"{}\"", text)));
         
         let mut table = TableFormatter::create_table();
@@ -24,30 +47,19 @@ pub fn convert(text: &str, target_case: Option<&str>) -> Result<()> {
             TableFormatter::header_cell("Result"),
         ]);
         
-        table.add_row(vec![
-            TableFormatter::value_cell("Snake Case"),
-            TableFormatter::highlight_cell(text.to_snake_case()),
-        ]);
-        table.add_row(vec![
-            TableFormatter::value_cell("Camel Case"),
-            TableFormatter::highlight_cell(text.to_lower_camel_case()),
-        ]);
-        table.add_row(vec![
-            TableFormatter::value_cell("Pascal Case"),
-            TableFormatter::highlight_cell(text.to_pascal_case()),
-        ]);
-        table.add_row(vec![
-            TableFormatter::value_cell("Kebab Case"),
-            TableFormatter::highlight_cell(text.to_kebab_case()),
-        ]);
-        table.add_row(vec![
-            TableFormatter::value_cell("Shouty Snake"),
-            TableFormatter::highlight_cell(text.to_shouty_snake_case()),
-        ]);
-        table.add_row(vec![
-            TableFormatter::value_cell("Train Case"),
-            TableFormatter::highlight_cell(text.to_train_case()),
-        ]);
+        for (name, value) in [
+            ("Snake Case", &conversions.snake),
+            ("Camel Case", &conversions.camel),
+            ("Pascal Case", &conversions.pascal),
+            ("Kebab Case", &conversions.kebab),
+            ("Shouty Snake", &conversions.shouty_snake),
+            ("Train Case", &conversions.train),
+        ] {
+            table.add_row(vec![
+                TableFormatter::value_cell(name),
+                TableFormatter::highlight_cell(value),
+            ]);
+        }
         
         println!("{}", table);
     }
@@ -4,14 +4,25 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use ignore::WalkBuilder;
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use comfy_table::Cell;
 use futures_util::stream::{self, StreamExt};
 use reqwest::Client;
+use serde::Serialize;
 use std::time::Duration;
 
-pub async fn run(path: &Path) -> Result<()> {
-    println!("{}", Theme::info(format!("Searching for links in: {} ...", path.display())));
+#[derive(Serialize)]
+struct LinkCheck {
+    url: String,
+    ok: bool,
+    status: String,
+    found_in: Vec<PathBuf>,
+}
+
+pub async fn run(path: &Path, format: OutputFormat) -> Result<()> {
+    if format.is_table() {
+        println!("{}", Theme::info(format!("Searching for links in: {} ...", path.display())));
+    }
 
     let mut files = Vec::new();
     if path.is_file() {
@@ -39,12 +50,14 @@ pub async fn run(path: &Path) -> Result<()> {
     }
 
     let urls: Vec<String> = url_to_files.keys().cloned().collect();
-    if urls.is_empty() {
+    if urls.is_empty() && format.is_table() {
         println!("{}", Theme::success("No links found."));
         return Ok(());
     }
 
-    println!("{}", Theme::info(format!("Checking {} unique links...", urls.len())));
+    if format.is_table() {
+        println!("{}", Theme::info(format!("Checking {} unique links...", urls.len())));
+    }
 
     let client = Client::builder()
         .timeout(Duration::from_secs(10))
@@ -60,18 +73,23 @@ pub async fn run(path: &Path) -> Result<()> {
             async move {
                 let res = client.head(&url).send().await;
                 match res {
-                    Ok(resp) => (url, resp.status().is_success(), Some(resp.status().to_string())),
-                    Err(e) => (url, false, Some(e.to_string())),
+                    Ok(resp) => (url, resp.status().is_success(), resp.status().to_string()),
+                    Err(e) => (url, false, e.to_string()),
                 }
             }
         })
         .buffer_unordered(concurrency_limit);
 
-    while let Some((url, success, status)) = stream.next().await {
-        results.push((url, success, status));
+    while let Some((url, ok, status)) = stream.next().await {
+        let found_in = url_to_files.remove(&url).unwrap_or_default();
+        results.push(LinkCheck { url, ok, status, found_in });
     }
 
-    results.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(&b.0)));
+    results.sort_by(|a, b| a.ok.cmp(&b.ok).then(a.url.cmp(&b.url)));
+
+    if !format.is_table() {
+        return format.print(&results);
+    }
 
     let mut table = TableFormatter::create_table();
     table.set_header(vec![
@@ -81,26 +99,26 @@ pub async fn run(path: &Path) -> Result<()> {
     ]);
 
     let mut broken_count = 0;
-    for (url, success, status) in results {
-        if !success {
+    for LinkCheck { url, ok, status, found_in } in results {
+        if !ok {
             broken_count += 1;
         }
 
-        let status_cell = if success {
-            Cell::new(status.unwrap_or_else(|| "OK".to_string())).fg(comfy_table::Color::Green)
+        let status_cell = if ok {
+            Cell::new(status).fg(comfy_table::Color::Green)
         } else {
-            Cell::new(status.unwrap_or_else(|| "FAILED".to_string())).fg(comfy_table::Color::Red).add_attribute(comfy_table::Attribute::Bold)
+            Cell::new(status).fg(comfy_table::Color::Red).add_attribute(comfy_table::Attribute::Bold)
         };
 
-        let files_str = url_to_files.get(&url).unwrap()
+        let files_str = found_in
             .iter()
             .take(3)
             .map(|p| p.file_name().and_then(|n| n.to_str()).unwrap_or("?"))
             .collect::<Vec<_>>()
             .join(", ");
         
-        let files_display = if url_to_files.get(&url).unwrap().len() > 3 {
-            format!("{} (+{} more)", files_str, url_to_files.get(&url).unwrap().len() - 3)
+        let files_display = if found_in.len() > 3 {
+            format!("{} (+{} more)", files_str, found_in.len() - 3)
         } else {
             files_str
         };
@@ -1,8 +1,25 @@
 use anyhow::Result;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use cli_core::ui::Theme;
+use serde::Serialize;
 
-pub fn calculate(input: &str) -> Result<()> {
+#[derive(Serialize)]
+struct Permissions {
+    numeric: String,
+    symbolic: String,
+    classes: Vec<ClassPermissions>,
+}
+
+#[derive(Serialize)]
+struct ClassPermissions {
+    class: &'static str,
+    read: bool,
+    write: bool,
+    execute: bool,
+    total: u32,
+}
+
+pub fn calculate(input: &str, format: OutputFormat) -> Result<()> {
     let (numeric, symbolic) = if input.len() == 3 && input.chars().all(|c| c.is_ascii_digit()) {
         // Numeric to Symbolic
         let octal = input;
@@ -30,6 +47,25 @@ pub fn calculate(input: &str) -> Result<()> {
         anyhow::bail!("Invalid input. Use numeric (e.g., 755) or symbolic (e.g., rwxr-xr-x) format.");
     };
 
+    let classes = ["Owner", "Group", "Others"];
+    let sym_chars: Vec<char> = symbolic.chars().collect();
+    let num_chars: Vec<char> = numeric.chars().collect();
+
+    if !format.is_table() {
+        let classes = classes
+            .iter()
+            .enumerate()
+            .map(|(i, class)| ClassPermissions {
+                class,
+                read: sym_chars[i * 3] == 'r',
+                write: sym_chars[i * 3 + 1] == 'w',
+                execute: sym_chars[i * 3 + 2] == 'x',
+                total: num_chars[i].to_digit(8).unwrap_or(0),
+            })
+            .collect();
+        return format.print(&Permissions { numeric, symbolic, classes });
+    }
+
     println!("{}", Theme::header("Chmod Permission Breakdown"));
     println!("  {} {} | {} {}", Theme::info("Numeric:"), Theme::value(&numeric), Theme::info("Symbolic:"), Theme::value(&symbolic));
     println!();
@@ -43,10 +79,6 @@ pub fn calculate(input: &str) -> Result<()> {
         TableFormatter::header_cell("Total"),
     ]);
 
-    let classes = ["Owner", "Group", "Others"];
-    let sym_chars: Vec<char> = symbolic.chars().collect();
-    let num_chars: Vec<char> = numeric.chars().collect();
-
     for (i, class) in classes.iter().enumerate() {
         let r = if sym_chars[i * 3] == 'r' { Theme::success("Yes") } else { Theme::dim("No") };
         let w = if sym_chars[i * 3 + 1] == 'w' { Theme::success("Yes") } else { Theme::dim("No") };
@@ -1,7 +1,33 @@
 use anyhow::Result;
 use owo_colors::OwoColorize;
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct ColorOutput {
+    hex: String,
+    rgb: [u8; 3],
+    hsl: Hsl,
+    palette: Vec<Swatch>,
+}
+
+/// Hue in degrees, saturation and lightness in percent.
+#[derive(Serialize)]
+struct Hsl {
+    h: f64,
+    s: f64,
+    l: f64,
+}
+
+#[derive(Serialize)]
+struct Swatch {
+    relation: &'static str,
+    hex: String,
+    hsl: Hsl,
+    #[serde(skip)]
+    rgb: (u8, u8, u8),
+}
 
 /// Parse a color given as #RRGGBB, bare RRGGBB hex, or "R,G,B".
 pub(crate) fn parse(input: &str) -> Result<(u8, u8, u8)> {
@@ -30,14 +56,35 @@ pub(crate) fn parse(input: &str) -> Result<(u8, u8, u8)> {
     }
 }
 
-pub fn convert(input: &str) -> Result<()> {
+pub fn convert(input: &str, format: OutputFormat) -> Result<()> {
     let (r, g, b) = parse(input)?;
 
     let hex = format!("#{:02X}{:02X}{:02X}", r, g, b);
     let (h, s, l) = rgb_to_hsl(r, g, b);
 
-    println!("{}", Theme::header(format!("--- Color Information: {} ---", hex)));
-    
+    let palette = vec![
+        // Complementary
+        swatch("Complementary", (h + 180.0) % 360.0, s, l),
+        // Analogous
+        swatch("Analogous Left", (h + 330.0) % 360.0, s, l),
+        swatch("Analogous Right", (h + 30.0) % 360.0, s, l),
+        // Triadic
+        swatch("Triadic 1", (h + 120.0) % 360.0, s, l),
+        swatch("Triadic 2", (h + 240.0) % 360.0, s, l),
+        // Monochromatic
+        swatch("Darker", h, s, (l - 0.2).max(0.0)),
+        swatch("Lighter", h, s, (l + 0.2).min(1.0)),
+    ];
+
+    if !format.is_table() {
+        return format.print(&ColorOutput {
+            hex,
+            rgb: [r, g, b],
+            hsl: Hsl::new(h, s, l),
+            palette,
+        });
+    }
+
     let mut info_table = TableFormatter::create_table();
     info_table.add_row(vec![TableFormatter::header_cell("Hex"), TableFormatter::value_cell(&hex)]);
     info_table.add_row(vec![TableFormatter::header_cell("RGB"), TableFormatter::value_cell(format!("rgb({}, {}, {})", r, g, b))]);
@@ -46,10 +93,6 @@ pub fn convert(input: &str) -> Result<()> {
         TableFormatter::header_cell("Preview"),
         TableFormatter::value_cell(format!("  {}  ", " ".on_truecolor(r, g, b)))
     ]);
-    println!("{info_table}");
-
-    println!("\n{}", Theme::header("--- Color Palettes ---"));
-    
     let mut palette_table = TableFormatter::create_table();
     palette_table.set_header(vec![
         TableFormatter::header_cell("Relation"),
@@ -57,37 +100,40 @@ pub fn convert(input: &str) -> Result<()> {
         TableFormatter::header_cell("HSL"),
         TableFormatter::header_cell("Preview"),
     ]);
+    for swatch in &palette {
+        let (r, g, b) = swatch.rgb;
+        palette_table.add_row(vec![
+            TableFormatter::value_cell(swatch.relation),
+            TableFormatter::value_cell(&swatch.hex),
+            TableFormatter::value_cell(format!("{:.0}°, {:.0}%, {:.0}%", swatch.hsl.h, swatch.hsl.s, swatch.hsl.l)),
+            TableFormatter::value_cell(format!("  {}  ", " ".on_truecolor(r, g, b))),
+        ]);
+    }
 
-    // Complementary
-    let comp_h = (h + 180.0) % 360.0;
-    add_palette_row(&mut palette_table, "Complementary", comp_h, s, l);
-
-    // Analogous
-    add_palette_row(&mut palette_table, "Analogous Left", (h + 330.0) % 360.0, s, l);
-    add_palette_row(&mut palette_table, "Analogous Right", (h + 30.0) % 360.0, s, l);
-
-    // Triadic
-    add_palette_row(&mut palette_table, "Triadic 1", (h + 120.0) % 360.0, s, l);
-    add_palette_row(&mut palette_table, "Triadic 2", (h + 240.0) % 360.0, s, l);
-
-    // Monochromatic
-    add_palette_row(&mut palette_table, "Darker", h, s, (l - 0.2).max(0.0));
-    add_palette_row(&mut palette_table, "Lighter", h, s, (l + 0.2).min(1.0));
+    println!("{}", Theme::header(format!("--- Color Information: {} ---", hex)));
+    println!("{info_table}");
 
+    println!("\n{}", Theme::header("--- Color Palettes ---"));
     println!("{palette_table}");
     
     Ok(())
 }
 
-fn add_palette_row(table: &mut comfy_table::Table, label: &str, h: f64, s: f64, l: f64) {
+impl Hsl {
+    /// From fractions of one for saturation and lightness.
+    fn new(h: f64, s: f64, l: f64) -> Self {
+        Self { h, s: s * 100.0, l: l * 100.0 }
+    }
+}
+
+fn swatch(relation: &'static str, h: f64, s: f64, l: f64) -> Swatch {
     let (r, g, b) = hsl_to_rgb(h, s, l);
-    let hex = format!("#{:02X}{:02X}{:02X}", r, g, b);
-    table.add_row(vec![
-        TableFormatter::value_cell(label),
-        TableFormatter::value_cell(hex),
-        TableFormatter::value_cell(format!("{:.0}°, {:.0}%, {:.0}%", h, s * 100.0, l * 100.0)),
-        TableFormatter::value_cell(format!("  {}  ", " ".on_truecolor(r, g, b))),
-    ]);
+    Swatch {
+        relation,
+        hex: format!("#{:02X}{:02X}{:02X}", r, g, b),
+        hsl: Hsl::new(h, s, l),
+        rgb: (r, g, b),
+    }
 }
 
 fn rgb_to_hsl(r: u8, g: u8, b: u8) -> (f64, f64, f64) {
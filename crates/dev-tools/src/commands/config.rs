@@ -0,0 +1,62 @@
+use anyhow::Result;
+use cli_core::output::{OutputFormat, TableFormatter};
+use cli_core::ui::Theme;
+use cli_core::{ConfigSource, LayeredConfig};
+use comfy_table::{Cell, Color};
+use std::path::Path;
+
+/// Print the shared cli-tools configuration resolved for `path`.
+///
+/// With `effective`, every key is listed with the layer it came from;
+/// otherwise the merged configuration is printed as TOML. Structured formats
+/// print the same data as records instead.
+pub fn show(path: &Path, effective: bool, format: OutputFormat) -> Result<()> {
+    let layered = LayeredConfig::load(path)?;
+
+    if !format.is_table() {
+        return if effective {
+            format.print(&layered.entries())
+        } else {
+            format.print(&layered.config()?)
+        };
+    }
+
+    if !effective {
+        let config = layered.config()?;
+        print!("{}", ::toml::to_string_pretty(&config)?);
+        return Ok(());
+    }
+
+    println!("{}", Theme::header("Effective Configuration"));
+    if layered.files().is_empty() {
+        println!("{}", Theme::dim("No config files found; using defaults and environment."));
+    }
+    for (source, file) in layered.files() {
+        println!("  {} {}", Theme::dim(format!("{source}:")), file.display());
+    }
+    println!();
+
+    let mut table = TableFormatter::create_table();
+    table.set_header(vec![
+        TableFormatter::header_cell("Key"),
+        TableFormatter::header_cell("Value"),
+        TableFormatter::header_cell("Source"),
+    ]);
+
+    for entry in layered.entries() {
+        let source = Cell::new(entry.source.to_string());
+        let source = match entry.source {
+            ConfigSource::Default => source.fg(Color::DarkGrey),
+            ConfigSource::User | ConfigSource::Repository => source.fg(Color::Cyan),
+            ConfigSource::Env | ConfigSource::Flag => source.fg(Color::Yellow),
+        };
+        table.add_row(vec![
+            TableFormatter::value_cell(&entry.key),
+            TableFormatter::value_cell(&entry.value),
+            source,
+        ]);
+    }
+
+    println!("{table}");
+    Ok(())
+}
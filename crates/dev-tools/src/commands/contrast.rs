@@ -1,14 +1,45 @@
 use super::color;
 use anyhow::Result;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use cli_core::ui::Theme;
 use owo_colors::OwoColorize;
+use serde::Serialize;
 
-pub fn run(foreground: &str, background: &str) -> Result<()> {
+const LEVELS: [(&str, f64); 4] = [
+    ("AA normal text", 4.5),
+    ("AA large text / UI", 3.0),
+    ("AAA normal text", 7.0),
+    ("AAA large text", 4.5),
+];
+
+#[derive(Serialize)]
+struct ContrastOutput<'a> {
+    foreground: &'a str,
+    background: &'a str,
+    ratio: f64,
+    levels: Vec<LevelResult>,
+}
+
+#[derive(Serialize)]
+struct LevelResult {
+    level: &'static str,
+    required: f64,
+    pass: bool,
+}
+
+pub fn run(foreground: &str, background: &str, format: OutputFormat) -> Result<()> {
     let fg = color::parse(foreground)?;
     let bg = color::parse(background)?;
     let ratio = contrast_ratio(fg, bg);
 
+    if !format.is_table() {
+        let levels = LEVELS
+            .iter()
+            .map(|&(level, required)| LevelResult { level, required, pass: ratio >= required })
+            .collect();
+        return format.print(&ContrastOutput { foreground, background, ratio, levels });
+    }
+
     println!("\n{}", Theme::header("WCAG Contrast"));
     println!(
         "  {} {}  on  {}   {}",
@@ -24,12 +55,7 @@ pub fn run(foreground: &str, background: &str) -> Result<()> {
         TableFormatter::header_cell("Required"),
         TableFormatter::header_cell("Result"),
     ]);
-    for (label, required) in [
-        ("AA normal text", 4.5),
-        ("AA large text / UI", 3.0),
-        ("AAA normal text", 7.0),
-        ("AAA large text", 4.5),
-    ] {
+    for (label, required) in LEVELS {
         let pass = ratio >= required;
         table.add_row(vec![
             TableFormatter::value_cell(label),
@@ -1,7 +1,7 @@
 use anyhow::Result;
 use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
-use serde::Deserialize;
-use cli_core::output::TableFormatter;
+use serde::{Deserialize, Serialize};
+use cli_core::output::{OutputFormat, TableFormatter};
 use cli_core::ui::Theme;
 
 #[derive(Deserialize, Debug)]
@@ -9,7 +9,7 @@ struct CrateResponse {
     crates: Vec<CrateInfo>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 struct CrateInfo {
     name: String,
     max_version: String,
@@ -17,14 +17,16 @@ struct CrateInfo {
     downloads: u64,
 }
 
-pub async fn search(query: &str) -> Result<()> {
+pub async fn search(query: &str, format: OutputFormat) -> Result<()> {
     let client = reqwest::Client::new();
     let mut headers = HeaderMap::new();
     headers.insert(USER_AGENT, HeaderValue::from_static("dev-tools (https://github.com/chann/cli-tools)"));
 
     let url = format!("https://crates.io/api/v1/crates?page=1&per_page=10&q={}", query);
     
-    println!("{}", Theme::info(format!("Searching for crates matching '{}'...", query)));
+    if format.is_table() {
+        println!("{}", Theme::info(format!("Searching for crates matching '{}'...", query)));
+    }
 
     let response = client.get(url)
         .headers(headers)
@@ -33,6 +35,10 @@ pub async fn search(query: &str) -> Result<()> {
         .json::<CrateResponse>()
         .await?;
 
+    if !format.is_table() {
+        return format.print(&response.crates);
+    }
+
     if response.crates.is_empty() {
         println!("{}", Theme::warning(format!("No crates found for query: {}", query)));
         return Ok(());
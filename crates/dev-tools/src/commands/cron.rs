@@ -1,47 +1,84 @@
 use anyhow::Result;
-use chrono::{Local, Utc};
-use cli_core::output::TableFormatter;
+use chrono::{Local, SecondsFormat, Utc};
+use cli_core::output::{OutputFormat, TableFormatter};
 use cli_core::ui::Theme;
 use cron::Schedule;
 use cron_descriptor::cronparser::cron_expression_descriptor;
 use owo_colors::OwoColorize;
+use serde::Serialize;
 use std::str::FromStr;
 
-pub fn explain(expression: &str) -> Result<()> {
+#[derive(Serialize)]
+struct CronOutput<'a> {
+    expression: &'a str,
+    description: String,
+    breakdown: Vec<Component>,
+    upcoming: Vec<Run>,
+}
+
+#[derive(Serialize)]
+struct Component {
+    component: &'static str,
+    value: String,
+    description: String,
+}
+
+#[derive(Serialize)]
+struct Run {
+    /// Local time, RFC 3339.
+    time: String,
+    remaining_seconds: i64,
+}
+
+pub fn explain(expression: &str, format: OutputFormat) -> Result<()> {
     let description = cron_expression_descriptor::get_description_cron(expression)
         .map_err(|e| anyhow::anyhow!("Failed to parse cron expression: {:?}", e))?;
 
-    println!("\n{}", Theme::header("Cron Expression Summary"));
-    println!("  {} {}", "Expression:".dimmed(), Theme::value(expression));
-    println!("  {} {}", "Description:".dimmed(), Theme::highlight(&description));
+    if format.is_table() {
+        println!("\n{}", Theme::header("Cron Expression Summary"));
+        println!("  {} {}", "Expression:".dimmed(), Theme::value(expression));
+        println!("  {} {}", "Description:".dimmed(), Theme::highlight(&description));
+    }
+
+    let mut breakdown = Vec::new();
+    let mut upcoming = Vec::new();
 
     // Breakdown components
     let parts: Vec<&str> = expression.split_whitespace().collect();
     if parts.len() >= 5 {
-        println!("\n{}", Theme::header("Expression Breakdown"));
-        let mut table = TableFormatter::create_table();
-        table.set_header(vec![
-            TableFormatter::header_cell("Component"),
-            TableFormatter::header_cell("Value"),
-            TableFormatter::header_cell("Description"),
-        ]);
-
         let labels = if parts.len() == 6 {
             vec!["Second", "Minute", "Hour", "Day of Month", "Month", "Day of Week"]
         } else {
             vec!["Minute", "Hour", "Day of Month", "Month", "Day of Week"]
         };
 
-        for (i, &part) in parts.iter().enumerate() {
-            if i < labels.len() {
+        breakdown = labels
+            .iter()
+            .zip(&parts)
+            .map(|(&label, &part)| Component {
+                component: label,
+                value: part.to_string(),
+                description: get_part_description(label, part),
+            })
+            .collect();
+
+        if format.is_table() {
+            let mut table = TableFormatter::create_table();
+            table.set_header(vec![
+                TableFormatter::header_cell("Component"),
+                TableFormatter::header_cell("Value"),
+                TableFormatter::header_cell("Description"),
+            ]);
+            for component in &breakdown {
                 table.add_row(vec![
-                    TableFormatter::value_cell(labels[i]),
-                    TableFormatter::highlight_cell(part),
-                    TableFormatter::value_cell(get_part_description(labels[i], part)),
+                    TableFormatter::value_cell(component.component),
+                    TableFormatter::highlight_cell(&component.value),
+                    TableFormatter::value_cell(&component.description),
                 ]);
             }
+            println!("\n{}", Theme::header("Expression Breakdown"));
+            println!("{}", table);
         }
-        println!("{}", table);
     }
 
     // Upcoming runs
@@ -51,7 +88,6 @@ pub fn explain(expression: &str) -> Result<()> {
     }
 
     if let Ok(schedule) = Schedule::from_str(&cron_expr) {
-        println!("\n{}", Theme::header("Next 10 Scheduled Runs"));
         let mut table = TableFormatter::create_table();
         table.set_header(vec![
             TableFormatter::header_cell("#"),
@@ -63,6 +99,10 @@ pub fn explain(expression: &str) -> Result<()> {
         for (i, datetime) in schedule.upcoming(Utc).take(10).enumerate() {
             let local_time = datetime.with_timezone(&Local);
             let duration = datetime.signed_duration_since(now);
+            upcoming.push(Run {
+                time: local_time.to_rfc3339_opts(SecondsFormat::Secs, false),
+                remaining_seconds: duration.num_seconds(),
+            });
             
             let remaining = if duration.num_days() > 0 {
                 format!("{}d {}h", duration.num_days(), duration.num_hours() % 24)
@@ -80,7 +120,14 @@ pub fn explain(expression: &str) -> Result<()> {
                 TableFormatter::value_cell(remaining),
             ]);
         }
-        println!("{}", table);
+        if format.is_table() {
+            println!("\n{}", Theme::header("Next 10 Scheduled Runs"));
+            println!("{}", table);
+        }
+    }
+
+    if !format.is_table() {
+        return format.print(&CronOutput { expression, description, breakdown, upcoming });
     }
 
     Ok(())
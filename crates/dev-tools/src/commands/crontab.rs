@@ -1,10 +1,11 @@
 use anyhow::{bail, Context, Result};
 use chrono::{DateTime, Local};
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use cli_core::ui::Theme;
 use cron::Schedule;
 use cron_descriptor::cronparser::cron_expression_descriptor;
 use owo_colors::OwoColorize;
+use serde::Serialize;
 use std::io::Write;
 use std::process::{Command, Stdio};
 use std::str::FromStr;
@@ -21,19 +22,42 @@ enum Line {
     Other(String),
 }
 
-pub fn list() -> Result<()> {
+#[derive(Serialize)]
+struct ListedEntry<'a> {
+    index: usize,
+    schedule: &'a str,
+    command: &'a str,
+    description: String,
+    next_run: Option<String>,
+}
+
+pub fn list(format: OutputFormat) -> Result<()> {
     let lines = parse(&read_crontab()?);
-    let entries: Vec<&Line> = lines
+    let entries: Vec<ListedEntry> = lines
         .iter()
-        .filter(|l| matches!(l, Line::Entry { .. }))
+        .filter_map(|l| match l {
+            Line::Entry { schedule, command, .. } => Some((schedule, command)),
+            Line::Other(_) => None,
+        })
+        .enumerate()
+        .map(|(i, (schedule, command))| ListedEntry {
+            index: i + 1,
+            schedule,
+            command,
+            description: describe(schedule),
+            next_run: next_run(schedule).map(|t| t.to_rfc3339()),
+        })
         .collect();
 
+    if !format.is_table() {
+        return format.print(&entries);
+    }
+
     if entries.is_empty() {
         println!("{}", Theme::info("Crontab is empty. Add an entry with: dev-tools crontab add \"0 9 * * *\" \"<command>\""));
         return Ok(());
     }
 
-    println!("\n{}", Theme::header(format!("Crontab ({} entries)", entries.len())));
     let mut table = TableFormatter::create_table();
     table.set_header(vec![
         TableFormatter::header_cell("#"),
@@ -43,21 +67,21 @@ pub fn list() -> Result<()> {
         TableFormatter::header_cell("Next Run"),
     ]);
 
-    for (i, line) in entries.iter().enumerate() {
-        if let Line::Entry { schedule, command, .. } = line {
-            table.add_row(vec![
-                TableFormatter::value_cell(i + 1),
-                TableFormatter::highlight_cell(schedule),
-                TableFormatter::value_cell(command),
-                TableFormatter::value_cell(describe(schedule)),
-                TableFormatter::value_cell(
-                    next_run(schedule).map_or("-".to_string(), |t| {
-                        t.format("%Y-%m-%d %H:%M:%S").to_string()
-                    }),
-                ),
-            ]);
-        }
+    for entry in &entries {
+        table.add_row(vec![
+            TableFormatter::value_cell(entry.index),
+            TableFormatter::highlight_cell(entry.schedule),
+            TableFormatter::value_cell(entry.command),
+            TableFormatter::value_cell(&entry.description),
+            TableFormatter::value_cell(
+                next_run(entry.schedule).map_or("-".to_string(), |t| {
+                    t.format("%Y-%m-%d %H:%M:%S").to_string()
+                }),
+            ),
+        ]);
     }
+
+    println!("\n{}", Theme::header(format!("Crontab ({} entries)", entries.len())));
     println!("{}", table);
     Ok(())
 }
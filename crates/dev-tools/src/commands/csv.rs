@@ -1,10 +1,34 @@
 use anyhow::Result;
-use csv::Reader;
+use csv::{Reader, StringRecord};
+use serde::ser::{Serialize, SerializeMap, Serializer};
 use serde_json::{Value, Map};
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 
-pub fn convert(input_path: &str, to_json: bool, to_yaml: bool, to_markdown: bool) -> Result<()> {
+/// A row serialized as a map in the file's column order, which the JSON
+/// object map would sort.
+struct Row<'a> {
+    headers: &'a StringRecord,
+    fields: &'a [String],
+}
+
+impl Serialize for Row<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.fields.len()))?;
+        for (header, field) in self.headers.iter().zip(self.fields) {
+            map.serialize_entry(header, field)?;
+        }
+        map.end()
+    }
+}
+
+pub fn convert(
+    input_path: &str,
+    to_json: bool,
+    to_yaml: bool,
+    to_markdown: bool,
+    format: OutputFormat,
+) -> Result<()> {
     let mut reader = Reader::from_path(input_path)?;
     let headers = reader.headers()?.clone();
     
@@ -48,6 +72,12 @@ pub fn convert(input_path: &str, to_json: bool, to_yaml: bool, to_markdown: bool
             output.push_str("\n");
         }
         println!("{}", output);
+    } else if !format.is_table() {
+        let ordered: Vec<Row> = rows
+            .iter()
+            .map(|row| Row { headers: &headers, fields: row })
+            .collect();
+        format.print(&ordered)?;
     } else {
         // Default: Show terminal preview and then JSON
         println!("{}", Theme::header("CSV Data Preview"));
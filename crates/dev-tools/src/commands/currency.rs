@@ -1,8 +1,10 @@
 use anyhow::{Result, Context};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
+use cli_core::currency::{rates_path, Currency, Rates};
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 
 #[derive(Deserialize, Debug)]
 struct CurrencyResponse {
@@ -10,11 +12,97 @@ struct CurrencyResponse {
     rates: HashMap<String, f64>,
 }
 
-pub async fn convert(amount: f64, from: &str, to: &str) -> Result<()> {
+#[derive(Serialize)]
+struct SavedRates<'a> {
+    file: PathBuf,
+    date: &'a str,
+    currencies: usize,
+}
+
+#[derive(Serialize)]
+struct Conversion<'a> {
+    amount: f64,
+    from: &'a str,
+    to: &'a str,
+    result: f64,
+    rate: f64,
+    date: &'a str,
+}
+
+/// Fetch the latest rates against USD and save them as the offline table.
+pub async fn update_rates(format: OutputFormat) -> Result<()> {
+    let path = rates_path().context("Cannot locate the configuration directory")?;
+
+    if format.is_table() {
+        println!("{}", Theme::info("Fetching the latest exchange rates..."));
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()?;
+
+    let resp = client
+        .get("https://api.frankfurter.app/latest?from=USD")
+        .send()
+        .await
+        .context("Failed to connect to currency exchange service")?;
+
+    if !resp.status().is_success() {
+        anyhow::bail!("Currency exchange service returned error: {}", resp.status());
+    }
+
+    let data = resp
+        .json::<CurrencyResponse>()
+        .await
+        .context("Failed to parse currency exchange response")?;
+
+    let rates = Rates {
+        base: Currency::USD,
+        date: data.date,
+        rates: data
+            .rates
+            .into_iter()
+            .filter_map(|(code, rate)| Some((code.parse().ok()?, rate)))
+            .collect(),
+        source: Some(path.clone()),
+    };
+    rates.save(&path)?;
+
+    if !format.is_table() {
+        return format.print(&SavedRates { file: path, date: &rates.date, currencies: rates.rates.len() });
+    }
+
+    let mut table = TableFormatter::create_table();
+    table.set_header(vec![
+        TableFormatter::header_cell("Field"),
+        TableFormatter::header_cell("Value"),
+    ]);
+    table.add_row(vec![
+        TableFormatter::value_cell("File"),
+        TableFormatter::value_cell(path.display().to_string()),
+    ]);
+    table.add_row(vec![
+        TableFormatter::value_cell("Date"),
+        TableFormatter::value_cell(&rates.date),
+    ]);
+    table.add_row(vec![
+        TableFormatter::value_cell("Currencies"),
+        TableFormatter::highlight_cell(rates.rates.len().to_string()),
+    ]);
+
+    println!("\n{}", Theme::header(" Exchange Rates Saved "));
+    println!("{}", table);
+
+    Ok(())
+}
+
+pub async fn convert(amount: f64, from: &str, to: &str, format: OutputFormat) -> Result<()> {
     let from = from.to_uppercase();
     let to = to.to_uppercase();
 
-    println!("{}", Theme::info(format!("Converting {} {} to {}...", amount, from, to)));
+    if format.is_table() {
+        println!("{}", Theme::info(format!("Converting {} {} to {}...", amount, from, to)));
+    }
 
     let url = format!(
         "https://api.frankfurter.app/latest?amount={}&from={}&to={}",
@@ -44,6 +132,17 @@ pub async fn convert(amount: f64, from: &str, to: &str) -> Result<()> {
         .context("Failed to parse currency exchange response")?;
 
     if let Some(&converted_amount) = data.rates.get(&to) {
+        if !format.is_table() {
+            return format.print(&Conversion {
+                amount,
+                from: &from,
+                to: &to,
+                result: converted_amount,
+                rate: converted_amount / amount,
+                date: &data.date,
+            });
+        }
+
         let mut table = TableFormatter::create_table();
         table.set_header(vec![
             TableFormatter::header_cell("Field"),
@@ -1,37 +1,42 @@
 use anyhow::Result;
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
-use serde::Deserialize;
+use cli_core::output::{OutputFormat, TableFormatter};
+use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 struct DictionaryEntry {
     word: String,
     phonetic: Option<String>,
     meanings: Vec<Meaning>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 struct Meaning {
     #[serde(rename = "partOfSpeech")]
     part_of_speech: String,
     definitions: Vec<Definition>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 struct Definition {
     definition: String,
     example: Option<String>,
 }
 
-pub async fn lookup(word: &str) -> Result<()> {
+pub async fn lookup(word: &str, format: OutputFormat) -> Result<()> {
     let url = format!("https://api.dictionaryapi.dev/api/v2/entries/en/{}", word);
 
-    println!("{}", Theme::info(format!("Looking up '{}'...", word)));
+    if format.is_table() {
+        println!("{}", Theme::info(format!("Looking up '{}'...", word)));
+    }
 
     let resp = reqwest::get(url).await?;
 
     if resp.status().is_success() {
         let entries: Vec<DictionaryEntry> = resp.json().await?;
+        if !format.is_table() {
+            return format.print(&entries);
+        }
         for entry in entries {
             println!("\n{} {}", Theme::highlight(&entry.word), Theme::dim(entry.phonetic.as_deref().unwrap_or_default()));
             
@@ -57,7 +62,7 @@ pub async fn lookup(word: &str) -> Result<()> {
             }
         }
     } else if resp.status() == 404 {
-        println!("{}", Theme::error("No definition found."));
+        eprintln!("{}", Theme::error("No definition found."));
     } else {
         anyhow::bail!("Failed to lookup dictionary: {}", resp.status());
     }
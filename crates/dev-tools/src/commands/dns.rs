@@ -2,57 +2,58 @@ use anyhow::Result;
 use trust_dns_resolver::TokioAsyncResolver;
 use trust_dns_resolver::config::*;
 use trust_dns_resolver::proto::rr::RecordType;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use cli_core::ui::Theme;
+use serde::Serialize;
 
-pub async fn lookup(domain: &str, record_type: &str) -> Result<()> {
+#[derive(Serialize)]
+struct DnsRecord {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    priority: Option<u16>,
+}
+
+impl DnsRecord {
+    fn new(kind: &'static str, value: impl ToString) -> Self {
+        Self { kind, value: value.to_string(), priority: None }
+    }
+}
+
+pub async fn lookup(domain: &str, record_type: &str, format: OutputFormat) -> Result<()> {
     let resolver = TokioAsyncResolver::tokio(ResolverConfig::default(), ResolverOpts::default());
     
     let rt_upper = record_type.to_uppercase();
-    println!("{}", Theme::info(format!("DNS Lookup: {} ({})", domain, rt_upper)));
+    if format.is_table() {
+        println!("{}", Theme::info(format!("DNS Lookup: {} ({})", domain, rt_upper)));
+    }
 
-    let mut table = TableFormatter::create_table();
-    table.set_header(vec![
-        TableFormatter::header_cell("Type"),
-        TableFormatter::header_cell("Value"),
-    ]);
+    let mut records = Vec::new();
 
     match record_type.to_lowercase().as_str() {
         "a" => {
             let response = resolver.lookup_ip(domain).await?;
-            for ip in response.iter() {
-                table.add_row(vec![
-                    TableFormatter::value_cell("A"),
-                    TableFormatter::highlight_cell(ip.to_string()),
-                ]);
-            }
+            records.extend(response.iter().map(|ip| DnsRecord::new("A", ip)));
         }
         "aaaa" => {
             let response = resolver.ipv6_lookup(domain).await?;
-            for ip in response.iter() {
-                table.add_row(vec![
-                    TableFormatter::value_cell("AAAA"),
-                    TableFormatter::highlight_cell(ip.to_string()),
-                ]);
-            }
+            records.extend(response.iter().map(|ip| DnsRecord::new("AAAA", ip)));
         }
         "mx" => {
             let response = resolver.mx_lookup(domain).await?;
             for mx in response.iter() {
-                table.add_row(vec![
-                    TableFormatter::value_cell("MX"),
-                    TableFormatter::value_cell(format!("{} (priority: {})", mx.exchange(), mx.preference())),
-                ]);
+                records.push(DnsRecord {
+                    priority: Some(mx.preference()),
+                    ..DnsRecord::new("MX", mx.exchange())
+                });
             }
         }
         "txt" => {
             let response = resolver.txt_lookup(domain).await?;
             for txt in response.iter() {
                 for data in txt.iter() {
-                    table.add_row(vec![
-                        TableFormatter::value_cell("TXT"),
-                        TableFormatter::value_cell(String::from_utf8_lossy(data)),
-                    ]);
+                    records.push(DnsRecord::new("TXT", String::from_utf8_lossy(data)));
                 }
             }
         }
@@ -60,10 +61,7 @@ pub async fn lookup(domain: &str, record_type: &str) -> Result<()> {
             let response = resolver.lookup(domain, RecordType::CNAME).await?;
             for record in response.records() {
                 if let Some(cname) = record.data().and_then(|d| d.as_cname()) {
-                    table.add_row(vec![
-                        TableFormatter::value_cell("CNAME"),
-                        TableFormatter::value_cell(cname.to_string()),
-                    ]);
+                    records.push(DnsRecord::new("CNAME", cname));
                 }
             }
         }
@@ -71,21 +69,35 @@ pub async fn lookup(domain: &str, record_type: &str) -> Result<()> {
             let response = resolver.lookup(domain, RecordType::NS).await?;
             for record in response.records() {
                 if let Some(ns) = record.data().and_then(|d| d.as_ns()) {
-                    table.add_row(vec![
-                        TableFormatter::value_cell("NS"),
-                        TableFormatter::value_cell(ns.to_string()),
-                    ]);
+                    records.push(DnsRecord::new("NS", ns));
                 }
             }
         }
         _ => anyhow::bail!("Unsupported record type: {}. Supported: a, aaaa, mx, txt, cname, ns", record_type),
     }
 
-    if table.is_empty() {
+    if !format.is_table() {
+        return format.print(&records);
+    }
+    if records.is_empty() {
         println!("{}", Theme::warning("No records found."));
-    } else {
-        println!("\n{}", table);
+        return Ok(());
+    }
+
+    let mut table = TableFormatter::create_table();
+    table.set_header(vec![
+        TableFormatter::header_cell("Type"),
+        TableFormatter::header_cell("Value"),
+    ]);
+    for record in &records {
+        let value = match record.priority {
+            Some(priority) => TableFormatter::value_cell(format!("{} (priority: {})", record.value, priority)),
+            None if matches!(record.kind, "A" | "AAAA") => TableFormatter::highlight_cell(&record.value),
+            None => TableFormatter::value_cell(&record.value),
+        };
+        table.add_row(vec![TableFormatter::value_cell(record.kind), value]);
     }
+    println!("\n{}", table);
 
     Ok(())
 }
@@ -1,37 +1,50 @@
 use anyhow::Result;
 use std::env;
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
+use serde::Serialize;
 
-pub fn list(filter: Option<String>) -> Result<()> {
+#[derive(Serialize)]
+struct EnvVar {
+    name: String,
+    value: String,
+}
+
+pub fn list(filter: Option<String>, format: OutputFormat) -> Result<()> {
     let mut vars: Vec<(String, String)> = env::vars().collect();
     vars.sort_by(|a, b| a.0.cmp(&b.0));
     
+    if let Some(ref f) = filter {
+        let f = f.to_lowercase();
+        vars.retain(|(key, _)| key.to_lowercase().contains(&f));
+    }
+    let count = vars.len();
+
+    if !format.is_table() {
+        let vars: Vec<EnvVar> = vars
+            .into_iter()
+            .map(|(name, value)| EnvVar { name, value })
+            .collect();
+        return format.print(&vars);
+    }
+
     let mut table = TableFormatter::create_table();
     table.set_header(vec![
         TableFormatter::header_cell("Variable"),
         TableFormatter::header_cell("Value"),
     ]);
     
-    let mut count = 0;
     for (key, value) in vars {
-        if let Some(ref f) = filter {
-            if key.to_lowercase().contains(&f.to_lowercase()) {
-                table.add_row(vec![
-                    TableFormatter::highlight_cell(key),
-                    TableFormatter::value_cell(value),
-                ]);
-                count += 1;
-            }
-        } else {
-            table.add_row(vec![
-                TableFormatter::value_cell(key),
-                TableFormatter::value_cell(value),
-            ]);
-            count += 1;
-        }
+        table.add_row(vec![
+            if filter.is_some() {
+                TableFormatter::highlight_cell(key)
+            } else {
+                TableFormatter::value_cell(key)
+            },
+            TableFormatter::value_cell(value),
+        ]);
     }
-    
+
     if let Some(f) = filter {
         println!("{}", Theme::info(format!("Filtered environment variables (matching \"{}\"): {} found", f, count)));
     } else {
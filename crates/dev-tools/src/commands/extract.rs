@@ -2,9 +2,16 @@ use anyhow::Result;
 use regex::Regex;
 use std::fs;
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
+use serde::Serialize;
 
-pub fn extract(input: &str, kind: &str, is_file: bool) -> Result<()> {
+#[derive(Serialize)]
+struct Extracted<'a> {
+    kind: &'static str,
+    value: &'a str,
+}
+
+pub fn extract(input: &str, kind: &str, is_file: bool, format: OutputFormat) -> Result<()> {
     let text = if is_file {
         fs::read_to_string(input)?
     } else {
@@ -28,13 +35,19 @@ pub fn extract(input: &str, kind: &str, is_file: bool) -> Result<()> {
     matches.sort_unstable();
     matches.dedup();
 
+    if !format.is_table() {
+        let extracted: Vec<Extracted> = matches
+            .iter()
+            .map(|value| Extracted { kind: label, value })
+            .collect();
+        return format.print(&extracted);
+    }
+
     if matches.is_empty() {
         println!("{}", Theme::warning(format!("No {} found in the input.", label)));
         return Ok(());
     }
 
-    println!("{}", Theme::header(format!("--- Extracted {}: {} ---", label, matches.len())));
-    
     let mut table = TableFormatter::create_table();
     table.set_header(vec![
         TableFormatter::header_cell("#"),
@@ -48,7 +61,7 @@ pub fn extract(input: &str, kind: &str, is_file: bool) -> Result<()> {
         ]);
     }
 
+    println!("{}", Theme::header(format!("--- Extracted {}: {} ---", label, matches.len())));
     println!("{}", table);
-
     Ok(())
 }
@@ -1,10 +1,15 @@
 use anyhow::{Result, anyhow};
 use std::process::Command;
-use serde_json::Value;
-use cli_core::output::TableFormatter;
+use serde::{Deserialize, Serialize};
+use cli_core::output::{OutputFormat, TableFormatter};
 use cli_core::ui::Theme;
 
-pub fn open(issue: Option<u32>, pr: Option<u32>) -> Result<()> {
+#[derive(Serialize)]
+struct Opened<'a> {
+    url: &'a str,
+}
+
+pub fn open(issue: Option<u32>, pr: Option<u32>, format: OutputFormat) -> Result<()> {
     let output = Command::new("git")
         .args(["remote", "get-url", "origin"])
         .output()
@@ -30,7 +35,11 @@ pub fn open(issue: Option<u32>, pr: Option<u32>) -> Result<()> {
         url = format!("{}/pull/{}", url, pr_num);
     }
 
-    println!("{}", Theme::info(format!("Opening {}...", url)));
+    if format.is_table() {
+        println!("{}", Theme::info(format!("Opening {}...", url)));
+    } else {
+        format.print(&Opened { url: &url })?;
+    }
 
     #[cfg(target_os = "macos")]
     {
@@ -48,7 +57,41 @@ pub fn open(issue: Option<u32>, pr: Option<u32>) -> Result<()> {
     Ok(())
 }
 
-pub async fn get_user(username: &str) -> Result<()> {
+#[derive(Deserialize, Serialize)]
+struct User {
+    login: String,
+    name: Option<String>,
+    bio: Option<String>,
+    location: Option<String>,
+    public_repos: u64,
+    followers: u64,
+    following: u64,
+    html_url: String,
+}
+
+#[derive(Deserialize, Serialize)]
+struct Repo {
+    full_name: String,
+    description: Option<String>,
+    language: Option<String>,
+    stargazers_count: u64,
+    forks_count: u64,
+    open_issues_count: u64,
+    license: Option<License>,
+    html_url: String,
+}
+
+#[derive(Deserialize, Serialize)]
+struct License {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct SearchResults {
+    items: Vec<Repo>,
+}
+
+pub async fn get_user(username: &str, format: OutputFormat) -> Result<()> {
     let client = reqwest::Client::new();
     let res = client.get(format!("https://api.github.com/users/{}", username))
         .header("User-Agent", "dev-tools")
@@ -59,26 +102,29 @@ pub async fn get_user(username: &str) -> Result<()> {
         anyhow::bail!("Failed to fetch user info: {}", res.status());
     }
 
-    let user: Value = res.json().await?;
+    let user: User = res.json().await?;
+
+    if !format.is_table() {
+        return format.print(&user);
+    }
 
     println!("{}", Theme::header(format!("GitHub User: {}", username)));
     
     let mut table = TableFormatter::create_table();
-    table.add_row(vec![TableFormatter::header_cell("Field"), TableFormatter::header_cell("Value")]);
-    table.add_row(vec![TableFormatter::value_cell("Name"), TableFormatter::highlight_cell(user["name"].as_str().unwrap_or("N/A"))]);
-    table.add_row(vec![TableFormatter::value_cell("Bio"), TableFormatter::value_cell(user["bio"].as_str().unwrap_or("N/A"))]);
-    table.add_row(vec![TableFormatter::value_cell("Location"), TableFormatter::value_cell(user["location"].as_str().unwrap_or("N/A"))]);
-    table.add_row(vec![TableFormatter::value_cell("Public Repos"), TableFormatter::value_cell(user["public_repos"].to_string())]);
-    table.add_row(vec![TableFormatter::value_cell("Followers"), TableFormatter::value_cell(user["followers"].to_string())]);
-    table.add_row(vec![TableFormatter::value_cell("Following"), TableFormatter::value_cell(user["following"].to_string())]);
-    table.add_row(vec![TableFormatter::value_cell("URL"), TableFormatter::value_cell(user["html_url"].as_str().unwrap_or("N/A"))]);
+    table.set_header(vec![TableFormatter::header_cell("Field"), TableFormatter::header_cell("Value")]);
+    table.add_row(vec![TableFormatter::value_cell("Name"), TableFormatter::highlight_cell(or_na(&user.name))]);
+    table.add_row(vec![TableFormatter::value_cell("Bio"), TableFormatter::value_cell(or_na(&user.bio))]);
+    table.add_row(vec![TableFormatter::value_cell("Location"), TableFormatter::value_cell(or_na(&user.location))]);
+    table.add_row(vec![TableFormatter::value_cell("Public Repos"), TableFormatter::value_cell(user.public_repos)]);
+    table.add_row(vec![TableFormatter::value_cell("Followers"), TableFormatter::value_cell(user.followers)]);
+    table.add_row(vec![TableFormatter::value_cell("Following"), TableFormatter::value_cell(user.following)]);
+    table.add_row(vec![TableFormatter::value_cell("URL"), TableFormatter::value_cell(&user.html_url)]);
 
     println!("{}", table);
-
     Ok(())
 }
 
-pub async fn get_repo(repo_path: &str) -> Result<()> {
+pub async fn get_repo(repo_path: &str, format: OutputFormat) -> Result<()> {
     let client = reqwest::Client::new();
     let res = client.get(format!("https://api.github.com/repos/{}", repo_path))
         .header("User-Agent", "dev-tools")
@@ -89,27 +135,30 @@ pub async fn get_repo(repo_path: &str) -> Result<()> {
         anyhow::bail!("Failed to fetch repo info: {}", res.status());
     }
 
-    let repo: Value = res.json().await?;
+    let repo: Repo = res.json().await?;
+
+    if !format.is_table() {
+        return format.print(&repo);
+    }
 
     println!("{}", Theme::header(format!("GitHub Repo: {}", repo_path)));
     
     let mut table = TableFormatter::create_table();
-    table.add_row(vec![TableFormatter::header_cell("Field"), TableFormatter::header_cell("Value")]);
-    table.add_row(vec![TableFormatter::value_cell("Full Name"), TableFormatter::highlight_cell(repo["full_name"].as_str().unwrap_or("N/A"))]);
-    table.add_row(vec![TableFormatter::value_cell("Description"), TableFormatter::value_cell(repo["description"].as_str().unwrap_or("N/A"))]);
-    table.add_row(vec![TableFormatter::value_cell("Language"), TableFormatter::value_cell(repo["language"].as_str().unwrap_or("N/A"))]);
-    table.add_row(vec![TableFormatter::value_cell("Stars"), TableFormatter::value_cell(repo["stargazers_count"].to_string())]);
-    table.add_row(vec![TableFormatter::value_cell("Forks"), TableFormatter::value_cell(repo["forks_count"].to_string())]);
-    table.add_row(vec![TableFormatter::value_cell("Open Issues"), TableFormatter::value_cell(repo["open_issues_count"].to_string())]);
-    table.add_row(vec![TableFormatter::value_cell("License"), TableFormatter::value_cell(repo["license"]["name"].as_str().unwrap_or("N/A"))]);
-    table.add_row(vec![TableFormatter::value_cell("URL"), TableFormatter::value_cell(repo["html_url"].as_str().unwrap_or("N/A"))]);
+    table.set_header(vec![TableFormatter::header_cell("Field"), TableFormatter::header_cell("Value")]);
+    table.add_row(vec![TableFormatter::value_cell("Full Name"), TableFormatter::highlight_cell(&repo.full_name)]);
+    table.add_row(vec![TableFormatter::value_cell("Description"), TableFormatter::value_cell(or_na(&repo.description))]);
+    table.add_row(vec![TableFormatter::value_cell("Language"), TableFormatter::value_cell(or_na(&repo.language))]);
+    table.add_row(vec![TableFormatter::value_cell("Stars"), TableFormatter::value_cell(repo.stargazers_count)]);
+    table.add_row(vec![TableFormatter::value_cell("Forks"), TableFormatter::value_cell(repo.forks_count)]);
+    table.add_row(vec![TableFormatter::value_cell("Open Issues"), TableFormatter::value_cell(repo.open_issues_count)]);
+    table.add_row(vec![TableFormatter::value_cell("License"), TableFormatter::value_cell(repo.license.as_ref().map_or("N/A", |l| l.name.as_str()))]);
+    table.add_row(vec![TableFormatter::value_cell("URL"), TableFormatter::value_cell(&repo.html_url)]);
 
     println!("{}", table);
-
     Ok(())
 }
 
-pub async fn search(query: &str) -> Result<()> {
+pub async fn search(query: &str, format: OutputFormat) -> Result<()> {
     let client = reqwest::Client::new();
     let res = client.get("https://api.github.com/search/repositories")
         .query(&[("q", query), ("sort", "stars"), ("order", "desc"), ("per_page", "10")])
@@ -121,8 +170,15 @@ pub async fn search(query: &str) -> Result<()> {
         anyhow::bail!("Failed to search repositories: {}", res.status());
     }
 
-    let search_results: Value = res.json().await?;
-    let items = search_results["items"].as_array().ok_or_else(|| anyhow!("Invalid response from GitHub"))?;
+    let items = res
+        .json::<SearchResults>()
+        .await
+        .map_err(|e| anyhow!("Invalid response from GitHub: {}", e))?
+        .items;
+
+    if !format.is_table() {
+        return format.print(&items);
+    }
 
     println!("{}", Theme::info(format!("GitHub Repository Search: {}", query)));
 
@@ -139,26 +195,27 @@ pub async fn search(query: &str) -> Result<()> {
         TableFormatter::header_cell("Language")
     ]);
 
-    for item in items {
-        let name = item["full_name"].as_str().unwrap_or("N/A");
-        let desc = item["description"].as_str().unwrap_or("N/A");
+    for item in &items {
+        let desc = or_na(&item.description);
         let desc_trimmed = if desc.chars().count() > 60 {
             format!("{}...", desc.chars().take(57).collect::<String>())
         } else {
             desc.to_string()
         };
-        let stars = item["stargazers_count"].as_u64().unwrap_or(0);
-        let lang = item["language"].as_str().unwrap_or("N/A");
 
         table.add_row(vec![
-            TableFormatter::highlight_cell(name),
+            TableFormatter::highlight_cell(&item.full_name),
             TableFormatter::value_cell(desc_trimmed),
-            TableFormatter::value_cell(stars.to_string()),
-            TableFormatter::value_cell(lang),
+            TableFormatter::value_cell(item.stargazers_count),
+            TableFormatter::value_cell(or_na(&item.language)),
         ]);
     }
 
-    println!("\n{table}");
-
+    println!();
+    println!("{}", table);
     Ok(())
 }
+
+fn or_na(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or("N/A")
+}
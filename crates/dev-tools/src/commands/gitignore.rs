@@ -1,4 +1,6 @@
 use anyhow::Result;
+use cli_core::scaffold::gitignore_template;
+use cli_core::ui::Theme;
 use reqwest;
 use owo_colors::OwoColorize;
 
@@ -36,7 +38,18 @@ pub async fn generate(targets: Vec<String>) -> Result<()> {
     }
 
     let url = format!("{}/{}", API_BASE, targets.join(","));
-    let response = reqwest::get(url).await?;
+    let response = match reqwest::get(url).await {
+        Ok(response) => response,
+        Err(e) => {
+            // Offline: fall back to the built-in templates when they cover every target
+            if let Some(content) = gitignore_template(&targets) {
+                eprintln!("{}", Theme::warning(format!("gitignore.io unreachable ({}); using built-in templates", e)));
+                println!("{}", content);
+                return Ok(());
+            }
+            return Err(e.into());
+        }
+    };
 
     if !response.status().is_success() {
         anyhow::bail!("Failed to fetch gitignore for: {}. Status: {}", targets.join(", "), response.status());
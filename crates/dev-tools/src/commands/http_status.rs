@@ -1,16 +1,28 @@
 use anyhow::Result;
 use std::collections::HashMap;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use cli_core::ui::Theme;
+use serde::Serialize;
 
-pub fn lookup(code: u16) -> Result<()> {
+#[derive(Serialize)]
+struct HttpStatus {
+    code: u16,
+    name: &'static str,
+    description: &'static str,
+}
+
+pub fn lookup(code: u16, format: OutputFormat) -> Result<()> {
     let statuses = get_statuses();
     
-    if let Some((name, description)) = statuses.get(&code) {
+    if let Some(&(name, description)) = statuses.get(&code) {
+        if !format.is_table() {
+            return format.print(&HttpStatus { code, name, description });
+        }
+
         println!("{}", Theme::header(format!("HTTP Status: {}", code)));
         
         let mut table = TableFormatter::create_table();
-        table.add_row(vec![TableFormatter::header_cell("Field"), TableFormatter::header_cell("Value")]);
+        table.set_header(vec![TableFormatter::header_cell("Field"), TableFormatter::header_cell("Value")]);
         table.add_row(vec![TableFormatter::value_cell("Code"), TableFormatter::highlight_cell(code.to_string())]);
         table.add_row(vec![TableFormatter::value_cell("Name"), TableFormatter::highlight_cell(name.to_string())]);
         table.add_row(vec![TableFormatter::value_cell("Description"), TableFormatter::value_cell(description.to_string())]);
@@ -23,35 +35,46 @@ pub fn lookup(code: u16) -> Result<()> {
     Ok(())
 }
 
-pub fn list() -> Result<()> {
-    let statuses = get_statuses();
-    let mut codes: Vec<_> = statuses.keys().cloned().collect();
-    codes.sort();
-    
-    let mut table = TableFormatter::create_table();
-    table.set_header(vec![
-        TableFormatter::header_cell("Code"),
-        TableFormatter::header_cell("Name"),
-        TableFormatter::header_cell("Description"),
-    ]);
+pub fn list(format: OutputFormat) -> Result<()> {
+    let statuses = sorted_statuses();
 
-    for code in codes {
-        let (name, desc) = statuses.get(&code).unwrap();
-        table.add_row(vec![
-            TableFormatter::highlight_cell(code.to_string()),
-            TableFormatter::value_cell(name.to_string()),
-            TableFormatter::value_cell(desc.to_string()),
-        ]);
+    if !format.is_table() {
+        return format.print(&statuses);
     }
     
-    println!("{}", table);
+    println!("{}", status_table(&statuses));
     Ok(())
 }
 
-pub fn search(query: &str) -> Result<()> {
-    let statuses = get_statuses();
+pub fn search(query: &str, format: OutputFormat) -> Result<()> {
     let query_lower = query.to_lowercase();
+    let mut statuses = sorted_statuses();
+    statuses.retain(|status| {
+        status.name.to_lowercase().contains(&query_lower) || status.description.to_lowercase().contains(&query_lower)
+    });
+
+    if !format.is_table() {
+        format.print(&statuses)?;
+    } else if !statuses.is_empty() {
+        println!("{}", Theme::info(format!("Search results for '{}':", query)));
+        println!("{}", status_table(&statuses));
+    } else {
+        println!("{}", Theme::warning(format!("No matching HTTP status codes found for '{}'.", query)));
+    }
     
+    Ok(())
+}
+
+fn sorted_statuses() -> Vec<HttpStatus> {
+    let mut statuses: Vec<HttpStatus> = get_statuses()
+        .into_iter()
+        .map(|(code, (name, description))| HttpStatus { code, name, description })
+        .collect();
+    statuses.sort_by_key(|status| status.code);
+    statuses
+}
+
+fn status_table(statuses: &[HttpStatus]) -> comfy_table::Table {
     let mut table = TableFormatter::create_table();
     table.set_header(vec![
         TableFormatter::header_cell("Code"),
@@ -59,30 +82,14 @@ pub fn search(query: &str) -> Result<()> {
         TableFormatter::header_cell("Description"),
     ]);
 
-    let mut found = false;
-    let mut codes: Vec<_> = statuses.keys().cloned().collect();
-    codes.sort();
-
-    for code in codes {
-        let (name, desc) = statuses.get(&code).unwrap();
-        if name.to_lowercase().contains(&query_lower) || desc.to_lowercase().contains(&query_lower) {
-            table.add_row(vec![
-                TableFormatter::highlight_cell(code.to_string()),
-                TableFormatter::value_cell(name.to_string()),
-                TableFormatter::value_cell(desc.to_string()),
-            ]);
-            found = true;
-        }
-    }
-
-    if found {
-        println!("{}", Theme::info(format!("Search results for '{}':", query)));
-        println!("{}", table);
-    } else {
-        println!("{}", Theme::warning(format!("No matching HTTP status codes found for '{}'.", query)));
+    for status in statuses {
+        table.add_row(vec![
+            TableFormatter::highlight_cell(status.code.to_string()),
+            TableFormatter::value_cell(status.name),
+            TableFormatter::value_cell(status.description),
+        ]);
     }
-    
-    Ok(())
+    table
 }
 
 fn get_statuses() -> HashMap<u16, (&'static str, &'static str)> {
@@ -1,11 +1,11 @@
 use anyhow::{Result, Context};
 use std::net::UdpSocket;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use owo_colors::OwoColorize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 struct IpInfo {
     ip: String,
     city: Option<String>,
@@ -20,8 +20,8 @@ struct IpInfo {
     currency: Option<String>,
 }
 
-pub async fn show(target: Option<String>) -> Result<()> {
-    if target.is_none() {
+pub async fn show(target: Option<String>, format: OutputFormat) -> Result<()> {
+    if target.is_none() && format.is_table() {
         // Local IP
         if let Ok(socket) = UdpSocket::bind("0.0.0.0:0") {
             if socket.connect("8.8.8.8:80").is_ok() {
@@ -37,7 +37,9 @@ pub async fn show(target: Option<String>) -> Result<()> {
         None => "https://ipapi.co/json/".to_string(),
     };
 
-    println!("{}", Theme::info("Fetching IP information..."));
+    if format.is_table() {
+        println!("{}", Theme::info("Fetching IP information..."));
+    }
 
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(10))
@@ -51,11 +53,15 @@ pub async fn show(target: Option<String>) -> Result<()> {
 
     let info = resp.json::<IpInfo>().await.context("Failed to parse IP info JSON")?;
 
+    if !format.is_table() {
+        return format.print(&info);
+    }
+
     println!("\n{}", Theme::header(" IP Information "));
     
     let mut table = TableFormatter::create_table();
     
-    table.add_row(vec![
+    table.set_header(vec![
         TableFormatter::header_cell("Field"),
         TableFormatter::header_cell("Value"),
     ]);
@@ -120,6 +126,5 @@ pub async fn show(target: Option<String>) -> Result<()> {
     }
 
     println!("{}", table);
-
     Ok(())
 }
@@ -2,22 +2,29 @@ use anyhow::Result;
 use serde_json::Value;
 use owo_colors::OwoColorize;
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
+use serde::Serialize;
 
-pub fn compare(left_str: &str, right_str: &str) -> Result<()> {
+pub fn compare(left_str: &str, right_str: &str, format: OutputFormat) -> Result<()> {
     let left: Value = serde_json::from_str(left_str)?;
     let right: Value = serde_json::from_str(right_str)?;
 
-    println!("{}", Theme::header("--- JSON Structural Diff ---"));
+    if format.is_table() {
+        println!("{}", Theme::header("--- JSON Structural Diff ---"));
+    }
     
     let mut differences = Vec::new();
     diff_values(&left, &right, "$", &mut differences);
 
-    if differences.is_empty() {
+    if differences.is_empty() && format.is_table() {
         println!("{}", Theme::success("No differences found. JSON objects are structurally identical."));
         return Ok(());
     }
 
+    if !format.is_table() {
+        return format.print(&differences);
+    }
+
     let mut table = TableFormatter::create_table();
     table.set_header(vec![
         TableFormatter::header_cell("Path"),
@@ -25,11 +32,14 @@ pub fn compare(left_str: &str, right_str: &str) -> Result<()> {
         TableFormatter::header_cell("Details"),
     ]);
 
-    for (path, diff_type, details) in differences {
-        let (type_label, details_label) = match diff_type {
-            DiffType::Added => (Theme::green("+ Added"), Theme::dim(details)),
-            DiffType::Removed => (Theme::red("- Removed"), Theme::dim(details)),
-            DiffType::Changed => (Theme::yellow("≠ Changed"), details),
+    for Difference { path, kind, left, right } in differences {
+        let (type_label, details_label) = match (kind, left, right) {
+            (DiffType::Added, _, Some(rv)) => (Theme::green("+ Added"), Theme::dim(rv.to_string())),
+            (DiffType::Removed, Some(lv), _) => (Theme::red("- Removed"), Theme::dim(lv.to_string())),
+            (_, lv, rv) => (
+                Theme::yellow("≠ Changed"),
+                format!("{} {} {}", display(lv).red(), "->".dimmed(), display(rv).green()),
+            ),
         };
 
         table.add_row(vec![
@@ -40,17 +50,32 @@ pub fn compare(left_str: &str, right_str: &str) -> Result<()> {
     }
 
     println!("{}", table);
-
     Ok(())
 }
 
+#[derive(Serialize)]
+struct Difference<'a> {
+    path: String,
+    kind: DiffType,
+    /// The value on the left, absent when it was added.
+    left: Option<&'a Value>,
+    /// The value on the right, absent when it was removed.
+    right: Option<&'a Value>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "lowercase")]
 enum DiffType {
     Added,
     Removed,
     Changed,
 }
 
-fn diff_values(left: &Value, right: &Value, path: &str, differences: &mut Vec<(String, DiffType, String)>) {
+fn display(value: Option<&Value>) -> String {
+    value.map(Value::to_string).unwrap_or_default()
+}
+
+fn diff_values<'a>(left: &'a Value, right: &'a Value, path: &str, differences: &mut Vec<Difference<'a>>) {
     match (left, right) {
         (Value::Object(l_map), Value::Object(r_map)) => {
             for (k, v) in l_map {
@@ -58,13 +83,13 @@ fn diff_values(left: &Value, right: &Value, path: &str, differences: &mut Vec<(S
                 if let Some(rv) = r_map.get(k) {
                     diff_values(v, rv, &new_path, differences);
                 } else {
-                    differences.push((new_path, DiffType::Removed, v.to_string()));
+                    differences.push(Difference { path: new_path, kind: DiffType::Removed, left: Some(v), right: None });
                 }
             }
             for (k, v) in r_map {
                 if !l_map.contains_key(k) {
                     let new_path = format!("{}.{}", path, k);
-                    differences.push((new_path, DiffType::Added, v.to_string()));
+                    differences.push(Difference { path: new_path, kind: DiffType::Added, left: None, right: Some(v) });
                 }
             }
         }
@@ -74,18 +99,14 @@ fn diff_values(left: &Value, right: &Value, path: &str, differences: &mut Vec<(S
                 let new_path = format!("{}[{}]", path, i);
                 match (l_arr.get(i), r_arr.get(i)) {
                     (Some(lv), Some(rv)) => diff_values(lv, rv, &new_path, differences),
-                    (Some(lv), None) => differences.push((new_path, DiffType::Removed, lv.to_string())),
-                    (None, Some(rv)) => differences.push((new_path, DiffType::Added, rv.to_string())),
+                    (Some(lv), None) => differences.push(Difference { path: new_path, kind: DiffType::Removed, left: Some(lv), right: None }),
+                    (None, Some(rv)) => differences.push(Difference { path: new_path, kind: DiffType::Added, left: None, right: Some(rv) }),
                     (None, None) => unreachable!(),
                 }
             }
         }
         (lv, rv) if lv != rv => {
-            differences.push((
-                path.to_string(),
-                DiffType::Changed,
-                format!("{} {} {}", lv.to_string().red(), "->".dimmed(), rv.to_string().green())
-            ));
+            differences.push(Difference { path: path.to_string(), kind: DiffType::Changed, left: Some(lv), right: Some(rv) });
         }
         _ => {}
     }
@@ -1,10 +1,37 @@
 use anyhow::Result;
 use ksuid::Ksuid;
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use chrono::{Utc, TimeZone};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct KsuidInfo {
+    id: String,
+    timestamp: u32,
+    time: String,
+    payload: String,
+}
+
+#[derive(Serialize)]
+struct GeneratedKsuid {
+    index: usize,
+    ksuid: String,
+    timestamp: String,
+}
+
+pub fn generate(count: usize, format: OutputFormat) -> Result<()> {
+    if !format.is_table() {
+        let ids: Vec<GeneratedKsuid> = (1..=count)
+            .map(|index| {
+                let id = Ksuid::generate();
+                let dt = Utc.timestamp_opt(id.time().sec, 0).unwrap();
+                GeneratedKsuid { index, ksuid: id.to_base62(), timestamp: dt.to_rfc3339() }
+            })
+            .collect();
+        return format.print(&ids);
+    }
 
-pub fn generate(count: usize) -> Result<()> {
     if count > 1 {
         println!("{}", Theme::info(format!("Generating {} KSUIDs:", count)));
     }
@@ -15,9 +42,22 @@ pub fn generate(count: usize) -> Result<()> {
     Ok(())
 }
 
-pub fn inspect(id_str: &str) -> Result<()> {
+pub fn inspect(id_str: &str, format: OutputFormat) -> Result<()> {
     let id = Ksuid::from_base62(id_str)
         .map_err(|_| anyhow::anyhow!("Invalid KSUID: {}", id_str))?;
+
+    let timestamp = id.timestamp();
+    // KSUID epoch is 14e8 (2014-05-13T16:53:20Z)
+    let dt = Utc.timestamp_opt(id.time().sec, 0).unwrap();
+
+    if !format.is_table() {
+        return format.print(&KsuidInfo {
+            id: id.to_base62(),
+            timestamp,
+            time: dt.to_rfc3339(),
+            payload: hex::encode(id.payload()),
+        });
+    }
     
     let mut table = TableFormatter::create_table();
     table.set_header(vec![
@@ -30,10 +70,6 @@ pub fn inspect(id_str: &str) -> Result<()> {
         TableFormatter::highlight_cell(id.to_base62()),
     ]);
     
-    let timestamp = id.timestamp();
-    // KSUID epoch is 14e8 (2014-05-13T16:53:20Z)
-    let dt = Utc.timestamp_opt(timestamp as i64, 0).unwrap();
-    
     table.add_row(vec![
         TableFormatter::value_cell("Timestamp"),
         TableFormatter::value_cell(format!("{} ({})", timestamp, dt.format("%Y-%m-%d %H:%M:%S UTC"))),
@@ -46,6 +82,5 @@ pub fn inspect(id_str: &str) -> Result<()> {
 
     println!("\n{}", Theme::header("KSUID Inspection:"));
     println!("{}", table);
-    
     Ok(())
 }
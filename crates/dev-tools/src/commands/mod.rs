@@ -17,6 +17,7 @@ pub mod check_links;
 pub mod checksum;
 pub mod completion;
 pub mod compress;
+pub mod config;
 pub mod contrast;
 pub mod crates;
 pub mod cron;
@@ -105,6 +106,7 @@ pub mod xml;
 pub mod yaml;
 
 use clap::Subcommand;
+use cli_core::output::FormatArg;
 use std::path::PathBuf;
 
 #[derive(Subcommand, Debug)]
@@ -143,6 +145,8 @@ pub enum Commands {
         /// ID to inspect
         #[arg(short, long)]
         inspect: Option<String>,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Base64 encoding/decoding
     Base64 {
@@ -278,6 +282,8 @@ pub enum Commands {
     Ip {
         /// Optional IP address or hostname to lookup
         target: Option<String>,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Generate random data
     Random {
@@ -305,6 +311,8 @@ pub enum Commands {
         /// Use uppercase only (for string)
         #[arg(short, long)]
         uppercase: bool,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// String case conversion
     Case {
@@ -313,6 +321,8 @@ pub enum Commands {
         /// Target case (snake, camel, pascal, kebab, shouty, train)
         #[arg(short, long)]
         to: Option<String>,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Inspect or generate JWT token
     Jwt {
@@ -332,13 +342,20 @@ pub enum Commands {
     Env {
         /// Filter by name
         filter: Option<String>,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Show system information
-    Sys,
+    Sys {
+        #[command(flatten)]
+        output: FormatArg,
+    },
     /// Color conversion (Hex <-> RGB)
     Color {
         /// Color value (#RRGGBB or R,G,B)
         input: String,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// WCAG contrast ratio between two colors
     Contrast {
@@ -346,6 +363,8 @@ pub enum Commands {
         foreground: String,
         /// Background color (#RRGGBB or R,G,B)
         background: String,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Data size conversion
     Size {
@@ -432,6 +451,8 @@ pub enum Commands {
         /// Resolve to absolute path (canonicalize)
         #[arg(short, long)]
         resolve: bool,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// YAML formatting and conversion
     Yaml {
@@ -491,6 +512,13 @@ pub enum Commands {
     Cron {
         /// Cron expression (e.g., "0 0 * * *")
         expression: String,
+        #[command(flatten)]
+        output: FormatArg,
+    },
+    /// Inspect the shared cli-tools configuration
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
     },
     /// Manage user crontab entries (list, add, remove, edit)
     Crontab {
@@ -501,6 +529,8 @@ pub enum Commands {
     Tz {
         /// Search query (e.g., "seoul", "new york"). Omit for the world clock.
         query: Option<String>,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Generate a table of contents from a Markdown file
     Toc {
@@ -559,6 +589,8 @@ pub enum Commands {
         /// Show all common bases (2, 8, 10, 16, 32, 36)
         #[arg(short, long)]
         all: bool,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Calculate checksums
     Checksum {
@@ -589,6 +621,8 @@ pub enum Commands {
         /// Record type (A, AAAA, MX, TXT, CNAME, NS)
         #[arg(short, long, default_value = "a")]
         record: String,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Gitignore utilities
     Gitignore {
@@ -620,6 +654,8 @@ pub enum Commands {
         /// Treat input as a file path
         #[arg(short, long)]
         file: bool,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Domain WHOIS information
     Whois {
@@ -645,15 +681,25 @@ pub enum Commands {
         from: String,
         /// Target unit
         to: String,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Currency conversion
     Currency {
         /// Amount to convert
-        amount: f64,
+        #[arg(required_unless_present = "update_rates")]
+        amount: Option<f64>,
         /// Source currency (e.g., USD)
-        from: String,
+        #[arg(required_unless_present = "update_rates")]
+        from: Option<String>,
         /// Target currency (e.g., EUR)
-        to: String,
+        #[arg(required_unless_present = "update_rates")]
+        to: Option<String>,
+        /// Save today's rates to the offline table code-cost and work-summary convert with
+        #[arg(long)]
+        update_rates: bool,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Generate or inspect ULIDs
     Ulid {
@@ -663,6 +709,8 @@ pub enum Commands {
         /// ID to inspect
         #[arg(short, long)]
         inspect: Option<String>,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Generate NanoIDs
     Nanoid {
@@ -675,6 +723,8 @@ pub enum Commands {
         /// Custom alphabet to use
         #[arg(short, long)]
         alphabet: Option<String>,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Generate a TOTP code or a new secret
     Totp {
@@ -689,6 +739,8 @@ pub enum Commands {
         /// Generate a new random secret instead of a code
         #[arg(short, long)]
         generate_secret: bool,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Convert CSV to JSON/YAML/Markdown
     Csv {
@@ -703,6 +755,8 @@ pub enum Commands {
         /// Convert to Markdown table
         #[arg(long)]
         markdown: bool,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Shorten a URL
     Shorten {
@@ -719,6 +773,8 @@ pub enum Commands {
         /// Treat input as a file path
         #[arg(short, long)]
         file: bool,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Basic SQL formatter
     Sql {
@@ -747,6 +803,8 @@ pub enum Commands {
     Chmod {
         /// Value to convert (e.g., 755 or rwxr-xr-x)
         input: String,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Base32 encoding/decoding
     Base32 {
@@ -816,11 +874,15 @@ pub enum Commands {
         /// Convert from binary instead of to binary
         #[arg(short, long)]
         from: bool,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Generate or inspect a random user agent string
     UserAgent {
         /// Optional user agent string to inspect
         ua: Option<String>,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Mime type utilities
     Mime {
@@ -843,6 +905,8 @@ pub enum Commands {
         /// Kind of secret key (default, django, rails, hex, alphanumeric)
         #[arg(short, long, default_value = "default")]
         kind: String,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Generate or inspect Snowflake IDs
     Snowflake {
@@ -852,6 +916,8 @@ pub enum Commands {
         /// Number of IDs to generate
         #[arg(short, long, default_value = "1")]
         count: usize,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Semantic Versioning utilities
     Semver {
@@ -875,6 +941,8 @@ pub enum Commands {
         /// Number of IDs to generate
         #[arg(short, long, default_value = "1")]
         count: usize,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// XML formatting
     Xml {
@@ -907,6 +975,8 @@ pub enum Commands {
         /// Timeout per port in milliseconds
         #[arg(short, long, default_value = "100")]
         timeout: u64,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Environment file (.env) utilities
     Dotenv {
@@ -958,6 +1028,8 @@ pub enum Commands {
         /// Cost factor (4-31)
         #[arg(short, long)]
         cost: Option<u32>,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Gzip compression/decompression
     Compress {
@@ -980,13 +1052,20 @@ pub enum Commands {
         /// Search by name or description
         #[arg(short, long)]
         search: Option<String>,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Display ASCII table
-    AsciiTable,
+    AsciiTable {
+        #[command(flatten)]
+        output: FormatArg,
+    },
     /// Parse a URL into its components
     UrlParse {
         /// URL to parse
         url: String,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// GitHub utilities
     Github {
@@ -1005,11 +1084,15 @@ pub enum Commands {
         /// Search for repositories
         #[arg(short, long)]
         search: Option<String>,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Search for Rust crates on crates.io
     Crates {
         /// Search query
         query: String,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Convert JSON to TypeScript interfaces
     Typescript {
@@ -1028,6 +1111,8 @@ pub enum Commands {
     Dictionary {
         /// Word to lookup
         word: String,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Translate text
     Translate {
@@ -1047,6 +1132,8 @@ pub enum Commands {
         /// Number of runs
         #[arg(short, long, default_value = "10")]
         count: usize,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Show current user and environment info
     Whoami,
@@ -1056,6 +1143,8 @@ pub enum Commands {
         left: String,
         /// New JSON
         right: String,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Convert JSON to Rust structs
     Rust {
@@ -1124,12 +1213,16 @@ pub enum Commands {
         /// Show all (duplicates, empty, dirs, links)
         #[arg(short, long)]
         all: bool,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Check for broken links in files
     CheckLinks {
         /// Target directory or file
         #[arg(default_value = ".")]
         path: PathBuf,
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Run a command silently in the background
     Detach {
@@ -1203,10 +1296,28 @@ pub enum Commands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Show the configuration resolved for a directory
+    Show {
+        /// List every key with the layer it came from (default, user, repository, env)
+        #[arg(long)]
+        effective: bool,
+        /// Directory whose .cli-tools.toml should be applied
+        #[arg(short, long, default_value = ".")]
+        path: PathBuf,
+        #[command(flatten)]
+        output: FormatArg,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub enum CrontabAction {
     /// Show crontab entries with descriptions and next run times (default)
-    List,
+    List {
+        #[command(flatten)]
+        output: FormatArg,
+    },
     /// Add an entry (schedule is validated before install)
     Add {
         /// Cron schedule (e.g., "0 9 * * 1-5" or "@daily")
@@ -1,8 +1,15 @@
 use anyhow::Result;
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
+use serde::Serialize;
 
-pub fn generate(length: usize, count: usize, alphabet: Option<&str>) -> Result<()> {
+#[derive(Serialize)]
+struct NanoId {
+    index: usize,
+    id: String,
+}
+
+pub fn generate(length: usize, count: usize, alphabet: Option<&str>, format: OutputFormat) -> Result<()> {
     if count == 0 {
         return Ok(());
     }
@@ -13,22 +20,29 @@ pub fn generate(length: usize, count: usize, alphabet: Option<&str>) -> Result<(
         "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_-".chars().collect()
     };
 
-    if count == 1 {
+    if count == 1 && format.is_table() {
         let id = nanoid::nanoid!(length, &alphabet_chars);
         println!("{}", Theme::highlight(id));
         return Ok(());
     }
 
+    let ids: Vec<NanoId> = (1..=count)
+        .map(|index| NanoId { index, id: nanoid::nanoid!(length, &alphabet_chars) })
+        .collect();
+
+    if !format.is_table() {
+        return format.print(&ids);
+    }
+
     let mut table = TableFormatter::create_table();
     table.set_header(vec![
         TableFormatter::header_cell("#"),
         TableFormatter::header_cell("NanoID"),
     ]);
 
-    for i in 0..count {
-        let id = nanoid::nanoid!(length, &alphabet_chars);
+    for NanoId { index, id } in ids {
         table.add_row(vec![
-            TableFormatter::value_cell(i + 1),
+            TableFormatter::value_cell(index),
             TableFormatter::highlight_cell(id),
         ]);
     }
@@ -1,10 +1,50 @@
 use anyhow::Result;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use cli_core::ui::Theme;
-use std::path::Path;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
 
-pub fn analyze(path: &str) -> Result<()> {
+#[derive(Serialize)]
+struct PathInfo<'a> {
+    input: &'a str,
+    absolute: Option<PathBuf>,
+    parent: Option<&'a Path>,
+    file_name: Option<String>,
+    extension: Option<String>,
+    exists: bool,
+    #[serde(rename = "type")]
+    kind: Option<&'static str>,
+    size_bytes: Option<u64>,
+}
+
+pub fn analyze(path: &str, format: OutputFormat) -> Result<()> {
     let p = Path::new(path);
+    let exists = p.exists();
+    let info = PathInfo {
+        input: path,
+        absolute: std::fs::canonicalize(p).ok(),
+        parent: p.parent(),
+        file_name: p.file_name().map(|name| name.to_string_lossy().into_owned()),
+        extension: p.extension().map(|ext| ext.to_string_lossy().into_owned()),
+        exists,
+        kind: exists.then(|| {
+            if p.is_dir() {
+                "Directory"
+            } else if p.is_file() {
+                "File"
+            } else if p.is_symlink() {
+                "Symlink"
+            } else {
+                "Other"
+            }
+        }),
+        size_bytes: if exists { p.metadata().ok().map(|meta| meta.len()) } else { None },
+    };
+
+    if !format.is_table() {
+        return format.print(&info);
+    }
+
     let mut table = TableFormatter::create_table();
     table.set_header(vec![
         TableFormatter::header_cell("Property"),
@@ -13,10 +53,10 @@ pub fn analyze(path: &str) -> Result<()> {
 
     table.add_row(vec![
         TableFormatter::value_cell("Input"),
-        TableFormatter::value_cell(path),
+        TableFormatter::value_cell(info.input),
     ]);
 
-    if let Ok(abs) = std::fs::canonicalize(p) {
+    if let Some(abs) = &info.absolute {
         table.add_row(vec![
             TableFormatter::value_cell("Absolute"),
             TableFormatter::highlight_cell(abs.display()),
@@ -28,28 +68,27 @@ pub fn analyze(path: &str) -> Result<()> {
         ]);
     }
 
-    if let Some(parent) = p.parent() {
+    if let Some(parent) = info.parent {
         table.add_row(vec![
             TableFormatter::value_cell("Parent"),
             TableFormatter::value_cell(parent.display()),
         ]);
     }
 
-    if let Some(name) = p.file_name() {
+    if let Some(name) = &info.file_name {
         table.add_row(vec![
             TableFormatter::value_cell("File Name"),
-            TableFormatter::value_cell(name.to_string_lossy()),
+            TableFormatter::value_cell(name),
         ]);
     }
 
-    if let Some(ext) = p.extension() {
+    if let Some(ext) = &info.extension {
         table.add_row(vec![
             TableFormatter::value_cell("Extension"),
-            TableFormatter::value_cell(ext.to_string_lossy()),
+            TableFormatter::value_cell(ext),
         ]);
     }
 
-    let exists = p.exists();
     table.add_row(vec![
         TableFormatter::value_cell("Exists"),
         if exists {
@@ -59,38 +98,29 @@ pub fn analyze(path: &str) -> Result<()> {
         },
     ]);
 
-    if exists {
+    if let Some(kind) = info.kind {
         table.add_row(vec![
             TableFormatter::value_cell("Type"),
-            if p.is_dir() {
-                TableFormatter::value_cell("Directory")
-            } else if p.is_file() {
-                TableFormatter::value_cell("File")
-            } else if p.is_symlink() {
-                TableFormatter::value_cell("Symlink")
-            } else {
-                TableFormatter::value_cell("Other")
-            },
+            TableFormatter::value_cell(kind),
         ]);
+    }
 
-        if let Ok(meta) = p.metadata() {
-            table.add_row(vec![
-                TableFormatter::value_cell("Size"),
-                TableFormatter::value_cell(format!("{} bytes", meta.len())),
-            ]);
-        }
+    if let Some(size) = info.size_bytes {
+        table.add_row(vec![
+            TableFormatter::value_cell("Size"),
+            TableFormatter::value_cell(format!("{} bytes", size)),
+        ]);
     }
 
     println!("{}", Theme::header("Path Analysis"));
     println!("{}", table);
-
     Ok(())
 }
 
-pub fn normalize(path: &str) -> Result<()> {
-    analyze(path)
+pub fn normalize(path: &str, format: OutputFormat) -> Result<()> {
+    analyze(path, format)
 }
 
-pub fn resolve(path: &str) -> Result<()> {
-    analyze(path)
+pub fn resolve(path: &str, format: OutputFormat) -> Result<()> {
+    analyze(path, format)
 }
@@ -6,11 +6,20 @@ use tokio::net::TcpStream;
 use tokio::time::timeout;
 use futures_util::stream::{self, StreamExt};
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use comfy_table::Cell;
+use serde::Serialize;
 
-pub async fn scan(host: &str, start: u16, end: u16, timeout_ms: u64) -> Result<()> {
-    println!("{}", Theme::info(format!("Scanning {} (ports {}-{})...", host, start, end)));
+#[derive(Serialize)]
+struct OpenPort {
+    port: u16,
+    service: String,
+}
+
+pub async fn scan(host: &str, start: u16, end: u16, timeout_ms: u64, format: OutputFormat) -> Result<()> {
+    if format.is_table() {
+        println!("{}", Theme::info(format!("Scanning {} (ports {}-{})...", host, start, end)));
+    }
 
     // Resolve host once
     let addrs: Vec<_> = format!("{}:{}", host, 0).to_socket_addrs()?.collect();
@@ -48,11 +57,24 @@ pub async fn scan(host: &str, start: u16, end: u16, timeout_ms: u64) -> Result<(
 
     open_ports.sort_unstable();
 
+    let open_ports: Vec<OpenPort> = open_ports
+        .into_iter()
+        .map(|port| OpenPort {
+            port,
+            service: common_ports.get(&port).cloned().unwrap_or_else(|| "Unknown".to_string()),
+        })
+        .collect();
+
+    if !format.is_table() {
+        return format.print(&open_ports);
+    }
+
     if open_ports.is_empty() {
         println!("\n{}", Theme::warning("No open ports found."));
     } else {
         println!("\n{}", Theme::success(format!("Scan complete. Found {} open ports.", open_ports.len())));
-        
+        println!();
+
         let mut table = TableFormatter::create_table();
         table.set_header(vec![
             TableFormatter::header_cell("Port"),
@@ -60,16 +82,15 @@ pub async fn scan(host: &str, start: u16, end: u16, timeout_ms: u64) -> Result<(
             TableFormatter::header_cell("Service"),
         ]);
 
-        for port in open_ports {
-            let service = common_ports.get(&port).cloned().unwrap_or_else(|| "Unknown".to_string());
+        for OpenPort { port, service } in open_ports {
             table.add_row(vec![
                 TableFormatter::value_cell(port),
                 Cell::new("OPEN").fg(comfy_table::Color::Green).add_attribute(comfy_table::Attribute::Bold),
                 TableFormatter::value_cell(service),
             ]);
         }
-        
-        println!("\n{}", table);
+
+        println!("{}", table);
     }
 
     Ok(())
@@ -1,7 +1,33 @@
 use anyhow::Result;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use cli_core::ui::Theme;
 use rand::{thread_rng, Rng};
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Value {
+    Text(String),
+    Number(usize),
+    Bool(bool),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text(text) => f.write_str(text),
+            Self::Number(n) => write!(f, "{}", n),
+            Self::Bool(b) => write!(f, "{}", b),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Generated {
+    index: usize,
+    value: Value,
+}
 
 pub fn generate(
     kind: &str,
@@ -12,6 +38,7 @@ pub fn generate(
     numeric: bool,
     symbols: bool,
     uppercase: bool,
+    format: OutputFormat,
 ) -> Result<()> {
     let mut rng = thread_rng();
     let mut results = Vec::new();
@@ -30,21 +57,17 @@ pub fn generate(
                     charset.push_str("!@#$%^&*()_+-=[]{}|;:,.<>?");
                 }
 
-                (0..length)
-                    .map(|_| {
-                        let idx = rng.gen_range(0..charset.len());
-                        charset.chars().nth(idx).unwrap()
-                    })
-                    .collect::<String>()
-            }
-            "number" | "n" | "int" => {
-                let val = rng.gen_range(min..=max);
-                val.to_string()
-            }
-            "boolean" | "bool" | "b" => {
-                let val: bool = rng.gen();
-                val.to_string()
+                Value::Text(
+                    (0..length)
+                        .map(|_| {
+                            let idx = rng.gen_range(0..charset.len());
+                            charset.chars().nth(idx).unwrap()
+                        })
+                        .collect::<String>(),
+                )
             }
+            "number" | "n" | "int" => Value::Number(rng.gen_range(min..=max)),
+            "boolean" | "bool" | "b" => Value::Bool(rng.gen()),
             _ => anyhow::bail!(
                 "Unsupported random kind: {}. Supported: string, number, boolean",
                 kind
@@ -53,8 +76,17 @@ pub fn generate(
         results.push(value);
     }
 
+    if !format.is_table() {
+        let generated: Vec<Generated> = results
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| Generated { index: i + 1, value })
+            .collect();
+        return format.print(&generated);
+    }
+
     if count == 1 {
-        println!("{} {}", Theme::success(format!("Random {}:", kind)), Theme::highlight(&results[0]));
+        println!("{} {}", Theme::success(format!("Random {}:", kind)), Theme::highlight(results[0].to_string()));
     } else {
         let mut table = TableFormatter::create_table();
         table.set_header(vec![
@@ -65,7 +97,7 @@ pub fn generate(
         for (i, val) in results.iter().enumerate() {
             table.add_row(vec![
                 TableFormatter::value_cell(i + 1),
-                TableFormatter::highlight_cell(val),
+                TableFormatter::highlight_cell(val.to_string()),
             ]);
         }
 
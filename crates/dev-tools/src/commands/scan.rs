@@ -6,7 +6,8 @@ use std::io::Read;
 use std::path::{Path, PathBuf};
 use ignore::WalkBuilder;
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
+use serde::Serialize;
 
 pub struct ScanOptions {
     pub duplicates: bool,
@@ -16,8 +17,32 @@ pub struct ScanOptions {
     pub min_size: u64,
 }
 
-pub fn run(path: &Path, opts: ScanOptions) -> Result<()> {
-    println!("{}", Theme::info(format!("Scanning: {} ...", path.display())));
+#[derive(Serialize, Default)]
+struct ScanOutput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duplicates: Option<Vec<DuplicateGroup>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    empty_files: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    empty_dirs: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    broken_links: Option<Vec<String>>,
+}
+
+#[derive(Serialize)]
+struct DuplicateGroup {
+    hash: String,
+    files: Vec<String>,
+}
+
+fn display_paths(paths: &[PathBuf]) -> Vec<String> {
+    paths.iter().map(|p| p.display().to_string()).collect()
+}
+
+pub fn run(path: &Path, opts: ScanOptions, format: OutputFormat) -> Result<()> {
+    if format.is_table() {
+        println!("{}", Theme::info(format!("Scanning: {} ...", path.display())));
+    }
 
     let mut files_by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
     let mut empty_files = Vec::new();
@@ -51,6 +76,8 @@ pub fn run(path: &Path, opts: ScanOptions) -> Result<()> {
         }
     }
 
+    let mut output = ScanOutput::default();
+
     if opts.duplicates {
         let mut duplicates: HashMap<String, Vec<PathBuf>> = HashMap::new();
         for (_size, paths) in files_by_size.iter().filter(|(_, p)| p.len() > 1) {
@@ -61,35 +88,32 @@ pub fn run(path: &Path, opts: ScanOptions) -> Result<()> {
             }
         }
 
-        println!("\n{}", Theme::header("Duplicate Files"));
-        let mut found = false;
-        
-        let mut table = TableFormatter::create_table();
-        table.set_header(vec![
-            TableFormatter::header_cell("Hash"),
-            TableFormatter::header_cell("Files"),
-        ]);
+        let groups: Vec<DuplicateGroup> = duplicates
+            .into_iter()
+            .filter(|(_, p)| p.len() > 1)
+            .map(|(hash, paths)| DuplicateGroup { hash, files: display_paths(&paths) })
+            .collect();
 
-        for (hash, paths) in duplicates.iter().filter(|(_, p)| p.len() > 1) {
-            found = true;
-            let paths_str = paths.iter()
-                .map(|p| p.display().to_string())
-                .collect::<Vec<_>>()
-                .join("\n");
-            
-            table.add_row(vec![
-                TableFormatter::value_cell(&hash[..12]),
-                TableFormatter::value_cell(paths_str),
-            ]);
-        }
-        
-        if found {
-            println!("{}", table);
+        if format.is_table() {
+            print_duplicates(&groups);
         } else {
-            println!("  {}", Theme::dim("None found"));
+            output.duplicates = Some(groups);
         }
     }
 
+    if !format.is_table() {
+        if opts.empty {
+            output.empty_files = Some(display_paths(&empty_files));
+        }
+        if opts.dirs {
+            output.empty_dirs = Some(display_paths(&empty_dirs));
+        }
+        if opts.links {
+            output.broken_links = Some(display_paths(&broken_links));
+        }
+        return format.print(&output);
+    }
+
     if opts.empty {
         println!("\n{}", Theme::header("Empty Files"));
         if empty_files.is_empty() {
@@ -126,6 +150,29 @@ pub fn run(path: &Path, opts: ScanOptions) -> Result<()> {
     Ok(())
 }
 
+fn print_duplicates(groups: &[DuplicateGroup]) {
+    println!("\n{}", Theme::header("Duplicate Files"));
+    if groups.is_empty() {
+        println!("  {}", Theme::dim("None found"));
+        return;
+    }
+
+    let mut table = TableFormatter::create_table();
+    table.set_header(vec![
+        TableFormatter::header_cell("Hash"),
+        TableFormatter::header_cell("Files"),
+    ]);
+
+    for group in groups {
+        table.add_row(vec![
+            TableFormatter::value_cell(&group.hash[..12]),
+            TableFormatter::value_cell(group.files.join("\n")),
+        ]);
+    }
+
+    println!("{}", table);
+}
+
 fn hash_file(path: &Path) -> Result<String> {
     let mut file = File::open(path)?;
     let mut hasher = Sha256::new();
@@ -1,9 +1,16 @@
 use anyhow::Result;
 use rand::Rng;
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
+use serde::Serialize;
 
-pub fn generate(length: usize, count: usize, kind: &str) -> Result<()> {
+#[derive(Serialize)]
+struct Secret {
+    index: usize,
+    secret: String,
+}
+
+pub fn generate(length: usize, count: usize, kind: &str, format: OutputFormat) -> Result<()> {
     if count == 0 {
         return Ok(());
     }
@@ -29,22 +36,29 @@ pub fn generate(length: usize, count: usize, kind: &str) -> Result<()> {
             .collect()
     };
 
-    if count == 1 {
+    if count == 1 && format.is_table() {
         let secret = generate_one(length, &char_vec, &mut rng);
         println!("{}", Theme::highlight(secret));
         return Ok(());
     }
 
+    let secrets: Vec<Secret> = (1..=count)
+        .map(|index| Secret { index, secret: generate_one(length, &char_vec, &mut rng) })
+        .collect();
+
+    if !format.is_table() {
+        return format.print(&secrets);
+    }
+
     let mut table = TableFormatter::create_table();
     table.set_header(vec![
         TableFormatter::header_cell("#"),
         TableFormatter::header_cell("Secret Key"),
     ]);
 
-    for i in 0..count {
-        let secret = generate_one(length, &char_vec, &mut rng);
+    for Secret { index, secret } in secrets {
         table.add_row(vec![
-            TableFormatter::value_cell(i + 1),
+            TableFormatter::value_cell(index),
             TableFormatter::highlight_cell(secret),
         ]);
     }
@@ -1,12 +1,36 @@
 use anyhow::{Result, anyhow};
 use snowid::SnowID;
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
-use chrono::{Utc, TimeZone};
+use cli_core::output::{OutputFormat, TableFormatter};
+use chrono::{SecondsFormat, TimeZone, Utc};
+use serde::Serialize;
 
-pub fn generate(count: usize) -> Result<()> {
+#[derive(Serialize)]
+struct Snowflake {
+    id: i64,
+    /// Milliseconds since the 2024-01-01 epoch.
+    timestamp_ms: u64,
+    time: String,
+    node: u16,
+    sequence: u16,
+}
+
+#[derive(Serialize)]
+struct GeneratedSnowflake {
+    index: usize,
+    id: u64,
+}
+
+pub fn generate(count: usize, format: OutputFormat) -> Result<()> {
     let generator = SnowID::new(1)
         .map_err(|e| anyhow!("Failed to create SnowID generator: {}", e))?;
+
+    if !format.is_table() {
+        let ids: Vec<GeneratedSnowflake> = (1..=count)
+            .map(|index| GeneratedSnowflake { index, id: generator.generate() })
+            .collect();
+        return format.print(&ids);
+    }
     
     if count > 1 {
         println!("{}", Theme::info(format!("Generating {} Snowflake IDs:", count)));
@@ -19,10 +43,25 @@ pub fn generate(count: usize) -> Result<()> {
     Ok(())
 }
 
-pub fn inspect(id: i64) -> Result<()> {
+pub fn inspect(id: i64, format: OutputFormat) -> Result<()> {
     let generator = SnowID::new(1).unwrap();
     let (timestamp, node, sequence) = generator.extract.decompose(id as u64);
     
+    // Default epoch is 2024-01-01T00:00:00Z (1704067200000 ms)
+    let epoch_ms = 1704067200000i64;
+    let absolute_ts = epoch_ms + timestamp as i64;
+    let dt = Utc.timestamp_millis_opt(absolute_ts).unwrap();
+
+    if !format.is_table() {
+        return format.print(&Snowflake {
+            id,
+            timestamp_ms: timestamp,
+            time: dt.to_rfc3339_opts(SecondsFormat::Millis, true),
+            node,
+            sequence,
+        });
+    }
+    
     let mut table = TableFormatter::create_table();
     table.set_header(vec![
         TableFormatter::header_cell("Property"),
@@ -34,11 +73,6 @@ pub fn inspect(id: i64) -> Result<()> {
         TableFormatter::highlight_cell(id.to_string()),
     ]);
     
-    // Default epoch is 2024-01-01T00:00:00Z (1704067200000 ms)
-    let epoch_ms = 1704067200000i64;
-    let absolute_ts = epoch_ms + timestamp as i64;
-    let dt = Utc.timestamp_millis_opt(absolute_ts).unwrap();
-    
     table.add_row(vec![
         TableFormatter::value_cell("Timestamp"),
         TableFormatter::value_cell(format!("{}ms ({})", timestamp, dt.format("%Y-%m-%d %H:%M:%S.%3f UTC"))),
@@ -56,6 +90,5 @@ pub fn inspect(id: i64) -> Result<()> {
 
     println!("\n{}", Theme::header("Snowflake Inspection:"));
     println!("{}", table);
-    
     Ok(())
 }
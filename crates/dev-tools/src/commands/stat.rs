@@ -1,18 +1,56 @@
 use anyhow::Result;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use cli_core::ui::Theme;
+use serde::Serialize;
 use std::path::Path;
 use chrono::{DateTime, Local};
 
-pub fn analyze(input: &str, is_file: bool) -> Result<()> {
+pub fn analyze(input: &str, is_file: bool, format: OutputFormat) -> Result<()> {
     if is_file {
-        analyze_file(input)
+        analyze_file(input, format)
     } else {
-        analyze_string(input)
+        analyze_string(input, format)
     }
 }
 
-fn analyze_string(text: &str) -> Result<()> {
+#[derive(Serialize)]
+struct TextStats {
+    characters: usize,
+    characters_no_space: usize,
+    words: usize,
+    lines: usize,
+    avg_word_length: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct FileStats<'a> {
+    path: &'a str,
+    size_bytes: u64,
+    #[serde(rename = "type")]
+    kind: &'static str,
+    created: Option<String>,
+    modified: Option<String>,
+    /// Unix permission bits in octal.
+    permissions: Option<String>,
+    uid: Option<u32>,
+    gid: Option<u32>,
+}
+
+fn analyze_string(text: &str, format: OutputFormat) -> Result<()> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let stats = TextStats {
+        characters: text.len(),
+        characters_no_space: text.chars().filter(|c| !c.is_whitespace()).count(),
+        words: words.len(),
+        lines: text.lines().count(),
+        avg_word_length: (!words.is_empty())
+            .then(|| words.iter().map(|w| w.len()).sum::<usize>() as f64 / words.len() as f64),
+    };
+
+    if !format.is_table() {
+        return format.print(&stats);
+    }
+
     let mut table = TableFormatter::create_table();
     table.set_header(vec![
         TableFormatter::header_cell("Metric"),
@@ -21,27 +59,25 @@ fn analyze_string(text: &str) -> Result<()> {
 
     table.add_row(vec![
         TableFormatter::value_cell("Characters"),
-        TableFormatter::highlight_cell(text.len()),
+        TableFormatter::highlight_cell(stats.characters),
     ]);
 
     table.add_row(vec![
         TableFormatter::value_cell("Characters (no space)"),
-        TableFormatter::value_cell(text.chars().filter(|c| !c.is_whitespace()).count()),
+        TableFormatter::value_cell(stats.characters_no_space),
     ]);
 
-    let words: Vec<&str> = text.split_whitespace().collect();
     table.add_row(vec![
         TableFormatter::value_cell("Words"),
-        TableFormatter::highlight_cell(words.len()),
+        TableFormatter::highlight_cell(stats.words),
     ]);
 
     table.add_row(vec![
         TableFormatter::value_cell("Lines"),
-        TableFormatter::highlight_cell(text.lines().count()),
+        TableFormatter::highlight_cell(stats.lines),
     ]);
 
-    if !words.is_empty() {
-        let avg_len = words.iter().map(|w| w.len()).sum::<usize>() as f64 / words.len() as f64;
+    if let Some(avg_len) = stats.avg_word_length {
         table.add_row(vec![
             TableFormatter::value_cell("Avg. Word Length"),
             TableFormatter::value_cell(format!("{:.2}", avg_len)),
@@ -53,10 +89,44 @@ fn analyze_string(text: &str) -> Result<()> {
     Ok(())
 }
 
-fn analyze_file(path_str: &str) -> Result<()> {
+fn analyze_file(path_str: &str, format: OutputFormat) -> Result<()> {
     let path = Path::new(path_str);
     let metadata = std::fs::metadata(path)?;
 
+    let timestamp = |time: std::io::Result<std::time::SystemTime>| {
+        time.ok().map(|time| DateTime::<Local>::from(time).format("%Y-%m-%d %H:%M:%S").to_string())
+    };
+    let mut stats = FileStats {
+        path: path_str,
+        size_bytes: metadata.len(),
+        kind: if metadata.is_dir() {
+            "Directory"
+        } else if metadata.is_file() {
+            "File"
+        } else if metadata.is_symlink() {
+            "Symlink"
+        } else {
+            "Other"
+        },
+        created: timestamp(metadata.created()),
+        modified: timestamp(metadata.modified()),
+        permissions: None,
+        uid: None,
+        gid: None,
+    };
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        stats.permissions = Some(format!("{:o}", metadata.mode() & 0o777));
+        stats.uid = Some(metadata.uid());
+        stats.gid = Some(metadata.gid());
+    }
+
+    if !format.is_table() {
+        return format.print(&stats);
+    }
+
     let mut table = TableFormatter::create_table();
     table.set_header(vec![
         TableFormatter::header_cell("Property"),
@@ -65,57 +135,49 @@ fn analyze_file(path_str: &str) -> Result<()> {
 
     table.add_row(vec![
         TableFormatter::value_cell("Path"),
-        TableFormatter::value_cell(path_str),
+        TableFormatter::value_cell(stats.path),
     ]);
 
     table.add_row(vec![
         TableFormatter::value_cell("Size"),
-        TableFormatter::highlight_cell(format!("{} bytes", metadata.len())),
+        TableFormatter::highlight_cell(format!("{} bytes", stats.size_bytes)),
     ]);
 
     table.add_row(vec![
         TableFormatter::value_cell("Type"),
-        if metadata.is_dir() {
-            TableFormatter::value_cell("Directory")
-        } else if metadata.is_file() {
-            TableFormatter::value_cell("File")
-        } else if metadata.is_symlink() {
-            TableFormatter::value_cell("Symlink")
-        } else {
-            TableFormatter::value_cell("Other")
-        },
+        TableFormatter::value_cell(stats.kind),
     ]);
 
-    if let Ok(created) = metadata.created() {
-        let dt: DateTime<Local> = created.into();
+    if let Some(created) = &stats.created {
         table.add_row(vec![
             TableFormatter::value_cell("Created"),
-            TableFormatter::value_cell(dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+            TableFormatter::value_cell(created),
         ]);
     }
 
-    if let Ok(modified) = metadata.modified() {
-        let dt: DateTime<Local> = modified.into();
+    if let Some(modified) = &stats.modified {
         table.add_row(vec![
             TableFormatter::value_cell("Modified"),
-            TableFormatter::value_cell(dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+            TableFormatter::value_cell(modified),
         ]);
     }
 
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::MetadataExt;
+    if let Some(permissions) = &stats.permissions {
         table.add_row(vec![
             TableFormatter::value_cell("Permissions"),
-            TableFormatter::value_cell(format!("{:o}", metadata.mode() & 0o777)),
+            TableFormatter::value_cell(permissions),
         ]);
+    }
+    if let Some(uid) = stats.uid {
         table.add_row(vec![
             TableFormatter::value_cell("Owner (UID)"),
-            TableFormatter::value_cell(metadata.uid()),
+            TableFormatter::value_cell(uid),
         ]);
+    }
+    if let Some(gid) = stats.gid {
         table.add_row(vec![
             TableFormatter::value_cell("Group (GID)"),
-            TableFormatter::value_cell(metadata.gid()),
+            TableFormatter::value_cell(gid),
         ]);
     }
 
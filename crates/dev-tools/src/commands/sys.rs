@@ -1,9 +1,59 @@
 use anyhow::Result;
 use sysinfo::{System, CpuRefreshKind, RefreshKind, MemoryRefreshKind, Disks, Networks};
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
+use serde::Serialize;
 
-pub fn show() -> Result<()> {
+#[derive(Serialize)]
+struct SysOutput {
+    system: SystemInfo,
+    cpu: CpuInfo,
+    memory: MemoryInfo,
+    disks: Vec<DiskInfo>,
+    networks: Vec<NetworkInfo>,
+}
+
+#[derive(Serialize)]
+struct SystemInfo {
+    os: String,
+    version: String,
+    kernel: String,
+    hostname: String,
+    uptime_seconds: u64,
+}
+
+#[derive(Serialize)]
+struct CpuInfo {
+    brand: String,
+    cores: usize,
+    usage_percent: f32,
+}
+
+#[derive(Serialize)]
+struct MemoryInfo {
+    total_mb: u64,
+    used_mb: u64,
+    free_mb: u64,
+    used_percent: f32,
+}
+
+#[derive(Serialize)]
+struct DiskInfo {
+    mount: String,
+    file_system: String,
+    used_gb: u64,
+    total_gb: u64,
+    used_percent: f32,
+}
+
+#[derive(Serialize)]
+struct NetworkInfo {
+    interface: String,
+    received_mb: f64,
+    transmitted_mb: f64,
+}
+
+pub fn show(format: OutputFormat) -> Result<()> {
     let mut sys = System::new_with_specifics(
         RefreshKind::new()
             .with_cpu(CpuRefreshKind::everything())
@@ -14,43 +64,79 @@ pub fn show() -> Result<()> {
     std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
     sys.refresh_cpu();
 
-    println!("{}", Theme::header("--- System Information ---"));
-    
+    let system = SystemInfo {
+        os: System::name().unwrap_or_default(),
+        version: System::os_version().unwrap_or_default(),
+        kernel: System::kernel_version().unwrap_or_default(),
+        hostname: System::host_name().unwrap_or_default(),
+        uptime_seconds: System::uptime(),
+    };
+
+    let cpu = CpuInfo {
+        brand: sys.cpus().get(0).map(|c| c.brand()).unwrap_or("Unknown").to_string(),
+        cores: sys.cpus().len(),
+        usage_percent: sys.global_cpu_info().cpu_usage(),
+    };
+
+    let total_mem = sys.total_memory() / 1024 / 1024;
+    let used_mem = sys.used_memory() / 1024 / 1024;
+    let memory = MemoryInfo {
+        total_mb: total_mem,
+        used_mb: used_mem,
+        free_mb: sys.free_memory() / 1024 / 1024,
+        used_percent: (used_mem as f32 / total_mem as f32) * 100.0,
+    };
+
+    let disks: Vec<DiskInfo> = Disks::new_with_refreshed_list()
+        .iter()
+        .map(|disk| {
+            let total = disk.total_space() / 1024 / 1024 / 1024;
+            let available = disk.available_space() / 1024 / 1024 / 1024;
+            let used = total - available;
+            DiskInfo {
+                mount: disk.mount_point().to_string_lossy().into_owned(),
+                file_system: disk.file_system().to_string_lossy().into_owned(),
+                used_gb: used,
+                total_gb: total,
+                used_percent: (used as f32 / total as f32) * 100.0,
+            }
+        })
+        .collect();
+
+    let networks: Vec<NetworkInfo> = Networks::new_with_refreshed_list()
+        .iter()
+        .map(|(interface_name, data)| NetworkInfo {
+            interface: interface_name.clone(),
+            received_mb: data.total_received() as f64 / 1024.0 / 1024.0,
+            transmitted_mb: data.total_transmitted() as f64 / 1024.0 / 1024.0,
+        })
+        .filter(|network| network.received_mb > 0.0 || network.transmitted_mb > 0.0)
+        .collect();
+
+    if !format.is_table() {
+        return format.print(&SysOutput { system, cpu, memory, disks, networks });
+    }
+
     let mut info_table = TableFormatter::create_table();
-    info_table.add_row(vec![TableFormatter::header_cell("OS"), TableFormatter::value_cell(System::name().unwrap_or_default())]);
-    info_table.add_row(vec![TableFormatter::header_cell("Version"), TableFormatter::value_cell(System::os_version().unwrap_or_default())]);
-    info_table.add_row(vec![TableFormatter::header_cell("Kernel"), TableFormatter::value_cell(System::kernel_version().unwrap_or_default())]);
-    info_table.add_row(vec![TableFormatter::header_cell("Hostname"), TableFormatter::value_cell(System::host_name().unwrap_or_default())]);
-    info_table.add_row(vec![TableFormatter::header_cell("Uptime"), TableFormatter::value_cell(format!("{} seconds", System::uptime()))]);
-    println!("{info_table}");
-    
-    println!("\n{}", Theme::header("--- CPU ---"));
+    info_table.add_row(vec![TableFormatter::header_cell("OS"), TableFormatter::value_cell(&system.os)]);
+    info_table.add_row(vec![TableFormatter::header_cell("Version"), TableFormatter::value_cell(&system.version)]);
+    info_table.add_row(vec![TableFormatter::header_cell("Kernel"), TableFormatter::value_cell(&system.kernel)]);
+    info_table.add_row(vec![TableFormatter::header_cell("Hostname"), TableFormatter::value_cell(&system.hostname)]);
+    info_table.add_row(vec![TableFormatter::header_cell("Uptime"), TableFormatter::value_cell(format!("{} seconds", system.uptime_seconds))]);
+
     let mut cpu_table = TableFormatter::create_table();
-    cpu_table.add_row(vec![TableFormatter::header_cell("Brand"), TableFormatter::value_cell(sys.cpus().get(0).map(|c| c.brand()).unwrap_or("Unknown"))]);
-    cpu_table.add_row(vec![TableFormatter::header_cell("Cores"), TableFormatter::value_cell(sys.cpus().len().to_string())]);
-    
-    let global_cpu_usage = sys.global_cpu_info().cpu_usage();
+    cpu_table.add_row(vec![TableFormatter::header_cell("Brand"), TableFormatter::value_cell(&cpu.brand)]);
+    cpu_table.add_row(vec![TableFormatter::header_cell("Cores"), TableFormatter::value_cell(cpu.cores)]);
     cpu_table.add_row(vec![
         TableFormatter::header_cell("Global Usage"), 
-        TableFormatter::value_cell(format!("{:.2}%", global_cpu_usage))
+        TableFormatter::value_cell(format!("{:.2}%", cpu.usage_percent))
     ]);
-    println!("{cpu_table}");
-    print_progress_bar(global_cpu_usage);
 
-    println!("\n{}", Theme::header("--- Memory ---"));
-    let total_mem = sys.total_memory() / 1024 / 1024;
-    let used_mem = sys.used_memory() / 1024 / 1024;
-    let free_mem = sys.free_memory() / 1024 / 1024;
-    let mem_percentage = (used_mem as f32 / total_mem as f32) * 100.0;
-    
     let mut mem_table = TableFormatter::create_table();
-    mem_table.add_row(vec![TableFormatter::header_cell("Total"), TableFormatter::value_cell(format!("{} MB", total_mem))]);
-    mem_table.add_row(vec![TableFormatter::header_cell("Used"), TableFormatter::highlight_cell(format!("{} MB ({:.1}%)", used_mem, mem_percentage))]);
-    mem_table.add_row(vec![TableFormatter::header_cell("Free"), TableFormatter::value_cell(format!("{} MB", free_mem))]);
-    println!("{mem_table}");
-    print_progress_bar(mem_percentage);
+    mem_table.add_row(vec![TableFormatter::header_cell("Total"), TableFormatter::value_cell(format!("{} MB", memory.total_mb))]);
+    mem_table.add_row(vec![TableFormatter::header_cell("Used"), TableFormatter::highlight_cell(format!("{} MB ({:.1}%)", memory.used_mb, memory.used_percent))]);
+    mem_table.add_row(vec![TableFormatter::header_cell("Free"), TableFormatter::value_cell(format!("{} MB", memory.free_mb))]);
 
-    println!("\n{}", Theme::header("--- Disks ---"));
     let mut disks_table = TableFormatter::create_table();
     disks_table.set_header(vec![
         TableFormatter::header_cell("Mount"),
@@ -59,23 +145,15 @@ pub fn show() -> Result<()> {
         TableFormatter::header_cell("Total"),
     ]);
 
-    let disks = Disks::new_with_refreshed_list();
     for disk in &disks {
-        let total = disk.total_space() / 1024 / 1024 / 1024;
-        let available = disk.available_space() / 1024 / 1024 / 1024;
-        let used = total - available;
-        let usage_percentage = (used as f32 / total as f32) * 100.0;
-        
         disks_table.add_row(vec![
-            TableFormatter::value_cell(disk.mount_point().to_string_lossy()),
-            TableFormatter::value_cell(disk.file_system().to_string_lossy()),
-            TableFormatter::highlight_cell(format!("{} GB ({:.1}%)", used, usage_percentage)),
-            TableFormatter::value_cell(format!("{} GB", total)),
+            TableFormatter::value_cell(&disk.mount),
+            TableFormatter::value_cell(&disk.file_system),
+            TableFormatter::highlight_cell(format!("{} GB ({:.1}%)", disk.used_gb, disk.used_percent)),
+            TableFormatter::value_cell(format!("{} GB", disk.total_gb)),
         ]);
     }
-    println!("{disks_table}");
 
-    println!("\n{}", Theme::header("--- Networks ---"));
     let mut net_table = TableFormatter::create_table();
     net_table.set_header(vec![
         TableFormatter::header_cell("Interface"),
@@ -83,19 +161,29 @@ pub fn show() -> Result<()> {
         TableFormatter::header_cell("Transmitted"),
     ]);
 
-    let networks = Networks::new_with_refreshed_list();
-    for (interface_name, data) in &networks {
-        let received = data.total_received() as f64 / 1024.0 / 1024.0;
-        let transmitted = data.total_transmitted() as f64 / 1024.0 / 1024.0;
-        
-        if received > 0.0 || transmitted > 0.0 {
-            net_table.add_row(vec![
-                TableFormatter::value_cell(interface_name),
-                TableFormatter::value_cell(format!("{:.2} MB", received)),
-                TableFormatter::value_cell(format!("{:.2} MB", transmitted)),
-            ]);
-        }
+    for network in &networks {
+        net_table.add_row(vec![
+            TableFormatter::value_cell(&network.interface),
+            TableFormatter::value_cell(format!("{:.2} MB", network.received_mb)),
+            TableFormatter::value_cell(format!("{:.2} MB", network.transmitted_mb)),
+        ]);
     }
+
+    println!("{}", Theme::header("--- System Information ---"));
+    println!("{info_table}");
+
+    println!("\n{}", Theme::header("--- CPU ---"));
+    println!("{cpu_table}");
+    print_progress_bar(cpu.usage_percent);
+
+    println!("\n{}", Theme::header("--- Memory ---"));
+    println!("{mem_table}");
+    print_progress_bar(memory.used_percent);
+
+    println!("\n{}", Theme::header("--- Disks ---"));
+    println!("{disks_table}");
+
+    println!("\n{}", Theme::header("--- Networks ---"));
     println!("{net_table}");
 
     Ok(())
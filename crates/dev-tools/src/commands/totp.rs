@@ -1,18 +1,36 @@
 use anyhow::Result;
 use totp_rs::{Algorithm, Secret, TOTP};
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
+use serde::Serialize;
 use std::time::{SystemTime, UNIX_EPOCH};
 use rand::Rng;
 
-pub fn generate(secret_str: &str, digits: usize, skew: u8) -> Result<()> {
+const STEP_SECONDS: u64 = 30;
+const SECRET_BYTES: usize = 20;
+
+#[derive(Serialize)]
+struct TotpCode {
+    code: String,
+    remaining_seconds: u64,
+    step_seconds: u64,
+    algorithm: &'static str,
+}
+
+#[derive(Serialize)]
+struct TotpSecret {
+    secret: String,
+    length_bytes: usize,
+}
+
+pub fn generate(secret_str: &str, digits: usize, skew: u8, format: OutputFormat) -> Result<()> {
     let secret = Secret::Encoded(secret_str.to_string()).to_bytes()?;
     
     let totp = TOTP::new(
         Algorithm::SHA1,
         digits,
         skew,
-        30,
+        STEP_SECONDS,
         secret,
     )?;
 
@@ -20,12 +38,21 @@ pub fn generate(secret_str: &str, digits: usize, skew: u8) -> Result<()> {
     
     // Calculate remaining time
     let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
-    let remaining = 30 - (now % 30);
+    let remaining = STEP_SECONDS - (now % STEP_SECONDS);
+
+    if !format.is_table() {
+        return format.print(&TotpCode {
+            code,
+            remaining_seconds: remaining,
+            step_seconds: STEP_SECONDS,
+            algorithm: "SHA1",
+        });
+    }
 
     println!("\n{}", Theme::header("TOTP Code Generation"));
-    
+
     let mut table = TableFormatter::create_table();
-    table.add_row(vec![
+    table.set_header(vec![
         TableFormatter::header_cell("Field"),
         TableFormatter::header_cell("Value"),
     ]);
@@ -42,7 +69,7 @@ pub fn generate(secret_str: &str, digits: usize, skew: u8) -> Result<()> {
 
     table.add_row(vec![
         TableFormatter::value_cell("Step"),
-        TableFormatter::value_cell("30 seconds"),
+        TableFormatter::value_cell(format!("{} seconds", STEP_SECONDS)),
     ]);
 
     table.add_row(vec![
@@ -54,17 +81,24 @@ pub fn generate(secret_str: &str, digits: usize, skew: u8) -> Result<()> {
     Ok(())
 }
 
-pub fn generate_new_secret() -> Result<()> {
+pub fn generate_new_secret(format: OutputFormat) -> Result<()> {
     let mut rng = rand::thread_rng();
-    let mut data = [0u8; 20];
+    let mut data = [0u8; SECRET_BYTES];
     rng.fill(&mut data);
     
     let encoded = base32::encode(base32::Alphabet::Rfc4648 { padding: false }, &data);
 
+    if !format.is_table() {
+        return format.print(&TotpSecret {
+            secret: encoded,
+            length_bytes: SECRET_BYTES,
+        });
+    }
+
     println!("\n{}", Theme::success("New TOTP Secret Generated!"));
-    
+
     let mut table = TableFormatter::create_table();
-    table.add_row(vec![
+    table.set_header(vec![
         TableFormatter::header_cell("Field"),
         TableFormatter::header_cell("Value"),
     ]);
@@ -76,7 +110,7 @@ pub fn generate_new_secret() -> Result<()> {
 
     table.add_row(vec![
         TableFormatter::value_cell("Length"),
-        TableFormatter::value_cell("20 bytes"),
+        TableFormatter::value_cell(format!("{} bytes", SECRET_BYTES)),
     ]);
 
     table.add_row(vec![
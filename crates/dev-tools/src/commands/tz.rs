@@ -1,8 +1,9 @@
 use anyhow::{bail, Result};
 use chrono::{Local, NaiveDate, Utc};
 use chrono_tz::{Tz, TZ_VARIANTS};
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use cli_core::ui::Theme;
+use serde::Serialize;
 
 const WORLD_CLOCK: &[&str] = &[
     "Pacific/Auckland",
@@ -25,7 +26,16 @@ const WORLD_CLOCK: &[&str] = &[
     "America/Los_Angeles",
 ];
 
-pub fn show(query: Option<&str>) -> Result<()> {
+#[derive(Serialize)]
+struct ZoneTime {
+    zone: &'static str,
+    time: String,
+    utc_offset: String,
+    /// Days ahead of (or, negative, behind) the local date.
+    day_offset: i64,
+}
+
+pub fn show(query: Option<&str>, format: OutputFormat) -> Result<()> {
     let zones: Vec<Tz> = match query {
         None => WORLD_CLOCK
             .iter()
@@ -40,6 +50,25 @@ pub fn show(query: Option<&str>) -> Result<()> {
         }
     };
 
+    let now = Utc::now();
+    let local_date = now.with_timezone(&Local).date_naive();
+
+    if !format.is_table() {
+        let times: Vec<ZoneTime> = zones
+            .iter()
+            .map(|zone| {
+                let time = now.with_timezone(zone);
+                ZoneTime {
+                    zone: zone.name(),
+                    time: time.to_rfc3339(),
+                    utc_offset: time.format("%:z").to_string(),
+                    day_offset: (time.date_naive() - local_date).num_days(),
+                }
+            })
+            .collect();
+        return format.print(&times);
+    }
+
     let title = match query {
         None => "World Clock".to_string(),
         Some(q) => format!("Timezones matching {:?} ({})", q, zones.len()),
@@ -54,8 +83,6 @@ pub fn show(query: Option<&str>) -> Result<()> {
         TableFormatter::header_cell("Day"),
     ]);
 
-    let now = Utc::now();
-    let local_date = now.with_timezone(&Local).date_naive();
     const LIMIT: usize = 30;
     for zone in zones.iter().take(LIMIT) {
         let time = now.with_timezone(zone);
@@ -1,20 +1,48 @@
 use ulid::Ulid;
 use anyhow::{Result, Context};
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use chrono::{DateTime, Utc};
+use serde::Serialize;
 
-pub fn generate(count: usize) -> Result<()> {
+#[derive(Serialize)]
+struct GeneratedUlid {
+    index: usize,
+    ulid: String,
+    timestamp: String,
+}
+
+#[derive(Serialize)]
+struct UlidInfo {
+    ulid: String,
+    timestamp: String,
+    timestamp_ms: u64,
+    random: String,
+}
+
+pub fn generate(count: usize, format: OutputFormat) -> Result<()> {
     if count == 0 {
         return Ok(());
     }
 
-    if count == 1 {
+    if count == 1 && format.is_table() {
         let id = Ulid::new();
         println!("{}", Theme::highlight(id.to_string()));
         return Ok(());
     }
 
+    let ulids: Vec<GeneratedUlid> = (1..=count)
+        .map(|index| {
+            let id = Ulid::new();
+            let dt: DateTime<Utc> = id.datetime().into();
+            GeneratedUlid { index, ulid: id.to_string(), timestamp: dt.to_rfc3339() }
+        })
+        .collect();
+
+    if !format.is_table() {
+        return format.print(&ulids);
+    }
+
     let mut table = TableFormatter::create_table();
     table.set_header(vec![
         TableFormatter::header_cell("#"),
@@ -22,13 +50,11 @@ pub fn generate(count: usize) -> Result<()> {
         TableFormatter::header_cell("Timestamp"),
     ]);
 
-    for i in 0..count {
-        let id = Ulid::new();
-        let dt: DateTime<Utc> = id.datetime().into();
+    for GeneratedUlid { index, ulid, timestamp } in ulids {
         table.add_row(vec![
-            TableFormatter::value_cell(i + 1),
-            TableFormatter::highlight_cell(id.to_string()),
-            TableFormatter::value_cell(dt.to_rfc3339()),
+            TableFormatter::value_cell(index),
+            TableFormatter::highlight_cell(ulid),
+            TableFormatter::value_cell(timestamp),
         ]);
     }
 
@@ -37,38 +63,48 @@ pub fn generate(count: usize) -> Result<()> {
     Ok(())
 }
 
-pub fn inspect(id: &str) -> Result<()> {
+pub fn inspect(id: &str, format: OutputFormat) -> Result<()> {
     let id = Ulid::from_string(id)
         .with_context(|| format!("Failed to parse ULID: '{}'", id))?;
 
     let dt: DateTime<Utc> = id.datetime().into();
+    let info = UlidInfo {
+        ulid: id.to_string(),
+        timestamp: dt.to_rfc3339(),
+        timestamp_ms: id.timestamp_ms(),
+        random: format!("{:020x}", id.random()),
+    };
+
+    if !format.is_table() {
+        return format.print(&info);
+    }
 
     println!("\n{}", Theme::header("ULID Inspection Report"));
-    
+
     let mut table = TableFormatter::create_table();
-    table.add_row(vec![
+    table.set_header(vec![
         TableFormatter::header_cell("Field"),
         TableFormatter::header_cell("Value"),
     ]);
 
     table.add_row(vec![
         TableFormatter::value_cell("ULID"),
-        TableFormatter::highlight_cell(id.to_string()),
+        TableFormatter::highlight_cell(&info.ulid),
     ]);
 
     table.add_row(vec![
         TableFormatter::value_cell("Timestamp"),
-        TableFormatter::highlight_cell(dt.to_rfc3339()),
+        TableFormatter::highlight_cell(&info.timestamp),
     ]);
 
     table.add_row(vec![
         TableFormatter::value_cell("Milliseconds"),
-        TableFormatter::value_cell(id.timestamp_ms()),
+        TableFormatter::value_cell(info.timestamp_ms),
     ]);
 
     table.add_row(vec![
         TableFormatter::value_cell("Random Part"),
-        TableFormatter::value_cell(format!("{:020x}", id.random())),
+        TableFormatter::value_cell(&info.random),
     ]);
 
     println!("{}", table);
@@ -1,8 +1,18 @@
 use anyhow::Result;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use cli_core::ui::Theme;
+use serde::Serialize;
 
-pub fn convert(value: f64, from: &str, to: &str) -> Result<()> {
+#[derive(Serialize)]
+struct Conversion<'a> {
+    value: f64,
+    from: &'a str,
+    to: &'a str,
+    result: f64,
+    unit: &'static str,
+}
+
+pub fn convert(value: f64, from: &str, to: &str, format: OutputFormat) -> Result<()> {
     let from = from.to_lowercase();
     let to = to.to_lowercase();
 
@@ -41,6 +51,10 @@ pub fn convert(value: f64, from: &str, to: &str) -> Result<()> {
     };
 
     if let Some((res, unit)) = result {
+        if !format.is_table() {
+            return format.print(&Conversion { value, from: &from, to: &to, result: res, unit });
+        }
+
         let mut table = TableFormatter::create_table();
         table.set_header(vec![
             TableFormatter::header_cell("From"),
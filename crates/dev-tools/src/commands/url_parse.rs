@@ -1,12 +1,50 @@
 use anyhow::Result;
 use url::Url;
 use owo_colors::OwoColorize;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use cli_core::ui::Theme;
+use serde::Serialize;
 
-pub fn parse(input: &str) -> Result<()> {
+#[derive(Serialize)]
+struct UrlOutput<'a> {
+    url: &'a str,
+    scheme: &'a str,
+    host: Option<&'a str>,
+    port: Option<u16>,
+    path: &'a str,
+    fragment: Option<&'a str>,
+    username: Option<&'a str>,
+    /// Whether the URL carries a password; the password itself is never shown.
+    has_password: bool,
+    query: Vec<QueryParam>,
+}
+
+#[derive(Serialize)]
+struct QueryParam {
+    key: String,
+    value: String,
+}
+
+pub fn parse(input: &str, format: OutputFormat) -> Result<()> {
     let u = Url::parse(input)?;
 
+    if !format.is_table() {
+        return format.print(&UrlOutput {
+            url: input,
+            scheme: u.scheme(),
+            host: u.host_str(),
+            port: u.port(),
+            path: u.path(),
+            fragment: u.fragment(),
+            username: Some(u.username()).filter(|name| !name.is_empty()),
+            has_password: u.password().is_some(),
+            query: u
+                .query_pairs()
+                .map(|(key, value)| QueryParam { key: key.into_owned(), value: value.into_owned() })
+                .collect(),
+        });
+    }
+
     println!("{}", Theme::header(" URL Analysis "));
     println!("{} {}\n", Theme::info("URL:"), input.bright_white().bold());
 
@@ -63,25 +101,25 @@ pub fn parse(input: &str) -> Result<()> {
         ]);
     }
 
+    let mut q_table = TableFormatter::create_table();
+    q_table.set_header(vec![
+        TableFormatter::header_cell("Key"),
+        TableFormatter::header_cell("Value"),
+    ]);
+
+    for (key, value) in u.query_pairs() {
+        q_table.add_row(vec![
+            TableFormatter::highlight_cell(key),
+            TableFormatter::value_cell(value),
+        ]);
+    }
+
     println!("{}", table);
 
     if u.query().is_some() {
         println!("\n{}", Theme::header(" Query Parameters "));
-        let mut q_table = TableFormatter::create_table();
-        q_table.set_header(vec![
-            TableFormatter::header_cell("Key"),
-            TableFormatter::header_cell("Value"),
-        ]);
-
-        for (key, value) in u.query_pairs() {
-            q_table.add_row(vec![
-                TableFormatter::highlight_cell(key),
-                TableFormatter::value_cell(value),
-            ]);
-        }
         println!("{}", q_table);
     }
 
     Ok(())
 }
-
@@ -1,9 +1,11 @@
 use anyhow::Result;
 use rand::seq::SliceRandom;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use cli_core::ui::Theme;
 use woothee::parser::Parser;
 use comfy_table::Cell;
+use serde::Serialize;
+use std::borrow::Cow;
 
 const USER_AGENTS: &[&str] = &[
     "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/121.0.0.0 Safari/537.36",
@@ -17,37 +19,68 @@ const USER_AGENTS: &[&str] = &[
     "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.2 Safari/605.1.15",
 ];
 
-pub fn generate() -> Result<()> {
+pub fn generate(format: OutputFormat) -> Result<()> {
     let mut rng = rand::thread_rng();
     if let Some(ua) = USER_AGENTS.choose(&mut rng) {
-        println!("{}", Theme::header("Generated User Agent"));
-        println!("  {}", Theme::highlight(ua.to_string()));
-        println!();
-        inspect(ua)?;
+        if format.is_table() {
+            println!("{}", Theme::header("Generated User Agent"));
+            println!("  {}", Theme::highlight(ua.to_string()));
+            println!();
+        }
+        inspect(ua, format)?;
     }
     Ok(())
 }
 
-pub fn inspect(ua: &str) -> Result<()> {
+#[derive(Serialize)]
+struct UserAgent<'a> {
+    user_agent: &'a str,
+    /// Absent when the string could not be parsed.
+    #[serde(flatten)]
+    parsed: Option<ParsedAgent<'a>>,
+}
+
+#[derive(Serialize)]
+struct ParsedAgent<'a> {
+    browser: &'a str,
+    browser_version: &'a str,
+    os: &'a str,
+    os_version: Cow<'a, str>,
+    category: &'a str,
+    vendor: &'a str,
+}
+
+pub fn inspect(ua: &str, format: OutputFormat) -> Result<()> {
     let parser = Parser::new();
-    let result = parser.parse(ua);
+    let parsed = parser.parse(ua).map(|r| ParsedAgent {
+        browser: r.name,
+        browser_version: r.version,
+        os: r.os,
+        os_version: r.os_version,
+        category: r.category,
+        vendor: r.vendor,
+    });
+
+    if !format.is_table() {
+        return format.print(&UserAgent { user_agent: ua, parsed });
+    }
 
-    println!("{}", Theme::header("User Agent Inspection"));
-    
     let mut table = TableFormatter::create_table();
     table.set_header(vec![
         TableFormatter::header_cell("Property"),
         TableFormatter::header_cell("Value"),
     ]);
 
-    if let Some(r) = result {
+    println!("{}", Theme::header("User Agent Inspection"));
+
+    if let Some(r) = parsed {
         table.add_row(vec![
             TableFormatter::value_cell("Browser"),
-            TableFormatter::highlight_cell(r.name),
+            TableFormatter::highlight_cell(r.browser),
         ]);
         table.add_row(vec![
             TableFormatter::value_cell("Browser Version"),
-            TableFormatter::value_cell(r.version),
+            TableFormatter::value_cell(r.browser_version),
         ]);
         table.add_row(vec![
             TableFormatter::value_cell("Operating System"),
@@ -1,20 +1,50 @@
 use uuid::Uuid;
 use anyhow::{Result, Context};
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use chrono::{Utc, TimeZone};
+use serde::Serialize;
 
-pub fn generate(count: usize, v7: bool) -> Result<()> {
+#[derive(Serialize)]
+struct GeneratedUuid {
+    index: usize,
+    uuid: String,
+    version: u8,
+}
+
+#[derive(Serialize)]
+struct UuidInfo {
+    uuid: String,
+    version: usize,
+    variant: String,
+    description: Option<&'static str>,
+    /// Creation time, for v7.
+    timestamp: Option<String>,
+}
+
+pub fn generate(count: usize, v7: bool, format: OutputFormat) -> Result<()> {
     if count == 0 {
         return Ok(());
     }
 
-    if count == 1 {
+    if count == 1 && format.is_table() {
         let uuid = if v7 { Uuid::now_v7() } else { Uuid::new_v4() };
         println!("{}", Theme::highlight(uuid.to_string()));
         return Ok(());
     }
 
+    let uuids: Vec<GeneratedUuid> = (1..=count)
+        .map(|index| GeneratedUuid {
+            index,
+            uuid: if v7 { Uuid::now_v7() } else { Uuid::new_v4() }.to_string(),
+            version: if v7 { 7 } else { 4 },
+        })
+        .collect();
+
+    if !format.is_table() {
+        return format.print(&uuids);
+    }
+
     let mut table = TableFormatter::create_table();
     table.set_header(vec![
         TableFormatter::header_cell("#"),
@@ -22,11 +52,10 @@ pub fn generate(count: usize, v7: bool) -> Result<()> {
         TableFormatter::header_cell("Version"),
     ]);
 
-    for i in 0..count {
-        let uuid = if v7 { Uuid::now_v7() } else { Uuid::new_v4() };
+    for GeneratedUuid { index, uuid, .. } in uuids {
         table.add_row(vec![
-            TableFormatter::value_cell(i + 1),
-            TableFormatter::highlight_cell(uuid.to_string()),
+            TableFormatter::value_cell(index),
+            TableFormatter::highlight_cell(uuid),
             TableFormatter::value_cell(if v7 { "v7 (Timestamp)" } else { "v4 (Random)" }),
         ]);
     }
@@ -36,24 +65,47 @@ pub fn generate(count: usize, v7: bool) -> Result<()> {
     Ok(())
 }
 
-pub fn inspect(id: &str) -> Result<()> {
+pub fn inspect(id: &str, format: OutputFormat) -> Result<()> {
     let uuid = Uuid::parse_str(id)
         .with_context(|| format!("Failed to parse UUID: '{}'", id))?;
 
     let version = uuid.get_version();
     let variant = uuid.get_variant();
 
+    // Extract timestamp from v7
+    let timestamp = match version {
+        Some(uuid::Version::SortRand) => {
+            let (secs, nanos) = uuid.get_timestamp()
+                .map(|ts| ts.to_unix())
+                .unwrap_or((0, 0));
+            (secs > 0).then(|| Utc.timestamp_opt(secs as i64, nanos).unwrap().to_rfc3339())
+        }
+        _ => None,
+    };
+
+    let info = UuidInfo {
+        uuid: uuid.to_string(),
+        version: uuid.get_version_num(),
+        variant: format!("{:?}", variant),
+        description: version.and_then(describe),
+        timestamp,
+    };
+
+    if !format.is_table() {
+        return format.print(&info);
+    }
+
     println!("\n{}", Theme::header("UUID Inspection Report"));
-    
+
     let mut table = TableFormatter::create_table();
-    table.add_row(vec![
+    table.set_header(vec![
         TableFormatter::header_cell("Field"),
         TableFormatter::header_cell("Value"),
     ]);
 
     table.add_row(vec![
         TableFormatter::value_cell("UUID"),
-        TableFormatter::highlight_cell(uuid.to_string()),
+        TableFormatter::highlight_cell(&info.uuid),
     ]);
 
     table.add_row(vec![
@@ -63,82 +115,38 @@ pub fn inspect(id: &str) -> Result<()> {
 
     table.add_row(vec![
         TableFormatter::value_cell("Variant"),
-        TableFormatter::value_cell(format!("{:?}", variant)),
+        TableFormatter::value_cell(&info.variant),
     ]);
 
-    if let Some(version) = version {
-        match version {
-            uuid::Version::Nil => {
-                table.add_row(vec![
-                    TableFormatter::value_cell("Description"),
-                    TableFormatter::value_cell("The Nil UUID is a special form of UUID that is specified to have all 128 bits set to zero."),
-                ]);
-            }
-            uuid::Version::Mac => {
-                table.add_row(vec![
-                    TableFormatter::value_cell("Description"),
-                    TableFormatter::value_cell("v1 (MAC address & timestamp)"),
-                ]);
-            }
-            uuid::Version::Dce => {
-                table.add_row(vec![
-                    TableFormatter::value_cell("Description"),
-                    TableFormatter::value_cell("v2 (DCE Security version)"),
-                ]);
-            }
-            uuid::Version::Md5 => {
-                table.add_row(vec![
-                    TableFormatter::value_cell("Description"),
-                    TableFormatter::value_cell("v3 (MD5 hash name-based)"),
-                ]);
-            }
-            uuid::Version::Random => {
-                table.add_row(vec![
-                    TableFormatter::value_cell("Description"),
-                    TableFormatter::value_cell("v4 (Fully random)"),
-                ]);
-            }
-            uuid::Version::Sha1 => {
-                table.add_row(vec![
-                    TableFormatter::value_cell("Description"),
-                    TableFormatter::value_cell("v5 (SHA-1 hash name-based)"),
-                ]);
-            }
-            uuid::Version::SortMac => {
-                table.add_row(vec![
-                    TableFormatter::value_cell("Description"),
-                    TableFormatter::value_cell("v6 (Reordered v1 for database locality)"),
-                ]);
-            }
-            uuid::Version::SortRand => {
-                table.add_row(vec![
-                    TableFormatter::value_cell("Description"),
-                    TableFormatter::value_cell("v7 (Unix timestamp & random)"),
-                ]);
-
-                // Extract timestamp from v7
-                let (secs, nanos) = uuid.get_timestamp()
-                    .map(|ts| ts.to_unix())
-                    .unwrap_or((0, 0));
-                
-                if secs > 0 {
-                    let dt = Utc.timestamp_opt(secs as i64, nanos).unwrap();
-                    table.add_row(vec![
-                        TableFormatter::value_cell("Timestamp"),
-                        TableFormatter::highlight_cell(dt.to_rfc3339()),
-                    ]);
-                }
-            }
-            uuid::Version::Custom => {
-                table.add_row(vec![
-                    TableFormatter::value_cell("Description"),
-                    TableFormatter::value_cell("v8 (Custom implementation)"),
-                ]);
-            }
-            _ => {}
-        }
+    if let Some(description) = info.description {
+        table.add_row(vec![
+            TableFormatter::value_cell("Description"),
+            TableFormatter::value_cell(description),
+        ]);
+    }
+
+    if let Some(timestamp) = &info.timestamp {
+        table.add_row(vec![
+            TableFormatter::value_cell("Timestamp"),
+            TableFormatter::highlight_cell(timestamp),
+        ]);
     }
 
     println!("{}", table);
     Ok(())
 }
+
+fn describe(version: uuid::Version) -> Option<&'static str> {
+    match version {
+        uuid::Version::Nil => Some("The Nil UUID is a special form of UUID that is specified to have all 128 bits set to zero."),
+        uuid::Version::Mac => Some("v1 (MAC address & timestamp)"),
+        uuid::Version::Dce => Some("v2 (DCE Security version)"),
+        uuid::Version::Md5 => Some("v3 (MD5 hash name-based)"),
+        uuid::Version::Random => Some("v4 (Fully random)"),
+        uuid::Version::Sha1 => Some("v5 (SHA-1 hash name-based)"),
+        uuid::Version::SortMac => Some("v6 (Reordered v1 for database locality)"),
+        uuid::Version::SortRand => Some("v7 (Unix timestamp & random)"),
+        uuid::Version::Custom => Some("v8 (Custom implementation)"),
+        _ => None,
+    }
+}
@@ -3,6 +3,7 @@ use crate::commands::Commands;
 
 use anyhow::Result;
 use clap::Parser;
+use cli_core::output::{FormatArg, OutputFormat};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -32,11 +33,11 @@ async fn main() -> Result<()> {
         Commands::Serve { path, port } => {
             commands::serve::run(path, port).await?;
         }
-        Commands::Uuid { count, v7, inspect } => {
+        Commands::Uuid { count, v7, inspect, output: FormatArg { format } } => {
             if let Some(id) = inspect {
-                commands::uuid::inspect(&id)?;
+                commands::uuid::inspect(&id, format)?;
             } else {
-                commands::uuid::generate(count, v7)?;
+                commands::uuid::generate(count, v7, format)?;
             }
         }
         Commands::Base64 { input, decode, file, output, data_uri, url_safe } => {
@@ -117,14 +118,14 @@ async fn main() -> Result<()> {
         Commands::Tree { path, depth, size, git } => {
             commands::tree::print_tree(&path, depth, size, git)?;
         }
-        Commands::Ip { target } => {
-            commands::ip::show(target).await?;
+        Commands::Ip { target, output: FormatArg { format } } => {
+            commands::ip::show(target, format).await?;
         }
-        Commands::Random { kind, count, length, min, max, numeric, symbols, uppercase } => {
-            commands::random::generate(&kind, count, length, min, max, numeric, symbols, uppercase)?;
+        Commands::Random { kind, count, length, min, max, numeric, symbols, uppercase, output: FormatArg { format } } => {
+            commands::random::generate(&kind, count, length, min, max, numeric, symbols, uppercase, format)?;
         }
-        Commands::Case { text, to } => {
-            commands::case::convert(&text, to.as_deref())?;
+        Commands::Case { text, to, output: FormatArg { format } } => {
+            commands::case::convert(&text, to.as_deref(), format)?;
         }
         Commands::Jwt { token, sign, payload, secret } => {
             if sign {
@@ -139,17 +140,17 @@ async fn main() -> Result<()> {
                 anyhow::bail!("JWT token is required for inspection or use --sign");
             }
         }
-        Commands::Env { filter } => {
-            commands::env::list(filter)?;
+        Commands::Env { filter, output: FormatArg { format } } => {
+            commands::env::list(filter, format)?;
         }
-        Commands::Sys => {
-            commands::sys::show()?;
+        Commands::Sys { output: FormatArg { format } } => {
+            commands::sys::show(format)?;
         }
-        Commands::Contrast { foreground, background } => {
-            commands::contrast::run(&foreground, &background)?;
+        Commands::Contrast { foreground, background, output: FormatArg { format } } => {
+            commands::contrast::run(&foreground, &background, format)?;
         }
-        Commands::Color { input } => {
-            commands::color::convert(&input)?;
+        Commands::Color { input, output: FormatArg { format } } => {
+            commands::color::convert(&input, format)?;
         }
         Commands::Size { value, unit } => {
             commands::size::convert(value, &unit)?;
@@ -172,11 +173,11 @@ async fn main() -> Result<()> {
         Commands::Qr { text, output, level, size } => {
             commands::qr::generate(&text, output, &level, size)?;
         }
-        Commands::Path { path, resolve } => {
+        Commands::Path { path, resolve, output: FormatArg { format } } => {
             if resolve {
-                commands::path::resolve(&path)?;
+                commands::path::resolve(&path, format)?;
             } else {
-                commands::path::normalize(&path)?;
+                commands::path::normalize(&path, format)?;
             }
         }
         Commands::Yaml { text, json, toml } => {
@@ -211,13 +212,21 @@ async fn main() -> Result<()> {
                 truncate,
             )?;
         }
-        Commands::Cron { expression } => {
-            commands::cron::explain(&expression)?;
+        Commands::Cron { expression, output: FormatArg { format } } => {
+            commands::cron::explain(&expression, format)?;
+        }
+        Commands::Config { action } => {
+            use commands::ConfigAction;
+            match action {
+                ConfigAction::Show { effective, path, output: FormatArg { format } } => {
+                    commands::config::show(&path, effective, format)?;
+                }
+            }
         }
         Commands::Crontab { action } => {
             use commands::CrontabAction;
-            match action.unwrap_or(CrontabAction::List) {
-                CrontabAction::List => commands::crontab::list()?,
+            match action.unwrap_or(CrontabAction::List { output: FormatArg { format: OutputFormat::Table } }) {
+                CrontabAction::List { output: FormatArg { format } } => commands::crontab::list(format)?,
                 CrontabAction::Add { schedule, command, comment } => {
                     commands::crontab::add(&schedule, &command, comment.as_deref())?;
                 }
@@ -233,8 +242,8 @@ async fn main() -> Result<()> {
         Commands::Toc { file, min_depth, max_depth } => {
             commands::toc::run(&file, min_depth, max_depth)?;
         }
-        Commands::Tz { query } => {
-            commands::tz::show(query.as_deref())?;
+        Commands::Tz { query, output: FormatArg { format } } => {
+            commands::tz::show(query.as_deref(), format)?;
         }
         Commands::Unicode { text } => {
             commands::unicode::inspect(&text)?;
@@ -260,8 +269,8 @@ async fn main() -> Result<()> {
                 _ => anyhow::bail!("Unsupported escape kind: {}", kind),
             }
         }
-        Commands::Base { value, from, to, all } => {
-            commands::base::convert(&value, from, to, all)?;
+        Commands::Base { value, from, to, all, output: FormatArg { format } } => {
+            commands::base::convert(&value, from, to, all, format)?;
         }
         Commands::Checksum { input, algo, file, check } => {
             if let Some(c) = check {
@@ -273,8 +282,8 @@ async fn main() -> Result<()> {
         Commands::Hexview { input, file } => {
             commands::hexview::view(&input, file)?;
         }
-        Commands::Dns { domain, record } => {
-            commands::dns::lookup(&domain, &record).await?;
+        Commands::Dns { domain, record, output: FormatArg { format } } => {
+            commands::dns::lookup(&domain, &record, format).await?;
         }
         Commands::Gitignore { list, targets } => {
             if list {
@@ -292,8 +301,8 @@ async fn main() -> Result<()> {
                 anyhow::bail!("Please specify a license kind or use --list");
             }
         }
-        Commands::Stat { input, file } => {
-            commands::stat::analyze(&input, file)?;
+        Commands::Stat { input, file, output: FormatArg { format } } => {
+            commands::stat::analyze(&input, file, format)?;
         }
         Commands::Whois { domain } => {
             commands::whois::lookup(&domain)?;
@@ -307,39 +316,44 @@ async fn main() -> Result<()> {
                 anyhow::bail!("Either host or file must be provided");
             }
         }
-        Commands::Unit { value, from, to } => {
-            commands::unit::convert(value, &from, &to)?;
+        Commands::Unit { value, from, to, output: FormatArg { format } } => {
+            commands::unit::convert(value, &from, &to, format)?;
         }
-        Commands::Currency { amount, from, to } => {
-            commands::currency::convert(amount, &from, &to).await?;
+        Commands::Currency { amount, from, to, update_rates, output: FormatArg { format } } => {
+            if update_rates {
+                commands::currency::update_rates(format).await?;
+            }
+            if let (Some(amount), Some(from), Some(to)) = (amount, from, to) {
+                commands::currency::convert(amount, &from, &to, format).await?;
+            }
         }
-        Commands::Ulid { count, inspect } => {
+        Commands::Ulid { count, inspect, output: FormatArg { format } } => {
             if let Some(id) = inspect {
-                commands::ulid::inspect(&id)?;
+                commands::ulid::inspect(&id, format)?;
             } else {
-                commands::ulid::generate(count)?;
+                commands::ulid::generate(count, format)?;
             }
         }
-        Commands::Nanoid { length, count, alphabet } => {
-            commands::nanoid::generate(length, count, alphabet.as_deref())?;
+        Commands::Nanoid { length, count, alphabet, output: FormatArg { format } } => {
+            commands::nanoid::generate(length, count, alphabet.as_deref(), format)?;
         }
-        Commands::Totp { secret, digits, skew, generate_secret } => {
+        Commands::Totp { secret, digits, skew, generate_secret, output: FormatArg { format } } => {
             if generate_secret {
-                commands::totp::generate_new_secret()?;
+                commands::totp::generate_new_secret(format)?;
             } else if let Some(s) = secret {
-                commands::totp::generate(&s, digits, skew)?;
+                commands::totp::generate(&s, digits, skew, format)?;
             } else {
                 anyhow::bail!("Secret key is required for TOTP generation, or use --generate-secret");
             }
         }
-        Commands::Csv { input, json, yaml, markdown } => {
-            commands::csv::convert(&input, json, yaml, markdown)?;
+        Commands::Csv { input, json, yaml, markdown, output: FormatArg { format } } => {
+            commands::csv::convert(&input, json, yaml, markdown, format)?;
         }
         Commands::Shorten { url } => {
             commands::shorten::shorten(&url).await?;
         }
-        Commands::Extract { input, kind, file } => {
-            commands::extract::extract(&input, &kind, file)?;
+        Commands::Extract { input, kind, file, output: FormatArg { format } } => {
+            commands::extract::extract(&input, &kind, file, format)?;
         }
         Commands::Sql { query, file, indent, tabs, lowercase } => {
             let sql = if let Some(f) = file {
@@ -352,8 +366,8 @@ async fn main() -> Result<()> {
         Commands::Slug { text } => {
             commands::slug::generate(&text)?;
         }
-        Commands::Chmod { input } => {
-            commands::chmod::calculate(&input)?;
+        Commands::Chmod { input, output: FormatArg { format } } => {
+            commands::chmod::calculate(&input, format)?;
         }
         Commands::Base32 { input, decode, file, output } => {
             if decode {
@@ -386,18 +400,18 @@ async fn main() -> Result<()> {
         Commands::Hmac { text, key, algo } => {
             commands::hmac::calculate(&text, &key, &algo)?;
         }
-        Commands::Binary { input, from } => {
+        Commands::Binary { input, from, output: FormatArg { format } } => {
             if from {
-                commands::binary::from_binary(&input)?;
+                commands::binary::from_binary(&input, format)?;
             } else {
-                commands::binary::to_binary(&input)?;
+                commands::binary::to_binary(&input, format)?;
             }
         }
-        Commands::UserAgent { ua } => {
+        Commands::UserAgent { ua, output: FormatArg { format } } => {
             if let Some(val) = ua {
-                commands::user_agent::inspect(&val)?;
+                commands::user_agent::inspect(&val, format)?;
             } else {
-                commands::user_agent::generate()?;
+                commands::user_agent::generate(format)?;
             }
         }
         Commands::Mime { input, extension } => {
@@ -410,14 +424,14 @@ async fn main() -> Result<()> {
         Commands::Joke => {
             commands::joke::random()?;
         }
-        Commands::Secret { length, count, kind } => {
-            commands::secret::generate(length, count, &kind)?;
+        Commands::Secret { length, count, kind, output: FormatArg { format } } => {
+            commands::secret::generate(length, count, &kind, format)?;
         }
-        Commands::Snowflake { inspect, count } => {
+        Commands::Snowflake { inspect, count, output: FormatArg { format } } => {
             if let Some(id) = inspect {
-                commands::snowflake::inspect(id)?;
+                commands::snowflake::inspect(id, format)?;
             } else {
-                commands::snowflake::generate(count)?;
+                commands::snowflake::generate(count, format)?;
             }
         }
         Commands::Semver { text, op, increment, compare } => {
@@ -440,11 +454,11 @@ async fn main() -> Result<()> {
                 _ => anyhow::bail!("Unsupported semver operation: {}", op),
             }
         }
-        Commands::Ksuid { inspect, count } => {
+        Commands::Ksuid { inspect, count, output: FormatArg { format } } => {
             if let Some(id) = inspect {
-                commands::ksuid::inspect(&id)?;
+                commands::ksuid::inspect(&id, format)?;
             } else {
-                commands::ksuid::generate(count)?;
+                commands::ksuid::generate(count, format)?;
             }
         }
         Commands::Xml { text, minify } => {
@@ -456,8 +470,8 @@ async fn main() -> Result<()> {
         Commands::Subnet { cidr } => {
             commands::subnet::run(&cidr)?;
         }
-        Commands::Portscan { host, start, end, timeout } => {
-            commands::portscan::scan(&host, start, end, timeout).await?;
+        Commands::Portscan { host, start, end, timeout, output: FormatArg { format } } => {
+            commands::portscan::scan(&host, start, end, timeout, format).await?;
         }
         Commands::Dotenv { op, path, example } => {
             match op.as_str() {
@@ -481,11 +495,11 @@ async fn main() -> Result<()> {
                 commands::mac::generate(count)?;
             }
         }
-        Commands::Bcrypt { password, hash, cost } => {
+        Commands::Bcrypt { password, hash, cost, output: FormatArg { format } } => {
             if let Some(h) = hash {
-                commands::bcrypt::verify_password(&password, &h)?;
+                commands::bcrypt::verify_password(&password, &h, format)?;
             } else {
-                commands::bcrypt::hash_password(&password, cost)?;
+                commands::bcrypt::hash_password(&password, cost, format)?;
             }
         }
         Commands::Compress { input, output, decompress } => {
@@ -495,36 +509,36 @@ async fn main() -> Result<()> {
                 commands::compress::gzip(&input, &output)?;
             }
         }
-        Commands::HttpStatus { code, list, search } => {
+        Commands::HttpStatus { code, list, search, output: FormatArg { format } } => {
             if list {
-                commands::http_status::list()?;
+                commands::http_status::list(format)?;
             } else if let Some(q) = search {
-                commands::http_status::search(&q)?;
+                commands::http_status::search(&q, format)?;
             } else if let Some(c) = code {
-                commands::http_status::lookup(c)?;
+                commands::http_status::lookup(c, format)?;
             } else {
                 anyhow::bail!("Status code is required, or use --list or --search");
             }
         }
-        Commands::AsciiTable => {
-            commands::ascii_table::show()?;
+        Commands::AsciiTable { output: FormatArg { format } } => {
+            commands::ascii_table::show(format)?;
         }
-        Commands::UrlParse { url } => {
-            commands::url_parse::parse(&url)?;
+        Commands::UrlParse { url, output: FormatArg { format } } => {
+            commands::url_parse::parse(&url, format)?;
         }
-        Commands::Github { issue, pr, user, repo, search } => {
+        Commands::Github { issue, pr, user, repo, search, output: FormatArg { format } } => {
             if let Some(u) = user {
-                commands::github::get_user(&u).await?;
+                commands::github::get_user(&u, format).await?;
             } else if let Some(r) = repo {
-                commands::github::get_repo(&r).await?;
+                commands::github::get_repo(&r, format).await?;
             } else if let Some(q) = search {
-                commands::github::search(&q).await?;
+                commands::github::search(&q, format).await?;
             } else {
-                commands::github::open(issue, pr)?;
+                commands::github::open(issue, pr, format)?;
             }
         }
-        Commands::Crates { query } => {
-            commands::crates::search(&query).await?;
+        Commands::Crates { query, output: FormatArg { format } } => {
+            commands::crates::search(&query, format).await?;
         }
         Commands::Typescript { text, name } => {
             commands::typescript::from_json(&text, &name)?;
@@ -532,20 +546,20 @@ async fn main() -> Result<()> {
         Commands::Weather { location } => {
             commands::weather::get_weather(location).await?;
         }
-        Commands::Dictionary { word } => {
-            commands::dictionary::lookup(&word).await?;
+        Commands::Dictionary { word, output: FormatArg { format } } => {
+            commands::dictionary::lookup(&word, format).await?;
         }
         Commands::Translate { text, to } => {
             commands::translate::translate(&text, &to).await?;
         }
-        Commands::Bench { command, args, count } => {
-            commands::bench::run(&command, args, count)?;
+        Commands::Bench { command, args, count, output: FormatArg { format } } => {
+            commands::bench::run(&command, args, count, format)?;
         }
         Commands::Whoami => {
             commands::whoami::show()?;
         }
-        Commands::JsonDiff { left, right } => {
-            commands::json_diff::compare(&left, &right)?;
+        Commands::JsonDiff { left, right, output: FormatArg { format } } => {
+            commands::json_diff::compare(&left, &right, format)?;
         }
         Commands::Rust { text, name } => {
             commands::rust::from_json(&text, &name)?;
@@ -572,7 +586,7 @@ async fn main() -> Result<()> {
         Commands::Cheat { query } => {
             commands::cheat::run(&query).await?;
         }
-        Commands::Scan { path, min_size, duplicates, empty, dirs, links, all } => {
+        Commands::Scan { path, min_size, duplicates, empty, dirs, links, all, output: FormatArg { format } } => {
             let mut opts = commands::scan::ScanOptions {
                 duplicates,
                 empty,
@@ -586,10 +600,10 @@ async fn main() -> Result<()> {
                 opts.dirs = true;
                 opts.links = true;
             }
-            commands::scan::run(&path, opts)?;
+            commands::scan::run(&path, opts, format)?;
         }
-        Commands::CheckLinks { path } => {
-            commands::check_links::run(&path).await?;
+        Commands::CheckLinks { path, output: FormatArg { format } } => {
+            commands::check_links::run(&path, format).await?;
         }
         Commands::Detach { command, args } => {
             commands::detach::run(&command, args)?;
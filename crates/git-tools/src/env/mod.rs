@@ -1,64 +1,120 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use cli_core::output::OutputFormat;
 use cli_core::ui::Theme;
-use std::collections::HashSet;
+use serde::Serialize;
+use std::collections::BTreeSet;
 use std::fs;
 use std::path::Path;
 
-pub fn check(path: &Path) -> Result<()> {
+/// Key differences between `.env.example` and `.env`, as emitted by `env --format json`.
+#[derive(Serialize, Default)]
+struct EnvReport {
+    example_found: bool,
+    env_found: bool,
+    /// Keys declared in `.env.example`.
+    expected: Vec<String>,
+    /// Keys in `.env.example` but not in `.env`.
+    missing: Vec<String>,
+    /// Keys in `.env` but not in `.env.example`.
+    extra: Vec<String>,
+    max_missing: Option<usize>,
+}
+
+pub fn check(path: &Path, max_missing: Option<usize>, format: OutputFormat) -> Result<()> {
+    let report = compare(path, max_missing)?;
+
+    if format.is_table() {
+        print_report(&report);
+    } else {
+        format.print(&report)?;
+    }
+
+    if let Some(message) = threshold_error(report.missing.len(), max_missing) {
+        bail!(message);
+    }
+    Ok(())
+}
+
+/// Error describing the missed `max_missing`, if any.
+fn threshold_error(missing: usize, max_missing: Option<usize>) -> Option<String> {
+    let max = max_missing?;
+    (missing > max).then(|| {
+        format!(
+            "{} keys are missing from .env, more than the maximum of {}",
+            missing, max
+        )
+    })
+}
+
+fn compare(path: &Path, max_missing: Option<usize>) -> Result<EnvReport> {
     let env_file = path.join(".env");
     let example_file = path.join(".env.example");
 
-    if !example_file.exists() {
+    let mut report = EnvReport {
+        example_found: example_file.exists(),
+        env_found: env_file.exists(),
+        max_missing,
+        ..EnvReport::default()
+    };
+    if !report.example_found {
+        return Ok(report);
+    }
+
+    let example_keys = extract_keys(&example_file)?;
+    let env_keys = if report.env_found {
+        extract_keys(&env_file)?
+    } else {
+        BTreeSet::new()
+    };
+
+    report.missing = example_keys.difference(&env_keys).cloned().collect();
+    report.extra = env_keys.difference(&example_keys).cloned().collect();
+    report.expected = example_keys.into_iter().collect();
+    Ok(report)
+}
+
+fn print_report(report: &EnvReport) {
+    if !report.example_found {
         println!(
             "{} .env.example not found. Skipping validation.",
             Theme::dim("Skipped:")
         );
-        return Ok(());
+        return;
     }
 
-    let example_keys = extract_keys(&example_file)?;
-    if example_keys.is_empty() {
+    if report.expected.is_empty() {
         println!(
             "{} No keys found in .env.example.",
             Theme::info("Note:")
         );
-        return Ok(());
+        return;
     }
 
-    if !env_file.exists() {
+    if !report.env_found {
         println!(
             "{} .env file is missing! Found {} keys in .env.example that should be configured.",
             Theme::error("Error:"),
-            example_keys.len()
+            report.expected.len()
         );
-        for key in example_keys {
+        for key in &report.expected {
             println!("  • {}", key);
         }
-        return Ok(());
-    }
-
-    let env_keys = extract_keys(&env_file)?;
-    let mut missing_keys = Vec::new();
-
-    for key in &example_keys {
-        if !env_keys.contains(key) {
-            missing_keys.push(key);
-        }
+        return;
     }
 
-    if missing_keys.is_empty() {
+    if report.missing.is_empty() {
         println!(
             "{} All {} keys from .env.example are present in .env",
             Theme::success("Success:"),
-            example_keys.len()
+            report.expected.len()
         );
     } else {
         println!(
             "{} Found {} missing keys in .env:",
             Theme::warning("Warning:"),
-            missing_keys.len()
+            report.missing.len()
         );
-        for key in missing_keys {
+        for key in &report.missing {
             println!("  • {}", key);
         }
         println!();
@@ -68,14 +124,24 @@ pub fn check(path: &Path) -> Result<()> {
         );
     }
 
-    Ok(())
+    if !report.extra.is_empty() {
+        println!();
+        println!(
+            "{} {} keys in .env are not documented in .env.example:",
+            Theme::info("Note:"),
+            report.extra.len()
+        );
+        for key in &report.extra {
+            println!("  • {}", key);
+        }
+    }
 }
 
-fn extract_keys(path: &Path) -> Result<HashSet<String>> {
+fn extract_keys(path: &Path) -> Result<BTreeSet<String>> {
     let content = fs::read_to_string(path)
         .with_context(|| format!("Failed to read file: {}", path.display()))?;
-    
-    let mut keys = HashSet::new();
+
+    let mut keys = BTreeSet::new();
     for line in content.lines() {
         let line = line.trim();
         if line.is_empty() || line.starts_with('#') {
@@ -91,3 +157,59 @@ fn extract_keys(path: &Path) -> Result<HashSet<String>> {
     }
     Ok(keys)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "git-tools-env-{}-{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn max_missing_allows_exactly_that_many_keys() {
+        assert_eq!(threshold_error(2, None), None);
+        assert_eq!(threshold_error(2, Some(2)), None);
+        assert_eq!(
+            threshold_error(3, Some(2)).as_deref(),
+            Some("3 keys are missing from .env, more than the maximum of 2")
+        );
+        assert!(threshold_error(1, Some(0)).is_some());
+    }
+
+    #[test]
+    fn report_matches_the_documented_schema() {
+        let dir = temp_dir();
+        fs::write(
+            dir.join(".env.example"),
+            "# Database\nDB_URL=\nAPI_KEY = x\n",
+        )
+        .unwrap();
+        fs::write(dir.join(".env"), "DB_URL=postgres://\nDEBUG=1\n").unwrap();
+
+        let report = compare(&dir, Some(0)).unwrap();
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::json!({
+                "example_found": true,
+                "env_found": true,
+                "expected": ["API_KEY", "DB_URL"],
+                "missing": ["API_KEY"],
+                "extra": ["DEBUG"],
+                "max_missing": 0
+            })
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
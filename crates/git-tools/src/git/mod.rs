@@ -1,11 +1,27 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use cli_core::output::OutputFormat;
 use cli_core::ui::Theme;
 use git2::{BranchType, Repository, StatusOptions};
+use serde::Serialize;
 use std::path::Path;
 use std::collections::HashMap;
 
-pub fn cleanup(path: &Path, force: bool, target: Option<&str>) -> Result<()> {
-    // ... (existing cleanup implementation)
+/// Merged branches found (and deleted with `--force`), as emitted by `cleanup --format json`.
+#[derive(Serialize)]
+struct CleanupReport {
+    target: String,
+    merged: Vec<String>,
+    deleted: Vec<String>,
+    max_merged: Option<usize>,
+}
+
+pub fn cleanup(
+    path: &Path,
+    force: bool,
+    target: Option<&str>,
+    max_merged: Option<usize>,
+    format: OutputFormat,
+) -> Result<()> {
     let repo = Repository::open(path).context("Failed to open repository")?;
 
     let target_branch_name = if let Some(t) = target {
@@ -23,11 +39,13 @@ pub fn cleanup(path: &Path, force: bool, target: Option<&str>) -> Result<()> {
         }
     };
 
-    println!(
-        "{} Checking branches merged into {}",
-        Theme::info("Info:"),
-        Theme::highlight(&target_branch_name)
-    );
+    if format.is_table() {
+        println!(
+            "{} Checking branches merged into {}",
+            Theme::info("Info:"),
+            Theme::highlight(&target_branch_name)
+        );
+    }
 
     let target_branch = repo
         .find_branch(&target_branch_name, BranchType::Local)
@@ -65,9 +83,48 @@ pub fn cleanup(path: &Path, force: bool, target: Option<&str>) -> Result<()> {
         }
     }
 
+    let mut deleted = Vec::new();
+    if force {
+        for name in &merged_branches {
+            let mut branch = repo.find_branch(name, BranchType::Local)?;
+            branch.delete()?;
+            deleted.push(name.clone());
+        }
+    }
+
+    if format.is_table() {
+        print_cleanup(&merged_branches, force);
+    } else {
+        format.print(&CleanupReport {
+            target: target_branch_name,
+            merged: merged_branches.clone(),
+            deleted: deleted.clone(),
+            max_merged,
+        })?;
+    }
+
+    let remaining = merged_branches.len() - deleted.len();
+    if let Some(message) = threshold_error(remaining, max_merged) {
+        bail!(message);
+    }
+    Ok(())
+}
+
+/// Error describing the missed `max_merged`, if any.
+fn threshold_error(remaining: usize, max_merged: Option<usize>) -> Option<String> {
+    let max = max_merged?;
+    (remaining > max).then(|| {
+        format!(
+            "{} merged branches remain, more than the maximum of {}",
+            remaining, max
+        )
+    })
+}
+
+fn print_cleanup(merged_branches: &[String], force: bool) {
     if merged_branches.is_empty() {
         println!("{}", Theme::success("No merged branches found. Everything is clean!"));
-        return Ok(());
+        return;
     }
 
     println!(
@@ -76,7 +133,7 @@ pub fn cleanup(path: &Path, force: bool, target: Option<&str>) -> Result<()> {
         merged_branches.len()
     );
 
-    for name in &merged_branches {
+    for name in merged_branches {
         println!("  • {}", name);
     }
 
@@ -84,8 +141,6 @@ pub fn cleanup(path: &Path, force: bool, target: Option<&str>) -> Result<()> {
         println!();
         println!("{}", Theme::info("Deleting branches..."));
         for name in merged_branches {
-            let mut branch = repo.find_branch(&name, BranchType::Local)?;
-            branch.delete()?;
             println!("  {} {}", Theme::success("Deleted:"), name);
         }
         println!();
@@ -97,10 +152,9 @@ pub fn cleanup(path: &Path, force: bool, target: Option<&str>) -> Result<()> {
             Theme::dim("Tip: Run with --force to delete these branches.")
         );
     }
-
-    Ok(())
 }
 
+#[derive(Serialize)]
 pub struct GitStatusSummary {
     pub modified: usize,
     pub added: usize,
@@ -151,8 +205,15 @@ pub fn get_status_summary(path: &Path) -> Result<GitStatusSummary> {
     })
 }
 
+#[derive(Serialize)]
+pub struct Contributor {
+    pub name: String,
+    pub commits: usize,
+}
+
+#[derive(Serialize)]
 pub struct RecentActivity {
-    pub top_contributors: Vec<(String, usize)>,
+    pub top_contributors: Vec<Contributor>,
     pub total_commits_last_7_days: usize,
 }
 
@@ -192,8 +253,11 @@ pub fn get_recent_activity(path: &Path) -> Result<RecentActivity> {
         }
     }
 
-    let mut top_contributors: Vec<_> = contributors.into_iter().collect();
-    top_contributors.sort_by(|a, b| b.1.cmp(&a.1));
+    let mut top_contributors: Vec<Contributor> = contributors
+        .into_iter()
+        .map(|(name, commits)| Contributor { name, commits })
+        .collect();
+    top_contributors.sort_by(|a, b| b.commits.cmp(&a.commits));
     top_contributors.truncate(3);
 
     Ok(RecentActivity {
@@ -201,3 +265,38 @@ pub fn get_recent_activity(path: &Path) -> Result<RecentActivity> {
         total_commits_last_7_days: recent_commits,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_merged_allows_exactly_that_many_branches() {
+        assert_eq!(threshold_error(3, None), None);
+        assert_eq!(threshold_error(3, Some(3)), None);
+        assert_eq!(
+            threshold_error(4, Some(3)).as_deref(),
+            Some("4 merged branches remain, more than the maximum of 3")
+        );
+        assert!(threshold_error(1, Some(0)).is_some());
+    }
+
+    #[test]
+    fn report_matches_the_documented_schema() {
+        let report = CleanupReport {
+            target: "main".to_string(),
+            merged: vec!["done".to_string()],
+            deleted: Vec::new(),
+            max_merged: Some(0),
+        };
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::json!({
+                "target": "main",
+                "merged": ["done"],
+                "deleted": [],
+                "max_merged": 0
+            })
+        );
+    }
+}
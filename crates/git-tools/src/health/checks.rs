@@ -0,0 +1,438 @@
+//! Built-in checks: the generic list every project gets, plus one pack per
+//! ecosystem that is enabled when its manifest is detected.
+
+use cli_core::config::{Ecosystem, Severity};
+use git2::{Repository, StatusOptions};
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+
+use super::HealthCheck;
+
+/// Directories never searched by the file-walking checks.
+const SKIP_DIRS: &[&str] = &[".git", "target", "node_modules", ".venv", "vendor", ".gemini"];
+
+fn walk(path: &Path) -> impl Iterator<Item = walkdir::DirEntry> {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_entry(|e| !e.file_name().to_str().map(|s| SKIP_DIRS.contains(&s)).unwrap_or(false))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+}
+
+fn any_exists(p: &Path, files: &[&str]) -> bool {
+    files.iter().any(|f| p.join(f).exists())
+}
+
+fn file_contains(p: &Path, file: &str, needles: &[&str]) -> bool {
+    fs::read_to_string(p.join(file))
+        .map(|content| needles.iter().any(|n| content.contains(n)))
+        .unwrap_or(false)
+}
+
+pub(super) fn generic() -> Vec<HealthCheck> {
+    vec![
+        // 1. Documentation
+        HealthCheck::new(
+            "README",
+            "Project documentation",
+            "Create a README.md file to document your project.",
+            |p| any_exists(p, &["README.md", "README"]),
+        )
+        .weight(2.0),
+        HealthCheck::new(
+            "CONTRIBUTING",
+            "Contribution guidelines",
+            "Add CONTRIBUTING.md to help others contribute to your project.",
+            |p| any_exists(p, &["CONTRIBUTING.md", "CONTRIBUTING"]),
+        ),
+        HealthCheck::new(
+            "CHANGELOG",
+            "Project history and changes",
+            "Keep a CHANGELOG.md to track changes between versions.",
+            |p| any_exists(p, &["CHANGELOG.md", "CHANGELOG", "HISTORY.md"]),
+        ),
+        HealthCheck::new(
+            "GEMINI.md",
+            "Gemini CLI instructions",
+            "Add GEMINI.md with project-specific instructions for the agent.",
+            |p| p.join("GEMINI.md").exists(),
+        )
+        .optional(),
+        // 2. Legal & Security
+        HealthCheck::new(
+            "LICENSE",
+            "Legal license file",
+            "Add a LICENSE file to define how others can use your code.",
+            |p| any_exists(p, &["LICENSE", "LICENSE.md", "LICENSE.txt"]),
+        )
+        .weight(2.0),
+        HealthCheck::new(
+            "SECURITY",
+            "Security policy",
+            "Add SECURITY.md to explain how to report vulnerabilities.",
+            |p| p.join("SECURITY.md").exists(),
+        ),
+        HealthCheck::new(
+            "CODEOWNERS",
+            "Code ownership definitions",
+            "Define CODEOWNERS to manage pull request reviews effectively.",
+            |p| any_exists(p, &["CODEOWNERS", ".github/CODEOWNERS"]),
+        ),
+        // 3. Git Hygiene
+        HealthCheck::new(
+            ".gitignore",
+            "Git exclusion rules",
+            "Create a .gitignore file to avoid committing unnecessary files.",
+            |p| p.join(".gitignore").exists(),
+        )
+        .weight(2.0),
+        HealthCheck::new(
+            "Clean Repo",
+            "No uncommitted changes",
+            "Commit or stash your current changes for a clean state.",
+            |p| {
+                if let Ok(repo) = Repository::discover(p) {
+                    if let Ok(statuses) = repo.statuses(None) {
+                        return statuses.is_empty();
+                    }
+                }
+                true
+            },
+        ),
+        // 4. Standards
+        HealthCheck::new(
+            ".editorconfig",
+            "Consistent coding styles",
+            "Add .editorconfig to maintain consistent styles across different editors.",
+            |p| p.join(".editorconfig").exists(),
+        ),
+        // 5. Structure & Manifests
+        HealthCheck::new(
+            "Manifest File",
+            "Dependency management file",
+            "Ensure your project has a standard manifest file (e.g., Cargo.toml).",
+            |p| !Ecosystem::detect(p).is_empty(),
+        ),
+        // 6. CI/CD
+        HealthCheck::new(
+            "CI Configuration",
+            "Continuous Integration setup",
+            "Setup CI (e.g., GitHub Actions) to automate testing and deployment.",
+            |p| any_exists(p, &[".github", ".gitlab-ci.yml", "circleci", ".travis.yml"]),
+        ),
+        // 7. Security
+        HealthCheck::new(
+            "Large Files",
+            "No files larger than 50MB",
+            "Remove large files from Git or use Git LFS.",
+            |p| {
+                !walk(p).any(|entry| {
+                    entry
+                        .metadata()
+                        .map(|metadata| metadata.len() > 50 * 1024 * 1024)
+                        .unwrap_or(false)
+                })
+            },
+        ),
+        HealthCheck::new(
+            "Tracked Secrets",
+            "No sensitive files tracked in Git",
+            "Stop tracking sensitive files (git rm --cached) and add them to .gitignore.",
+            |p| {
+                if let Ok(repo) = Repository::discover(p) {
+                    let mut opts = StatusOptions::new();
+                    opts.include_untracked(false);
+                    if let Ok(statuses) = repo.statuses(Some(&mut opts)) {
+                        for entry in statuses.iter() {
+                            if let Some(path) = entry.path() {
+                                if path.contains(".env") || path.contains("id_rsa") || path.contains(".pem") {
+                                    return false;
+                                }
+                            }
+                        }
+                    }
+                }
+                true
+            },
+        )
+        .weight(2.0),
+        HealthCheck::new(
+            "Vuln Scanning",
+            "Security vulnerability scanning in CI",
+            "Add vulnerability scanning (e.g., cargo-audit, npm audit, pip-audit, govulncheck) to your CI workflow.",
+            |p| {
+                if any_exists(p, &[".github/dependabot.yml", ".github/dependabot.yaml"]) {
+                    return true;
+                }
+                let scanners = ["audit", "security-events", "snyk", "govulncheck"];
+                fs::read_dir(p.join(".github").join("workflows"))
+                    .map(|entries| {
+                        entries.flatten().any(|entry| {
+                            fs::read_to_string(entry.path())
+                                .map(|content| scanners.iter().any(|s| content.contains(s)))
+                                .unwrap_or(false)
+                        })
+                    })
+                    .unwrap_or(false)
+            },
+        ),
+        // 8. Automation
+        HealthCheck::new(
+            "Automation",
+            "Task runner (Makefile, Justfile)",
+            "Add a Makefile or Justfile to automate common tasks.",
+            |p| any_exists(p, &["Makefile", "Justfile", "justfile"]),
+        )
+        .optional(),
+        HealthCheck::new(
+            "Pre-commit Hooks",
+            "Pre-commit hook configuration",
+            "Use pre-commit or husky to run checks before every commit.",
+            |p| any_exists(p, &[".pre-commit-config.yaml", ".husky"]),
+        ),
+        // 9. Containerization
+        HealthCheck::new(
+            "Docker",
+            "Containerization configuration",
+            "Consider adding a Dockerfile for consistent development and deployment environments.",
+            |p| any_exists(p, &["Dockerfile", "docker-compose.yml"]),
+        )
+        .optional(),
+        // 10. Configuration
+        HealthCheck::new(
+            "Registry Config",
+            "Registry or dependency configuration",
+            "Consider adding registry configuration (e.g., .npmrc) for shared dependency settings.",
+            |p| any_exists(p, &[".npmrc", ".cargo/config.toml", ".yarnrc.yml", "pip.conf"]),
+        )
+        .optional(),
+        // 11. Testing
+        HealthCheck::new(
+            "Tests",
+            "Test directory presence",
+            "Create a tests/ directory to organize your integration tests.",
+            |p| any_exists(p, &["tests", "test", "spec"]),
+        ),
+        // 12. Advanced Documentation
+        HealthCheck::new(
+            "Examples",
+            "Usage examples",
+            "Add an examples/ directory to demonstrate how to use your code.",
+            |p| any_exists(p, &["examples", "samples"]),
+        )
+        .optional(),
+        HealthCheck::new(
+            "Extended Docs",
+            "Dedicated documentation directory",
+            "Consider adding a docs/ directory for in-depth documentation.",
+            |p| any_exists(p, &["docs", "documentation"]),
+        )
+        .optional(),
+        // 13. Housekeeping
+        HealthCheck::new(
+            "Pending Tasks",
+            "Check for TODO or FIXME comments",
+            "Address outstanding TODO or FIXME comments in your code.",
+            |p| {
+                let extensions = ["rs", "md", "toml", "js", "jsx", "ts", "tsx", "py", "go"];
+                !walk(p).any(|entry| {
+                    let source = entry
+                        .path()
+                        .extension()
+                        .and_then(|ext| ext.to_str())
+                        .map(|ext| extensions.contains(&ext))
+                        .unwrap_or(false);
+                    source
+                        && fs::read_to_string(entry.path())
+                            .map(|content| content.contains("TODO") || content.contains("FIXME"))
+                            .unwrap_or(false)
+                })
+            },
+        )
+        .optional(),
+    ]
+}
+
+/// Checks that only make sense for `ecosystem`.
+pub(super) fn pack(ecosystem: Ecosystem) -> Vec<HealthCheck> {
+    let checks = match ecosystem {
+        Ecosystem::Rust => rust(),
+        Ecosystem::Node => node(),
+        Ecosystem::Go => go(),
+        Ecosystem::Python => python(),
+    };
+    checks.into_iter().map(|check| check.pack(ecosystem)).collect()
+}
+
+fn rust() -> Vec<HealthCheck> {
+    vec![
+        HealthCheck::new(
+            "Rust Lock File",
+            "Cargo.lock for deterministic builds",
+            "Commit Cargo.lock to ensure deterministic builds.",
+            |p| p.join("Cargo.lock").exists(),
+        ),
+        HealthCheck::new(
+            "Rust Toolchain",
+            "Rust toolchain configuration",
+            "Add rust-toolchain.toml to pin the Rust version for all contributors.",
+            |p| any_exists(p, &["rust-toolchain", "rust-toolchain.toml"]),
+        ),
+        HealthCheck::new(
+            "Rustfmt",
+            "Rust formatting configuration",
+            "Add rustfmt.toml to enforce consistent code style.",
+            |p| any_exists(p, &[".rustfmt.toml", "rustfmt.toml"]),
+        ),
+        HealthCheck::new(
+            "Clippy",
+            "Rust linting configuration",
+            "Add clippy.toml to configure custom lints for your project.",
+            |p| any_exists(p, &[".clippy.toml", "clippy.toml"]),
+        ),
+        HealthCheck::new(
+            "Crate Metadata",
+            "Rich Cargo.toml metadata",
+            "Add keywords, categories, and repository fields to your Cargo.toml.",
+            |p| {
+                fs::read_to_string(p.join("Cargo.toml"))
+                    .map(|content| {
+                        content.contains("keywords =")
+                            && content.contains("categories =")
+                            && content.contains("repository =")
+                    })
+                    .unwrap_or(false)
+            },
+        )
+        .severity(Severity::Info),
+    ]
+}
+
+fn node() -> Vec<HealthCheck> {
+    vec![
+        HealthCheck::new(
+            "Node Lock File",
+            "npm, Yarn, pnpm, or Bun lock file",
+            "Commit your lock file (e.g., package-lock.json) to ensure deterministic installs.",
+            |p| any_exists(p, &["package-lock.json", "yarn.lock", "pnpm-lock.yaml", "bun.lockb", "bun.lock"]),
+        ),
+        HealthCheck::new(
+            "Node Version",
+            "Pinned Node.js version",
+            "Add .nvmrc or an `engines.node` field to package.json.",
+            |p| any_exists(p, &[".nvmrc", ".node-version"]) || file_contains(p, "package.json", &["\"engines\""]),
+        ),
+        HealthCheck::new(
+            "ESLint",
+            "JavaScript/TypeScript linting configuration",
+            "Add an ESLint configuration (eslint.config.js) to catch bugs early.",
+            |p| {
+                any_exists(p, &[
+                    "eslint.config.js",
+                    "eslint.config.mjs",
+                    "eslint.config.cjs",
+                    "eslint.config.ts",
+                    ".eslintrc",
+                    ".eslintrc.js",
+                    ".eslintrc.cjs",
+                    ".eslintrc.json",
+                    ".eslintrc.yml",
+                    "biome.json",
+                ]) || file_contains(p, "package.json", &["\"eslintConfig\""])
+            },
+        ),
+        HealthCheck::new(
+            "Prettier",
+            "JavaScript/TypeScript formatting configuration",
+            "Add a Prettier configuration (.prettierrc) to enforce consistent code style.",
+            |p| {
+                any_exists(p, &[
+                    ".prettierrc",
+                    ".prettierrc.json",
+                    ".prettierrc.yml",
+                    ".prettierrc.js",
+                    "prettier.config.js",
+                    "prettier.config.mjs",
+                    "biome.json",
+                ]) || file_contains(p, "package.json", &["\"prettier\""])
+            },
+        ),
+        HealthCheck::new(
+            "Test Script",
+            "`npm test` is defined",
+            "Add a `test` script to package.json.",
+            |p| {
+                fs::read_to_string(p.join("package.json"))
+                    .ok()
+                    .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+                    .and_then(|package| package.get("scripts")?.get("test").cloned())
+                    .is_some()
+            },
+        ),
+    ]
+}
+
+fn python() -> Vec<HealthCheck> {
+    vec![
+        HealthCheck::new(
+            "Python Lock File",
+            "Locked dependency versions",
+            "Commit a lock file (uv.lock, poetry.lock, Pipfile.lock) to ensure deterministic installs.",
+            |p| any_exists(p, &["uv.lock", "poetry.lock", "Pipfile.lock", "pdm.lock", "requirements.lock"]),
+        ),
+        HealthCheck::new(
+            "Python Version",
+            "Pinned Python version",
+            "Add .python-version or `requires-python` to pyproject.toml.",
+            |p| p.join(".python-version").exists() || file_contains(p, "pyproject.toml", &["requires-python"]),
+        ),
+        HealthCheck::new(
+            "Python Linter",
+            "Ruff or Flake8 configuration",
+            "Configure a linter such as Ruff (ruff.toml or [tool.ruff]).",
+            |p| {
+                any_exists(p, &["ruff.toml", ".ruff.toml", ".flake8"])
+                    || file_contains(p, "pyproject.toml", &["[tool.ruff", "[tool.flake8"])
+                    || file_contains(p, "setup.cfg", &["[flake8]"])
+            },
+        ),
+        HealthCheck::new(
+            "Type Checking",
+            "mypy or Pyright configuration",
+            "Configure a type checker such as mypy (mypy.ini or [tool.mypy]).",
+            |p| {
+                any_exists(p, &["mypy.ini", ".mypy.ini", "pyrightconfig.json"])
+                    || file_contains(p, "pyproject.toml", &["[tool.mypy", "[tool.pyright"])
+            },
+        )
+        .severity(Severity::Info),
+    ]
+}
+
+fn go() -> Vec<HealthCheck> {
+    vec![
+        HealthCheck::new(
+            "Go Lock File",
+            "go.sum for verified module downloads",
+            "Commit go.sum (run `go mod tidy`).",
+            |p| p.join("go.sum").exists(),
+        ),
+        HealthCheck::new(
+            "Go Version",
+            "go directive in go.mod",
+            "Declare the Go version with a `go 1.xx` line in go.mod.",
+            |p| {
+                fs::read_to_string(p.join("go.mod"))
+                    .map(|content| content.lines().any(|line| line.trim_start().starts_with("go ")))
+                    .unwrap_or(false)
+            },
+        ),
+        HealthCheck::new(
+            "golangci-lint",
+            "Go linting configuration",
+            "Add .golangci.yml to run a consistent set of linters.",
+            |p| any_exists(p, &[".golangci.yml", ".golangci.yaml", ".golangci.toml", ".golangci.json"]),
+        ),
+    ]
+}
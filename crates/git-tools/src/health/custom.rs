@@ -0,0 +1,184 @@
+//! Checks declared as `[[git_tools.health.checks]]` in configuration.
+
+use anyhow::{bail, Context, Result};
+use cli_core::config::{CheckRule, CustomCheck};
+use globset::{GlobBuilder, GlobMatcher};
+use ignore::WalkBuilder;
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+
+use super::{HealthCheck, Rule};
+
+pub(super) fn build(check: &CustomCheck) -> Result<HealthCheck> {
+    // A zero, negative or NaN weight would skew or poison the whole score.
+    if !(check.weight.is_finite() && check.weight > 0.0) {
+        bail!(
+            "Invalid weight {} in health check \"{}\": expected a positive number",
+            check.weight,
+            check.name
+        );
+    }
+
+    let (description, advice, rule): (String, String, Rule) = match &check.rule {
+        CheckRule::FileExists { file_exists } => {
+            let file = file_exists.clone();
+            (
+                format!("{} exists", file_exists),
+                format!("Create {}.", file_exists),
+                Box::new(move |p| p.join(&file).exists()),
+            )
+        }
+        CheckRule::GlobAbsent { glob_absent } => {
+            let matcher = GlobBuilder::new(glob_absent)
+                .literal_separator(true)
+                .build()
+                .with_context(|| format!("Invalid glob in health check \"{}\"", check.name))?
+                .compile_matcher();
+            (
+                format!("No files match {}", glob_absent),
+                format!("Remove the files matching {}.", glob_absent),
+                Box::new(move |p| !any_file_matches(p, &matcher)),
+            )
+        }
+        CheckRule::RegexPresent { file, regex } => {
+            let pattern = Regex::new(regex)
+                .with_context(|| format!("Invalid regex in health check \"{}\"", check.name))?;
+            let target = file.clone();
+            (
+                format!("{} matches /{}/", file, regex),
+                format!("Update {} to match /{}/.", file, regex),
+                Box::new(move |p| {
+                    fs::read_to_string(p.join(&target))
+                        .map(|content| pattern.is_match(&content))
+                        .unwrap_or(false)
+                }),
+            )
+        }
+    };
+
+    Ok(HealthCheck::new(
+        &check.name,
+        check.description.as_deref().unwrap_or(&description),
+        check.advice.as_deref().unwrap_or(&advice),
+        rule,
+    )
+    .severity(check.severity)
+    .weight(check.weight))
+}
+
+/// Whether any non-ignored file under `root` matches, relative to `root`.
+fn any_file_matches(root: &Path, matcher: &GlobMatcher) -> bool {
+    WalkBuilder::new(root)
+        .hidden(false)
+        .filter_entry(|e| e.file_name() != ".git")
+        .build()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().map(|t| t.is_file()).unwrap_or(false))
+        .any(|e| {
+            e.path()
+                .strip_prefix(root)
+                .map(|relative| matcher.is_match(relative))
+                .unwrap_or(false)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cli_core::config::{HealthConfig, Severity};
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "git-tools-health-{}-{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn custom(name: &str, weight: f64, rule: CheckRule) -> CustomCheck {
+        CustomCheck {
+            name: name.to_string(),
+            description: None,
+            advice: None,
+            severity: Severity::Error,
+            weight,
+            rule,
+        }
+    }
+
+    #[test]
+    fn custom_checks_are_evaluated_with_their_weight() {
+        let dir = temp_dir();
+        fs::write(dir.join("CHANGELOG.md"), "## 1.0.0\n").unwrap();
+        fs::create_dir_all(dir.join("keys")).unwrap();
+        fs::write(dir.join("keys/server.pem"), "secret").unwrap();
+        let config = HealthConfig {
+            checks: vec![
+                custom(
+                    "Changelog",
+                    3.0,
+                    CheckRule::FileExists {
+                        file_exists: "CHANGELOG.md".to_string(),
+                    },
+                ),
+                custom(
+                    "Versioned",
+                    2.0,
+                    CheckRule::RegexPresent {
+                        file: "CHANGELOG.md".to_string(),
+                        regex: r"## \d+\.\d+\.\d+".to_string(),
+                    },
+                ),
+                custom(
+                    "No keys",
+                    0.5,
+                    CheckRule::GlobAbsent {
+                        glob_absent: "**/*.pem".to_string(),
+                    },
+                ),
+            ],
+            ..HealthConfig::default()
+        };
+
+        let report = super::super::evaluate(&dir, &config).unwrap();
+        let result = |name: &str| {
+            let check = report.checks.iter().find(|c| c.name == name).unwrap();
+            (check.passed, check.weight, check.severity)
+        };
+        assert_eq!(result("Changelog"), (true, 3.0, Severity::Error));
+        assert_eq!(result("Versioned"), (true, 2.0, Severity::Error));
+        assert_eq!(result("No keys"), (false, 0.5, Severity::Error));
+        assert_eq!(
+            report.threshold_error().as_deref(),
+            Some("Failed error-severity health checks: No keys")
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn weights_must_be_positive_and_finite() {
+        let rule = CheckRule::FileExists {
+            file_exists: "README.md".to_string(),
+        };
+        for weight in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let error = build(&custom("Readme", weight, rule.clone()))
+                .err()
+                .unwrap();
+            assert_eq!(
+                error.to_string(),
+                format!(
+                    "Invalid weight {weight} in health check \"Readme\": expected a positive number"
+                )
+            );
+        }
+        assert!(build(&custom("Readme", 0.25, rule)).is_ok());
+    }
+}
@@ -0,0 +1,524 @@
+use anyhow::{bail, Context, Result};
+use chrono::{Datelike, Local};
+use cli_core::output::OutputFormat;
+use cli_core::scaffold::{gitignore_template, Ecosystem, LicenseType, ProjectMetadata};
+use cli_core::ui::Theme;
+use std::fs;
+use std::path::Path;
+
+use super::HealthReport;
+use crate::prompt::prompt_bool;
+
+pub struct FixOptions {
+    /// Only list the files that would be created.
+    pub dry_run: bool,
+    /// Create every file without asking.
+    pub yes: bool,
+}
+
+/// A starter file that makes a failing check pass.
+struct Scaffold {
+    path: &'static str,
+    render: fn(&Project) -> Result<String>,
+}
+
+/// Everything the templates are filled in from.
+struct Project {
+    name: String,
+    metadata: ProjectMetadata,
+    holder: String,
+    year: i32,
+}
+
+impl Project {
+    fn load(path: &Path) -> Self {
+        let metadata = ProjectMetadata::load(path);
+        let name = metadata.project_name(path);
+        let holder = metadata
+            .holder()
+            .or_else(|| git_user_name(path))
+            .unwrap_or_else(|| format!("The {} authors", name));
+        Self {
+            name,
+            metadata,
+            holder,
+            year: Local::now().year(),
+        }
+    }
+
+    fn is(&self, ecosystem: Ecosystem) -> bool {
+        self.metadata.ecosystems.contains(&ecosystem)
+    }
+}
+
+fn git_user_name(path: &Path) -> Option<String> {
+    let repo = git2::Repository::discover(path).ok()?;
+    repo.config().ok()?.get_string("user.name").ok()
+}
+
+fn scaffold_for(check: &str) -> Option<Scaffold> {
+    let (path, render): (&'static str, fn(&Project) -> Result<String>) = match check {
+        "README" => ("README.md", readme),
+        "CONTRIBUTING" => ("CONTRIBUTING.md", contributing),
+        "CHANGELOG" => ("CHANGELOG.md", changelog),
+        "LICENSE" => ("LICENSE", license),
+        "SECURITY" => ("SECURITY.md", security),
+        "CODEOWNERS" => (".github/CODEOWNERS", codeowners),
+        ".gitignore" => (".gitignore", gitignore),
+        ".editorconfig" => (".editorconfig", editorconfig),
+        "Rust Toolchain" => ("rust-toolchain.toml", rust_toolchain),
+        "Rustfmt" => ("rustfmt.toml", rustfmt),
+        "Clippy" => ("clippy.toml", clippy),
+        "Pre-commit Hooks" => (".pre-commit-config.yaml", pre_commit),
+        _ => return None,
+    };
+    Some(Scaffold { path, render })
+}
+
+/// Create starter files for the failing checks in `report`; returns how many were written.
+pub fn apply(path: &Path, report: &HealthReport, options: &FixOptions, format: OutputFormat) -> Result<usize> {
+    if !format.is_table() && !options.dry_run && !options.yes {
+        bail!("--fix with a structured --format needs --yes or --dry-run");
+    }
+
+    let say = |message: String| {
+        if format.is_table() {
+            println!("{}", message);
+        } else {
+            eprintln!("{}", message);
+        }
+    };
+
+    let project = Project::load(path);
+    let pending: Vec<_> = report
+        .checks
+        .iter()
+        .filter(|check| !check.passed)
+        .filter_map(|check| scaffold_for(&check.name).map(|scaffold| (check, scaffold)))
+        .filter(|(_, scaffold)| !path.join(scaffold.path).exists())
+        .collect();
+
+    if pending.is_empty() {
+        say(Theme::info("Nothing to scaffold: no failing check has a starter file."));
+        return Ok(0);
+    }
+
+    let mut created = 0;
+    for (check, scaffold) in pending {
+        let contents = match (scaffold.render)(&project) {
+            Ok(contents) => contents,
+            Err(e) => {
+                say(format!("  {} {:<24} {}", Theme::warning("Skip"), scaffold.path, Theme::dim(e.to_string())));
+                continue;
+            }
+        };
+
+        if options.dry_run {
+            say(format!(
+                "  {} {:<24} {}",
+                Theme::info("Would create"),
+                scaffold.path,
+                Theme::dim(format!("{} lines, fixes {}", contents.lines().count(), check.name))
+            ));
+            continue;
+        }
+
+        if !options.yes && !prompt_bool(&format!("Create {} ({} check)?", scaffold.path, check.name))? {
+            continue;
+        }
+
+        let target = path.join(scaffold.path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::write(&target, contents)
+            .with_context(|| format!("Failed to write {}", target.display()))?;
+        say(format!("  {} {}", Theme::success("Created"), scaffold.path));
+        created += 1;
+    }
+
+    if format.is_table() {
+        println!();
+    }
+    Ok(created)
+}
+
+/// Build/test commands for the detected ecosystems.
+fn dev_commands(project: &Project) -> Vec<&'static str> {
+    let mut commands = Vec::new();
+    if project.is(Ecosystem::Rust) {
+        commands.extend([
+            "cargo build",
+            "cargo test",
+            "cargo fmt --all",
+            "cargo clippy --all-targets -- -D warnings",
+        ]);
+    }
+    if project.is(Ecosystem::Node) {
+        commands.extend(["npm install", "npm test"]);
+    }
+    if project.is(Ecosystem::Go) {
+        commands.extend(["go build ./...", "go test ./..."]);
+    }
+    if project.is(Ecosystem::Python) {
+        commands.extend(["pip install -e .", "pytest"]);
+    }
+    commands
+}
+
+fn install_command(project: &Project) -> Option<&'static str> {
+    match project.metadata.ecosystems.first()? {
+        Ecosystem::Rust => Some("cargo install --path ."),
+        Ecosystem::Node => Some("npm install"),
+        Ecosystem::Go => Some("go install ./..."),
+        Ecosystem::Python => Some("pip install ."),
+    }
+}
+
+fn readme(project: &Project) -> Result<String> {
+    let mut out = format!("# {}\n\n", project.name);
+    match &project.metadata.description {
+        Some(description) => out.push_str(&format!("{}\n", description)),
+        None => out.push_str(&format!("A short description of what {} does.\n", project.name)),
+    }
+    if let Some(install) = install_command(project) {
+        out.push_str(&format!("\n## Installation\n\n```bash\n{}\n```\n", install));
+    }
+    out.push_str(&format!("\n## Usage\n\nDescribe how to use {}.\n", project.name));
+    if let Some(license) = &project.metadata.license {
+        out.push_str(&format!("\n## License\n\nLicensed under {}.\n", license));
+    }
+    Ok(out)
+}
+
+fn contributing(project: &Project) -> Result<String> {
+    let mut out = format!(
+        "# Contributing to {}\n\nThanks for taking the time to contribute!\n",
+        project.name
+    );
+    let commands = dev_commands(project);
+    if !commands.is_empty() {
+        out.push_str(&format!(
+            "\n## Development\n\n```bash\n{}\n```\n",
+            commands.join("\n")
+        ));
+    }
+    out.push_str(
+        "\n## Pull Requests\n\n\
+         1. Create a branch from the default branch.\n\
+         2. Add tests for any change in behavior.\n\
+         3. Make sure the checks above pass.\n\
+         4. Open a pull request that explains the change and its motivation.\n",
+    );
+    Ok(out)
+}
+
+fn changelog(_project: &Project) -> Result<String> {
+    Ok("# Changelog\n\n\
+        All notable changes to this project will be documented in this file.\n\n\
+        The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/),\n\
+        and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).\n\n\
+        ## [Unreleased]\n"
+        .to_string())
+}
+
+fn license(project: &Project) -> Result<String> {
+    let declared = project
+        .metadata
+        .license
+        .as_deref()
+        .context("no `license` declared in Cargo.toml or package.json")?;
+    let license = LicenseType::from_expression(declared)
+        .with_context(|| format!("no built-in text for license \"{}\"", declared))?;
+    let mut text = license.text(project.year, &project.holder);
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+fn security(project: &Project) -> Result<String> {
+    let contact = match (&project.metadata.contact_email(), project.metadata.github_owner()) {
+        (Some(email), _) => format!("email {}", email),
+        (None, Some(_)) => "use GitHub's private vulnerability reporting".to_string(),
+        (None, None) => "contact the maintainers privately".to_string(),
+    };
+    Ok(format!(
+        "# Security Policy\n\n\
+         ## Reporting a Vulnerability\n\n\
+         Please do not open public issues for security problems in {}. Instead, {}\n\
+         with a description of the issue and the steps to reproduce it.\n\n\
+         You should receive a response within a few days. Once the issue is confirmed,\n\
+         a fix will be released and the report credited unless you prefer otherwise.\n",
+        project.name, contact
+    ))
+}
+
+fn codeowners(project: &Project) -> Result<String> {
+    let owner = project
+        .metadata
+        .github_owner()
+        .context("no GitHub `repository` URL in Cargo.toml or package.json")?;
+    Ok(format!("# Default reviewers for every file\n* @{}\n", owner))
+}
+
+fn gitignore(project: &Project) -> Result<String> {
+    let mut targets: Vec<&str> = project
+        .metadata
+        .ecosystems
+        .iter()
+        .map(|e| e.gitignore_target())
+        .collect();
+    targets.extend(["macos", "windows", "linux"]);
+    gitignore_template(&targets).context("missing built-in .gitignore template")
+}
+
+fn editorconfig(_project: &Project) -> Result<String> {
+    Ok("root = true\n\n\
+        [*]\n\
+        charset = utf-8\n\
+        end_of_line = lf\n\
+        insert_final_newline = true\n\
+        trim_trailing_whitespace = true\n\
+        indent_style = space\n\
+        indent_size = 4\n\n\
+        [*.{json,yml,yaml}]\n\
+        indent_size = 2\n\n\
+        [*.md]\n\
+        trim_trailing_whitespace = false\n\n\
+        [Makefile]\n\
+        indent_style = tab\n"
+        .to_string())
+}
+
+fn require_rust(project: &Project) -> Result<()> {
+    if !project.is(Ecosystem::Rust) {
+        bail!("not a Rust project (no Cargo.toml)");
+    }
+    Ok(())
+}
+
+fn rust_toolchain(project: &Project) -> Result<String> {
+    require_rust(project)?;
+    let channel = project.metadata.rust_version.as_deref().unwrap_or("stable");
+    Ok(format!(
+        "[toolchain]\nchannel = \"{}\"\ncomponents = [\"rustfmt\", \"clippy\"]\n",
+        channel
+    ))
+}
+
+fn rustfmt(project: &Project) -> Result<String> {
+    require_rust(project)?;
+    let edition = project.metadata.edition.as_deref().unwrap_or("2021");
+    Ok(format!("edition = \"{}\"\n", edition))
+}
+
+fn clippy(project: &Project) -> Result<String> {
+    require_rust(project)?;
+    let mut out = "# https://doc.rust-lang.org/clippy/configuration.html\n".to_string();
+    if let Some(msrv) = &project.metadata.rust_version {
+        out.push_str(&format!("msrv = \"{}\"\n", msrv));
+    }
+    Ok(out)
+}
+
+fn pre_commit(project: &Project) -> Result<String> {
+    let mut out = "repos:\n  \
+        - repo: https://github.com/pre-commit/pre-commit-hooks\n    \
+        rev: v5.0.0\n    \
+        hooks:\n      \
+        - id: trailing-whitespace\n      \
+        - id: end-of-file-fixer\n      \
+        - id: check-yaml\n      \
+        - id: check-merge-conflict\n"
+        .to_string();
+    if project.is(Ecosystem::Rust) {
+        out.push_str(
+            "  - repo: local\n    \
+             hooks:\n      \
+             - id: cargo-fmt\n        \
+             name: cargo fmt\n        \
+             entry: cargo fmt --all -- --check\n        \
+             language: system\n        \
+             types: [rust]\n        \
+             pass_filenames: false\n      \
+             - id: cargo-clippy\n        \
+             name: cargo clippy\n        \
+             entry: cargo clippy --all-targets -- -D warnings\n        \
+             language: system\n        \
+             types: [rust]\n        \
+             pass_filenames: false\n",
+        );
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::health::CheckResult;
+    use cli_core::config::Severity;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+    /// A Rust project with a license and a GitHub repository, so every
+    /// scaffold below can be rendered.
+    fn project() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "git-tools-fix-{}-{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\nlicense = \"MIT\"\n\
+             repository = \"https://github.com/acme/demo\"\n",
+        )
+        .unwrap();
+        dir
+    }
+
+    /// A report in which every named check failed.
+    fn failing(names: &[&str]) -> HealthReport {
+        let checks: Vec<CheckResult> = names
+            .iter()
+            .map(|name| CheckResult {
+                name: name.to_string(),
+                description: String::new(),
+                passed: false,
+                severity: Severity::Warning,
+                weight: 1.0,
+                pack: None,
+                fix_advice: String::new(),
+            })
+            .collect();
+        HealthReport {
+            passed: 0,
+            total: checks.len(),
+            score: 0.0,
+            min_score: None,
+            packs: Vec::new(),
+            checks,
+        }
+    }
+
+    fn files(dir: &Path) -> Vec<String> {
+        let mut files: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        files
+    }
+
+    const CHECKS: [&str; 5] = ["README", "LICENSE", "CODEOWNERS", "CHANGELOG", "Custom"];
+
+    #[test]
+    fn dry_run_writes_nothing() {
+        let dir = project();
+        let options = FixOptions {
+            dry_run: true,
+            yes: false,
+        };
+        for format in [OutputFormat::Table, OutputFormat::Json] {
+            assert_eq!(apply(&dir, &failing(&CHECKS), &options, format).unwrap(), 0);
+        }
+        assert_eq!(files(&dir), ["Cargo.toml"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn structured_formats_need_yes() {
+        let dir = project();
+        let options = FixOptions {
+            dry_run: false,
+            yes: false,
+        };
+        for format in [
+            OutputFormat::Json,
+            OutputFormat::JsonPretty,
+            OutputFormat::Yaml,
+            OutputFormat::Ndjson,
+            OutputFormat::Csv,
+        ] {
+            let error = apply(&dir, &failing(&CHECKS), &options, format).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "--fix with a structured --format needs --yes or --dry-run"
+            );
+        }
+        assert_eq!(files(&dir), ["Cargo.toml"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn existing_files_are_left_alone() {
+        let dir = project();
+        fs::write(dir.join("LICENSE"), "Proprietary\n").unwrap();
+        fs::create_dir_all(dir.join(".github")).unwrap();
+        fs::write(dir.join(".github/CODEOWNERS"), "* @someone\n").unwrap();
+        let options = FixOptions {
+            dry_run: false,
+            yes: true,
+        };
+
+        let created = apply(&dir, &failing(&CHECKS), &options, OutputFormat::Json).unwrap();
+        assert_eq!(created, 2);
+        assert_eq!(
+            files(&dir),
+            [
+                ".github",
+                "CHANGELOG.md",
+                "Cargo.toml",
+                "LICENSE",
+                "README.md"
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("LICENSE")).unwrap(),
+            "Proprietary\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join(".github/CODEOWNERS")).unwrap(),
+            "* @someone\n"
+        );
+        assert!(fs::read_to_string(dir.join("README.md"))
+            .unwrap()
+            .starts_with("# demo\n"));
+
+        // With the files in place a second run has nothing left to do.
+        assert_eq!(
+            apply(&dir, &failing(&CHECKS), &options, OutputFormat::Json).unwrap(),
+            0
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn missing_files_are_created_from_the_project_metadata() {
+        let dir = project();
+        let options = FixOptions {
+            dry_run: false,
+            yes: true,
+        };
+
+        let report = failing(&["LICENSE", "CODEOWNERS"]);
+        assert_eq!(
+            apply(&dir, &report, &options, OutputFormat::Json).unwrap(),
+            2
+        );
+        assert!(fs::read_to_string(dir.join("LICENSE"))
+            .unwrap()
+            .starts_with("MIT License"));
+        assert_eq!(
+            fs::read_to_string(dir.join(".github/CODEOWNERS")).unwrap(),
+            "# Default reviewers for every file\n* @acme\n"
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
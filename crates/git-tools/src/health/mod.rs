@@ -1,325 +1,213 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
+use cli_core::config::{Ecosystem, HealthConfig, Severity};
+use cli_core::output::OutputFormat;
 use cli_core::ui::Theme;
+use serde::Serialize;
 use std::path::Path;
-use std::fs;
-use git2::{Repository, StatusOptions};
-use walkdir::WalkDir;
 
-#[derive(Clone)]
+mod checks;
+mod custom;
+mod fix;
+
+pub use fix::FixOptions;
+
+type Rule = Box<dyn Fn(&Path) -> bool>;
+
 struct HealthCheck {
     name: String,
     description: String,
-    check_fn: fn(&Path) -> bool,
     fix_advice: String,
+    severity: Severity,
+    /// Share of the score relative to other checks (default 1).
+    weight: f64,
+    /// Ecosystem pack the check belongs to; `None` for generic checks.
+    pack: Option<Ecosystem>,
+    rule: Rule,
 }
 
-fn get_all_checks() -> Vec<HealthCheck> {
-    vec![
-        // 1. Documentation
-        HealthCheck {
-            name: "README".to_string(),
-            description: "Project documentation".to_string(),
-            check_fn: |p| p.join("README.md").exists() || p.join("README").exists(),
-            fix_advice: "Create a README.md file to document your project.".to_string(),
-        },
-        HealthCheck {
-            name: "CONTRIBUTING".to_string(),
-            description: "Contribution guidelines".to_string(),
-            check_fn: |p| p.join("CONTRIBUTING.md").exists() || p.join("CONTRIBUTING").exists(),
-            fix_advice: "Add CONTRIBUTING.md to help others contribute to your project.".to_string(),
-        },
-        HealthCheck {
-            name: "CHANGELOG".to_string(),
-            description: "Project history and changes".to_string(),
-            check_fn: |p| p.join("CHANGELOG.md").exists() || p.join("CHANGELOG").exists() || p.join("HISTORY.md").exists(),
-            fix_advice: "Keep a CHANGELOG.md to track changes between versions.".to_string(),
-        },
-        HealthCheck {
-            name: "GEMINI.md".to_string(),
-            description: "Gemini CLI instructions".to_string(),
-            check_fn: |p| p.join("GEMINI.md").exists(),
-            fix_advice: "Add GEMINI.md with project-specific instructions for the agent.".to_string(),
-        },
-        // 2. Legal & Security
-        HealthCheck {
-            name: "LICENSE".to_string(),
-            description: "Legal license file".to_string(),
-            check_fn: |p| p.join("LICENSE").exists() || p.join("LICENSE.md").exists() || p.join("LICENSE.txt").exists(),
-            fix_advice: "Add a LICENSE file to define how others can use your code.".to_string(),
-        },
-        HealthCheck {
-            name: "SECURITY".to_string(),
-            description: "Security policy".to_string(),
-            check_fn: |p| p.join("SECURITY.md").exists(),
-            fix_advice: "Add SECURITY.md to explain how to report vulnerabilities.".to_string(),
-        },
-        HealthCheck {
-            name: "CODEOWNERS".to_string(),
-            description: "Code ownership definitions".to_string(),
-            check_fn: |p| p.join("CODEOWNERS").exists() || p.join(".github").join("CODEOWNERS").exists(),
-            fix_advice: "Define CODEOWNERS to manage pull request reviews effectively.".to_string(),
-        },
-        // 3. Git Hygiene
-        HealthCheck {
-            name: ".gitignore".to_string(),
-            description: "Git exclusion rules".to_string(),
-            check_fn: |p| p.join(".gitignore").exists(),
-            fix_advice: "Create a .gitignore file to avoid committing unnecessary files.".to_string(),
-        },
-        HealthCheck {
-            name: "Clean Repo".to_string(),
-            description: "No uncommitted changes".to_string(),
-            check_fn: |p| {
-                if let Ok(repo) = Repository::discover(p) {
-                    if let Ok(statuses) = repo.statuses(None) {
-                        return statuses.is_empty();
-                    }
-                }
-                true
-            },
-            fix_advice: "Commit or stash your current changes for a clean state.".to_string(),
-        },
-        // 4. Standards
-        HealthCheck {
-            name: ".editorconfig".to_string(),
-            description: "Consistent coding styles".to_string(),
-            check_fn: |p| p.join(".editorconfig").exists(),
-            fix_advice: "Add .editorconfig to maintain consistent styles across different editors.".to_string(),
-        },
-        HealthCheck {
-            name: "Rust Toolchain".to_string(),
-            description: "Rust toolchain configuration".to_string(),
-            check_fn: |p| {
-                if p.join("Cargo.toml").exists() {
-                    p.join("rust-toolchain").exists() || p.join("rust-toolchain.toml").exists()
-                } else {
-                    true
-                }
-            },
-            fix_advice: "Add rust-toolchain.toml to pin the Rust version for all contributors.".to_string(),
-        },
-        // 5. Structure & Manifests
-        HealthCheck {
-            name: "Manifest File".to_string(),
-            description: "Dependency management file".to_string(),
-            check_fn: |p| {
-                let manifests = ["Cargo.toml", "package.json", "go.mod", "requirements.txt", "pyproject.toml"];
-                manifests.iter().any(|f| p.join(f).exists())
-            },
-            fix_advice: "Ensure your project has a standard manifest file (e.g., Cargo.toml).".to_string(),
-        },
-        HealthCheck {
-            name: "Lock File".to_string(),
-            description: "Dependency lock file for deterministic builds".to_string(),
-            check_fn: |p| {
-                let locks = ["Cargo.lock", "package-lock.json", "yarn.lock", "pnpm-lock.yaml", "go.sum"];
-                locks.iter().any(|f| p.join(f).exists())
-            },
-            fix_advice: "Commit your lock file (e.g., Cargo.lock) to ensure deterministic builds.".to_string(),
-        },
-        // 6. CI/CD
-        HealthCheck {
-            name: "CI Configuration".to_string(),
-            description: "Continuous Integration setup".to_string(),
-            check_fn: |p| {
-                p.join(".github").exists() 
-                    || p.join(".gitlab-ci.yml").exists() 
-                    || p.join("circleci").exists()
-                    || p.join(".travis.yml").exists()
-            },
-            fix_advice: "Setup CI (e.g., GitHub Actions) to automate testing and deployment.".to_string(),
-        },
-        // 7. Code Quality
-        HealthCheck {
-            name: "Rustfmt".to_string(),
-            description: "Rust formatting configuration".to_string(),
-            check_fn: |p| p.join(".rustfmt.toml").exists() || p.join("rustfmt.toml").exists(),
-            fix_advice: "Add rustfmt.toml to enforce consistent code style.".to_string(),
-        },
-        HealthCheck {
-            name: "Clippy".to_string(),
-            description: "Rust linting configuration".to_string(),
-            check_fn: |p| p.join(".clippy.toml").exists() || p.join("clippy.toml").exists(),
-            fix_advice: "Add clippy.toml to configure custom lints for your project.".to_string(),
-        },
-        // 8. Security
-        HealthCheck {
-            name: "Large Files".to_string(),
-            description: "No files larger than 50MB".to_string(),
-            check_fn: |p| {
-                for entry in WalkDir::new(p)
-                    .into_iter()
-                    .filter_entry(|e| !e.file_name().to_str().map(|s| s == ".git" || s == "target").unwrap_or(false))
-                    .filter_map(|e| e.ok()) {
-                    if entry.file_type().is_file() {
-                        if let Ok(metadata) = entry.metadata() {
-                            if metadata.len() > 50 * 1024 * 1024 {
-                                return false;
-                            }
-                        }
-                    }
-                }
-                true
-            },
-            fix_advice: "Remove large files from Git or use Git LFS.".to_string(),
-        },
-        HealthCheck {
-            name: "Tracked Secrets".to_string(),
-            description: "No sensitive files tracked in Git".to_string(),
-            check_fn: |p| {
-                if let Ok(repo) = Repository::discover(p) {
-                    let mut opts = StatusOptions::new();
-                    opts.include_untracked(false);
-                    if let Ok(statuses) = repo.statuses(Some(&mut opts)) {
-                        for entry in statuses.iter() {
-                            if let Some(path) = entry.path() {
-                                if path.contains(".env") || path.contains("id_rsa") || path.contains(".pem") {
-                                    return false;
-                                }
-                            }
-                        }
-                    }
-                }
-                true
-            },
-            fix_advice: "Stop tracking sensitive files (git rm --cached) and add them to .gitignore.".to_string(),
-        },
-        // 9. Automation
-        HealthCheck {
-            name: "Automation".to_string(),
-            description: "Task runner (Makefile, Justfile)".to_string(),
-            check_fn: |p| p.join("Makefile").exists() || p.join("Justfile").exists() || p.join("justfile").exists(),
-            fix_advice: "Add a Makefile or Justfile to automate common tasks.".to_string(),
-        },
-        // 10. Containerization
-        HealthCheck {
-            name: "Docker".to_string(),
-            description: "Containerization configuration".to_string(),
-            check_fn: |p| p.join("Dockerfile").exists() || p.join("docker-compose.yml").exists(),
-            fix_advice: "Consider adding a Dockerfile for consistent development and deployment environments.".to_string(),
-        },
-        // 11. Configuration
-        HealthCheck {
-            name: "Registry Config".to_string(),
-            description: "Registry or dependency configuration".to_string(),
-            check_fn: |p| p.join(".npmrc").exists() || p.join(".cargo").join("config.toml").exists() || p.join(".yarnrc.yml").exists(),
-            fix_advice: "Consider adding registry configuration (e.g., .npmrc) for shared dependency settings.".to_string(),
-        },
-        // 12. Testing
-        HealthCheck {
-            name: "Tests".to_string(),
-            description: "Test directory presence".to_string(),
-            check_fn: |p| p.join("tests").exists() || p.join("test").exists() || p.join("spec").exists(),
-            fix_advice: "Create a tests/ directory to organize your integration tests.".to_string(),
-        },
-        // 13. Advanced Documentation
-        HealthCheck {
-            name: "Examples".to_string(),
-            description: "Usage examples".to_string(),
-            check_fn: |p| p.join("examples").exists() || p.join("samples").exists(),
-            fix_advice: "Add an examples/ directory to demonstrate how to use your code.".to_string(),
-        },
-        HealthCheck {
-            name: "Extended Docs".to_string(),
-            description: "Dedicated documentation directory".to_string(),
-            check_fn: |p| p.join("docs").exists() || p.join("documentation").exists(),
-            fix_advice: "Consider adding a docs/ directory for in-depth documentation.".to_string(),
-        },
-        // 14. Rust Specific
-        HealthCheck {
-            name: "Crate Metadata".to_string(),
-            description: "Rich Cargo.toml metadata".to_string(),
-            check_fn: |p| {
-                if let Ok(content) = fs::read_to_string(p.join("Cargo.toml")) {
-                    content.contains("keywords =") && content.contains("categories =") && content.contains("repository =")
-                } else {
-                    true
-                }
-            },
-            fix_advice: "Add keywords, categories, and repository fields to your Cargo.toml.".to_string(),
-        },
-        // 15. Modern Workflow
-        HealthCheck {
-            name: "Pre-commit Hooks".to_string(),
-            description: "Pre-commit hook configuration".to_string(),
-            check_fn: |p| p.join(".pre-commit-config.yaml").exists() || p.join(".husky").exists(),
-            fix_advice: "Use pre-commit or husky to run checks before every commit.".to_string(),
-        },
-        HealthCheck {
-            name: "Vuln Scanning".to_string(),
-            description: "Security vulnerability scanning in CI".to_string(),
-            check_fn: |p| {
-                let github_workflows = p.join(".github").join("workflows");
-                if let Ok(entries) = fs::read_dir(github_workflows) {
-                    for entry in entries.flatten() {
-                        if let Ok(content) = fs::read_to_string(entry.path()) {
-                            if content.contains("audit") || content.contains("security-events") || content.contains("snyk") {
-                                return true;
-                            }
-                        }
-                    }
-                }
-                false
-            },
-            fix_advice: "Add vulnerability scanning (e.g., cargo-audit) to your CI workflow.".to_string(),
-        },
-        HealthCheck {
-            name: "Pending Tasks".to_string(),
-            description: "Check for TODO or FIXME comments".to_string(),
-            check_fn: |p| {
-                let mut found = false;
-                for entry in WalkDir::new(p)
-                    .into_iter()
-                    .filter_entry(|e| !e.file_name().to_str().map(|s| s == ".git" || s == "target" || s == ".gemini").unwrap_or(false))
-                    .filter_map(|e| e.ok()) {
-                    if entry.file_type().is_file() {
-                        if let Some(ext) = entry.path().extension() {
-                            if ext == "rs" || ext == "md" || ext == "toml" {
-                                if let Ok(content) = fs::read_to_string(entry.path()) {
-                                    if content.contains("TODO") || content.contains("FIXME") {
-                                        found = true;
-                                        break;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-                !found
-            },
-            fix_advice: "Address outstanding TODO or FIXME comments in your code.".to_string(),
-        },
-    ]
+impl HealthCheck {
+    fn new(name: &str, description: &str, fix_advice: &str, rule: impl Fn(&Path) -> bool + 'static) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            fix_advice: fix_advice.to_string(),
+            severity: Severity::Warning,
+            weight: 1.0,
+            pack: None,
+            rule: Box::new(rule),
+        }
+    }
+
+    fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    fn weight(mut self, weight: f64) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    fn pack(mut self, ecosystem: Ecosystem) -> Self {
+        self.pack = Some(ecosystem);
+        self
+    }
+
+    /// Nice to have: informational and worth half a regular check.
+    fn optional(self) -> Self {
+        self.severity(Severity::Info).weight(0.5)
+    }
+}
+
+/// Ecosystem packs that apply: those detected in `path` plus `[git_tools.health] packs`.
+fn active_packs(path: &Path, config: &HealthConfig) -> Vec<Ecosystem> {
+    let detected = Ecosystem::detect(path);
+    Ecosystem::ALL
+        .into_iter()
+        .filter(|e| detected.contains(e) || config.packs.contains(e))
+        .collect()
+}
+
+/// Generic, pack, and configured checks, minus those listed in `[git_tools.health] skip`.
+fn active_checks(packs: &[Ecosystem], config: &HealthConfig) -> Result<Vec<HealthCheck>> {
+    let mut active = checks::generic();
+    for ecosystem in packs {
+        active.extend(checks::pack(*ecosystem));
+    }
+    for check in &config.checks {
+        active.push(custom::build(check)?);
+    }
+    active.retain(|check| !config.skip.iter().any(|name| name.eq_ignore_ascii_case(&check.name)));
+    Ok(active)
 }
 
-pub fn check(path: &Path, verbose: bool) -> Result<()> {
-    let checks = get_all_checks();
-    let mut results = Vec::new();
-    
-    for check in checks {
-        let passed = (check.check_fn)(path);
-        results.push((check, passed));
+/// Outcome of a single check.
+#[derive(Serialize)]
+pub struct CheckResult {
+    pub name: String,
+    pub description: String,
+    pub passed: bool,
+    pub severity: Severity,
+    pub weight: f64,
+    pub pack: Option<Ecosystem>,
+    pub fix_advice: String,
+}
+
+/// Every check result plus the overall score, as emitted by `health --format json`.
+#[derive(Serialize)]
+pub struct HealthReport {
+    pub passed: usize,
+    pub total: usize,
+    /// Weighted percentage of passing checks (0-100).
+    pub score: f64,
+    pub min_score: Option<f64>,
+    /// Ecosystem packs that contributed checks.
+    pub packs: Vec<Ecosystem>,
+    pub checks: Vec<CheckResult>,
+}
+
+impl HealthReport {
+    /// Error describing failed `error`-severity checks or the missed `min_score`, if any.
+    pub fn threshold_error(&self) -> Option<String> {
+        let errors: Vec<&str> = self
+            .checks
+            .iter()
+            .filter(|c| !c.passed && c.severity == Severity::Error)
+            .map(|c| c.name.as_str())
+            .collect();
+        if !errors.is_empty() {
+            return Some(format!("Failed error-severity health checks: {}", errors.join(", ")));
+        }
+
+        let min = self.min_score?;
+        (self.score < min).then(|| {
+            format!("Health score {:.1}% is below the minimum of {}%", self.score, min)
+        })
     }
+}
 
-    let passed_count = results.iter().filter(|(_, p)| *p).count();
-    let total_count = results.len();
+pub fn evaluate(path: &Path, config: &HealthConfig) -> Result<HealthReport> {
+    let packs = active_packs(path, config);
+    let checks: Vec<CheckResult> = active_checks(&packs, config)?
+        .into_iter()
+        .map(|check| CheckResult {
+            passed: (check.rule)(path),
+            name: check.name,
+            description: check.description,
+            severity: check.severity,
+            weight: check.weight,
+            pack: check.pack,
+            fix_advice: check.fix_advice,
+        })
+        .collect();
+
+    let passed = checks.iter().filter(|c| c.passed).count();
+    let total_weight: f64 = checks.iter().map(|c| c.weight).sum();
+    let passed_weight: f64 = checks.iter().filter(|c| c.passed).map(|c| c.weight).sum();
+    Ok(HealthReport {
+        passed,
+        total: checks.len(),
+        score: if total_weight > 0.0 { passed_weight / total_weight * 100.0 } else { 100.0 },
+        min_score: config.min_score,
+        packs,
+        checks,
+    })
+}
+
+pub fn check(
+    path: &Path,
+    verbose: bool,
+    config: &HealthConfig,
+    fix: Option<FixOptions>,
+    format: OutputFormat,
+) -> Result<()> {
+    let mut report = evaluate(path, config)?;
+
+    // Scaffold first so the report reflects the files that were just created.
+    if let Some(options) = fix {
+        if fix::apply(path, &report, &options, format)? > 0 {
+            report = evaluate(path, config)?;
+        }
+    }
+
+    match format {
+        OutputFormat::Table => print_report(&report, verbose),
+        OutputFormat::Csv | OutputFormat::Ndjson => format.print(&report.checks)?,
+        _ => format.print(&report)?,
+    }
+
+    if let Some(message) = report.threshold_error() {
+        bail!(message);
+    }
+    Ok(())
+}
+
+fn print_report(report: &HealthReport, verbose: bool) {
+    let passed_count = report.passed;
+    let total_count = report.total;
 
     println!(
-        "{} Score: {}/{}",
+        "{} Score: {}/{} ({:.0}%)",
         Theme::info("Overall Health:"),
         Theme::highlight(&passed_count.to_string()),
-        total_count
+        total_count,
+        report.score
     );
+    if !report.packs.is_empty() {
+        let packs: Vec<&str> = report.packs.iter().map(|p| p.name()).collect();
+        println!("{} {}", Theme::info("Packs:"), Theme::value(packs.join(", ")));
+    }
     println!();
 
-    for (check, passed) in &results {
-        let status = if *passed {
-            Theme::success("PASS")
-        } else {
-            Theme::error("FAIL")
+    for check in &report.checks {
+        let status = match (check.passed, check.severity) {
+            (true, _) => Theme::success("PASS"),
+            (false, Severity::Error) => Theme::error("FAIL"),
+            (false, Severity::Warning) => Theme::warning("WARN"),
+            (false, Severity::Info) => Theme::info("INFO"),
         };
 
-        if verbose || !*passed {
+        if verbose || !check.passed {
             println!(
                 "  [{}] {:<18} {}",
                 status,
@@ -327,32 +215,127 @@ pub fn check(path: &Path, verbose: bool) -> Result<()> {
                 Theme::dim(&check.description)
             );
             
-            if !*passed {
+            if !check.passed {
                 println!("       {}", Theme::warning(format!("Advice: {}", check.fix_advice)));
             }
         }
     }
 
+    println!();
     if passed_count == total_count {
-        println!();
         println!("{}", Theme::success("Perfect! Your project follows all industry standards and best practices."));
-    } else if passed_count > total_count * 3 / 4 {
-        println!();
+    } else if report.score > 75.0 {
         println!("{}", Theme::success("Great! Most essential files are present."));
-    } else if passed_count > total_count / 2 {
-        println!();
+    } else if report.score > 50.0 {
         println!("{}", Theme::warning("Good, but there's room for significant improvement."));
     } else {
-        println!();
         println!("{}", Theme::error("Your project is missing many essential files. Consider adding them."));
     }
-
-    Ok(())
 }
 
-pub fn get_score(path: &Path) -> (usize, usize) {
-    let checks = get_all_checks();
-    let total = checks.len();
-    let passed = checks.iter().filter(|c| (c.check_fn)(path)).count();
-    (passed, total)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(name: &str, passed: bool, severity: Severity) -> CheckResult {
+        CheckResult {
+            name: name.to_string(),
+            description: format!("{name} exists"),
+            passed,
+            severity,
+            weight: 1.0,
+            pack: None,
+            fix_advice: format!("Add {name}"),
+        }
+    }
+
+    fn report(score: f64, min_score: Option<f64>, checks: Vec<CheckResult>) -> HealthReport {
+        HealthReport {
+            passed: checks.iter().filter(|c| c.passed).count(),
+            total: checks.len(),
+            score,
+            min_score,
+            packs: Vec::new(),
+            checks,
+        }
+    }
+
+    #[test]
+    fn min_score_is_met_at_exactly_the_minimum() {
+        assert_eq!(report(75.0, Some(75.0), Vec::new()).threshold_error(), None);
+        assert_eq!(report(10.0, None, Vec::new()).threshold_error(), None);
+        assert_eq!(
+            report(74.9, Some(75.0), Vec::new())
+                .threshold_error()
+                .as_deref(),
+            Some("Health score 74.9% is below the minimum of 75%")
+        );
+    }
+
+    #[test]
+    fn failed_error_checks_miss_the_threshold_whatever_the_score() {
+        let checks = vec![
+            result("README", false, Severity::Warning),
+            result("LICENSE", true, Severity::Error),
+        ];
+        assert_eq!(report(50.0, None, checks).threshold_error(), None);
+
+        let checks = vec![
+            result("README", false, Severity::Error),
+            result("LICENSE", false, Severity::Error),
+            result("CI", true, Severity::Error),
+        ];
+        assert_eq!(
+            report(100.0, Some(0.0), checks)
+                .threshold_error()
+                .as_deref(),
+            Some("Failed error-severity health checks: README, LICENSE")
+        );
+    }
+
+    #[test]
+    fn report_matches_the_documented_schema() {
+        let mut report = report(
+            50.0,
+            Some(40.0),
+            vec![
+                result("README", true, Severity::Warning),
+                result("Cargo.lock", false, Severity::Info),
+            ],
+        );
+        report.packs = vec![Ecosystem::Rust];
+        report.checks[1].pack = Some(Ecosystem::Rust);
+        report.checks[1].weight = 0.5;
+
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::json!({
+                "passed": 1,
+                "total": 2,
+                "score": 50.0,
+                "min_score": 40.0,
+                "packs": ["rust"],
+                "checks": [
+                    {
+                        "name": "README",
+                        "description": "README exists",
+                        "passed": true,
+                        "severity": "warning",
+                        "weight": 1.0,
+                        "pack": null,
+                        "fix_advice": "Add README"
+                    },
+                    {
+                        "name": "Cargo.lock",
+                        "description": "Cargo.lock exists",
+                        "passed": false,
+                        "severity": "info",
+                        "weight": 0.5,
+                        "pack": "rust",
+                        "fix_advice": "Add Cargo.lock"
+                    }
+                ]
+            })
+        );
+    }
 }
@@ -6,11 +6,15 @@ mod changelog;
 mod commit;
 mod stats;
 mod pulse;
+mod prompt;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use cli_core::output::FormatArg;
 use cli_core::ui::Theme;
-use std::path::PathBuf;
+use cli_core::config::GitToolsConfig;
+use cli_core::{Config, LayeredConfig};
+use std::path::{Path, PathBuf};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -40,6 +44,13 @@ enum Commands {
         /// Target branch to check against (default: main or master)
         #[arg(short, long)]
         target: Option<String>,
+
+        /// Exit non-zero when more merged branches remain (overrides git_tools.cleanup.max_merged)
+        #[arg(long)]
+        max_merged: Option<usize>,
+
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Scan for TODOs, FIXMEs, and other markers
     Scan {
@@ -50,19 +61,58 @@ enum Commands {
         /// Include hidden files
         #[arg(long)]
         hidden: bool,
+
+        /// Exit non-zero when more markers are found (overrides git_tools.scan.max_markers)
+        #[arg(long)]
+        max_markers: Option<usize>,
+
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Check project health (README, LICENSE, etc.)
     Health {
         /// Show detailed check results
         #[arg(short, long)]
         verbose: bool,
+
+        /// Exit non-zero below this percentage of passing checks (overrides git_tools.health.min_score)
+        #[arg(long)]
+        min_score: Option<f64>,
+
+        /// Create starter files (README, LICENSE, .gitignore, ...) for failing checks
+        #[arg(long)]
+        fix: bool,
+
+        /// With --fix, list the files that would be created without writing them
+        #[arg(long, requires = "fix")]
+        dry_run: bool,
+
+        /// With --fix, create every file without prompting
+        #[arg(short, long, requires = "fix")]
+        yes: bool,
+
+        #[command(flatten)]
+        output: FormatArg,
     },
     /// Show project statistics (LOC, language breakdown)
-    Stats,
+    Stats {
+        #[command(flatten)]
+        output: FormatArg,
+    },
     /// High-level project pulse (Health + Scan Summary + Stats)
-    Pulse,
+    Pulse {
+        #[command(flatten)]
+        output: FormatArg,
+    },
     /// Validate .env file parity with .env.example
-    Env,
+    Env {
+        /// Exit non-zero when more keys are missing from .env (overrides git_tools.env.max_missing)
+        #[arg(long)]
+        max_missing: Option<usize>,
+
+        #[command(flatten)]
+        output: FormatArg,
+    },
     /// Generate a changelog from git history
     Changelog {
         /// Start from this ref (e.g., tag or branch)
@@ -85,34 +135,80 @@ enum Commands {
     Commit,
 }
 
+impl Commands {
+    /// Whether the command reads `[git_tools]` settings. The others never load
+    /// the configuration, so a broken file does not stop them.
+    fn uses_config(&self) -> bool {
+        !matches!(self, Self::Stats { .. } | Self::Changelog { .. } | Self::Commit)
+    }
+}
+
+/// Resolve the shared configuration for `path`, with threshold flags on top.
+fn load_config(path: &Path, command: &Commands) -> Result<Config> {
+    let mut layered = LayeredConfig::load(path)?;
+    match command {
+        Commands::Cleanup { max_merged: Some(max), .. } => {
+            layered.set_flag("git_tools.cleanup.max_merged", *max as i64);
+        }
+        Commands::Scan { max_markers: Some(max), .. } => {
+            layered.set_flag("git_tools.scan.max_markers", *max as i64);
+        }
+        Commands::Health { min_score: Some(score), .. } => {
+            layered.set_flag("git_tools.health.min_score", *score);
+        }
+        Commands::Env { max_missing: Some(max), .. } => {
+            layered.set_flag("git_tools.env.max_missing", *max as i64);
+        }
+        _ => {}
+    }
+    layered.config()
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    let config = match cli.command.uses_config() {
+        true => load_config(&cli.path, &cli.command)?.git_tools,
+        false => GitToolsConfig::default(),
+    };
 
     match cli.command {
-        Commands::Cleanup { force, target } => {
-            println!("{}", Theme::header("🧹 Git Branch Cleanup"));
-            git::cleanup(&cli.path, force, target.as_deref())?;
+        Commands::Cleanup { force, target, output: FormatArg { format }, .. } => {
+            if format.is_table() {
+                println!("{}", Theme::header("🧹 Git Branch Cleanup"));
+            }
+            git::cleanup(&cli.path, force, target.as_deref(), config.cleanup.max_merged, format)?;
         }
-        Commands::Scan { markers, hidden } => {
-            println!("{}", Theme::header("🔍 Marker Scanner"));
-            scanner::scan(&cli.path, markers, hidden).await?;
+        Commands::Scan { markers, hidden, output: FormatArg { format }, .. } => {
+            if format.is_table() {
+                println!("{}", Theme::header("🔍 Marker Scanner"));
+            }
+            scanner::scan(&cli.path, markers, hidden, config.scan.max_markers, format).await?;
         }
-        Commands::Health { verbose } => {
-            println!("{}", Theme::header("🏥 Project Health Check"));
-            health::check(&cli.path, verbose)?;
+        Commands::Health { verbose, fix, dry_run, yes, output: FormatArg { format }, .. } => {
+            if format.is_table() {
+                println!("{}", Theme::header("🏥 Project Health Check"));
+            }
+            let fix = fix.then_some(health::FixOptions { dry_run, yes });
+            health::check(&cli.path, verbose, &config.health, fix, format)?;
         }
-        Commands::Stats => {
-            println!("{}", Theme::header("📊 Project Statistics"));
-            stats::show(&cli.path).await?;
+        Commands::Stats { output: FormatArg { format } } => {
+            if format.is_table() {
+                println!("{}", Theme::header("📊 Project Statistics"));
+            }
+            stats::show(&cli.path, format).await?;
         }
-        Commands::Pulse => {
-            println!("{}", Theme::header("💓 Project Pulse Dashboard"));
-            pulse::show(&cli.path).await?;
+        Commands::Pulse { output: FormatArg { format } } => {
+            if format.is_table() {
+                println!("{}", Theme::header("💓 Project Pulse Dashboard"));
+            }
+            pulse::show(&cli.path, &config, format).await?;
         }
-        Commands::Env => {
-            println!("{}", Theme::header("🔐 .env Validator"));
-            env::check(&cli.path)?;
+        Commands::Env { output: FormatArg { format }, .. } => {
+            if format.is_table() {
+                println!("{}", Theme::header("🔐 .env Validator"));
+            }
+            env::check(&cli.path, config.env.max_missing, format)?;
         }
         Commands::Changelog { from, to, limit, format } => {
             println!("{}", Theme::header("📜 Changelog Generator"));
@@ -0,0 +1,46 @@
+use anyhow::Result;
+use cli_core::ui::Theme;
+use std::io::{self, Write};
+
+pub fn prompt(label: &str) -> Result<String> {
+    print!("{}: ", label);
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(input.trim().to_string())
+}
+
+pub fn prompt_required(label: &str) -> Result<String> {
+    loop {
+        let input = prompt(label)?;
+        if !input.is_empty() {
+            return Ok(input);
+        }
+        println!("{}", Theme::error("This field is required."));
+    }
+}
+
+pub fn prompt_range(label: &str, min: usize, max: usize) -> Result<usize> {
+    loop {
+        let input = prompt(&format!("{} ({}-{})", label, min, max))?;
+        if let Ok(val) = input.parse::<usize>() {
+            if val >= min && val <= max {
+                return Ok(val);
+            }
+        }
+        println!("{} Please enter a number between {} and {}.", Theme::error("Invalid input:"), min, max);
+    }
+}
+
+pub fn prompt_bool(label: &str) -> Result<bool> {
+    loop {
+        let input = prompt(&format!("{} (y/n)", label))?.to_lowercase();
+        if input == "y" || input == "yes" {
+            return Ok(true);
+        }
+        if input == "n" || input == "no" || input.is_empty() {
+            return Ok(false);
+        }
+        println!("{} Please enter 'y' or 'n'.", Theme::error("Invalid input:"));
+    }
+}
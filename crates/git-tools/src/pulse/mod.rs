@@ -1,21 +1,88 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
+use cli_core::config::GitToolsConfig;
+use cli_core::output::OutputFormat;
 use cli_core::ui::Theme;
+use serde::Serialize;
 use std::path::Path;
 use crate::{health, scanner, stats, git};
 use owo_colors::OwoColorize;
 
-pub async fn show(path: &Path) -> Result<()> {
+#[derive(Serialize)]
+struct HealthScore {
+    passed: usize,
+    total: usize,
+    score: f64,
+}
+
+#[derive(Serialize)]
+struct StatsSummary {
+    total_files: usize,
+    total_lines: usize,
+    primary_extension: Option<String>,
+}
+
+/// Condensed health, status, stats, markers, and activity, as emitted by `pulse --format json`.
+#[derive(Serialize)]
+struct PulseReport {
+    health: HealthScore,
+    git: Option<git::GitStatusSummary>,
+    stats: StatsSummary,
+    markers: Vec<scanner::MarkerCount>,
+    activity: Option<git::RecentActivity>,
+}
+
+pub async fn show(path: &Path, config: &GitToolsConfig, format: OutputFormat) -> Result<()> {
+    let health = health::evaluate(path, &config.health)?;
+    let project_stats = stats::get_stats(path).await?;
+
+    let mut sorted_exts: Vec<_> = project_stats.extension_counts.into_iter().collect();
+    sorted_exts.sort_by(|a, b| b.1.1.cmp(&a.1.1));
+
+    let report = PulseReport {
+        health: HealthScore {
+            passed: health.passed,
+            total: health.total,
+            score: health.score,
+        },
+        git: git::get_status_summary(path).ok(),
+        stats: StatsSummary {
+            total_files: project_stats.total_files,
+            total_lines: project_stats.total_lines,
+            primary_extension: sorted_exts.first().map(|(ext, _)| ext.clone()),
+        },
+        markers: scanner::get_summary(path, None, false).await?,
+        activity: git::get_recent_activity(path).ok(),
+    };
+
+    if format.is_table() {
+        let primary_lines = sorted_exts.first().map(|(_, (_, lines))| *lines).unwrap_or(0);
+        print_report(&report, primary_lines);
+    } else {
+        format.print(&report)?;
+    }
+
+    if let Some(message) = health.threshold_error() {
+        bail!(message);
+    }
+    let total_markers = report.markers.iter().map(|m| m.count).sum();
+    if let Some(message) = scanner::threshold_error(total_markers, config.scan.max_markers) {
+        bail!(message);
+    }
+    Ok(())
+}
+
+fn print_report(report: &PulseReport, primary_lines: usize) {
     // 1. Health Score
-    let (passed, total) = health::get_score(path);
+    let HealthScore { passed, total, .. } = report.health;
     println!("{} {}/{}", Theme::info("Health Score:"), Theme::highlight(&passed.to_string()), total);
-    
+
     let bar_width = 40;
     let filled = (passed as f32 / total as f32 * bar_width as f32) as usize;
     println!("  [{}{}]", "■".repeat(filled).bright_green(), "□".repeat(bar_width - filled).dimmed());
     println!();
 
     // 2. Git Status
-    if let Ok(status) = git::get_status_summary(path) {
+    if let Some(status) = &report.git {
         println!("{}", Theme::info("Git Status:"));
         println!("  Branch:   {}", Theme::highlight(&status.branch_name));
         print!("  Changes:  ");
@@ -30,48 +97,38 @@ pub async fn show(path: &Path) -> Result<()> {
     }
 
     // 3. Project Stats
-    let project_stats = stats::get_stats(path).await?;
     println!("{}", Theme::info("Project Stats:"));
-    println!("  Files:    {}", Theme::value(&project_stats.total_files.to_string()));
-    println!("  Lines:    {}", Theme::value(&project_stats.total_lines.to_string()));
-    
-    let mut sorted_exts: Vec<_> = project_stats.extension_counts.into_iter().collect();
-    sorted_exts.sort_by(|a, b| b.1.1.cmp(&a.1.1));
-    
-    if let Some((ext, (_, lines))) = sorted_exts.first() {
-        println!("  Primary:  {} ({} lines)", Theme::highlight(ext), lines);
+    println!("  Files:    {}", Theme::value(&report.stats.total_files.to_string()));
+    println!("  Lines:    {}", Theme::value(&report.stats.total_lines.to_string()));
+
+    if let Some(ext) = &report.stats.primary_extension {
+        println!("  Primary:  {} ({} lines)", Theme::highlight(ext), primary_lines);
     }
     println!();
 
     // 4. Marker Summary
-    let marker_summary = scanner::get_summary(path, None, false).await?;
     println!("{}", Theme::info("Code Markers:"));
-    if marker_summary.is_empty() {
+    if report.markers.is_empty() {
         println!("  {}", Theme::success("No markers found."));
     } else {
-        let mut sorted_markers: Vec<_> = marker_summary.into_iter().collect();
-        sorted_markers.sort_by(|a, b| b.1.cmp(&a.1));
-        
-        for (kind, count) in sorted_markers {
-            println!("  {:<10} {}", Theme::highlight(&kind), Theme::value(&count.to_string()));
+        for marker in &report.markers {
+            println!("  {:<10} {}", Theme::highlight(&marker.kind), Theme::value(&marker.count.to_string()));
         }
     }
     println!();
 
     // 5. Recent Activity
-    if let Ok(activity) = git::get_recent_activity(path) {
+    if let Some(activity) = &report.activity {
         println!("{}", Theme::info("Recent Activity (Last 7 Days):"));
         println!("  Commits:  {}", Theme::value(&activity.total_commits_last_7_days.to_string()));
-        
+
         if !activity.top_contributors.is_empty() {
             println!("  Top Contributors:");
-            for (name, count) in activity.top_contributors {
-                println!("    • {:<15} {}", name.dimmed(), Theme::value(&count.to_string()));
+            for contributor in &activity.top_contributors {
+                println!("    • {:<15} {}", contributor.name.dimmed(), Theme::value(&contributor.commits.to_string()));
             }
         }
     }
 
     println!("\n{}", Theme::success("Project pulse is steady. Keep up the good work!"));
-
-    Ok(())
 }
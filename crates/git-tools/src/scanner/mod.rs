@@ -1,79 +1,50 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
+use cli_core::output::OutputFormat;
 use cli_core::ui::Theme;
 use ignore::WalkBuilder;
 use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
-#[derive(Debug)]
-struct Marker {
-    kind: String,
-    content: String,
-    line: usize,
-    file: String,
+#[derive(Debug, Serialize)]
+pub struct Marker {
+    pub kind: String,
+    pub file: String,
+    pub line: usize,
+    pub content: String,
 }
 
-pub async fn get_summary(path: &Path, markers: Option<Vec<String>>, hidden: bool) -> Result<std::collections::HashMap<String, usize>> {
-    let default_markers = vec![
-        "TODO".to_string(),
-        "FIXME".to_string(),
-        "BUG".to_string(),
-        "HACK".to_string(),
-        "OPTIMIZE".to_string(),
-    ];
-    let active_markers = markers.unwrap_or(default_markers);
-    let pattern = format!(r"(?i)\b({})\b:?\s*(.*)", active_markers.join("|"));
-    let re = Regex::new(&pattern)?;
-
-    let mut summary = std::collections::HashMap::new();
-
-    let walker = WalkBuilder::new(path)
-        .hidden(!hidden)
-        .git_ignore(true)
-        .build();
-
-    for result in walker {
-        let entry = match result {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
-
-        if entry.file_type().map_or(false, |ft| ft.is_file()) {
-            let path = entry.path();
-            if let Ok(content) = fs::read_to_string(path) {
-                for line in content.lines() {
-                    if let Some(caps) = re.captures(line) {
-                        let kind = caps.get(1).unwrap().as_str().to_uppercase();
-                        *summary.entry(kind).or_insert(0) += 1;
-                    }
-                }
-            }
-        }
-    }
+#[derive(Debug, Serialize)]
+pub struct MarkerCount {
+    pub kind: String,
+    pub count: usize,
+}
 
-    Ok(summary)
+/// Every marker found plus per-kind counts, as emitted by `scan --format json`.
+#[derive(Serialize)]
+struct ScanReport {
+    total: usize,
+    max_markers: Option<usize>,
+    summary: Vec<MarkerCount>,
+    markers: Vec<Marker>,
 }
 
-pub async fn scan(path: &Path, markers: Option<Vec<String>>, hidden: bool) -> Result<()> {
-    let default_markers = vec![
-        "TODO".to_string(),
-        "FIXME".to_string(),
-        "BUG".to_string(),
-        "HACK".to_string(),
-        "OPTIMIZE".to_string(),
-    ];
-    let active_markers = markers.unwrap_or(default_markers);
+fn active_markers(markers: Option<Vec<String>>) -> Vec<String> {
+    markers.unwrap_or_else(|| {
+        ["TODO", "FIXME", "BUG", "HACK", "OPTIMIZE"]
+            .iter()
+            .map(|m| m.to_string())
+            .collect()
+    })
+}
 
+fn collect_markers(path: &Path, active_markers: &[String], hidden: bool) -> Result<Vec<Marker>> {
     // Create a regex to match markers
     let pattern = format!(r"(?i)\b({})\b:?\s*(.*)", active_markers.join("|"));
     let re = Regex::new(&pattern)?;
 
-    println!(
-        "{} Scanning for markers: {}",
-        Theme::info("Info:"),
-        Theme::highlight(&active_markers.join(", "))
-    );
-
     let mut found_markers = Vec::new();
 
     let walker = WalkBuilder::new(path)
@@ -94,12 +65,12 @@ pub async fn scan(path: &Path, markers: Option<Vec<String>>, hidden: bool) -> Re
                     if let Some(caps) = re.captures(line) {
                         let kind = caps.get(1).unwrap().as_str().to_uppercase();
                         let text = caps.get(2).map_or("", |m| m.as_str().trim());
-                        
+
                         found_markers.push(Marker {
                             kind,
-                            content: text.to_string(),
+                            file: path.to_string_lossy().to_string(),
                             line: i + 1,
-                            file: entry.path().to_string_lossy().to_string(),
+                            content: text.to_string(),
                         });
                     }
                 }
@@ -107,9 +78,76 @@ pub async fn scan(path: &Path, markers: Option<Vec<String>>, hidden: bool) -> Re
         }
     }
 
+    Ok(found_markers)
+}
+
+/// Marker counts per kind, most frequent first.
+fn summarize(markers: &[Marker]) -> Vec<MarkerCount> {
+    let mut summary: HashMap<&str, usize> = HashMap::new();
+    for marker in markers {
+        *summary.entry(&marker.kind).or_insert(0) += 1;
+    }
+
+    let mut sorted: Vec<MarkerCount> = summary
+        .into_iter()
+        .map(|(kind, count)| MarkerCount { kind: kind.to_string(), count })
+        .collect();
+    sorted.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.kind.cmp(&b.kind)));
+    sorted
+}
+
+pub async fn get_summary(path: &Path, markers: Option<Vec<String>>, hidden: bool) -> Result<Vec<MarkerCount>> {
+    let found = collect_markers(path, &active_markers(markers), hidden)?;
+    Ok(summarize(&found))
+}
+
+/// Error describing the missed `max_markers`, if any.
+pub fn threshold_error(total: usize, max_markers: Option<usize>) -> Option<String> {
+    let max = max_markers?;
+    (total > max).then(|| format!("Found {} markers, more than the maximum of {}", total, max))
+}
+
+pub async fn scan(
+    path: &Path,
+    markers: Option<Vec<String>>,
+    hidden: bool,
+    max_markers: Option<usize>,
+    format: OutputFormat,
+) -> Result<()> {
+    let active_markers = active_markers(markers);
+
+    if format.is_table() {
+        println!(
+            "{} Scanning for markers: {}",
+            Theme::info("Info:"),
+            Theme::highlight(&active_markers.join(", "))
+        );
+    }
+
+    let found_markers = collect_markers(path, &active_markers, hidden)?;
+    let total = found_markers.len();
+
+    match format {
+        OutputFormat::Table => print_markers(&found_markers),
+        OutputFormat::Csv | OutputFormat::Ndjson => format.print(&found_markers)?,
+        _ => format.print(&ScanReport {
+            total,
+            max_markers,
+            summary: summarize(&found_markers),
+            markers: found_markers,
+        })?,
+    }
+
+    if let Some(message) = threshold_error(total, max_markers) {
+        bail!(message);
+    }
+    Ok(())
+}
+
+fn print_markers(found_markers: &[Marker]) {
     if found_markers.is_empty() {
         println!("{}", Theme::success("No markers found. Your code is clean!"));
-        return Ok(());
+        return;
     }
 
     println!(
@@ -119,7 +157,7 @@ pub async fn scan(path: &Path, markers: Option<Vec<String>>, hidden: bool) -> Re
     );
     println!();
 
-    for marker in &found_markers {
+    for marker in found_markers {
         println!(
             "  {} {} {} {}",
             Theme::highlight(&format!("[{}]", marker.kind)),
@@ -131,17 +169,53 @@ pub async fn scan(path: &Path, markers: Option<Vec<String>>, hidden: bool) -> Re
 
     // Add summary
     println!("\n{}", Theme::header("Summary:"));
-    let mut summary = std::collections::HashMap::new();
-    for marker in found_markers {
-        *summary.entry(marker.kind).or_insert(0) += 1;
+    for MarkerCount { kind, count } in summarize(found_markers) {
+        println!("  {:<10} {}", Theme::highlight(&kind), Theme::value(&count.to_string()));
     }
+}
 
-    let mut sorted_summary: Vec<_> = summary.into_iter().collect();
-    sorted_summary.sort_by(|a, b| b.1.cmp(&a.1));
-
-    for (kind, count) in sorted_summary {
-        println!("  {:<10} {}", Theme::highlight(&kind), Theme::value(&count.to_string()));
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_markers_allows_exactly_that_many_markers() {
+        assert_eq!(threshold_error(20, None), None);
+        assert_eq!(threshold_error(20, Some(20)), None);
+        assert_eq!(
+            threshold_error(21, Some(20)).as_deref(),
+            Some("Found 21 markers, more than the maximum of 20")
+        );
+        assert!(threshold_error(1, Some(0)).is_some());
     }
 
-    Ok(())
+    #[test]
+    fn report_matches_the_documented_schema() {
+        let markers = vec![Marker {
+            kind: "TODO".to_string(),
+            file: "src/main.rs".to_string(),
+            line: 3,
+            content: "// TODO: parse flags".to_string(),
+        }];
+        let report = ScanReport {
+            total: 1,
+            max_markers: None,
+            summary: summarize(&markers),
+            markers,
+        };
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::json!({
+                "total": 1,
+                "max_markers": null,
+                "summary": [{"kind": "TODO", "count": 1}],
+                "markers": [{
+                    "kind": "TODO",
+                    "file": "src/main.rs",
+                    "line": 3,
+                    "content": "// TODO: parse flags"
+                }]
+            })
+        );
+    }
 }
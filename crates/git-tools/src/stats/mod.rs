@@ -1,7 +1,8 @@
 use anyhow::Result;
 use cli_core::ui::Theme;
-use cli_core::output::TableFormatter;
+use cli_core::output::{OutputFormat, TableFormatter};
 use ignore::WalkBuilder;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
@@ -53,12 +54,56 @@ pub async fn get_stats(path: &Path) -> Result<ProjectStats> {
     })
 }
 
-pub async fn show(path: &Path) -> Result<()> {
+#[derive(Serialize)]
+struct ExtensionStat {
+    extension: String,
+    files: usize,
+    lines: usize,
+    percentage: f64,
+}
+
+#[derive(Serialize)]
+struct StatsOutput {
+    total_files: usize,
+    total_lines: usize,
+    extensions: Vec<ExtensionStat>,
+}
+
+pub async fn show(path: &Path, format: OutputFormat) -> Result<()> {
     let stats = get_stats(path).await?;
     let total_files = stats.total_files;
     let total_lines = stats.total_lines;
     let extension_counts = stats.extension_counts;
 
+    let mut sorted_stats: Vec<_> = extension_counts.into_iter().collect();
+    sorted_stats.sort_by(|a, b| b.1.1.cmp(&a.1.1).then_with(|| a.0.cmp(&b.0))); // Sort by lines descending
+
+    let extensions: Vec<ExtensionStat> = sorted_stats
+        .into_iter()
+        .map(|(extension, (files, lines))| ExtensionStat {
+            extension,
+            files,
+            lines,
+            percentage: if total_lines > 0 {
+                (lines as f64 / total_lines as f64) * 100.0
+            } else {
+                0.0
+            },
+        })
+        .collect();
+
+    match format {
+        OutputFormat::Table => {}
+        OutputFormat::Csv | OutputFormat::Ndjson => return format.print(&extensions),
+        _ => {
+            return format.print(&StatsOutput {
+                total_files,
+                total_lines,
+                extensions,
+            })
+        }
+    }
+
     let mut summary_table = TableFormatter::create_table();
     summary_table.add_row(vec![
         TableFormatter::header_cell("Metric"),
@@ -75,9 +120,6 @@ pub async fn show(path: &Path) -> Result<()> {
     println!("{}", summary_table);
 
     println!("\n{}", Theme::header("Language Breakdown"));
-    
-    let mut sorted_stats: Vec<_> = extension_counts.into_iter().collect();
-    sorted_stats.sort_by(|a, b| b.1.1.cmp(&a.1.1)); // Sort by lines descending
 
     let mut breakdown_table = TableFormatter::create_table();
     breakdown_table.set_header(vec![
@@ -87,25 +129,19 @@ pub async fn show(path: &Path) -> Result<()> {
         TableFormatter::header_cell("Percentage"),
     ]);
 
-    for (ext, (files, lines)) in sorted_stats.iter().take(10) {
-        let percentage = if total_lines > 0 {
-            (*lines as f64 / total_lines as f64) * 100.0
-        } else {
-            0.0
-        };
-
+    for stat in extensions.iter().take(10) {
         breakdown_table.add_row(vec![
-            TableFormatter::highlight_cell(ext),
-            TableFormatter::value_cell(files.to_string()),
-            TableFormatter::value_cell(lines.to_string()),
-            TableFormatter::value_cell(format!("{:.1}%", percentage)),
+            TableFormatter::highlight_cell(&stat.extension),
+            TableFormatter::value_cell(stat.files.to_string()),
+            TableFormatter::value_cell(stat.lines.to_string()),
+            TableFormatter::value_cell(format!("{:.1}%", stat.percentage)),
         ]);
     }
 
     println!("{}", breakdown_table);
 
-    if sorted_stats.len() > 10 {
-        println!("  {}", Theme::dim(format!("... and {} more extensions", sorted_stats.len() - 10)));
+    if extensions.len() > 10 {
+        println!("  {}", Theme::dim(format!("... and {} more extensions", extensions.len() - 10)));
     }
 
     Ok(())
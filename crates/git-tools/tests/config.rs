@@ -0,0 +1,51 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+/// A directory whose `.cli-tools.toml` does not parse, with no user config.
+struct BrokenConfig(PathBuf);
+
+impl BrokenConfig {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("git-tools-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("xdg")).unwrap();
+        fs::write(dir.join(".cli-tools.toml"), "[git_tools\n").unwrap();
+        Self(dir)
+    }
+
+    fn run(&self, args: &[&str]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_git-tools"))
+            .arg("--path")
+            .arg(&self.0)
+            .args(args)
+            .env("XDG_CONFIG_HOME", self.0.join("xdg"))
+            .env("NO_COLOR", "1")
+            .output()
+            .unwrap()
+    }
+}
+
+impl Drop for BrokenConfig {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn commands_without_settings_ignore_a_broken_config() {
+    let dir = BrokenConfig::new("ignored");
+    let output = dir.run(&["stats", "--format", "json"]);
+
+    assert!(output.status.success(), "stats failed: {output:?}");
+}
+
+#[test]
+fn commands_with_settings_report_a_broken_config() {
+    let dir = BrokenConfig::new("reported");
+    let output = dir.run(&["env", "--format", "json"]);
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Failed to parse config file"), "{stderr}");
+}
@@ -20,8 +20,9 @@ impl ContributorStats {
         let total_commits = commits.len();
 
         for commit in commits {
+            // Email case varies between clients; the address does not.
             let entry = contributor_map
-                .entry(commit.email.clone())
+                .entry(commit.email.to_lowercase())
                 .or_insert_with(|| ContributorData {
                     name: commit.author.clone(),
                     email: commit.email.clone(),
@@ -58,7 +59,11 @@ impl ContributorStats {
             })
             .collect();
 
-        stats.sort_by(|a, b| b.commit_count.cmp(&a.commit_count));
+        stats.sort_by(|a, b| {
+            b.commit_count
+                .cmp(&a.commit_count)
+                .then_with(|| a.email.cmp(&b.email))
+        });
 
         stats
     }
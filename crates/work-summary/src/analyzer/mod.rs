@@ -2,6 +2,7 @@ pub mod value_calculator;
 pub mod contribution;
 
 use crate::git::CommitInfo;
+use cli_core::currency::Currency;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -31,6 +32,7 @@ impl WorkAnalysis {
         commits: &[CommitInfo],
         estimated_hours: f64,
         hourly_rate: f64,
+        currency: Currency,
     ) -> Self {
         let total_commits = commits.len();
 
@@ -41,7 +43,7 @@ impl WorkAnalysis {
         let mut language_map: HashMap<String, (usize, usize)> = HashMap::new();
 
         for commit in commits {
-            contributors.insert(commit.email.clone());
+            contributors.insert(commit.email.to_lowercase());
             total_files_changed += commit.files_changed;
             total_insertions += commit.insertions;
             total_deletions += commit.deletions;
@@ -79,6 +81,7 @@ impl WorkAnalysis {
         let value_estimate = value_calculator::ValueEstimate::calculate(
             estimated_hours,
             hourly_rate,
+            currency,
             total_insertions + total_deletions,
         );
 
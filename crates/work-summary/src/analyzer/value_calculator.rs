@@ -1,7 +1,10 @@
+use cli_core::currency::Currency;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValueEstimate {
+    /// Currency of every rate and value below
+    pub currency: Currency,
     pub estimated_hours: f64,
     pub base_hourly_rate: f64,
     pub developer_levels: Vec<DeveloperLevel>,
@@ -17,7 +20,12 @@ pub struct DeveloperLevel {
 }
 
 impl ValueEstimate {
-    pub fn calculate(estimated_hours: f64, base_hourly_rate: f64, total_changes: usize) -> Self {
+    pub fn calculate(
+        estimated_hours: f64,
+        base_hourly_rate: f64,
+        currency: Currency,
+        total_changes: usize,
+    ) -> Self {
         let complexity_factor = Self::calculate_complexity_factor(total_changes);
 
         let levels = vec![
@@ -50,6 +58,7 @@ impl ValueEstimate {
             .unwrap_or(0.0);
 
         Self {
+            currency,
             estimated_hours,
             base_hourly_rate,
             developer_levels,
@@ -0,0 +1,90 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+
+/// `--author` and `--exclude-author` patterns, matched like `git log --author`
+/// against "Name <email>".
+#[derive(Debug, Clone, Default)]
+pub struct AuthorFilter {
+    include: Vec<Regex>,
+    exclude: Vec<Regex>,
+}
+
+impl AuthorFilter {
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        Ok(Self {
+            include: compile(include)?,
+            exclude: compile(exclude)?,
+        })
+    }
+
+    /// Whether a commit is counted, given every identity its author goes by:
+    /// one must match an `--author` pattern, if any were given, and none an
+    /// `--exclude-author` pattern.
+    pub fn accepts(&self, identities: &[String]) -> bool {
+        let matches = |patterns: &[Regex]| {
+            identities
+                .iter()
+                .any(|identity| patterns.iter().any(|pattern| pattern.is_match(identity)))
+        };
+        (self.include.is_empty() || matches(&self.include)) && !matches(&self.exclude)
+    }
+}
+
+fn compile(patterns: &[String]) -> Result<Vec<Regex>> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            Regex::new(pattern).with_context(|| format!("Invalid author pattern: {pattern}"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(include: &[&str], exclude: &[&str]) -> AuthorFilter {
+        let strings =
+            |patterns: &[&str]| patterns.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+        AuthorFilter::new(&strings(include), &strings(exclude)).unwrap()
+    }
+
+    fn identities(list: &[&str]) -> Vec<String> {
+        list.iter().map(|identity| identity.to_string()).collect()
+    }
+
+    #[test]
+    fn no_patterns_accept_everyone() {
+        assert!(AuthorFilter::default().accepts(&identities(&["Dev <dev@example.com>"])));
+    }
+
+    #[test]
+    fn include_patterns_need_one_matching_identity() {
+        let filter = filter(&["^Jane ", "@example\\.org>$"], &[]);
+        assert!(filter.accepts(&identities(&["Jane <jane@example.com>"])));
+        assert!(filter.accepts(&identities(&["Joe <joe@example.org>"])));
+        assert!(filter.accepts(&identities(&[
+            "jdoe <jdoe@work.example>",
+            "Jane Doe <jane@example.com>"
+        ])));
+        assert!(!filter.accepts(&identities(&["Mary Jane <mary@example.com>"])));
+    }
+
+    #[test]
+    fn exclude_patterns_win_over_include_patterns() {
+        let filter = filter(&["example\\.com"], &["\\[bot\\]"]);
+        assert!(filter.accepts(&identities(&["Dev <dev@example.com>"])));
+        assert!(!filter.accepts(&identities(&["renovate[bot] <bot@example.com>"])));
+        // Any identity matching excludes the commit.
+        assert!(!filter.accepts(&identities(&[
+            "Dev <dev@example.com>",
+            "ci[bot] <ci@example.com>"
+        ])));
+    }
+
+    #[test]
+    fn invalid_patterns_are_errors() {
+        let error = AuthorFilter::new(&[], &["(unclosed".to_string()]).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid author pattern: (unclosed");
+    }
+}
@@ -1,13 +1,17 @@
+pub mod authors;
 pub mod time_estimator;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use chrono::{DateTime, Utc};
+use cli_core::config::Identity;
 use cli_core::date_range::DateRange;
-use git2::{Commit, DiffOptions, Repository};
+use git2::{Commit, Diff, DiffOptions, Mailmap, Oid, Repository, Signature, Sort};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
+use authors::AuthorFilter;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommitInfo {
     pub hash: String,
@@ -29,12 +33,44 @@ pub struct LanguageChange {
 
 pub struct CommitAnalyzer {
     repo: Repository,
+    /// The repository's `.mailmap`, when it has one.
+    mailmap: Option<Mailmap>,
+    identities: Vec<Identity>,
+    authors: AuthorFilter,
+    /// Reference globs to walk from; HEAD when empty.
+    refs: Vec<String>,
 }
 
 impl CommitAnalyzer {
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
         let repo = Repository::open(path).context("Failed to open git repository")?;
-        Ok(Self { repo })
+        let mailmap = repo.mailmap().ok();
+        Ok(Self {
+            repo,
+            mailmap,
+            identities: Vec::new(),
+            authors: AuthorFilter::default(),
+            refs: Vec::new(),
+        })
+    }
+
+    /// Merge the configured identities, after `.mailmap` has been applied.
+    pub fn with_identities(mut self, identities: Vec<Identity>) -> Self {
+        self.identities = identities;
+        self
+    }
+
+    /// Count only the commits whose author `authors` accepts.
+    pub fn with_authors(mut self, authors: AuthorFilter) -> Self {
+        self.authors = authors;
+        self
+    }
+
+    /// Walk the commits reachable from the references matching `refs`, such
+    /// as `refs/heads/*`, instead of HEAD.
+    pub fn with_refs(mut self, refs: Vec<String>) -> Self {
+        self.refs = refs;
+        self
     }
 
     pub fn analyze_commits(
@@ -43,10 +79,28 @@ impl CommitAnalyzer {
         date_range: &DateRange,
     ) -> Result<Vec<CommitInfo>> {
         let mut revwalk = self.repo.revwalk()?;
-        revwalk.push_head()?;
+        // Newest first across every tip, so `limit` keeps the latest work.
+        revwalk.set_sorting(Sort::TIME)?;
+        if self.refs.is_empty() {
+            revwalk.push_head()?;
+        }
+        for glob in &self.refs {
+            // A typo in a glob would otherwise silently walk nothing.
+            let mut matching = self.repo.references_glob(&full_glob(glob))?;
+            if matching.next().is_none() {
+                bail!("No references match {glob}");
+            }
+            revwalk
+                .push_glob(glob)
+                .with_context(|| format!("Failed to walk references matching {glob}"))?;
+        }
 
         let mut commits = Vec::new();
         let mut count = 0;
+        // A commit reachable from several references is walked once, but a
+        // cherry-pick or a rebased copy on another branch is a commit of its
+        // own; only the newest with each patch is counted.
+        let mut patches = HashSet::new();
 
         for oid in revwalk {
             if let Some(lim) = limit {
@@ -58,10 +112,32 @@ impl CommitAnalyzer {
             let oid = oid?;
             let commit = self.repo.find_commit(oid)?;
 
-            if let Ok(info) = self.extract_commit_info(&commit) {
+            let raw = commit.author();
+            let (name, email) = self.resolve_author(&commit);
+            let identities = [
+                format!(
+                    "{} <{}>",
+                    raw.name().unwrap_or(""),
+                    raw.email().unwrap_or("")
+                ),
+                format!("{name} <{email}>"),
+            ];
+            if !self.authors.accepts(&identities) {
+                continue;
+            }
+
+            let Ok(diff) = self.commit_diff(&commit) else {
+                continue;
+            };
+            if let Ok(info) = self.extract_commit_info(&commit, &diff, name, email) {
                 if !date_range.contains(info.timestamp) {
                     continue;
                 }
+                if let Some(patch_id) = Self::patch_id(&commit, &diff) {
+                    if !patches.insert(patch_id) {
+                        continue;
+                    }
+                }
 
                 commits.push(info);
                 count += 1;
@@ -71,18 +147,43 @@ impl CommitAnalyzer {
         Ok(commits)
     }
 
-    fn extract_commit_info(&self, commit: &Commit) -> Result<CommitInfo> {
+    /// The author's canonical name and email: `.mailmap` first, then the
+    /// configured identities.
+    fn resolve_author(&self, commit: &Commit) -> (String, String) {
+        let author = commit.author();
+        let (name, email) = self
+            .mailmap
+            .as_ref()
+            .and_then(|mailmap| apply_mailmap(mailmap, &author))
+            .unwrap_or_else(|| {
+                let name = author.name().unwrap_or("Unknown");
+                (name.to_string(), author.email().unwrap_or("").to_string())
+            });
+        match self
+            .identities
+            .iter()
+            .find(|identity| identity.matches(&name, &email))
+        {
+            Some(identity) => (identity.name.clone(), identity.email.clone()),
+            None => (name, email),
+        }
+    }
+
+    fn extract_commit_info(
+        &self,
+        commit: &Commit,
+        diff: &Diff,
+        author_name: String,
+        email: String,
+    ) -> Result<CommitInfo> {
         let author = commit.author();
         let timestamp = DateTime::from_timestamp(author.when().seconds(), 0)
             .unwrap_or_else(|| Utc::now());
 
         let hash = commit.id().to_string();
         let message = commit.message().unwrap_or("").to_string();
-        let author_name = author.name().unwrap_or("Unknown").to_string();
-        let email = author.email().unwrap_or("").to_string();
 
-        let (files_changed, insertions, deletions, language_changes) =
-            self.analyze_diff(commit)?;
+        let (files_changed, insertions, deletions, language_changes) = self.analyze_diff(diff)?;
 
         Ok(CommitInfo {
             hash,
@@ -97,12 +198,8 @@ impl CommitAnalyzer {
         })
     }
 
-    fn analyze_diff(&self, commit: &Commit) -> Result<(usize, usize, usize, HashMap<String, LanguageChange>)> {
-        let mut files_changed = 0;
-        let mut total_insertions = 0;
-        let mut total_deletions = 0;
-        let mut language_changes: HashMap<String, LanguageChange> = HashMap::new();
-
+    /// Changes against the first parent.
+    fn commit_diff(&self, commit: &Commit) -> Result<Diff<'_>> {
         let tree = commit.tree()?;
         let parent_tree = if commit.parent_count() > 0 {
             Some(commit.parent(0)?.tree()?)
@@ -110,11 +207,27 @@ impl CommitAnalyzer {
             None
         };
 
-        let diff = self.repo.diff_tree_to_tree(
+        Ok(self.repo.diff_tree_to_tree(
             parent_tree.as_ref(),
             Some(&tree),
             Some(&mut DiffOptions::new()),
-        )?;
+        )?)
+    }
+
+    /// The `git patch-id` of a commit's changes, the same for every copy of
+    /// them. None for merges and empty commits, which are never collapsed.
+    fn patch_id(commit: &Commit, diff: &Diff) -> Option<Oid> {
+        if commit.parent_count() > 1 || diff.deltas().len() == 0 {
+            return None;
+        }
+        diff.patchid(None).ok()
+    }
+
+    fn analyze_diff(&self, diff: &Diff) -> Result<(usize, usize, usize, HashMap<String, LanguageChange>)> {
+        let mut files_changed = 0;
+        let mut total_insertions = 0;
+        let mut total_deletions = 0;
+        let mut language_changes: HashMap<String, LanguageChange> = HashMap::new();
 
         diff.foreach(
             &mut |delta, _| {
@@ -182,3 +295,298 @@ impl CommitAnalyzer {
         }
     }
 }
+
+/// `glob` as `push_glob` reads it: under `refs/`, and everything below it
+/// when it has no wildcard.
+fn full_glob(glob: &str) -> String {
+    let mut full = match glob.starts_with("refs/") {
+        true => glob.to_string(),
+        false => format!("refs/{glob}"),
+    };
+    if !full.contains(['?', '*', '[']) {
+        full.push_str("/*");
+    }
+    full
+}
+
+/// The name and email `.mailmap` gives `author`, if it lists them. libgit2
+/// compares emails case-sensitively where git does not, so a miss is retried
+/// with the email lowercased.
+fn apply_mailmap(mailmap: &Mailmap, author: &Signature) -> Option<(String, String)> {
+    let (name, email) = (author.name()?, author.email()?);
+    let lowered = email.to_lowercase();
+    let mapped = [email, lowered.as_str()].into_iter().find_map(|email| {
+        let signature = Signature::new(name, email, &author.when()).ok()?;
+        let resolved = mailmap.resolve_signature(&signature).ok()?;
+        let mapped = (resolved.name()?.to_string(), resolved.email()?.to_string());
+        (mapped.0 != name || mapped.1 != email).then_some(mapped)
+    });
+    mapped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::{RepositoryInitOptions, Time};
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static NEXT_TEMP_ID: AtomicU64 = AtomicU64::new(0);
+
+    /// A repository whose commits are built from trees directly, so branches
+    /// and merges need no checkout.
+    struct TempRepo {
+        dir: PathBuf,
+        repo: Repository,
+        time: i64,
+    }
+
+    impl TempRepo {
+        fn new() -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "work-summary-git-{}-{}",
+                std::process::id(),
+                NEXT_TEMP_ID.fetch_add(1, Ordering::Relaxed)
+            ));
+            let _ = std::fs::remove_dir_all(&dir);
+            let repo =
+                Repository::init_opts(&dir, RepositoryInitOptions::new().initial_head("main"))
+                    .unwrap();
+            Self {
+                dir,
+                repo,
+                time: 1_700_000_000,
+            }
+        }
+
+        /// Commit `files` as the whole tree on `branch`.
+        fn commit(&mut self, branch: &str, parents: &[Oid], files: &[(&str, &str)]) -> Oid {
+            self.commit_with(("Dev", "dev@example.com"), branch, parents, files)
+        }
+
+        /// Commit by `author` on main, changing a file so the patch is new.
+        fn commit_by(&mut self, author: (&str, &str), parents: &[Oid]) -> Oid {
+            let content = format!("{}\n", self.time);
+            self.commit_with(author, "main", parents, &[("a.txt", &content)])
+        }
+
+        fn commit_with(
+            &mut self,
+            (name, email): (&str, &str),
+            branch: &str,
+            parents: &[Oid],
+            files: &[(&str, &str)],
+        ) -> Oid {
+            let mut builder = self.repo.treebuilder(None).unwrap();
+            for (name, content) in files {
+                let blob = self.repo.blob(content.as_bytes()).unwrap();
+                builder.insert(name, blob, 0o100644).unwrap();
+            }
+            let tree = self.repo.find_tree(builder.write().unwrap()).unwrap();
+            let parents: Vec<Commit> = parents
+                .iter()
+                .map(|oid| self.repo.find_commit(*oid).unwrap())
+                .collect();
+
+            self.time += 60;
+            let signature = Signature::new(name, email, &Time::new(self.time, 0)).unwrap();
+            let reference = format!("refs/heads/{branch}");
+            self.repo
+                .commit(
+                    None,
+                    &signature,
+                    &signature,
+                    &format!("commit {}", self.time),
+                    &tree,
+                    &parents.iter().collect::<Vec<_>>(),
+                )
+                .and_then(|oid| {
+                    self.repo.reference(&reference, oid, true, "test")?;
+                    Ok(oid)
+                })
+                .unwrap()
+        }
+
+        /// Who each commit is credited to, newest first.
+        fn authors(&self, analyzer: CommitAnalyzer) -> Vec<(String, String)> {
+            analyzer
+                .analyze_commits(None, &DateRange::default())
+                .unwrap()
+                .into_iter()
+                .map(|commit| (commit.author, commit.email))
+                .collect()
+        }
+
+        fn analyze(&self, refs: &[&str]) -> Result<Vec<Oid>> {
+            let analyzer = CommitAnalyzer::new(&self.dir)?
+                .with_refs(refs.iter().map(|glob| glob.to_string()).collect());
+            Ok(analyzer
+                .analyze_commits(None, &DateRange::default())?
+                .into_iter()
+                .map(|commit| Oid::from_str(&commit.hash).unwrap())
+                .collect())
+        }
+    }
+
+    impl Drop for TempRepo {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    #[test]
+    fn commits_on_several_branches_count_once() {
+        let mut repo = TempRepo::new();
+        let base = repo.commit("main", &[], &[("a.txt", "a\n")]);
+        let shared = repo.commit("main", &[base], &[("a.txt", "a\nb\n")]);
+        let feature = repo.commit("feature", &[shared], &[("a.txt", "a\nb\nc\n")]);
+        repo.commit("other", &[shared], &[("a.txt", "a\nb\n"), ("d.txt", "d\n")]);
+
+        let commits = repo.analyze(&["refs/heads/*"]).unwrap();
+        assert_eq!(commits.len(), 4);
+        assert_eq!(commits.iter().filter(|oid| **oid == shared).count(), 1);
+        assert!(commits.contains(&feature));
+        // Without refs only HEAD's history is walked.
+        assert_eq!(repo.analyze(&[]).unwrap(), [shared, base]);
+    }
+
+    #[test]
+    fn cherry_picks_collapse_to_the_newest_copy() {
+        let mut repo = TempRepo::new();
+        let base = repo.commit("main", &[], &[("a.txt", "a\n")]);
+        let original = repo.commit("feature", &[base], &[("a.txt", "a\n"), ("b.txt", "b\n")]);
+        let picked = repo.commit("main", &[base], &[("a.txt", "a\n"), ("b.txt", "b\n")]);
+
+        let commits = repo.analyze(&["refs/heads/*"]).unwrap();
+        assert_eq!(commits, [picked, base]);
+        assert!(!commits.contains(&original));
+    }
+
+    #[test]
+    fn merges_and_empty_commits_are_never_collapsed() {
+        let mut repo = TempRepo::new();
+        let base = repo.commit("main", &[], &[("a.txt", "a\n")]);
+        let left = repo.commit("main", &[base], &[("a.txt", "a\nleft\n")]);
+        let right = repo.commit("right", &[base], &[("a.txt", "a\n"), ("r.txt", "r\n")]);
+        // Two merges bringing in the same change have the same patch.
+        let merged = [("a.txt", "a\nleft\n"), ("r.txt", "r\n")];
+        let merge = repo.commit("main", &[left, right], &merged);
+        let remerge = repo.commit("remerge", &[left, right], &merged);
+        // Two empty commits have no patch at all.
+        let empty = repo.commit("main", &[merge], &merged);
+        let also_empty = repo.commit("main", &[empty], &merged);
+
+        let commits = repo.analyze(&["refs/heads/*"]).unwrap();
+        for oid in [merge, remerge, empty, also_empty] {
+            assert!(commits.contains(&oid), "{oid} was collapsed");
+        }
+        assert_eq!(commits.len(), 7);
+    }
+
+    #[test]
+    fn globs_that_match_nothing_are_errors() {
+        let mut repo = TempRepo::new();
+        repo.commit("main", &[], &[("a.txt", "a\n")]);
+        repo.commit("feature/x", &[], &[("b.txt", "b\n")]);
+
+        let error = repo
+            .analyze(&["refs/heads/*", "refs/heads/featuer/*"])
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "No references match refs/heads/featuer/*"
+        );
+        assert!(repo.analyze(&["refs/remotes/*"]).is_err());
+
+        // Globs are read as git does: under refs/, and a prefix without
+        // wildcards matches everything below it.
+        assert_eq!(repo.analyze(&["heads/feature/*"]).unwrap().len(), 1);
+        assert_eq!(repo.analyze(&["refs/heads/feature"]).unwrap().len(), 1);
+    }
+
+    fn author(name: &str, email: &str) -> (String, String) {
+        (name.to_string(), email.to_string())
+    }
+
+    #[test]
+    fn mailmap_matches_emails_in_any_case() {
+        let mut repo = TempRepo::new();
+        let base = repo.commit_by(("Jane", "Jane@Work.Example"), &[]);
+        repo.commit_by(("jdoe", "jdoe@home.example"), &[base]);
+        std::fs::write(
+            repo.dir.join(".mailmap"),
+            "Jane Doe <jane@example.com> <jane@work.example>\n",
+        )
+        .unwrap();
+
+        let analyzer = CommitAnalyzer::new(&repo.dir).unwrap();
+        assert_eq!(
+            repo.authors(analyzer),
+            [
+                author("jdoe", "jdoe@home.example"),
+                author("Jane Doe", "jane@example.com"),
+            ]
+        );
+    }
+
+    #[test]
+    fn identities_merge_emails_into_one_contributor() {
+        let mut repo = TempRepo::new();
+        let base = repo.commit_by(("Jane", "jane@home.example"), &[]);
+        let work = repo.commit_by(("jdoe", "JDOE@Work.Example"), &[base]);
+        repo.commit_by(("Dev", "dev@example.com"), &[work]);
+
+        let identity = Identity {
+            name: "Jane Doe".to_string(),
+            email: "jane@home.example".to_string(),
+            aliases: vec!["jdoe@work.example".to_string()],
+        };
+        let analyzer = CommitAnalyzer::new(&repo.dir)
+            .unwrap()
+            .with_identities(vec![identity]);
+        let commits = analyzer
+            .analyze_commits(None, &DateRange::default())
+            .unwrap();
+        let contributors = crate::analyzer::contribution::ContributorStats::from_commits(&commits);
+
+        assert_eq!(contributors.len(), 2);
+        let jane = contributors
+            .iter()
+            .find(|contributor| contributor.name == "Jane Doe")
+            .unwrap();
+        assert_eq!(jane.email, "jane@home.example");
+        assert_eq!(jane.commit_count, 2);
+    }
+
+    #[test]
+    fn author_patterns_match_raw_and_resolved_identities() {
+        let mut repo = TempRepo::new();
+        let base = repo.commit_by(("jdoe", "jdoe@work.example"), &[]);
+        let bot = repo.commit_by(("ci-bot", "bot@example.com"), &[base]);
+        repo.commit_by(("Dev", "dev@example.com"), &[bot]);
+        let identity = Identity {
+            name: "Jane Doe".to_string(),
+            email: "jane@example.com".to_string(),
+            aliases: vec!["jdoe".to_string()],
+        };
+        let filtered = |include: &[&str], exclude: &[&str]| {
+            let strings =
+                |patterns: &[&str]| patterns.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+            let authors = AuthorFilter::new(&strings(include), &strings(exclude)).unwrap();
+            let analyzer = CommitAnalyzer::new(&repo.dir)
+                .unwrap()
+                .with_identities(vec![identity.clone()])
+                .with_authors(authors);
+            repo.authors(analyzer)
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>()
+        };
+
+        // The configured name and the one in the commit both match.
+        assert_eq!(filtered(&["^Jane Doe "], &[]), ["Jane Doe"]);
+        assert_eq!(filtered(&["jdoe@work"], &[]), ["Jane Doe"]);
+        assert_eq!(filtered(&[], &["bot"]), ["Dev", "Jane Doe"]);
+        assert_eq!(filtered(&["example\\.com"], &["bot"]), ["Dev", "Jane Doe"]);
+    }
+}
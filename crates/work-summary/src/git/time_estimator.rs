@@ -1,20 +1,43 @@
 use super::CommitInfo;
+use anyhow::{bail, Result};
 use chrono::Duration;
-
-const MAX_SESSION_GAP_HOURS: i64 = 4;
-const LINES_PER_HOUR: f64 = 20.0;
-const TIME_WEIGHT: f64 = 0.6;
-const CHANGE_WEIGHT: f64 = 0.4;
+use cli_core::config::WorkSummaryConfig;
 
 pub struct TimeEstimator {
     language_weights: fn(&str) -> f64,
+    max_session_gap_hours: f64,
+    lines_per_hour: f64,
+    time_weight: f64,
+    change_weight: f64,
 }
 
 impl TimeEstimator {
     pub fn new() -> Self {
-        Self {
-            language_weights: get_language_weight,
+        Self::from_config(&WorkSummaryConfig::default()).expect("default weights are valid")
+    }
+
+    /// Build an estimator from the `[work_summary]` configuration section.
+    pub fn from_config(config: &WorkSummaryConfig) -> Result<Self> {
+        // A negative or NaN weight would skew or poison every estimate.
+        for (key, weight) in [
+            ("time_weight", config.time_weight),
+            ("change_weight", config.change_weight),
+        ] {
+            if !(weight.is_finite() && weight >= 0.0) {
+                bail!("Invalid work_summary.{key} {weight}: expected a number of 0 or more");
+            }
         }
+        if config.time_weight + config.change_weight <= 0.0 {
+            bail!("work_summary.time_weight and change_weight must not both be 0");
+        }
+
+        Ok(Self {
+            language_weights: get_language_weight,
+            max_session_gap_hours: config.max_session_gap_hours,
+            lines_per_hour: config.lines_per_hour.max(f64::EPSILON),
+            time_weight: config.time_weight,
+            change_weight: config.change_weight,
+        })
     }
 
     pub fn estimate_work_hours(&self, commits: &[CommitInfo]) -> f64 {
@@ -38,7 +61,7 @@ impl TimeEstimator {
             let change_based = self.estimate_from_changes(commit);
 
             let hybrid_estimate = if time_based > 0.0 {
-                (time_based * TIME_WEIGHT) + (change_based * CHANGE_WEIGHT)
+                (time_based * self.time_weight) + (change_based * self.change_weight)
             } else {
                 change_based
             };
@@ -56,7 +79,7 @@ impl TimeEstimator {
             return 0.0;
         }
 
-        hours.min(MAX_SESSION_GAP_HOURS as f64)
+        hours.min(self.max_session_gap_hours)
     }
 
     fn estimate_from_changes(&self, commit: &CommitInfo) -> f64 {
@@ -76,7 +99,7 @@ impl TimeEstimator {
             weighted_lines = total_lines;
         }
 
-        let base_hours = weighted_lines / LINES_PER_HOUR;
+        let base_hours = weighted_lines / self.lines_per_hour;
 
         let complexity_factor = if commit.files_changed > 5 {
             1.2
@@ -111,3 +134,42 @@ fn get_language_weight(language: &str) -> f64 {
         _ => 1.0,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weights(time_weight: f64, change_weight: f64) -> Result<TimeEstimator> {
+        TimeEstimator::from_config(&WorkSummaryConfig {
+            time_weight,
+            change_weight,
+            ..WorkSummaryConfig::default()
+        })
+    }
+
+    #[test]
+    fn weights_must_be_finite_and_not_negative() {
+        for (time_weight, change_weight, invalid) in [
+            (-0.1, 0.4, "time_weight -0.1"),
+            (0.6, f64::NAN, "change_weight NaN"),
+            (f64::INFINITY, 0.4, "time_weight inf"),
+        ] {
+            let error = weights(time_weight, change_weight).err().unwrap();
+            assert_eq!(
+                error.to_string(),
+                format!("Invalid work_summary.{invalid}: expected a number of 0 or more")
+            );
+        }
+    }
+
+    #[test]
+    fn weights_must_not_both_be_zero() {
+        let error = weights(0.0, 0.0).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "work_summary.time_weight and change_weight must not both be 0"
+        );
+        assert!(weights(0.0, 1.0).is_ok());
+        assert!(weights(2.0, 0.0).is_ok());
+    }
+}
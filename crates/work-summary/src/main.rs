@@ -1,16 +1,19 @@
 mod git;
 mod analyzer;
 mod patterns;
+mod report;
 mod summary;
 
 use anyhow::{Context, Result};
 use clap::Parser;
 use cli_core::date_range::{DateRange, DateRangeArgs};
-use cli_core::output::format_currency_krw;
+use cli_core::currency::{Amount, Currency, Pricing};
+use cli_core::output::{FormatArg, OutputFormat};
 use comfy_table::{presets::UTF8_FULL, Cell, Color, Table};
-use git::{time_estimator::TimeEstimator, CommitAnalyzer};
+use git::{authors::AuthorFilter, time_estimator::TimeEstimator, CommitAnalyzer};
+use cli_core::{Config, LayeredConfig};
 use owo_colors::OwoColorize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use summary::{RepositorySummary, TotalSummary};
 
 #[derive(serde::Serialize)]
@@ -23,7 +26,8 @@ struct ExportRow {
     insertions: usize,
     deletions: usize,
     estimated_hours: f64,
-    recommended_value_krw: f64,
+    currency: Currency,
+    recommended_value: f64,
 }
 
 #[derive(Parser)]
@@ -33,14 +37,24 @@ struct Cli {
     #[arg(value_name = "PATH", help = "Repository paths to analyze")]
     paths: Vec<PathBuf>,
 
-    #[arg(short, long, default_value = "table", help = "Output format")]
-    format: String,
+    #[command(flatten)]
+    output: FormatArg,
 
     #[arg(long, help = "Export results to file")]
     export: Option<PathBuf>,
 
-    #[arg(long, default_value = "10030", help = "Hourly rate in KRW")]
-    hourly_rate: f64,
+    #[arg(
+        long,
+        help = "Hourly rate, optionally with its currency such as 45USD (default: config `hourly_rate`, else 10030 KRW)"
+    )]
+    hourly_rate: Option<Amount>,
+
+    #[arg(
+        long,
+        value_name = "CODE",
+        help = "Report values in this currency, e.g. USD (default: config `report_currency`, else the rate's)"
+    )]
+    currency: Option<Currency>,
 
     #[arg(long, help = "Show simple summary only")]
     simple: bool,
@@ -53,6 +67,51 @@ struct Cli {
 
     #[arg(long, help = "Limit to N most recent commits")]
     limit: Option<usize>,
+
+    #[arg(
+        long,
+        value_name = "PATTERN",
+        help = "Only count commits whose \"Name <email>\" matches this regex (repeatable)"
+    )]
+    author: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "PATTERN",
+        help = "Skip commits whose \"Name <email>\" matches this regex, e.g. bots (repeatable)"
+    )]
+    exclude_author: Vec<String>,
+
+    #[arg(long, help = "Analyze every local branch instead of HEAD")]
+    all_branches: bool,
+
+    #[arg(
+        long,
+        help = "Analyze remote-tracking branches (with --all-branches, as well as local ones)"
+    )]
+    remote: bool,
+
+    #[arg(
+        long,
+        value_name = "GLOB",
+        help = "Analyze the references matching this glob, e.g. 'refs/heads/feature/*' (repeatable)"
+    )]
+    refs: Vec<String>,
+}
+
+impl Cli {
+    /// Reference globs to walk; none means HEAD.
+    fn ref_globs(&self) -> Vec<String> {
+        let mut globs = Vec::new();
+        if self.all_branches {
+            globs.push("refs/heads/*".to_string());
+        }
+        if self.remote {
+            globs.push("refs/remotes/*".to_string());
+        }
+        globs.extend(self.refs.iter().cloned());
+        globs
+    }
 }
 
 fn main() -> Result<()> {
@@ -64,14 +123,18 @@ fn main() -> Result<()> {
         std::mem::take(&mut cli.paths)
     };
     let date_range = cli.date_range.resolve()?;
+    let format = cli.output.format;
+    let authors = AuthorFilter::new(&cli.author, &cli.exclude_author)?;
 
-    println!("{}", "Work Summary".bold().bright_cyan());
-    println!("{}\n", format!("v{}", env!("CARGO_PKG_VERSION")).dimmed());
+    if format.is_table() {
+        println!("{}", "Work Summary".bold().bright_cyan());
+        println!("{}\n", format!("v{}", env!("CARGO_PKG_VERSION")).dimmed());
+    }
 
     let mut summaries = Vec::new();
 
     for path in &paths {
-        match analyze_repository(path, &cli, &date_range) {
+        match analyze_repository(path, &cli, &date_range, &authors) {
             Ok(summary) => summaries.push(summary),
             Err(e) => {
                 eprintln!("{}: {} - {}", "Error".red(), path.display(), e);
@@ -80,21 +143,25 @@ fn main() -> Result<()> {
     }
 
     if summaries.is_empty() {
-        println!("{}", "No repositories analyzed successfully.".yellow());
+        eprintln!("{}", "No repositories analyzed successfully.".yellow());
         return Ok(());
     }
 
     let total_summary = TotalSummary::from_repositories(summaries.clone());
 
-    if cli.simple {
-        print_simple_summary(&total_summary);
-    } else {
-        print_detailed_summary(&total_summary);
+    match format {
+        OutputFormat::Table if cli.simple => print_simple_summary(&total_summary),
+        OutputFormat::Table => print_detailed_summary(&total_summary),
+        OutputFormat::Csv => format.print(&export_rows(&total_summary))?,
+        OutputFormat::Ndjson => format.print(&total_summary.repositories)?,
+        _ => format.print(&total_summary)?,
     }
 
     if let Some(export_path) = cli.export {
         export_summary(&total_summary, &export_path)?;
-        println!("\n{} {}", "Exported to:".green(), export_path.display());
+        if format.is_table() {
+            println!("\n{} {}", "Exported to:".green(), export_path.display());
+        }
     }
 
     Ok(())
@@ -104,9 +171,16 @@ fn analyze_repository(
     path: &PathBuf,
     cli: &Cli,
     date_range: &DateRange,
+    authors: &AuthorFilter,
 ) -> Result<RepositorySummary> {
+    let config = load_config(path, cli)?;
+    let pricing = Pricing::from_config(&config)?;
+    let estimator = TimeEstimator::from_config(&config.work_summary)?;
     let analyzer = CommitAnalyzer::new(path)
-        .context(format!("Failed to open repository at {}", path.display()))?;
+        .context(format!("Failed to open repository at {}", path.display()))?
+        .with_identities(config.identities.clone())
+        .with_authors(authors.clone())
+        .with_refs(cli.ref_globs());
 
     let commits = analyzer.analyze_commits(cli.limit, date_range)?;
 
@@ -115,21 +189,28 @@ fn analyze_repository(
             path.clone(),
             commits,
             0.0,
-            cli.hourly_rate,
+            pricing.hourly_rate,
+            pricing.currency,
         ));
     }
 
-    let estimator = TimeEstimator::new();
     let estimated_hours = estimator.estimate_work_hours(&commits);
 
     Ok(RepositorySummary::new(
         path.clone(),
         commits,
         estimated_hours,
-        cli.hourly_rate,
+        pricing.hourly_rate,
+        pricing.currency,
     ))
 }
 
+fn load_config(path: &Path, cli: &Cli) -> Result<Config> {
+    let mut layered = LayeredConfig::load(path)?;
+    cli_core::currency::set_flags(&mut layered, cli.hourly_rate, cli.currency);
+    layered.config()
+}
+
 fn print_simple_summary(summary: &TotalSummary) {
     println!("{}", "═".repeat(60).dimmed());
     println!("{}", "Total Summary".bold().bright_yellow());
@@ -147,7 +228,10 @@ fn print_simple_summary(summary: &TotalSummary) {
         println!(
             "  {}: {}",
             "Value (Mid-level)".dimmed(),
-            format_currency_krw(repo.analysis.value_estimate.recommended_value)
+            repo.analysis
+                .value_estimate
+                .currency
+                .format(repo.analysis.value_estimate.recommended_value)
                 .bright_green()
         );
     }
@@ -160,7 +244,7 @@ fn print_simple_summary(summary: &TotalSummary) {
         println!("  Total Hours: {:.1}h", summary.total_hours);
         println!(
             "  Total Value: {}",
-            format_currency_krw(summary.total_value).bright_green()
+            summary.currency.format(summary.total_value).bright_green()
         );
         println!("  Contributors: {}", summary.total_contributors);
     }
@@ -340,9 +424,10 @@ fn print_value_estimates(repo: &RepositorySummary) {
     table.load_preset(UTF8_FULL);
     table.set_header(vec!["Level", "Multiplier", "Hourly Rate", "Total Value"]);
 
+    let currency = repo.analysis.value_estimate.currency;
     for level in &repo.analysis.value_estimate.developer_levels {
         let is_recommended = level.level == "Mid-level";
-        let total_value = format_currency_krw(level.total_value);
+        let total_value = currency.format(level.total_value);
 
         table.add_row(vec![
             if is_recommended {
@@ -351,7 +436,7 @@ fn print_value_estimates(repo: &RepositorySummary) {
                 Cell::new(&level.level)
             },
             Cell::new(format!("{}x", level.multiplier)),
-            Cell::new(format_currency_krw(level.hourly_rate)),
+            Cell::new(currency.format(level.hourly_rate)),
             if is_recommended {
                 Cell::new(total_value).fg(Color::Green)
             } else {
@@ -373,11 +458,28 @@ fn print_total_summary(summary: &TotalSummary) {
     println!("  Total Hours: {:.1}h", summary.total_hours);
     println!(
         "  Total Value (Mid-level): {}",
-        format_currency_krw(summary.total_value).bright_green()
+        summary.currency.format(summary.total_value).bright_green()
     );
     println!("  Unique Contributors: {}", summary.total_contributors);
 }
 
+fn export_rows(summary: &TotalSummary) -> Vec<ExportRow> {
+    summary.repositories.iter().map(|repo| {
+        ExportRow {
+            repository: repo.path.display().to_string(),
+            period: repo.period.description.clone(),
+            commits: repo.commits.len(),
+            contributors: repo.analysis.unique_contributors,
+            files_changed: repo.analysis.total_files_changed,
+            insertions: repo.analysis.total_insertions,
+            deletions: repo.analysis.total_deletions,
+            estimated_hours: repo.analysis.estimated_hours,
+            currency: repo.analysis.value_estimate.currency,
+            recommended_value: repo.analysis.value_estimate.recommended_value,
+        }
+    }).collect()
+}
+
 fn export_summary(
     summary: &TotalSummary,
     path: &PathBuf,
@@ -392,35 +494,24 @@ fn export_summary(
             std::fs::write(path, content)?;
         }
         "csv" | "html" | "md" | "markdown" => {
-            use cli_core::output::{CsvExporter, ExportFormat, HtmlExporter, MarkdownExporter};
+            use cli_core::output::{
+                CsvExporter, ExportFormat, HtmlExporter, MarkdownExporter, ToReport,
+            };
             
             let export_format = ExportFormat::from_extension(ext)?;
-            let rows: Vec<ExportRow> = summary.repositories.iter().map(|repo| {
-                ExportRow {
-                    repository: repo.path.display().to_string(),
-                    period: repo.period.description.clone(),
-                    commits: repo.commits.len(),
-                    contributors: repo.analysis.unique_contributors,
-                    files_changed: repo.analysis.total_files_changed,
-                    insertions: repo.analysis.total_insertions,
-                    deletions: repo.analysis.total_deletions,
-                    estimated_hours: repo.analysis.estimated_hours,
-                    recommended_value_krw: repo.analysis.value_estimate.recommended_value,
-                }
-            }).collect();
 
             match export_format {
                 ExportFormat::Csv => {
                     let exporter = CsvExporter::new();
-                    exporter.export(&rows, path.to_str().unwrap())?;
+                    exporter.export(&export_rows(summary), path.to_str().unwrap())?;
                 }
                 ExportFormat::Html => {
                     let exporter = HtmlExporter::new();
-                    exporter.export(&rows, path.to_str().unwrap())?;
+                    exporter.export(&summary.to_report(), path.to_str().unwrap())?;
                 }
                 ExportFormat::Markdown => {
                     let exporter = MarkdownExporter::new();
-                    exporter.export(&rows, path.to_str().unwrap())?;
+                    exporter.export(&summary.to_report(), path.to_str().unwrap())?;
                 }
             }
         }
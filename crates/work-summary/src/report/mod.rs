@@ -0,0 +1,184 @@
+use cli_core::output::{
+    format_integer, Card, Histogram, Report, ReportTable, SectionContent, ToReport,
+};
+
+use crate::summary::{RepositorySummary, TotalSummary};
+
+const WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+impl ToReport for TotalSummary {
+    fn to_report(&self) -> Report {
+        build(self)
+    }
+}
+
+fn build(summary: &TotalSummary) -> Report {
+    let currency = summary.currency;
+    let mut report = Report::new("Work Summary Report", "work-summary")
+        .with_subtitle(match summary.repositories.len() {
+            1 => "1 repository analysed".to_string(),
+            n => format!("{n} repositories analysed"),
+        });
+
+    report.push(
+        "Overall Summary",
+        SectionContent::Cards(vec![
+            Card::new("Repositories", summary.repositories.len().to_string()),
+            Card::new("Commits", format_integer(summary.total_commits as i64)),
+            Card::new("Estimated Hours", format!("{:.1}", summary.total_hours)),
+            Card::new("Value", currency.format(summary.total_value)).with_detail("Mid-level"),
+            Card::new("Contributors", summary.total_contributors.to_string()),
+        ]),
+    );
+
+    let mut overview = ReportTable::new(&[
+        "Repository",
+        "Period",
+        ">Commits",
+        ">Insertions",
+        ">Deletions",
+        ">Est. Hours",
+        ">Value",
+    ]);
+    for repo in &summary.repositories {
+        overview.row(vec![
+            repo.path.display().to_string(),
+            repo.period.description.clone(),
+            format_integer(repo.commits.len() as i64),
+            format!("+{}", format_integer(repo.analysis.total_insertions as i64)),
+            format!("-{}", format_integer(repo.analysis.total_deletions as i64)),
+            format!("{:.1}", repo.analysis.estimated_hours),
+            repo.analysis
+                .value_estimate
+                .currency
+                .format(repo.analysis.value_estimate.recommended_value),
+        ]);
+    }
+    let insertions: usize = summary.repositories.iter().map(|r| r.analysis.total_insertions).sum();
+    let deletions: usize = summary.repositories.iter().map(|r| r.analysis.total_deletions).sum();
+    report.push(
+        "Repositories",
+        SectionContent::Table(overview.with_footer(vec![
+            "Total".to_string(),
+            String::new(),
+            format_integer(summary.total_commits as i64),
+            format!("+{}", format_integer(insertions as i64)),
+            format!("-{}", format_integer(deletions as i64)),
+            format!("{:.1}", summary.total_hours),
+            currency.format(summary.total_value),
+        ])),
+    );
+
+    for repo in &summary.repositories {
+        push_repository(&mut report, repo);
+    }
+
+    report
+}
+
+fn push_repository(report: &mut Report, repo: &RepositorySummary) {
+    let analysis = &repo.analysis;
+    let currency = analysis.value_estimate.currency;
+
+    report.push(
+        repo.path.display().to_string(),
+        SectionContent::Cards(vec![
+            Card::new("Period", repo.period.description.clone()),
+            Card::new("Commits", format_integer(repo.commits.len() as i64)),
+            Card::new("Contributors", analysis.unique_contributors.to_string()),
+            Card::new(
+                "Lines",
+                format!(
+                    "+{} / -{}",
+                    format_integer(analysis.total_insertions as i64),
+                    format_integer(analysis.total_deletions as i64)
+                ),
+            )
+            .with_detail(format!("{} files changed", analysis.total_files_changed)),
+            Card::new("Estimated Hours", format!("{:.1}", analysis.estimated_hours)),
+        ]),
+    );
+
+    if !analysis.language_breakdown.is_empty() {
+        let mut langs: Vec<_> = analysis.language_breakdown.iter().collect();
+        langs.sort_by(|a, b| {
+            b.1.percentage
+                .partial_cmp(&a.1.percentage)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.cmp(b.0))
+        });
+
+        let mut table =
+            ReportTable::new(&["Language", ">Insertions", ">Deletions", ">Net Change", ">%"]);
+        for (lang, stats) in langs {
+            table.row(vec![
+                lang.clone(),
+                format!("+{}", format_integer(stats.insertions as i64)),
+                format!("-{}", format_integer(stats.deletions as i64)),
+                format_integer(stats.net_change),
+                format!("{:.1}%", stats.percentage),
+            ]);
+        }
+        report.push_sub("Language Breakdown", SectionContent::Table(table));
+    }
+
+    if !analysis.contribution_breakdown.is_empty() {
+        let mut table =
+            ReportTable::new(&["Name", "Email", ">Commits", ">Insertions", ">Deletions", ">%"]);
+        for contributor in &analysis.contribution_breakdown {
+            table.row(vec![
+                contributor.name.clone(),
+                contributor.email.clone(),
+                format_integer(contributor.commit_count as i64),
+                format!("+{}", format_integer(contributor.insertions as i64)),
+                format!("-{}", format_integer(contributor.deletions as i64)),
+                format!("{:.1}%", contributor.percentage),
+            ]);
+        }
+        report.push_sub("Contributors", SectionContent::Table(table));
+    }
+
+    if !repo.commits.is_empty() {
+        let hourly = &repo.patterns.hourly_distribution;
+        report.push_sub(
+            "Commits by Hour",
+            SectionContent::Histogram(Histogram::new(
+                (0..24).map(|h| format!("{h:02}")).collect(),
+                (0..24)
+                    .map(|h| hourly.get(&h).copied().unwrap_or(0) as f64)
+                    .collect(),
+            )),
+        );
+
+        let daily = &repo.patterns.daily_distribution;
+        report.push_sub(
+            "Commits by Weekday",
+            SectionContent::Histogram(Histogram::new(
+                WEEKDAYS.iter().map(|day| day[..3].to_string()).collect(),
+                WEEKDAYS
+                    .iter()
+                    .map(|day| daily.get(*day).copied().unwrap_or(0) as f64)
+                    .collect(),
+            )),
+        );
+    }
+
+    let mut table = ReportTable::new(&["Level", ">Multiplier", ">Hourly Rate", ">Total Value"]);
+    for level in &analysis.value_estimate.developer_levels {
+        table.row(vec![
+            level.level.clone(),
+            format!("{}x", level.multiplier),
+            currency.format(level.hourly_rate),
+            currency.format(level.total_value),
+        ]);
+    }
+    report.push_sub("Value Estimates", SectionContent::Table(table));
+}
@@ -2,6 +2,7 @@ use crate::analyzer::WorkAnalysis;
 use crate::git::CommitInfo;
 use crate::patterns::WorkPatterns;
 use chrono::{DateTime, Utc};
+use cli_core::currency::Currency;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -27,6 +28,7 @@ impl RepositorySummary {
         commits: Vec<CommitInfo>,
         estimated_hours: f64,
         hourly_rate: f64,
+        currency: Currency,
     ) -> Self {
         let period = if commits.is_empty() {
             Period {
@@ -50,7 +52,7 @@ impl RepositorySummary {
             }
         };
 
-        let analysis = WorkAnalysis::from_commits(&commits, estimated_hours, hourly_rate);
+        let analysis = WorkAnalysis::from_commits(&commits, estimated_hours, hourly_rate, currency);
         let patterns = WorkPatterns::analyze(&commits);
 
         Self {
@@ -69,6 +71,7 @@ pub struct TotalSummary {
     pub total_commits: usize,
     pub total_hours: f64,
     pub total_value: f64,
+    pub currency: Currency,
     pub total_contributors: usize,
 }
 
@@ -86,10 +89,15 @@ impl TotalSummary {
             .map(|r| r.analysis.value_estimate.recommended_value)
             .sum();
 
+        let currency = repositories
+            .first()
+            .map(|r| r.analysis.value_estimate.currency)
+            .unwrap_or_default();
+
         let mut all_contributors = std::collections::HashSet::new();
         for repo in &repositories {
             for commit in &repo.commits {
-                all_contributors.insert(commit.email.clone());
+                all_contributors.insert(commit.email.to_lowercase());
             }
         }
 
@@ -98,6 +106,7 @@ impl TotalSummary {
             total_commits,
             total_hours,
             total_value,
+            currency,
             total_contributors: all_contributors.len(),
         }
     }
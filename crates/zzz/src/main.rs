@@ -27,8 +27,8 @@ Examples:
     version,
     about = "Run a command quietly in the background",
     long_about = "Run a command quietly in the background through your interactive shell, \
-                  save its output under ~/.commands, and notify you when it finishes. \
-                  Shell aliases and functions are supported.",
+                  save its output under ~/.commands (or the configured `[zzz] log_root`), \
+                  and notify you when it finishes. Shell aliases and functions are supported.",
     trailing_var_arg = true,
     styles = HELP_STYLES,
     after_help = AFTER_HELP